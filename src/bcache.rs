@@ -0,0 +1,323 @@
+/*!
+Read the bcache cache sets and backing devices from `/sys/fs/bcache` and `/sys/block` into the struct [`SysBcache`].
+
+bcache uses a fast device (the cache device, mostly an SSD) as a cache for one or more slow devices (the backing
+devices). The cache devices of a cache set are registered in `/sys/fs/bcache/<cache set uuid>`, which shows the
+state of the cache set, such as the percentage of the cache that is available (not dirty or used for metadata).
+
+A backing device has a `bcache` directory in its `/sys/block/<device>` (or `/sys/block/<device>/<partition>`)
+directory, which shows the state of the backing device (`no cache`, `clean`, `dirty` or `inconsistent`), the cache
+mode, the amount of dirty data in the cache that is not written to the backing device yet and the sequential cutoff,
+which is the size of sequential IO above which IO bypasses the cache.
+
+The cache sets and the backing devices both have statistics in `stats_total` (and in stats_five_minute, stats_hour
+and stats_day, which are not read), such as cache_hits, cache_misses and cache_hit_ratio.
+
+bcache shows sizes human readable, such as `4.0M`; these are converted to bytes.
+
+Documentation: <https://www.kernel.org/doc/Documentation/admin-guide/bcache.rst>
+
+Here is an example obtaining the bcache data:
+```no_run
+use proc_sys_parser::bcache;
+
+let sys_bcache = bcache::read();
+
+println!("{:#?}", sys_bcache);
+```
+Example output:
+```text
+SysBcache {
+    cache_sets: [
+        BcacheCacheSet {
+            uuid: "f3c2b4a1-8d2e-4c5b-9a7e-1f2d3c4b5a69",
+            cache_available_percent: Some(91),
+            congested: Some(0),
+            stats_total: BcacheStats { cache_hits: 1823710, cache_misses: 211003, cache_hit_ratio: 89, cache_bypass_hits: 2201, cache_bypass_misses: 19440, cache_miss_collisions: 12, bypassed_bytes: 1288490188 },
+        },
+    ],
+    backing_devices: [
+        BcacheBackingDevice {
+            name: "sdb",
+            bcache_device: Some("bcache0"),
+            cache_set_uuid: Some("f3c2b4a1-8d2e-4c5b-9a7e-1f2d3c4b5a69"),
+            label: Some(""),
+            state: "dirty",
+            cache_mode: "writeback",
+            running: Some(1),
+            dirty_data_bytes: 1258291,
+            sequential_cutoff_bytes: 4194304,
+            writeback_percent: Some(10),
+            stats_total: BcacheStats { cache_hits: 1823710, cache_misses: 211003, cache_hit_ratio: 89, cache_bypass_hits: 2201, cache_bypass_misses: 19440, cache_miss_collisions: 12, bypassed_bytes: 1288490188 },
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysBcache`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::bcache::Builder;
+
+let sys_bcache = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{parse_bracketed_value, read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_link_name};
+
+/// Struct for holding the bcache cache sets and backing devices
+#[derive(Debug, PartialEq, Default)]
+pub struct SysBcache {
+    pub cache_sets: Vec<BcacheCacheSet>,
+    pub backing_devices: Vec<BcacheBackingDevice>,
+}
+
+/// Struct for holding a single cache set
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BcacheCacheSet {
+    pub uuid: String,
+    /// The percentage of the cache that does not contain dirty data or metadata.
+    pub cache_available_percent: Option<u64>,
+    pub congested: Option<u64>,
+    pub stats_total: BcacheStats,
+}
+
+/// Struct for holding a single backing device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BcacheBackingDevice {
+    /// The name of the backing device, such as `sdb` or `sdb1`.
+    pub name: String,
+    /// The name of the bcache device created for the backing device, such as `bcache0`.
+    pub bcache_device: Option<String>,
+    /// The uuid of the cache set the backing device is attached to, None if the device is not attached.
+    pub cache_set_uuid: Option<String>,
+    pub label: Option<String>,
+    /// `no cache`, `clean`, `dirty` or `inconsistent`.
+    pub state: String,
+    /// The active cache mode: `writethrough`, `writeback`, `writearound` or `none`.
+    pub cache_mode: String,
+    pub running: Option<u64>,
+    pub dirty_data_bytes: u64,
+    pub sequential_cutoff_bytes: u64,
+    pub writeback_percent: Option<u64>,
+    pub stats_total: BcacheStats,
+}
+
+/// Struct for holding the statistics of a cache set or backing device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BcacheStats {
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// The hits as a percentage of the hits and misses.
+    pub cache_hit_ratio: u64,
+    pub cache_bypass_hits: u64,
+    pub cache_bypass_misses: u64,
+    pub cache_miss_collisions: u64,
+    /// The amount of IO that bypassed the cache.
+    pub bypassed_bytes: u64,
+}
+
+/// Builder pattern for [`SysBcache`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysBcache, ProcSysParserError> {
+        SysBcache::read_sys_bcache(
+            format!("{}/fs/bcache", &self.sys_path).as_str(),
+            format!("{}/block", &self.sys_path).as_str(),
+        )
+    }
+}
+
+/// The main function for building a [`SysBcache`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysBcache, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysBcache {
+    pub fn new() -> SysBcache {
+        SysBcache::default()
+    }
+    /// Parse a size printed human readable by bcache, such as `4.0M` or `512k`, into bytes.
+    /// bcache uses powers of 1024 and one decimal.
+    pub fn parse_human_readable(size: &str) -> Result<u64, ProcSysParserError> {
+        let size = size.trim();
+        let (number, multiplier) = match size.char_indices().last() {
+            Some((position, unit)) if unit.is_ascii_alphabetic() => {
+                let exponent = "kMGTPEZY".find(unit)
+                    .ok_or(ProcSysParserError::FindItemError { item: format!("bcache size unit {}", unit) })?;
+                (&size[..position], 1024_f64.powi(exponent as i32 + 1))
+            },
+            _ => (size, 1_f64),
+        };
+        Ok((number.parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)? * multiplier) as u64)
+    }
+    fn read_stats(stats_path: &Path) -> Result<BcacheStats, ProcSysParserError> {
+        Ok(BcacheStats {
+            cache_hits: read_file_u64(&stats_path.join("cache_hits"))?,
+            cache_misses: read_file_u64(&stats_path.join("cache_misses"))?,
+            cache_hit_ratio: read_file_u64(&stats_path.join("cache_hit_ratio"))?,
+            cache_bypass_hits: read_file_u64(&stats_path.join("cache_bypass_hits"))?,
+            cache_bypass_misses: read_file_u64(&stats_path.join("cache_bypass_misses"))?,
+            cache_miss_collisions: read_file_u64(&stats_path.join("cache_miss_collisions"))?,
+            bypassed_bytes: SysBcache::parse_human_readable(&read_file_string(&stats_path.join("bypassed"))?)?,
+        })
+    }
+    fn read_cache_set(cache_set_path: &Path, uuid: &str) -> Result<BcacheCacheSet, ProcSysParserError> {
+        Ok(BcacheCacheSet {
+            uuid: uuid.to_string(),
+            cache_available_percent: read_file_option_u64(&cache_set_path.join("cache_available_percent"))?,
+            congested: read_file_option_u64(&cache_set_path.join("congested"))?,
+            stats_total: SysBcache::read_stats(&cache_set_path.join("stats_total"))?,
+        })
+    }
+    fn read_backing_device(bcache_path: &Path, name: &str) -> Result<BcacheBackingDevice, ProcSysParserError> {
+        Ok(BcacheBackingDevice {
+            name: name.to_string(),
+            bcache_device: read_link_name(&bcache_path.join("dev")),
+            cache_set_uuid: read_link_name(&bcache_path.join("cache")),
+            label: read_file_option_string(&bcache_path.join("label")),
+            state: read_file_string(&bcache_path.join("state"))?,
+            cache_mode: parse_bracketed_value(&read_file_string(&bcache_path.join("cache_mode"))?)?,
+            running: read_file_option_u64(&bcache_path.join("running"))?,
+            dirty_data_bytes: SysBcache::parse_human_readable(&read_file_string(&bcache_path.join("dirty_data"))?)?,
+            sequential_cutoff_bytes: SysBcache::parse_human_readable(&read_file_string(&bcache_path.join("sequential_cutoff"))?)?,
+            writeback_percent: read_file_option_u64(&bcache_path.join("writeback_percent"))?,
+            stats_total: SysBcache::read_stats(&bcache_path.join("stats_total"))?,
+        })
+    }
+    pub fn read_sys_bcache(sys_fs_bcache_path: &str, sys_block_path: &str) -> Result<SysBcache, ProcSysParserError> {
+        let mut sysbcache = SysBcache::new();
+
+        // /sys/fs/bcache does not exist if the bcache module is not loaded.
+        if let Ok(cache_sets) = read_dir(sys_fs_bcache_path) {
+            // /sys/fs/bcache also contains the files register, register_quiet and pendings_cleanup.
+            for cache_set in cache_sets.flatten().filter(|entry| entry.path().is_dir()) {
+                sysbcache.cache_sets.push(SysBcache::read_cache_set(&cache_set.path(), &cache_set.file_name().to_string_lossy())?);
+            }
+        }
+        sysbcache.cache_sets.sort_by(|a, b| a.uuid.cmp(&b.uuid));
+
+        let block_devices = read_dir(sys_block_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_block_path.to_string(), error })?;
+        for block_device in block_devices.flatten() {
+            let block_device_name = block_device.file_name().to_string_lossy().to_string();
+            let mut candidates = vec![(block_device_name.clone(), block_device.path())];
+            // a partition can be a backing device too.
+            if let Ok(entries) = read_dir(block_device.path()) {
+                candidates.extend(entries.flatten()
+                    .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+                    .filter(|(name, _)| name.starts_with(&block_device_name)));
+            }
+            // the cache device has a bcache directory too, which does not contain the state file, and the bcache<N>
+            // device of an attached backing device has a bcache symlink back to the bcache directory of the backing device.
+            for (name, path) in candidates.into_iter()
+                .filter(|(_, path)| path.join("bcache/state").exists())
+                .filter(|(_, path)| !path.join("bcache").symlink_metadata().is_ok_and(|metadata| metadata.file_type().is_symlink())) {
+                sysbcache.backing_devices.push(SysBcache::read_backing_device(&path.join("bcache"), &name)?);
+            }
+        }
+        sysbcache.backing_devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysbcache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::os::unix::fs::symlink;
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    fn create_mock_stats(directory: &str) {
+        create_mock_files(directory, &[
+            ("cache_hits", "900"),
+            ("cache_misses", "100"),
+            ("cache_hit_ratio", "90"),
+            ("cache_bypass_hits", "5"),
+            ("cache_bypass_misses", "15"),
+            ("cache_miss_collisions", "1"),
+            ("bypassed", "1.5G"),
+        ]);
+    }
+
+    #[test]
+    fn parse_human_readable_sizes() {
+        assert_eq!(SysBcache::parse_human_readable("0").unwrap(), 0);
+        assert_eq!(SysBcache::parse_human_readable("512").unwrap(), 512);
+        assert_eq!(SysBcache::parse_human_readable("4.0M\n").unwrap(), 4194304);
+        assert_eq!(SysBcache::parse_human_readable("1.5k").unwrap(), 1536);
+        assert!(SysBcache::parse_human_readable("1.5x").is_err());
+    }
+
+    #[test]
+    fn create_sys_bcache_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let uuid = "f3c2b4a1-8d2e-4c5b-9a7e-1f2d3c4b5a69";
+        create_mock_files(&format!("{}/fs/bcache", test_path), &[("register", "")]);
+        create_mock_files(&format!("{}/fs/bcache/{}", test_path, uuid), &[("cache_available_percent", "91"), ("congested", "0")]);
+        create_mock_stats(&format!("{}/fs/bcache/{}/stats_total", test_path, uuid));
+        // the backing device, attached to the cache set.
+        create_mock_files(&format!("{}/block/sdb/bcache", test_path), &[
+            ("label", ""),
+            ("state", "dirty"),
+            ("cache_mode", "writethrough [writeback] writearound none"),
+            ("running", "1"),
+            ("dirty_data", "1.2M"),
+            ("sequential_cutoff", "4.0M"),
+            ("writeback_percent", "10"),
+        ]);
+        create_mock_stats(&format!("{}/block/sdb/bcache/stats_total", test_path));
+        create_dir_all(format!("{}/block/bcache0", test_path)).expect("Error creating mock directory.");
+        symlink(format!("{}/block/bcache0", test_path), format!("{}/block/sdb/bcache/dev", test_path)).expect("Error creating mock symlink.");
+        symlink(format!("{}/block/sdb/bcache", test_path), format!("{}/block/bcache0/bcache", test_path)).expect("Error creating mock symlink.");
+        symlink(format!("{}/fs/bcache/{}", test_path, uuid), format!("{}/block/sdb/bcache/cache", test_path)).expect("Error creating mock symlink.");
+        // a detached backing device on a partition.
+        create_mock_files(&format!("{}/block/sdc/sdc1/bcache", test_path), &[
+            ("state", "no cache"),
+            ("cache_mode", "[writethrough] writeback writearound none"),
+            ("dirty_data", "0"),
+            ("sequential_cutoff", "4.0M"),
+        ]);
+        create_mock_stats(&format!("{}/block/sdc/sdc1/bcache/stats_total", test_path));
+        // the cache device.
+        create_mock_files(&format!("{}/block/nvme0n1/bcache", test_path), &[("cache_replacement_policy", "[lru] fifo random")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        let stats = BcacheStats { cache_hits: 900, cache_misses: 100, cache_hit_ratio: 90, cache_bypass_hits: 5, cache_bypass_misses: 15, cache_miss_collisions: 1, bypassed_bytes: 1610612736 };
+        assert_eq!(result, SysBcache {
+            cache_sets: vec![BcacheCacheSet { uuid: uuid.to_string(), cache_available_percent: Some(91), congested: Some(0), stats_total: stats.clone() }],
+            backing_devices: vec![
+                BcacheBackingDevice { name: "sdb".to_string(), bcache_device: Some("bcache0".to_string()), cache_set_uuid: Some(uuid.to_string()), label: Some("".to_string()), state: "dirty".to_string(), cache_mode: "writeback".to_string(), running: Some(1), dirty_data_bytes: 1258291, sequential_cutoff_bytes: 4194304, writeback_percent: Some(10), stats_total: stats.clone() },
+                BcacheBackingDevice { name: "sdc1".to_string(), bcache_device: None, cache_set_uuid: None, label: None, state: "no cache".to_string(), cache_mode: "writethrough".to_string(), running: None, dirty_data_bytes: 0, sequential_cutoff_bytes: 4194304, writeback_percent: None, stats_total: stats },
+            ],
+        });
+    }
+}
@@ -0,0 +1,230 @@
+/*!
+Read the backing device (writeback) settings from `/sys/class/bdi` into the struct [`SysBdi`].
+
+Every block device, and every filesystem without a block device such as NFS, fuse or btrfs, has a backing device
+info (bdi) that controls the writeback of dirty pages to the device. The bdi is named after the major:minor number of
+the block device (such as `8:0`), or after the filesystem (such as `btrfs-1` or `0:53` for NFS). The settings are:
+- `read_ahead_kb`: the maximum size of read ahead in kilobytes.
+- `min_ratio`: the minimal percentage of the dirty page limit (`vm.dirty_ratio`/`vm.dirty_bytes`) that is reserved
+  for the device.
+- `max_ratio`: the maximal percentage of the dirty page limit that the device may use; the writer is throttled if
+  the device exceeds it.
+- `strict_limit`: whether the device is throttled on its own dirty limit, even if the global dirty limit is not
+  reached (linux 6.2+). This is set for fuse by default.
+- `stable_pages_required`: whether pages must not be modified while they are written (linux 3.9+).
+- `min_bytes`, `max_bytes`, `min_ratio_fine` and `max_ratio_fine`: the min_ratio and max_ratio in bytes and in parts
+  per million (linux 6.2+).
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-bdi>
+
+Here is an example obtaining the bdi data:
+```no_run
+use proc_sys_parser::bdi;
+
+let sys_bdi = bdi::read();
+
+println!("{:#?}", sys_bdi);
+```
+Example output:
+```text
+SysBdi {
+    devices: [
+        BackingDevice {
+            name: "253:0",
+            read_ahead_kb: 128,
+            min_ratio: 0,
+            max_ratio: 100,
+            strict_limit: Some(false),
+            stable_pages_required: Some(false),
+            min_bytes: Some(0),
+            max_bytes: Some(2812612608),
+            min_ratio_fine: Some(0),
+            max_ratio_fine: Some(1000000),
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysBdi`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::bdi::Builder;
+
+let sys_bdi = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_u64, read_file_u64};
+
+/// Struct for holding the backing devices
+#[derive(Debug, PartialEq, Default)]
+pub struct SysBdi {
+    /// The backing devices, sorted by name.
+    pub devices: Vec<BackingDevice>,
+}
+
+/// Struct for holding the writeback settings of a single backing device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BackingDevice {
+    /// The name of the backing device, which is major:minor for block devices, such as `8:0`.
+    pub name: String,
+    pub read_ahead_kb: u64,
+    /// The percentage of the dirty page limit that is reserved for the device.
+    pub min_ratio: u64,
+    /// The maximal percentage of the dirty page limit the device may use.
+    pub max_ratio: u64,
+    pub strict_limit: Option<bool>,
+    pub stable_pages_required: Option<bool>,
+    pub min_bytes: Option<u64>,
+    pub max_bytes: Option<u64>,
+    /// The min_ratio in parts per million.
+    pub min_ratio_fine: Option<u64>,
+    /// The max_ratio in parts per million.
+    pub max_ratio_fine: Option<u64>,
+}
+
+/// Builder pattern for [`SysBdi`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysBdi, ProcSysParserError> {
+        SysBdi::read_sys_bdi(format!("{}/class/bdi", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysBdi`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysBdi, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl BackingDevice {
+    /// The major and minor number of the backing device, None if the name is not a major:minor number.
+    pub fn major_minor(&self) -> Option<(u64, u64)> {
+        let (major, minor) = self.name.split_once(':')?;
+        Some((major.parse::<u64>().ok()?, minor.parse::<u64>().ok()?))
+    }
+    /// Is the writeback of the device limited below the global dirty limit, either by max_ratio or strict_limit?
+    pub fn is_limited(&self) -> bool {
+        self.max_ratio < 100 || self.strict_limit.unwrap_or(false)
+    }
+}
+
+impl SysBdi {
+    pub fn new() -> SysBdi {
+        SysBdi::default()
+    }
+    /// The backing device of a block device with the given major and minor number.
+    pub fn device(&self, major: u64, minor: u64) -> Option<&BackingDevice> {
+        self.devices.iter().find(|device| device.major_minor() == Some((major, minor)))
+    }
+    fn read_backing_device(device_path: &Path, name: &str) -> Result<BackingDevice, ProcSysParserError> {
+        Ok(BackingDevice {
+            name: name.to_string(),
+            read_ahead_kb: read_file_u64(&device_path.join("read_ahead_kb"))?,
+            min_ratio: read_file_u64(&device_path.join("min_ratio"))?,
+            max_ratio: read_file_u64(&device_path.join("max_ratio"))?,
+            strict_limit: read_file_option_u64(&device_path.join("strict_limit"))?.map(|strict_limit| strict_limit == 1),
+            stable_pages_required: read_file_option_u64(&device_path.join("stable_pages_required"))?.map(|stable_pages_required| stable_pages_required == 1),
+            min_bytes: read_file_option_u64(&device_path.join("min_bytes"))?,
+            max_bytes: read_file_option_u64(&device_path.join("max_bytes"))?,
+            min_ratio_fine: read_file_option_u64(&device_path.join("min_ratio_fine"))?,
+            max_ratio_fine: read_file_option_u64(&device_path.join("max_ratio_fine"))?,
+        })
+    }
+    pub fn read_sys_bdi(sys_class_bdi_path: &str) -> Result<SysBdi, ProcSysParserError> {
+        let mut sysbdi = SysBdi::new();
+
+        let devices = read_dir(sys_class_bdi_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_bdi_path.to_string(), error })?;
+        for device in devices.flatten() {
+            sysbdi.devices.push(SysBdi::read_backing_device(&device.path(), &device.file_name().to_string_lossy())?);
+        }
+        sysbdi.devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysbdi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_bdi_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/bdi/8:0", test_path), &[
+            ("read_ahead_kb", "128"),
+            ("min_ratio", "0"),
+            ("max_ratio", "100"),
+            ("strict_limit", "0"),
+            ("stable_pages_required", "0"),
+            ("min_bytes", "0"),
+            ("max_bytes", "2812612608"),
+            ("min_ratio_fine", "0"),
+            ("max_ratio_fine", "1000000"),
+        ]);
+        create_mock_files(&format!("{}/class/bdi/fuse-0:45", test_path), &[
+            ("read_ahead_kb", "128"),
+            ("min_ratio", "0"),
+            ("max_ratio", "1"),
+            ("stable_pages_required", "0"),
+        ]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysBdi {
+            devices: vec![
+                BackingDevice {
+                    name: "8:0".to_string(),
+                    read_ahead_kb: 128,
+                    min_ratio: 0,
+                    max_ratio: 100,
+                    strict_limit: Some(false),
+                    stable_pages_required: Some(false),
+                    min_bytes: Some(0),
+                    max_bytes: Some(2812612608),
+                    min_ratio_fine: Some(0),
+                    max_ratio_fine: Some(1000000),
+                },
+                BackingDevice {
+                    name: "fuse-0:45".to_string(),
+                    read_ahead_kb: 128,
+                    min_ratio: 0,
+                    max_ratio: 1,
+                    stable_pages_required: Some(false),
+                    ..Default::default()
+                },
+            ],
+        });
+        assert!(!result.device(8, 0).unwrap().is_limited());
+        assert!(result.devices[1].is_limited());
+        assert_eq!(result.devices[1].major_minor(), None);
+    }
+}
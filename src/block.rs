@@ -8,6 +8,38 @@ The documentation for `/sys/block` is found here:
 
 The main disk IO information is found in `/sys/block/<dev>/stat`, which is mostly equal to `/proc/diskstats`.
 
+The `holders` and `slaves` directories show how devices are stacked: an LVM logical volume (dm-0) on an md RAID
+device (md0) on two disks has md0 as slave, md0 has dm-0 as holder and the disks as slaves. Use
+[`SysBlock::bottom_devices`] to find the physical devices of a stacked device.
+
+The partitions of a device are the subdirectories of the device that contain a `partition` file, such as
+`/sys/block/sda/sda1`. For every partition the dev, start, size, ro and stat files are read into [`Partition`].
+
+Device-mapper devices (LVM, crypt, multipath) are named dm-<n> in `/sys/block`; the name that is used in
+`/dev/mapper`, such as "vg0-lvroot", and the uuid are read from `/sys/block/dm-<n>/dm` into `dm_name` and `dm_uuid`.
+No devices are filtered by default; set a filter regex to exclude devices, such as `Builder::new().regex("^dm-")` to
+exclude the dm devices.
+
+For md (software RAID) devices, the RAID level, number of disks, degraded count, sync state and the state of
+every member are read from `/sys/block/md<n>/md` into [`MdRaid`]. This is the same information as shown in
+`/proc/mdstat`, but structured.
+
+For loop devices with an attached file, the backing file, offset and flags are read from `/sys/block/loop<n>/loop`
+into [`LoopDevice`], which allows loop mounted images to be attributed to files.
+
+Every `/sys/block/<dev>` entry is a symlink to the device in `/sys/devices`, such as
+`/sys/devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0/nvme0n1`. The resolved path is stored in `device_path`,
+and is used to classify the device into a [`DeviceClass`], such as NVMe, SCSI, virtio or device-mapper, so devices
+can be grouped or filtered by type without matching on the device name.
+
+Not every device has every attribute file: virtual, md and some USB devices lack files such as `queue/dax` or
+`inflight`. If an attribute file of a device cannot be read or parsed, the field is set to its default value (or
+None) and a message is added to the `warnings` of the device, so a single device cannot fail reading all devices.
+
+For devices using blk-mq, the hardware queues (hctx) are the `/sys/block/<dev>/mq/<n>` directories, which show the
+number of tags (the queue depth) and the cpus that submit to the hardware queue into [`HardwareQueue`]. The number of
+requests in flight per hardware queue is only shown in debugfs and therefore not read.
+
 Here is an example obtaining the data from `/proc/sys_block`:
 ```no_run
 use proc_sys_parser::{block, block::SysBlock};
@@ -24,6 +56,25 @@ SysBlock {
             dev_block_major: 253,
             dev_block_minor: 0,
             device_name: "sda",
+            device_path: Some(
+                "/sys/devices/pci0000:00/0000:00:1f.2/ata1/host0/target0:0:0/0:0:0:0/block/sda",
+            ),
+            device_class: Scsi,
+            device_model: Some(
+                "Samsung SSD 870",
+            ),
+            device_vendor: Some(
+                "ATA",
+            ),
+            device_serial: None,
+            device_firmware_rev: Some(
+                "2B6Q",
+            ),
+            wwid: Some(
+                "naa.5002538e4098a5f1",
+            ),
+            dm_name: None,
+            dm_uuid: None,
             discard_alignment: 0,
             stat_reads_completed_success: 9718,
             stat_reads_merged: 3826,
@@ -72,6 +123,10 @@ SysBlock {
                 0,
             ),
             queue_scheduler: "none",
+            queue_schedulers_available: [
+                "none",
+                "mq-deadline",
+            ],
             queue_rotational: 1,
             queue_dax: 0,
             queue_add_random: 0,
@@ -99,6 +154,70 @@ SysBlock {
             queue_zoned: Some(
                 "none",
             ),
+            queue_dma_alignment: Some(
+                511,
+            ),
+            queue_fua: Some(
+                1,
+            ),
+            queue_io_timeout: Some(
+                30000,
+            ),
+            queue_stable_writes: Some(
+                0,
+            ),
+            queue_virt_boundary_mask: Some(
+                0,
+            ),
+            queue_wbt_lat_usec: Some(
+                2000,
+            ),
+            queue_write_zeroes_max_bytes: Some(
+                33550336,
+            ),
+            queue_max_active_zones: Some(
+                0,
+            ),
+            queue_max_open_zones: Some(
+                0,
+            ),
+            queue_zone_append_max_bytes: Some(
+                0,
+            ),
+            queue_zone_write_granularity: Some(
+                0,
+            ),
+            partitions: [
+                Partition {
+                    partition_name: "sda1",
+                    partition: 1,
+                    dev_block_major: 253,
+                    dev_block_minor: 1,
+                    start: 2048,
+                    size: 125827072,
+                    ro: 0,
+                    stat_reads_completed_success: 9600,
+                    ..
+                },
+            ],
+            hardware_queues: [
+                HardwareQueue {
+                    queue_nr: 0,
+                    nr_tags: 64,
+                    nr_reserved_tags: 0,
+                    cpu_list: [
+                        0,
+                        1,
+                    ],
+                },
+            ],
+            md: None,
+            loop_device: None,
+            holders: [
+                "dm-0",
+            ],
+            slaves: [],
+            warnings: [],
         },
     ],
 }
@@ -112,10 +231,21 @@ use proc_sys_parser::{block, block::{SysBlock, Builder}};
 
 let proc_block = Builder::new().path("/my-sys/block").read();
 ```
+
+Reading all fields opens about 50 files per device. For frequent sampling of the IO counters, select the groups of
+fields that are read; the device name and major and minor number are always read, and the fields of groups that are
+not read keep their default value:
+```no_run
+use proc_sys_parser::{block, block::{Builder, FieldGroup}};
+
+let proc_block = Builder::new().groups(&[FieldGroup::Stat, FieldGroup::Inflight]).read();
+```
 */
-use std::fs::{read_to_string, read_dir, DirEntry};
+use std::fs::{canonicalize, read_link, read_to_string, read_dir, DirEntry};
+use std::path::Path;
 use regex::Regex;
 use crate::ProcSysParserError;
+use crate::utils::parse_cpu_list;
 
 /// Struct for holding `/sys/block` block device statistics and information
 #[derive(Debug, PartialEq, Default)]
@@ -128,13 +258,15 @@ pub struct SysBlock {
 pub struct Builder {
     pub sys_path : String,
     pub filter : String,
+    pub groups : Vec<FieldGroup>,
 }
 
 impl Builder {
     pub fn new() -> Builder {
         Builder { 
             sys_path: "/sys".to_string(), 
-            filter: "^dm-".to_string(),
+            filter: "".to_string(),
+            groups: FieldGroup::all(),
         }
     }
     pub fn path(mut self, sys_path: &str) -> Builder {
@@ -145,8 +277,38 @@ impl Builder {
         self.filter = filter.to_string();
         self
     }
+    /// The groups of fields that are read, which are all groups by default.
+    pub fn groups(mut self, groups: &[FieldGroup]) -> Builder {
+        self.groups = groups.to_vec();
+        self
+    }
     pub fn read(self) -> Result<SysBlock, ProcSysParserError> {
-        SysBlock::read_sys_block_devices(format!("{}/block", self.sys_path).as_str(), self.filter.as_str())
+        SysBlock::read_sys_block_devices(format!("{}/block", self.sys_path).as_str(), self.filter.as_str(), &self.groups)
+    }
+}
+
+/// The groups of fields of a block device that can be selected to be read
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FieldGroup {
+    /// alignment_offset, cache_type, discard_alignment, diskseq, hidden, range, removable, ro and size.
+    Attributes,
+    /// device_path, device_class, the `device` files, wwid, dm_name and dm_uuid.
+    Device,
+    /// The `stat` file.
+    Stat,
+    /// The `inflight` file.
+    Inflight,
+    /// The `queue` files and the hardware queues in `mq`.
+    Queue,
+    /// The partitions.
+    Partitions,
+    /// holders, slaves, md and loop_device.
+    Stacking,
+}
+
+impl FieldGroup {
+    pub fn all() -> Vec<FieldGroup> {
+        vec![FieldGroup::Attributes, FieldGroup::Device, FieldGroup::Stat, FieldGroup::Inflight, FieldGroup::Queue, FieldGroup::Partitions, FieldGroup::Stacking]
     }
 }
 
@@ -161,6 +323,11 @@ pub fn read() -> Result<SysBlock, ProcSysParserError> {
 pub struct BlockDevice {
     /// `/sys/block/<device>` name.
     pub device_name: String,
+    /// The resolved path of the `/sys/block/<device>` symlink, which is the device in `/sys/devices`.
+    /// None if the entry is not a symlink.
+    pub device_path: Option<String>,
+    /// The class of the device, which is derived from the device path, or from the device name for virtual devices.
+    pub device_class: DeviceClass,
     //----------------------------------------------------------------------------------------------------------------//
     /// `/sys/block/<device>/alignment_offset`
     /// Number of bytes at the beginning of the device is offset from the disks natural alignment.
@@ -179,6 +346,28 @@ pub struct BlockDevice {
     pub dev_block_major: u64,
     /// From the `/sys/block/<device>/dev` file: block major number.
     pub dev_block_minor: u64,
+    /// `/sys/block/<device>/device/model`
+    /// The model of the physical device, such as "Samsung SSD 870". SCSI and NVMe devices only.
+    pub device_model: Option<String>,
+    /// `/sys/block/<device>/device/vendor`
+    /// The vendor of the physical device, such as "ATA" for SATA disks. SCSI devices only.
+    pub device_vendor: Option<String>,
+    /// `/sys/block/<device>/device/serial`
+    /// The serial number of the physical device. NVMe and some virtio devices only; the serial number of a
+    /// SCSI device is in the vital product data.
+    pub device_serial: Option<String>,
+    /// `/sys/block/<device>/device/firmware_rev` (NVMe) or `/sys/block/<device>/device/rev` (SCSI)
+    /// The firmware revision of the physical device.
+    pub device_firmware_rev: Option<String>,
+    /// `/sys/block/<device>/wwid` (NVMe) or `/sys/block/<device>/device/wwid` (SCSI)
+    /// The world wide identifier of the device, such as "naa.5002538e4098a5f1" or "eui.0025388b71b1b2d4".
+    pub wwid: Option<String>,
+    /// `/sys/block/<device>/dm/name`
+    /// The device-mapper name of a dm device, such as "vg0-lvroot", which is the name in `/dev/mapper`.
+    pub dm_name: Option<String>,
+    /// `/sys/block/<device>/dm/uuid`
+    /// The device-mapper uuid of a dm device, which starts with the subsystem that created it, such as "LVM-" or "CRYPT-".
+    pub dm_uuid: Option<String>,
     /// `/sys/block/<device>/discard_alignment`
     /// Devices that support discard functionality may internally allocate space in units that are bigger than the exported
     /// logical block size.
@@ -193,6 +382,12 @@ pub struct BlockDevice {
     /// The block device is hidden. It doesn't produce events, and can't be openend from userspace.
     /// Used for the underlying components of multipath devices.
     pub hidden: u64,
+    /// The blk-mq hardware queues of the device, which are the `/sys/block/<device>/mq/<n>` directories,
+    /// sorted by queue number. Empty for devices that do not use blk-mq, such as dm and md devices.
+    pub hardware_queues: Vec<HardwareQueue>,
+    /// `/sys/block/<device>/holders`
+    /// The names of the devices that are stacked on top of this device, such as the dm or md devices using it.
+    pub holders: Vec<String>,
     /// `/sys/block/<device>/inflight`
     /// Reports the number of pending IO requests in a device driver.
     /// The inflight file contains two fields: reads and writes.
@@ -203,6 +398,14 @@ pub struct BlockDevice {
     /// The inflight file contains two fields: reads and writes.
     /// Number of write requests.
     pub inflight_writes: u64,
+    /// The loop device details from `/sys/block/<device>/loop`, None if the device is not a loop device with a
+    /// file attached.
+    pub loop_device: Option<LoopDevice>,
+    /// The md RAID details from `/sys/block/<device>/md`, None if the device is not an md device.
+    pub md: Option<MdRaid>,
+    /// The partitions of the device, which are the `/sys/block/<device>/<partition>` directories,
+    /// sorted by partition number.
+    pub partitions: Vec<Partition>,
     /// `/sys/block/<device>/queue/add_random`
     /// Disk entropy contribution.
     pub queue_add_random: u64,
@@ -229,6 +432,12 @@ pub struct BlockDevice {
     /// This value is set by the driver to indicate the maximum amount that can be discarded in a single operation.
     /// A value of '0' means the device does not support the discard functionality.
     pub queue_discard_max_hw_bytes: u64,
+    /// `/sys/block/<device>/queue/dma_alignment`
+    /// The required alignment of the memory buffers for direct IO, as a mask: 511 means 512 bytes alignment.
+    pub queue_dma_alignment: Option<u64>,
+    /// `/sys/block/<device>/queue/fua`
+    /// Does the device support Force Unit Access (writes bypassing the volatile cache)? 0/no, 1/yes.
+    pub queue_fua: Option<u64>,
     /// `/sys/block/<device>/queue/hw_sector_size`
     /// The hardware sector size of the device, in bytes.
     pub queue_hw_sector_size: u64,
@@ -243,6 +452,10 @@ pub struct BlockDevice {
     /// slower than classic polling, but is more efficient.
     /// >0: number of microseconds before classic polling.
     pub queue_io_poll_delay: i64,
+    /// `/sys/block/<device>/queue/io_timeout`
+    /// Kernel 5.0+
+    /// The timeout of a request in milliseconds.
+    pub queue_io_timeout: Option<u64>,
     /// `/sys/block/<device>/queue/logical_block_size`
     /// The logical block size of the device, in bytes.
     pub queue_logical_block_size: u64,
@@ -261,6 +474,16 @@ pub struct BlockDevice {
     /// The maximum number of elements in a DMA scatter/gather list with integrity data that will be submitted
     /// by the block layer core to the associated driver.
     pub queue_max_integrity_segments: u64,
+    /// `/sys/block/<device>/queue/max_active_zones`
+    /// Kernel 5.9+
+    /// For a zoned block device, the maximum number of zones that can be in the open or closed state,
+    /// 0 means no limit.
+    pub queue_max_active_zones: Option<u64>,
+    /// `/sys/block/<device>/queue/max_open_zones`
+    /// Kernel 5.9+
+    /// For a zoned block device, the maximum number of zones that can be explicitly or implicitly open,
+    /// 0 means no limit.
+    pub queue_max_open_zones: Option<u64>,
     /// `/sys/block/<device>/queue/max_segments`
     pub queue_max_segments: u64,
     /// `/sys/block/<device>/queue/max_segment_size`
@@ -297,14 +520,29 @@ pub struct BlockDevice {
     pub queue_rotational: u64,
     /// `/sys/block/<device>/queue/rq_affinity`
     /// - 1: the block layer will migrate req. completions to the cpu group that originally submitted
-    ///  the request. Some workloads can reduce cpu cycles due to caching effects.
+    ///   the request. Some workloads can reduce cpu cycles due to caching effects.
     /// - 2: force completion to run on the requesting cpu (bypassing the group aggregate function)
-    ///  this maximizes distribution.
+    ///   this maximizes distribution.
     pub queue_rq_affinity: u64,
     /// `/sys/block/<device>/queue/scheduler`
     /// The scheduler file contains all available IO schedulers, and the current set IO scheduler is enclosed in '[]' brackets.
     /// When the file is parsed, it takes the current scheduler enclosed in the brackets.
     pub queue_scheduler: String,
+    /// `/sys/block/<device>/queue/scheduler`
+    /// All available IO schedulers, including the current scheduler, such as `["none", "mq-deadline", "kyber"]`.
+    pub queue_schedulers_available: Vec<String>,
+    /// `/sys/block/<device>/queue/stable_writes`
+    /// Kernel 5.10+
+    /// Does the device require the pages to be unchanged during a write (for example for checksumming)? 0/no, 1/yes.
+    pub queue_stable_writes: Option<u64>,
+    /// `/sys/block/<device>/queue/virt_boundary_mask`
+    /// Kernel 5.0+
+    /// The mask of the boundary within a page that a scatter/gather element must not cross, 0 if not applicable.
+    /// NVMe devices have a virt_boundary_mask of 4095.
+    pub queue_virt_boundary_mask: Option<u64>,
+    /// `/sys/block/<device>/queue/wbt_lat_usec`
+    /// The target latency of writeback throttling in microseconds, 0 if writeback throttling is disabled.
+    pub queue_wbt_lat_usec: Option<u64>,
     /// `/sys/block/<device>/queue/write_cache`
     /// Whether the device has:
     /// - "write back": write back caching enabled.
@@ -314,6 +552,19 @@ pub struct BlockDevice {
     /// The number of bytes the device can write in a single write-same command.
     /// A value of '0' means write-same is not supported by the device.
     pub queue_write_same_max_bytes: u64,
+    /// `/sys/block/<device>/queue/write_zeroes_max_bytes`
+    /// Kernel 4.10+
+    /// The number of bytes the device can zero in a single write-zeroes command.
+    /// A value of '0' means write-zeroes is not supported by the device.
+    pub queue_write_zeroes_max_bytes: Option<u64>,
+    /// `/sys/block/<device>/queue/zone_append_max_bytes`
+    /// Kernel 5.8+
+    /// For a zoned block device, the maximum number of bytes in a zone append command, 0 for a regular device.
+    pub queue_zone_append_max_bytes: Option<u64>,
+    /// `/sys/block/<device>/queue/zone_write_granularity`
+    /// Kernel 5.14+
+    /// For a zoned block device, the minimum size of a write in a sequential zone in bytes.
+    pub queue_zone_write_granularity: Option<u64>,
     /// `/sys/block/<device>/queue/zoned`
     /// Kernel 4.10+
     /// Indicates whether the device is a zoned blockdevice, and the zone model:
@@ -335,6 +586,13 @@ pub struct BlockDevice {
     /// The size of the block device in sectors.
     /// Sector size is 512 bytes.
     pub size: u64,
+    /// `/sys/block/<device>/slaves`
+    /// The names of the devices this device is stacked on, such as the physical devices or partitions of a
+    /// dm (LVM, crypt, multipath) or md device.
+    pub slaves: Vec<String>,
+    /// The attribute files of the device that could not be read or parsed. The fields of these are set to their
+    /// default value, instead of failing to read all the block devices.
+    pub warnings: Vec<String>,
     /// The stat file contents are in order of the fields:
     /// From the `/sys/block/<device>/stat` file: number of read requests IOs processed.
     pub stat_reads_completed_success: u64,
@@ -388,16 +646,219 @@ pub struct BlockDevice {
     pub stat_flush_requests_time_spent_ms: Option<u64>,
 }
 
+/// Struct for holding `/sys/block/<device>/<partition>` statistics and information
+#[derive(Debug, PartialEq, Default)]
+pub struct Partition {
+    /// `/sys/block/<device>/<partition>` name.
+    pub partition_name: String,
+    /// `/sys/block/<device>/<partition>/partition`
+    /// The partition number.
+    pub partition: u64,
+    /// From the `/sys/block/<device>/<partition>/dev` file: block major number.
+    pub dev_block_major: u64,
+    /// From the `/sys/block/<device>/<partition>/dev` file: block minor number.
+    pub dev_block_minor: u64,
+    /// `/sys/block/<device>/<partition>/start`
+    /// The start of the partition on the device in sectors.
+    /// Sector size is 512 bytes.
+    pub start: u64,
+    /// `/sys/block/<device>/<partition>/size`
+    /// The size of the partition in sectors.
+    /// Sector size is 512 bytes.
+    pub size: u64,
+    /// `/sys/block/<device>/<partition>/ro`
+    /// Is the partition readonly? 0/no, 1/yes.
+    pub ro: u64,
+    /// The stat fields of the partition, which are equal to the stat fields of [`BlockDevice`].
+    pub stat_reads_completed_success: u64,
+    pub stat_reads_merged: u64,
+    pub stat_reads_sectors: u64,
+    pub stat_reads_time_spent_ms: u64,
+    pub stat_writes_completed_success: u64,
+    pub stat_writes_merged: u64,
+    pub stat_writes_sectors: u64,
+    pub stat_writes_time_spent_ms: u64,
+    pub stat_ios_in_progress: u64,
+    pub stat_ios_time_spent_ms: u64,
+    pub stat_ios_weighted_time_spent_ms: u64,
+    pub stat_discards_completed_success: Option<u64>,
+    pub stat_discards_merged: Option<u64>,
+    pub stat_discards_sectors: Option<u64>,
+    pub stat_discards_time_spent_ms: Option<u64>,
+    pub stat_flush_requests_completed_success: Option<u64>,
+    pub stat_flush_requests_time_spent_ms: Option<u64>,
+}
+
+/// The class of a block device
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub enum DeviceClass {
+    /// A virtio-blk device, such as `vda`.
+    Virtio,
+    /// An NVMe namespace, such as `nvme0n1`.
+    Nvme,
+    /// A SCSI device, which includes SATA, SAS, USB storage and virtio-scsi devices, such as `sda` or `sr0`.
+    Scsi,
+    /// A device-mapper device (LVM, crypt, multipath), such as `dm-0`.
+    DeviceMapper,
+    /// An md software RAID device, such as `md0`.
+    Md,
+    /// A loop device, such as `loop0`.
+    Loop,
+    /// A memory backed device, such as `ram0` or `zram0`.
+    Ram,
+    /// Any other device, such as `nbd0` or `mmcblk0`.
+    #[default]
+    Other,
+}
+
+/// Struct for holding `/sys/block/<device>/loop` loop device information
+#[derive(Debug, PartialEq, Default)]
+pub struct LoopDevice {
+    /// `/sys/block/<device>/loop/backing_file`
+    /// The path of the file the loop device is attached to, which has " (deleted)" appended if the file is deleted.
+    pub backing_file: String,
+    /// `/sys/block/<device>/loop/offset`
+    /// The offset in the backing file in bytes.
+    pub offset: u64,
+    /// `/sys/block/<device>/loop/sizelimit`
+    /// The maximum size of the loop device in bytes, 0 means the size of the backing file.
+    pub sizelimit: u64,
+    /// `/sys/block/<device>/loop/autoclear`
+    /// Is the loop device detached automatically when it is last closed?
+    pub autoclear: bool,
+    /// `/sys/block/<device>/loop/partscan`
+    /// Are the partitions on the backing file scanned?
+    pub partscan: bool,
+    /// `/sys/block/<device>/loop/dio`
+    /// Is direct IO used for the backing file? Kernel 4.4+.
+    pub dio: Option<bool>,
+}
+
+/// Struct for holding `/sys/block/<device>/md` md RAID information
+#[derive(Debug, PartialEq, Default)]
+pub struct MdRaid {
+    /// `/sys/block/<device>/md/level`
+    /// The RAID level, such as "raid1", "raid5" or "linear".
+    pub level: String,
+    /// `/sys/block/<device>/md/raid_disks`
+    /// The number of devices in a fully functional array.
+    pub raid_disks: u64,
+    /// `/sys/block/<device>/md/array_state`
+    /// The state of the array, such as "clean", "active" or "inactive".
+    pub array_state: Option<String>,
+    /// `/sys/block/<device>/md/degraded`
+    /// The number of devices missing from the array. Redundant RAID levels only.
+    pub degraded: Option<u64>,
+    /// `/sys/block/<device>/md/sync_action`
+    /// The current sync action, such as "idle", "resync", "recover", "check" or "repair". Redundant RAID levels only.
+    pub sync_action: Option<String>,
+    /// `/sys/block/<device>/md/sync_speed`
+    /// The current sync speed in kilobytes per second, None if no sync is running.
+    pub sync_speed: Option<u64>,
+    /// `/sys/block/<device>/md/mismatch_cnt`
+    /// The number of sectors found to be inconsistent by the last check or repair. Redundant RAID levels only.
+    pub mismatch_cnt: Option<u64>,
+    /// The members of the array, which are the `/sys/block/<device>/md/dev-<member>` directories, sorted by name.
+    pub members: Vec<MdMember>,
+}
+
+/// Struct for holding `/sys/block/<device>/md/dev-<member>` md RAID member information
+#[derive(Debug, PartialEq, Default)]
+pub struct MdMember {
+    /// The name of the member device, such as "sda1".
+    pub name: String,
+    /// `/sys/block/<device>/md/dev-<member>/state`
+    /// The states of the member, such as "in_sync", "faulty", "spare" or "write_mostly".
+    pub state: Vec<String>,
+    /// `/sys/block/<device>/md/dev-<member>/slot`
+    /// The role of the member in the array, None for a spare or failed member.
+    pub slot: Option<u64>,
+    /// `/sys/block/<device>/md/dev-<member>/errors`
+    /// The number of read errors that were corrected.
+    pub errors: Option<u64>,
+}
+
+impl MdRaid {
+    /// Is the array missing one or more members?
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.unwrap_or(0) > 0
+    }
+    /// The names of the members that are in the faulty state.
+    pub fn faulty_members(&self) -> Vec<&str> {
+        self.members.iter().filter(|member| member.state.iter().any(|state| state == "faulty")).map(|member| member.name.as_str()).collect()
+    }
+}
+
+/// Struct for holding `/sys/block/<device>/mq/<n>` hardware queue information
+#[derive(Debug, PartialEq, Default)]
+pub struct HardwareQueue {
+    /// The hardware queue number, which is the name of the `/sys/block/<device>/mq/<n>` directory.
+    pub queue_nr: u64,
+    /// `/sys/block/<device>/mq/<n>/nr_tags`
+    /// The number of tags, which is the maximum number of requests in flight for the hardware queue.
+    pub nr_tags: u64,
+    /// `/sys/block/<device>/mq/<n>/nr_reserved_tags`
+    /// The number of tags reserved for internal use by the driver.
+    pub nr_reserved_tags: u64,
+    /// `/sys/block/<device>/mq/<n>/cpu_list`
+    /// The cpus that are mapped to the hardware queue.
+    pub cpu_list: Vec<u32>,
+}
+
 impl BlockDevice {
     pub fn new() -> BlockDevice {
         BlockDevice::default()
     }
+    /// Is the device a host-aware or host-managed zoned device (SMR disk or ZNS NVMe namespace)?
+    /// A device is considered zoned if queue/zoned is not "none" and it reports zones.
+    /// Drive-managed SMR disks show as not zoned, because the zones are not visible to the host.
+    pub fn is_zoned(&self) -> bool {
+        matches!(self.queue_zoned.as_deref(), Some(zoned) if zoned != "none")
+            && self.queue_nr_zones.unwrap_or(0) > 0
+    }
+    /// The device-mapper name for dm devices, otherwise the device name.
+    pub fn display_name(&self) -> &str {
+        self.dm_name.as_deref().unwrap_or(&self.device_name)
+    }
+    /// The hardware queue the given cpu submits IO to.
+    pub fn hardware_queue_of_cpu(&self, cpu: u32) -> Option<&HardwareQueue> {
+        self.hardware_queues.iter().find(|hardware_queue| hardware_queue.cpu_list.contains(&cpu))
+    }
+    /// The size of a zone in bytes for a zoned device, which is the chunk size.
+    pub fn zone_size_bytes(&self) -> Option<u64> {
+        if !self.is_zoned() { return None };
+        self.queue_chunk_sectors.map(|chunk_sectors| chunk_sectors * 512)
+    }
 }
 
 impl SysBlock {
     pub fn new() -> SysBlock {
         SysBlock::default() 
     }
+    /// The block device with the given name.
+    pub fn device(&self, device_name: &str) -> Option<&BlockDevice> {
+        self.block_devices.iter().find(|device| device.device_name == device_name)
+    }
+    /// The dm device with the given device-mapper name, such as "vg0-lvroot".
+    pub fn device_by_dm_name(&self, dm_name: &str) -> Option<&BlockDevice> {
+        self.block_devices.iter().find(|device| device.dm_name.as_deref() == Some(dm_name))
+    }
+    /// The devices at the bottom of the device stack of a device, by following the slaves recursively.
+    /// For example, for an LVM logical volume on an md RAID1 device this returns the disks or partitions of the
+    /// RAID1 device. A device without slaves, or that is not in [`SysBlock`] (because it is filtered or because it
+    /// is a partition), is a bottom device itself.
+    pub fn bottom_devices(&self, device_name: &str) -> Vec<String> {
+        let mut bottom_devices = Vec::new();
+        let mut stack = vec![device_name.to_string()];
+
+        while let Some(current) = stack.pop() {
+            match self.device(&current) {
+                Some(device) if !device.slaves.is_empty() => stack.extend(device.slaves.iter().rev().cloned()),
+                _ => if !bottom_devices.contains(&current) { bottom_devices.push(current) },
+            }
+        }
+        bottom_devices
+    }
     fn parse_dev(
         blockdevice_data: &mut BlockDevice,
         blockdevice_dir: &DirEntry,
@@ -439,6 +900,9 @@ impl SysBlock {
         let nr_requests = read_to_string(blockdevice_dir.path().join("queue").join("scheduler"))
             .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join("queue").join("scheduler").to_string_lossy().to_string(), error })?
             .trim_end_matches('\n').to_string();
+        blockdevice_data.queue_schedulers_available = nr_requests.split_whitespace()
+            .map(|scheduler| scheduler.trim_start_matches('[').trim_end_matches(']').to_string())
+            .collect();
         let left_bracket = nr_requests.find('[');
         let right_bracket = nr_requests.find(']');
 
@@ -456,12 +920,7 @@ impl SysBlock {
         let parse_next_and_conversion_into_option_u64 = |result: Option<&str>| -> Option<u64> {
             match result {
                 None => None,
-                Some(value) => {
-                    match value.parse::<u64>() {
-                        Err(_) => None,
-                        Ok(number) => Some(number),
-                    }
-                },
+                Some(value) => value.parse::<u64>().ok(),
             }
         };
 
@@ -524,67 +983,244 @@ impl SysBlock {
         blockdevice_data.stat_flush_requests_time_spent_ms = parse_next_and_conversion_into_option_u64(stat_contents_splitted.next());
         Ok(())
     }
+    fn classify_device(
+        device_name: &str,
+        device_path: Option<&str>,
+    ) -> DeviceClass {
+        let components: Vec<&str> = device_path.map(|device_path| device_path.split('/').collect()).unwrap_or_default();
+        // a SCSI device has a SCSI host in its path, which also is the case for virtio-scsi devices.
+        if components.iter().any(|component| component.starts_with("host") && component[4..].parse::<u32>().is_ok()) {
+            DeviceClass::Scsi
+        // a multipath nvme namespace is in the virtual nvme-subsystem, and an nvme over fabrics controller in the
+        // virtual nvme-fabrics device.
+        } else if components.iter().any(|component| ["nvme", "nvme-subsystem", "nvme-fabrics"].contains(component)) {
+            DeviceClass::Nvme
+        } else if components.iter().any(|component| component.starts_with("virtio") && component[6..].parse::<u32>().is_ok()) {
+            DeviceClass::Virtio
+        // virtual devices and entries that are not a symlink are classified by name.
+        } else if device_name.starts_with("dm-") {
+            DeviceClass::DeviceMapper
+        } else if device_name.starts_with("md") {
+            DeviceClass::Md
+        } else if device_name.starts_with("loop") {
+            DeviceClass::Loop
+        } else if device_name.starts_with("ram") || device_name.starts_with("zram") {
+            DeviceClass::Ram
+        } else if device_path.is_none() && device_name.starts_with("sd") {
+            DeviceClass::Scsi
+        } else if device_path.is_none() && device_name.starts_with("nvme") {
+            DeviceClass::Nvme
+        } else if device_path.is_none() && device_name.starts_with("vd") {
+            DeviceClass::Virtio
+        } else {
+            DeviceClass::Other
+        }
+    }
+    fn value_or_warning<T: Default>(
+        file: &Path,
+        result: Result<T, ProcSysParserError>,
+        warnings: &mut Vec<String>,
+    ) -> T {
+        result.unwrap_or_else(|error| {
+            match error {
+                // the read error contains the file name already.
+                ProcSysParserError::FileReadError { .. } => warnings.push(error.to_string()),
+                _ => warnings.push(format!("{} in file {}", error, file.to_string_lossy())),
+            }
+            T::default()
+        })
+    }
     fn parse_contents_file_u64(
         file: &str,
         blockdevice_dir: &DirEntry,
-    ) -> Result<u64, ProcSysParserError> {
-        read_to_string(blockdevice_dir.path().join(file))
-                .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error })?
-                .trim_end_matches('\n')
-                .to_string()
-                .parse::<u64>()
-                .map_err(ProcSysParserError::ParseToIntegerError)
+        warnings: &mut Vec<String>,
+    ) -> u64 {
+        let result = read_to_string(blockdevice_dir.path().join(file))
+                .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error })
+                .and_then(|contents| contents.trim_end_matches('\n').parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError));
+        SysBlock::value_or_warning(&blockdevice_dir.path().join(file), result, warnings)
     }
     fn parse_contents_file_i64(
         file: &str,
         blockdevice_dir: &DirEntry,
-    ) -> Result<i64, ProcSysParserError> {
-        read_to_string(blockdevice_dir.path().join(file))
-                .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error })?
-                .trim_end_matches('\n')
-                .to_string()
-                .parse::<i64>()
-                .map_err(ProcSysParserError::ParseToIntegerError)
+        warnings: &mut Vec<String>,
+    ) -> i64 {
+        let result = read_to_string(blockdevice_dir.path().join(file))
+                .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error })
+                .and_then(|contents| contents.trim_end_matches('\n').parse::<i64>().map_err(ProcSysParserError::ParseToIntegerError));
+        SysBlock::value_or_warning(&blockdevice_dir.path().join(file), result, warnings)
     }
     fn parse_contents_file_option_u64(
         file: &str,
         blockdevice_dir: &DirEntry,
-    ) -> Result<Option<u64>, ProcSysParserError>
+        warnings: &mut Vec<String>,
+    ) -> Option<u64>
     {
-        match read_to_string(blockdevice_dir.path().join(file)) {
-            Ok(result) => {
-                Ok(
-                    Some(result
-                        .trim_end_matches('\n')
-                        .to_string()
-                        .parse::<u64>()
-                        .map_err(ProcSysParserError::ParseToIntegerError)?)
-                )
-            },
-            Err(_) => Ok(None),
-        }
+        // a file that does not exist is expected for attributes that depend on the kernel version or device type.
+        let Ok(contents) = read_to_string(blockdevice_dir.path().join(file)) else { return None };
+        let result = contents.trim_end_matches('\n').parse::<u64>().map(Some).map_err(ProcSysParserError::ParseToIntegerError);
+        SysBlock::value_or_warning(&blockdevice_dir.path().join(file), result, warnings)
     }
     fn parse_contents_file_option_string(
         file: &str,
         blockdevice_dir: &DirEntry,
-    ) -> Result<Option<String>, ProcSysParserError> {
-        Ok(match read_to_string(blockdevice_dir.path().join(file)) {
-            Ok(result) => Some(result.trim_end_matches('\n').to_string()),
-            Err(_) => None
+    ) -> Option<String> {
+        read_to_string(blockdevice_dir.path().join(file)).ok().map(|result| result.trim_end_matches('\n').to_string())
+    }
+    fn parse_contents_file_option_trimmed_string(
+        file: &str,
+        blockdevice_dir: &DirEntry,
+    ) -> Option<String> {
+        // identity strings are padded with spaces by some devices.
+        read_to_string(blockdevice_dir.path().join(file)).ok().map(|result| result.trim().to_string())
+    }
+    fn parse_partitions(
+        blockdevice_dir: &DirEntry,
+        warnings: &mut Vec<String>,
+    ) -> Vec<Partition> {
+        let mut partitions = Vec::new();
+
+        let entries = match read_dir(blockdevice_dir.path()) {
+            Ok(entries) => entries,
+            Err(error) => {
+                warnings.push(ProcSysParserError::DirectoryReadError { directory: blockdevice_dir.path().to_string_lossy().to_string(), error }.to_string());
+                return partitions
+            },
+        };
+        // a partition is a directory that contains the partition file.
+        for partition_dir in entries.filter_map(|entry| entry.ok()).filter(|entry| entry.path().join("partition").exists()) {
+            // the dev and stat files have the same format as the ones of the block device.
+            let mut partition_data = BlockDevice::new();
+            SysBlock::value_or_warning(&partition_dir.path().join("dev"), SysBlock::parse_dev(&mut partition_data, &partition_dir), warnings);
+            SysBlock::value_or_warning(&partition_dir.path().join("stat"), SysBlock::parse_stat(&mut partition_data, &partition_dir), warnings);
+
+            partitions.push(Partition {
+                partition_name: partition_dir.file_name().to_string_lossy().to_string(),
+                partition: SysBlock::parse_contents_file_u64("partition", &partition_dir, warnings),
+                dev_block_major: partition_data.dev_block_major,
+                dev_block_minor: partition_data.dev_block_minor,
+                start: SysBlock::parse_contents_file_u64("start", &partition_dir, warnings),
+                size: SysBlock::parse_contents_file_u64("size", &partition_dir, warnings),
+                ro: SysBlock::parse_contents_file_u64("ro", &partition_dir, warnings),
+                stat_reads_completed_success: partition_data.stat_reads_completed_success,
+                stat_reads_merged: partition_data.stat_reads_merged,
+                stat_reads_sectors: partition_data.stat_reads_sectors,
+                stat_reads_time_spent_ms: partition_data.stat_reads_time_spent_ms,
+                stat_writes_completed_success: partition_data.stat_writes_completed_success,
+                stat_writes_merged: partition_data.stat_writes_merged,
+                stat_writes_sectors: partition_data.stat_writes_sectors,
+                stat_writes_time_spent_ms: partition_data.stat_writes_time_spent_ms,
+                stat_ios_in_progress: partition_data.stat_ios_in_progress,
+                stat_ios_time_spent_ms: partition_data.stat_ios_time_spent_ms,
+                stat_ios_weighted_time_spent_ms: partition_data.stat_ios_weighted_time_spent_ms,
+                stat_discards_completed_success: partition_data.stat_discards_completed_success,
+                stat_discards_merged: partition_data.stat_discards_merged,
+                stat_discards_sectors: partition_data.stat_discards_sectors,
+                stat_discards_time_spent_ms: partition_data.stat_discards_time_spent_ms,
+                stat_flush_requests_completed_success: partition_data.stat_flush_requests_completed_success,
+                stat_flush_requests_time_spent_ms: partition_data.stat_flush_requests_time_spent_ms,
+            });
+        }
+        partitions.sort_by_key(|partition| partition.partition);
+        partitions
+    }
+    fn parse_loop_device(
+        blockdevice_dir: &DirEntry,
+        warnings: &mut Vec<String>,
+    ) -> Option<LoopDevice> {
+        // the loop directory only exists for loop devices that have a file attached.
+        if !blockdevice_dir.path().join("loop").exists() { return None };
+        Some(LoopDevice {
+            backing_file: SysBlock::parse_contents_file_string("loop/backing_file", blockdevice_dir, warnings),
+            offset: SysBlock::parse_contents_file_u64("loop/offset", blockdevice_dir, warnings),
+            sizelimit: SysBlock::parse_contents_file_u64("loop/sizelimit", blockdevice_dir, warnings),
+            autoclear: SysBlock::parse_contents_file_u64("loop/autoclear", blockdevice_dir, warnings) == 1,
+            partscan: SysBlock::parse_contents_file_u64("loop/partscan", blockdevice_dir, warnings) == 1,
+            dio: SysBlock::parse_contents_file_option_u64("loop/dio", blockdevice_dir, warnings).map(|dio| dio == 1),
         })
     }
+    fn parse_md(
+        blockdevice_dir: &DirEntry,
+        warnings: &mut Vec<String>,
+    ) -> Option<MdRaid> {
+        // the md directory only exists for md devices.
+        let Ok(entries) = read_dir(blockdevice_dir.path().join("md")) else { return None };
+        let mut members = Vec::new();
+        for member_dir in entries.filter_map(|entry| entry.ok()) {
+            let Some(name) = member_dir.file_name().to_string_lossy().strip_prefix("dev-").map(|name| name.to_string()) else { continue };
+            members.push(MdMember {
+                name,
+                state: SysBlock::parse_contents_file_option_string("state", &member_dir)
+                    .map(|state| state.split(',').filter(|state| !state.is_empty()).map(|state| state.to_string()).collect())
+                    .unwrap_or_default(),
+                // the slot is "none" for spare and failed members.
+                slot: SysBlock::parse_contents_file_option_string("slot", &member_dir).and_then(|slot| slot.parse::<u64>().ok()),
+                errors: SysBlock::parse_contents_file_option_u64("errors", &member_dir, warnings),
+            });
+        }
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Some(MdRaid {
+            level: SysBlock::parse_contents_file_string("md/level", blockdevice_dir, warnings),
+            raid_disks: SysBlock::parse_contents_file_u64("md/raid_disks", blockdevice_dir, warnings),
+            array_state: SysBlock::parse_contents_file_option_string("md/array_state", blockdevice_dir),
+            degraded: SysBlock::parse_contents_file_option_u64("md/degraded", blockdevice_dir, warnings),
+            sync_action: SysBlock::parse_contents_file_option_string("md/sync_action", blockdevice_dir),
+            // the sync speed is "none" if no sync is running.
+            sync_speed: SysBlock::parse_contents_file_option_string("md/sync_speed", blockdevice_dir).and_then(|speed| speed.parse::<u64>().ok()),
+            mismatch_cnt: SysBlock::parse_contents_file_option_u64("md/mismatch_cnt", blockdevice_dir, warnings),
+            members,
+        })
+    }
+    fn parse_hardware_queues(
+        blockdevice_dir: &DirEntry,
+        warnings: &mut Vec<String>,
+    ) -> Vec<HardwareQueue> {
+        let mut hardware_queues = Vec::new();
+
+        // the mq directory does not exist for devices that do not use blk-mq.
+        let Ok(entries) = read_dir(blockdevice_dir.path().join("mq")) else { return hardware_queues };
+        for queue_dir in entries.filter_map(|entry| entry.ok()) {
+            let Ok(queue_nr) = queue_dir.file_name().to_string_lossy().parse::<u64>() else { continue };
+            // the cpu list is separated by a comma and a space, such as "0, 1, 2".
+            let cpu_list = SysBlock::parse_contents_file_string("cpu_list", &queue_dir, warnings).replace(' ', "");
+            hardware_queues.push(HardwareQueue {
+                queue_nr,
+                nr_tags: SysBlock::parse_contents_file_u64("nr_tags", &queue_dir, warnings),
+                nr_reserved_tags: SysBlock::parse_contents_file_u64("nr_reserved_tags", &queue_dir, warnings),
+                cpu_list: SysBlock::value_or_warning(&queue_dir.path().join("cpu_list"), parse_cpu_list(&cpu_list), warnings),
+            });
+        }
+        hardware_queues.sort_by_key(|hardware_queue| hardware_queue.queue_nr);
+        hardware_queues
+    }
+    fn parse_contents_directory_names(
+        directory: &str,
+        blockdevice_dir: &DirEntry,
+    ) -> Vec<String> {
+        // the directory does not exist for devices that cannot be stacked.
+        let Ok(entries) = read_dir(blockdevice_dir.path().join(directory)) else { return Vec::new() };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
     fn parse_contents_file_string(
         file: &str,
         blockdevice_dir: &DirEntry,
-    ) -> Result <String, ProcSysParserError> {
-        Ok(read_to_string(blockdevice_dir.path().join(file))
-            .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error })?
-            .trim_end_matches('\n')
-            .to_string())
+        warnings: &mut Vec<String>,
+    ) -> String {
+        let result = read_to_string(blockdevice_dir.path().join(file))
+            .map(|contents| contents.trim_end_matches('\n').to_string())
+            .map_err(|error| ProcSysParserError::FileReadError { file: blockdevice_dir.path().join(file).to_string_lossy().to_string(), error });
+        SysBlock::value_or_warning(&blockdevice_dir.path().join(file), result, warnings)
     }
     pub fn read_sys_block_devices(
         sys_block_path: &str,
         filter: &str,
+        groups: &[FieldGroup],
     ) -> Result<SysBlock, ProcSysParserError> {
         let mut sysblock = SysBlock::new();
 
@@ -602,48 +1238,91 @@ impl SysBlock {
             let mut blockdevice_data = BlockDevice::new();
 
             blockdevice_data.device_name = directory_entry.file_name().into_string().unwrap();
-            blockdevice_data.alignment_offset = SysBlock::parse_contents_file_u64("alignment_offset", &directory_entry)?;
-            blockdevice_data.cache_type = SysBlock::parse_contents_file_option_string("cache_type", &directory_entry)?;
-            SysBlock::parse_dev(&mut blockdevice_data, &directory_entry)?;
-            blockdevice_data.discard_alignment = SysBlock::parse_contents_file_u64("discard_alignment", &directory_entry)?;
-            blockdevice_data.diskseq = SysBlock::parse_contents_file_option_u64("diskseq", &directory_entry)?;
-            blockdevice_data.hidden = SysBlock::parse_contents_file_u64("hidden", &directory_entry)?;
-            SysBlock::parse_inflight(&mut blockdevice_data, &directory_entry)?;
-            blockdevice_data.queue_add_random = SysBlock::parse_contents_file_u64("queue/add_random", &directory_entry)?;
-            blockdevice_data.queue_chunk_sectors = SysBlock::parse_contents_file_option_u64("queue/chunk_sectors", &directory_entry)?;
-            blockdevice_data.queue_dax = SysBlock::parse_contents_file_u64("queue/dax", &directory_entry)?;
-            blockdevice_data.queue_discard_granularity = SysBlock::parse_contents_file_u64("queue/discard_granularity", &directory_entry)?;
-            blockdevice_data.queue_discard_max_bytes = SysBlock::parse_contents_file_u64("queue/discard_max_bytes", &directory_entry)?;
-            blockdevice_data.queue_discard_max_hw_bytes = SysBlock::parse_contents_file_u64("queue/discard_max_hw_bytes", &directory_entry)?;
-            blockdevice_data.queue_hw_sector_size = SysBlock::parse_contents_file_u64("queue/hw_sector_size", &directory_entry)?;
-            blockdevice_data.queue_io_poll = SysBlock::parse_contents_file_u64("queue/io_poll", &directory_entry)?;
-            blockdevice_data.queue_io_poll_delay = SysBlock::parse_contents_file_i64("queue/io_poll_delay", &directory_entry)?;
-            blockdevice_data.queue_logical_block_size = SysBlock::parse_contents_file_u64("queue/logical_block_size", &directory_entry)?;
-            blockdevice_data.queue_max_discard_segments = SysBlock::parse_contents_file_u64("queue/max_discard_segments", &directory_entry)?;
-            blockdevice_data.queue_max_hw_sectors_kb = SysBlock::parse_contents_file_u64("queue/max_hw_sectors_kb", &directory_entry)?;
-            blockdevice_data.queue_max_integrity_segments = SysBlock::parse_contents_file_u64("queue/max_integrity_segments", &directory_entry)?;
-            blockdevice_data.queue_max_sectors_kb = SysBlock::parse_contents_file_u64("queue/max_sectors_kb", &directory_entry)?;
-            blockdevice_data.queue_max_segment_size = SysBlock::parse_contents_file_u64("queue/max_segment_size", &directory_entry)?;
-            blockdevice_data.queue_max_segments = SysBlock::parse_contents_file_u64("queue/max_segments", &directory_entry)?;
-            blockdevice_data.queue_minimum_io_size = SysBlock::parse_contents_file_u64("queue/minimum_io_size", &directory_entry)?;
-            blockdevice_data.queue_nomerges = SysBlock::parse_contents_file_u64("queue/nomerges", &directory_entry)?;
-            blockdevice_data.queue_nr_requests = SysBlock::parse_contents_file_u64("queue/nr_requests", &directory_entry)?;
-            blockdevice_data.queue_nr_zones = SysBlock::parse_contents_file_option_u64("queue/nr_zones", &directory_entry)?;
-            blockdevice_data.queue_optimal_io_size = SysBlock::parse_contents_file_u64("queue/optimal_io_size", &directory_entry)?;
-            blockdevice_data.queue_physical_block_size = SysBlock::parse_contents_file_u64("queue/physical_block_size", &directory_entry)?;
-            blockdevice_data.queue_read_ahead_kb = SysBlock::parse_contents_file_u64("queue/read_ahead_kb", &directory_entry)?;
-            blockdevice_data.queue_rotational = SysBlock::parse_contents_file_u64("queue/rotational", &directory_entry)?;
-            blockdevice_data.queue_rq_affinity = SysBlock::parse_contents_file_u64("queue/rq_affinity", &directory_entry)?;
-            SysBlock::parse_queue_scheduler(&mut blockdevice_data, &directory_entry)?;
-            blockdevice_data.queue_write_cache = SysBlock::parse_contents_file_string("queue/write_cache", &directory_entry)?;
-            blockdevice_data.queue_write_same_max_bytes = SysBlock::parse_contents_file_u64("queue/write_same_max_bytes", &directory_entry)?;
-            blockdevice_data.queue_zoned = SysBlock::parse_contents_file_option_string("queue/zoned", &directory_entry)?;
-            blockdevice_data.range = SysBlock::parse_contents_file_u64("range", &directory_entry)?;
-            blockdevice_data.removable = SysBlock::parse_contents_file_u64("removable", &directory_entry)?;
-            blockdevice_data.ro = SysBlock::parse_contents_file_u64("ro", &directory_entry)?;
-            blockdevice_data.size = SysBlock::parse_contents_file_u64("size", &directory_entry)?;
-
-            SysBlock::parse_stat(&mut blockdevice_data, &directory_entry)?;
+            SysBlock::value_or_warning(&directory_entry.path().join("dev"), SysBlock::parse_dev(&mut blockdevice_data, &directory_entry), &mut blockdevice_data.warnings);
+            if groups.contains(&FieldGroup::Attributes) {
+                blockdevice_data.alignment_offset = SysBlock::parse_contents_file_u64("alignment_offset", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.cache_type = SysBlock::parse_contents_file_option_string("cache_type", &directory_entry);
+                blockdevice_data.discard_alignment = SysBlock::parse_contents_file_u64("discard_alignment", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.diskseq = SysBlock::parse_contents_file_option_u64("diskseq", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.hidden = SysBlock::parse_contents_file_u64("hidden", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.range = SysBlock::parse_contents_file_u64("range", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.removable = SysBlock::parse_contents_file_u64("removable", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.ro = SysBlock::parse_contents_file_u64("ro", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.size = SysBlock::parse_contents_file_u64("size", &directory_entry, &mut blockdevice_data.warnings);
+            }
+            if groups.contains(&FieldGroup::Device) {
+                blockdevice_data.device_path = read_link(directory_entry.path()).ok()
+                    .and_then(|_| canonicalize(directory_entry.path()).ok())
+                    .map(|device_path| device_path.to_string_lossy().to_string());
+                blockdevice_data.device_class = SysBlock::classify_device(&blockdevice_data.device_name, blockdevice_data.device_path.as_deref());
+                blockdevice_data.device_model = SysBlock::parse_contents_file_option_trimmed_string("device/model", &directory_entry);
+                blockdevice_data.device_vendor = SysBlock::parse_contents_file_option_trimmed_string("device/vendor", &directory_entry);
+                blockdevice_data.device_serial = SysBlock::parse_contents_file_option_trimmed_string("device/serial", &directory_entry);
+                blockdevice_data.device_firmware_rev = SysBlock::parse_contents_file_option_trimmed_string("device/firmware_rev", &directory_entry)
+                    .or_else(|| SysBlock::parse_contents_file_option_trimmed_string("device/rev", &directory_entry));
+                blockdevice_data.wwid = SysBlock::parse_contents_file_option_trimmed_string("wwid", &directory_entry)
+                    .or_else(|| SysBlock::parse_contents_file_option_trimmed_string("device/wwid", &directory_entry));
+                blockdevice_data.dm_name = SysBlock::parse_contents_file_option_string("dm/name", &directory_entry);
+                blockdevice_data.dm_uuid = SysBlock::parse_contents_file_option_string("dm/uuid", &directory_entry);
+            }
+            if groups.contains(&FieldGroup::Stat) {
+                SysBlock::value_or_warning(&directory_entry.path().join("stat"), SysBlock::parse_stat(&mut blockdevice_data, &directory_entry), &mut blockdevice_data.warnings);
+            }
+            if groups.contains(&FieldGroup::Inflight) {
+                SysBlock::value_or_warning(&directory_entry.path().join("inflight"), SysBlock::parse_inflight(&mut blockdevice_data, &directory_entry), &mut blockdevice_data.warnings);
+            }
+            if groups.contains(&FieldGroup::Queue) {
+                blockdevice_data.hardware_queues = SysBlock::parse_hardware_queues(&directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_add_random = SysBlock::parse_contents_file_u64("queue/add_random", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_chunk_sectors = SysBlock::parse_contents_file_option_u64("queue/chunk_sectors", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_dax = SysBlock::parse_contents_file_u64("queue/dax", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_discard_granularity = SysBlock::parse_contents_file_u64("queue/discard_granularity", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_discard_max_bytes = SysBlock::parse_contents_file_u64("queue/discard_max_bytes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_discard_max_hw_bytes = SysBlock::parse_contents_file_u64("queue/discard_max_hw_bytes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_dma_alignment = SysBlock::parse_contents_file_option_u64("queue/dma_alignment", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_fua = SysBlock::parse_contents_file_option_u64("queue/fua", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_hw_sector_size = SysBlock::parse_contents_file_u64("queue/hw_sector_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_io_poll = SysBlock::parse_contents_file_u64("queue/io_poll", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_io_poll_delay = SysBlock::parse_contents_file_i64("queue/io_poll_delay", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_io_timeout = SysBlock::parse_contents_file_option_u64("queue/io_timeout", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_logical_block_size = SysBlock::parse_contents_file_u64("queue/logical_block_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_discard_segments = SysBlock::parse_contents_file_u64("queue/max_discard_segments", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_hw_sectors_kb = SysBlock::parse_contents_file_u64("queue/max_hw_sectors_kb", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_integrity_segments = SysBlock::parse_contents_file_u64("queue/max_integrity_segments", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_active_zones = SysBlock::parse_contents_file_option_u64("queue/max_active_zones", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_open_zones = SysBlock::parse_contents_file_option_u64("queue/max_open_zones", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_sectors_kb = SysBlock::parse_contents_file_u64("queue/max_sectors_kb", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_segment_size = SysBlock::parse_contents_file_u64("queue/max_segment_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_max_segments = SysBlock::parse_contents_file_u64("queue/max_segments", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_minimum_io_size = SysBlock::parse_contents_file_u64("queue/minimum_io_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_nomerges = SysBlock::parse_contents_file_u64("queue/nomerges", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_nr_requests = SysBlock::parse_contents_file_u64("queue/nr_requests", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_nr_zones = SysBlock::parse_contents_file_option_u64("queue/nr_zones", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_optimal_io_size = SysBlock::parse_contents_file_u64("queue/optimal_io_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_physical_block_size = SysBlock::parse_contents_file_u64("queue/physical_block_size", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_read_ahead_kb = SysBlock::parse_contents_file_u64("queue/read_ahead_kb", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_rotational = SysBlock::parse_contents_file_u64("queue/rotational", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_rq_affinity = SysBlock::parse_contents_file_u64("queue/rq_affinity", &directory_entry, &mut blockdevice_data.warnings);
+                SysBlock::value_or_warning(&directory_entry.path().join("queue/scheduler"), SysBlock::parse_queue_scheduler(&mut blockdevice_data, &directory_entry), &mut blockdevice_data.warnings);
+                blockdevice_data.queue_stable_writes = SysBlock::parse_contents_file_option_u64("queue/stable_writes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_virt_boundary_mask = SysBlock::parse_contents_file_option_u64("queue/virt_boundary_mask", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_wbt_lat_usec = SysBlock::parse_contents_file_option_u64("queue/wbt_lat_usec", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_write_cache = SysBlock::parse_contents_file_string("queue/write_cache", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_write_same_max_bytes = SysBlock::parse_contents_file_u64("queue/write_same_max_bytes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_write_zeroes_max_bytes = SysBlock::parse_contents_file_option_u64("queue/write_zeroes_max_bytes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_zoned = SysBlock::parse_contents_file_option_string("queue/zoned", &directory_entry);
+                blockdevice_data.queue_zone_append_max_bytes = SysBlock::parse_contents_file_option_u64("queue/zone_append_max_bytes", &directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.queue_zone_write_granularity = SysBlock::parse_contents_file_option_u64("queue/zone_write_granularity", &directory_entry, &mut blockdevice_data.warnings);
+            }
+            if groups.contains(&FieldGroup::Partitions) {
+                blockdevice_data.partitions = SysBlock::parse_partitions(&directory_entry, &mut blockdevice_data.warnings);
+            }
+            if groups.contains(&FieldGroup::Stacking) {
+                blockdevice_data.holders = SysBlock::parse_contents_directory_names("holders", &directory_entry);
+                blockdevice_data.loop_device = SysBlock::parse_loop_device(&directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.md = SysBlock::parse_md(&directory_entry, &mut blockdevice_data.warnings);
+                blockdevice_data.slaves = SysBlock::parse_contents_directory_names("slaves", &directory_entry);
+            }
 
             sysblock.block_devices.push(blockdevice_data);
         }
@@ -654,54 +1333,62 @@ impl SysBlock {
 
 #[cfg(test)]
 mod tests {
-    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::fs::{write, remove_dir_all, remove_file, create_dir_all};
+    use std::os::unix::fs::symlink;
     use rand::{thread_rng, Rng};
     use rand::distributions::Alphanumeric;
     use super::*;
 
+    fn create_mock_block_device(test_path: &str, device: &str) {
+        create_dir_all(format!("{}/block/{}/queue", test_path, device)).expect("Error creating mock sysfs directories.");
+        for (file, contents) in [("alignment_offset", "0"), ("dev", "9:0"), ("discard_alignment", "0"), ("hidden", "0"), ("inflight", "       0        0"), ("range", "1"), ("removable", "0"), ("ro", "0"), ("size", "1048576"), ("stat", "    9718     3826  1052371     3026     2856     2331   312397     1947        0     6004     5554"),
+            ("queue/add_random", "0"), ("queue/dax", "0"), ("queue/discard_granularity", "512"), ("queue/discard_max_bytes", "0"), ("queue/discard_max_hw_bytes", "0"), ("queue/hw_sector_size", "512"), ("queue/io_poll", "0"), ("queue/io_poll_delay", "-1"), ("queue/logical_block_size", "512"), ("queue/max_discard_segments", "1"), ("queue/max_hw_sectors_kb", "1280"), ("queue/max_integrity_segments", "0"), ("queue/max_sectors_kb", "1280"), ("queue/max_segment_size", "4294967295"), ("queue/max_segments", "254"), ("queue/minimum_io_size", "512"), ("queue/nomerges", "0"), ("queue/nr_requests", "128"), ("queue/optimal_io_size", "0"), ("queue/physical_block_size", "512"), ("queue/read_ahead_kb", "128"), ("queue/rotational", "0"), ("queue/rq_affinity", "0"), ("queue/scheduler", "none"), ("queue/write_cache", "write back"), ("queue/write_same_max_bytes", "0")] {
+            write(format!("{}/block/{}/{}", test_path, device, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("error writing to mock sysfs {} file.", file));
+        }
+    }
     #[test]
     fn create_sys_block_device_parse_files() {
-        let alignment_offset = format!("0\n");
-        let cache_type = format!("write back\n");
-        let dev= format!("253:0\n");
-        let discard_alignment = format!("0\n");
-        let diskseq = format!("9\n");
-        let hidden = format!("0\n");
-        let inflight = format!("       1        2\n");
-        let queue_add_random= format!("0\n");
-        let queue_chunk_sectors = format!("0\n");
-        let queue_dax = format!("0\n");
-        let queue_discard_granularity = format!("512\n");
-        let queue_discard_max_bytes = format!("2147483136\n");
-        let queue_discard_max_hw_bytes = format!("2147483136\n");
-        let queue_hw_sector_size = format!("512\n");
-        let queue_io_poll = format!("0\n");
-        let queue_io_poll_delay = format!("-1\n");
-        let queue_logical_block_size = format!("512\n");
-        let queue_max_discard_segments = format!("1\n");
-        let queue_max_hw_sectors_kb = format!("2147483647\n");
-        let queue_max_integrity_segments = format!("0\n");
-        let queue_max_sectors_kb = format!("1280\n");
-        let queue_max_segment_size = format!("4294967295\n");
-        let queue_max_segments = format!("254\n");
-        let queue_minimum_io_size = format!("512\n");
-        let queue_nomerges = format!("0\n");
-        let queue_nr_requests = format!("256\n");
-        let queue_nr_zones = format!("0\n");
-        let queue_optimal_io_size = format!("0\n");
-        let queue_physical_block_size = format!("512\n");
-        let queue_read_ahead_kb = format!("128\n");
-        let queue_rotational = format!("1\n");
-        let queue_rq_affinity = format!("1\n");
-        let queue_scheduler = format!("[none] mq-deadline\n");
-        let queue_write_cache = format!("write back\n");
-        let queue_write_same_max_bytes = format!("0\n");
-        let queue_zoned = format!("none\n");
-        let range = format!("16\n");
-        let removable = format!("0\n");
-        let ro = format!("0\n");
-        let size = format!("125829120\n");
-        let stat = format!("    9718     3826  1052371     3026     2856     2331   312397     1947        0     6004     5554     7141        0 88014755      276      591      304\n");
+        let alignment_offset = "0\n".to_string();
+        let cache_type = "write back\n".to_string();
+        let dev= "253:0\n".to_string();
+        let discard_alignment = "0\n".to_string();
+        let diskseq = "9\n".to_string();
+        let hidden = "0\n".to_string();
+        let inflight = "       1        2\n".to_string();
+        let queue_add_random= "0\n".to_string();
+        let queue_chunk_sectors = "0\n".to_string();
+        let queue_dax = "0\n".to_string();
+        let queue_discard_granularity = "512\n".to_string();
+        let queue_discard_max_bytes = "2147483136\n".to_string();
+        let queue_discard_max_hw_bytes = "2147483136\n".to_string();
+        let queue_hw_sector_size = "512\n".to_string();
+        let queue_io_poll = "0\n".to_string();
+        let queue_io_poll_delay = "-1\n".to_string();
+        let queue_logical_block_size = "512\n".to_string();
+        let queue_max_discard_segments = "1\n".to_string();
+        let queue_max_hw_sectors_kb = "2147483647\n".to_string();
+        let queue_max_integrity_segments = "0\n".to_string();
+        let queue_max_sectors_kb = "1280\n".to_string();
+        let queue_max_segment_size = "4294967295\n".to_string();
+        let queue_max_segments = "254\n".to_string();
+        let queue_minimum_io_size = "512\n".to_string();
+        let queue_nomerges = "0\n".to_string();
+        let queue_nr_requests = "256\n".to_string();
+        let queue_nr_zones = "0\n".to_string();
+        let queue_optimal_io_size = "0\n".to_string();
+        let queue_physical_block_size = "512\n".to_string();
+        let queue_read_ahead_kb = "128\n".to_string();
+        let queue_rotational = "1\n".to_string();
+        let queue_rq_affinity = "1\n".to_string();
+        let queue_scheduler = "[none] mq-deadline\n".to_string();
+        let queue_write_cache = "write back\n".to_string();
+        let queue_write_same_max_bytes = "0\n".to_string();
+        let queue_zoned = "none\n".to_string();
+        let range = "16\n".to_string();
+        let removable = "0\n".to_string();
+        let ro = "0\n".to_string();
+        let size = "125829120\n".to_string();
+        let stat = "    9718     3826  1052371     3026     2856     2331   312397     1947        0     6004     5554     7141        0 88014755      276      591      304\n".to_string();
 
         let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
         let test_path = format!("/tmp/test.{}", directory_suffix);
@@ -747,17 +1434,42 @@ mod tests {
         write(format!("{}/block/sda/ro", test_path),ro).expect("error writing to mock sysfs ro file.");
         write(format!("{}/block/sda/size", test_path),size).expect("error writing to mock sysfs size file.");
         write(format!("{}/block/sda/stat", test_path),stat).expect("error writing to mock sysfs stat file.");
+        create_dir_all(format!("{}/block/sda/holders/md0", test_path)).expect("Error creating mock sysfs directories.");
+        create_dir_all(format!("{}/block/sda/holders/dm-1", test_path)).expect("Error creating mock sysfs directories.");
+        create_dir_all(format!("{}/block/sda/slaves", test_path)).expect("Error creating mock sysfs directories.");
+        create_dir_all(format!("{}/block/sda/device", test_path)).expect("Error creating mock sysfs directories.");
+        write(format!("{}/block/sda/device/model", test_path), "Samsung SSD 870 \n").expect("error writing to mock sysfs device/model file.");
+        write(format!("{}/block/sda/device/vendor", test_path), "ATA     \n").expect("error writing to mock sysfs device/vendor file.");
+        write(format!("{}/block/sda/device/rev", test_path), "2B6Q\n").expect("error writing to mock sysfs device/rev file.");
+        write(format!("{}/block/sda/device/wwid", test_path), "naa.5002538e4098a5f1\n").expect("error writing to mock sysfs device/wwid file.");
+        for (file, contents) in [("dma_alignment", "511\n"), ("fua", "1\n"), ("io_timeout", "30000\n"), ("stable_writes", "0\n"), ("virt_boundary_mask", "0\n"), ("wbt_lat_usec", "2000\n"), ("write_zeroes_max_bytes", "33550336\n"), ("max_open_zones", "0\n"), ("max_active_zones", "0\n"), ("zone_append_max_bytes", "0\n"), ("zone_write_granularity", "0\n")] {
+            write(format!("{}/block/sda/queue/{}", test_path, file), contents).expect("error writing to mock sysfs queue file.");
+        }
+        for queue_nr in ["0", "1"] {
+            create_dir_all(format!("{}/block/sda/mq/{}", test_path, queue_nr)).expect("Error creating mock sysfs directories.");
+            write(format!("{}/block/sda/mq/{}/nr_tags", test_path, queue_nr), "64\n").expect("error writing to mock sysfs mq/nr_tags file.");
+            write(format!("{}/block/sda/mq/{}/nr_reserved_tags", test_path, queue_nr), "0\n").expect("error writing to mock sysfs mq/nr_reserved_tags file.");
+        }
+        write(format!("{}/block/sda/mq/0/cpu_list", test_path), "0, 1\n").expect("error writing to mock sysfs mq/cpu_list file.");
+        write(format!("{}/block/sda/mq/1/cpu_list", test_path), "2, 3\n").expect("error writing to mock sysfs mq/cpu_list file.");
+        create_dir_all(format!("{}/block/sda/sda1", test_path)).expect("Error creating mock sysfs directories.");
+        for (file, contents) in [("partition", "1\n"), ("dev", "253:1\n"), ("start", "2048\n"), ("size", "125827072\n"), ("ro", "0\n"), ("stat", "    9600     3800  1050000     3000     2850     2330   312000     1940        0     5900     5500     7141        0 88014755      276        0        0\n")] {
+            write(format!("{}/block/sda/sda1/{}", test_path, file), contents).expect("error writing to mock sysfs partition file.");
+        }
 
         let result = Builder::new().path(&test_path).read().unwrap();
 
         remove_dir_all(test_path).unwrap();
 
+        assert_eq!(result.block_devices[0].hardware_queue_of_cpu(3).map(|hardware_queue| hardware_queue.queue_nr), Some(1));
         assert_eq!(result, SysBlock {
             block_devices: vec![
                 BlockDevice {
                     dev_block_major: 253,
                     dev_block_minor: 0,
                     device_name: "sda".to_string(),
+                    device_path: None,
+                    device_class: DeviceClass::Scsi,
                     discard_alignment: 0,
                     stat_reads_completed_success: 9718,
                     stat_reads_merged: 3826,
@@ -806,6 +1518,7 @@ mod tests {
                         0,
                     ),
                     queue_scheduler: "none".to_string(),
+                    queue_schedulers_available: vec!["none".to_string(), "mq-deadline".to_string()],
                     queue_rotational: 1,
                     queue_dax: 0,
                     queue_add_random: 0,
@@ -833,6 +1546,61 @@ mod tests {
                     queue_zoned: Some(
                         "none".to_string(),
                     ),
+                    partitions: vec![
+                        Partition {
+                            partition_name: "sda1".to_string(),
+                            partition: 1,
+                            dev_block_major: 253,
+                            dev_block_minor: 1,
+                            start: 2048,
+                            size: 125827072,
+                            ro: 0,
+                            stat_reads_completed_success: 9600,
+                            stat_reads_merged: 3800,
+                            stat_reads_sectors: 1050000,
+                            stat_reads_time_spent_ms: 3000,
+                            stat_writes_completed_success: 2850,
+                            stat_writes_merged: 2330,
+                            stat_writes_sectors: 312000,
+                            stat_writes_time_spent_ms: 1940,
+                            stat_ios_in_progress: 0,
+                            stat_ios_time_spent_ms: 5900,
+                            stat_ios_weighted_time_spent_ms: 5500,
+                            stat_discards_completed_success: Some(7141),
+                            stat_discards_merged: Some(0),
+                            stat_discards_sectors: Some(88014755),
+                            stat_discards_time_spent_ms: Some(276),
+                            stat_flush_requests_completed_success: Some(0),
+                            stat_flush_requests_time_spent_ms: Some(0),
+                        },
+                    ],
+                    device_model: Some("Samsung SSD 870".to_string()),
+                    device_vendor: Some("ATA".to_string()),
+                    device_serial: None,
+                    device_firmware_rev: Some("2B6Q".to_string()),
+                    wwid: Some("naa.5002538e4098a5f1".to_string()),
+                    dm_name: None,
+                    dm_uuid: None,
+                    queue_dma_alignment: Some(511),
+                    queue_fua: Some(1),
+                    queue_io_timeout: Some(30000),
+                    queue_stable_writes: Some(0),
+                    queue_virt_boundary_mask: Some(0),
+                    queue_wbt_lat_usec: Some(2000),
+                    queue_write_zeroes_max_bytes: Some(33550336),
+                    queue_max_active_zones: Some(0),
+                    queue_max_open_zones: Some(0),
+                    queue_zone_append_max_bytes: Some(0),
+                    queue_zone_write_granularity: Some(0),
+                    hardware_queues: vec![
+                        HardwareQueue { queue_nr: 0, nr_tags: 64, nr_reserved_tags: 0, cpu_list: vec![0, 1] },
+                        HardwareQueue { queue_nr: 1, nr_tags: 64, nr_reserved_tags: 0, cpu_list: vec![2, 3] },
+                    ],
+                    md: None,
+                    loop_device: None,
+                    holders: vec!["dm-1".to_string(), "md0".to_string()],
+                    slaves: vec![],
+                    warnings: vec![],
                 },
             ],
         }
@@ -933,6 +1701,8 @@ mod tests {
                                dev_block_major: 253,
                                dev_block_minor: 0,
                                device_name: "sda".to_string(),
+                               device_path: None,
+                               device_class: DeviceClass::Scsi,
                                discard_alignment: 0,
                                stat_reads_completed_success: 9718,
                                stat_reads_merged: 3826,
@@ -967,6 +1737,7 @@ mod tests {
                                queue_nr_requests: 256,
                                queue_nr_zones: None,
                                queue_scheduler: "none".to_string(),
+                               queue_schedulers_available: vec!["none".to_string(), "mq-deadline".to_string()],
                                queue_rotational: 1,
                                queue_dax: 0,
                                queue_add_random: 0,
@@ -990,12 +1761,192 @@ mod tests {
                                queue_write_same_max_bytes: 0,
                                queue_chunk_sectors: None,
                                queue_zoned: None,
+                               partitions: vec![],
+                               device_model: None,
+                               device_vendor: None,
+                               device_serial: None,
+                               device_firmware_rev: None,
+                               wwid: None,
+                               dm_name: None,
+                               dm_uuid: None,
+                               queue_dma_alignment: None,
+                               queue_fua: None,
+                               queue_io_timeout: None,
+                               queue_stable_writes: None,
+                               queue_virt_boundary_mask: None,
+                               queue_wbt_lat_usec: None,
+                               queue_write_zeroes_max_bytes: None,
+                               queue_max_active_zones: None,
+                               queue_max_open_zones: None,
+                               queue_zone_append_max_bytes: None,
+                               queue_zone_write_granularity: None,
+                               hardware_queues: vec![],
+                               md: None,
+                               loop_device: None,
+                               holders: vec![],
+                               slaves: vec![],
+                               warnings: vec![],
                            },
                        ],
                    }
         );
     }
     #[test]
+    fn create_sys_block_md_device() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_block_device(&test_path, "md0");
+        for member in ["dev-sda1", "dev-sdb1"] {
+            create_dir_all(format!("{}/block/md0/md/{}", test_path, member)).expect("Error creating mock sysfs directories.");
+        }
+        for (file, contents) in [("level", "raid1"), ("raid_disks", "2"), ("array_state", "clean"), ("degraded", "1"), ("sync_action", "recover"), ("sync_speed", "104857"), ("mismatch_cnt", "0"),
+            ("dev-sda1/state", "in_sync"), ("dev-sda1/slot", "0"), ("dev-sda1/errors", "0"),
+            ("dev-sdb1/state", "faulty,write_mostly"), ("dev-sdb1/slot", "none"), ("dev-sdb1/errors", "12")] {
+            write(format!("{}/block/md0/md/{}", test_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("error writing to mock sysfs md/{} file.", file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        let md = result.device("md0").unwrap().md.as_ref().unwrap();
+        assert_eq!(md, &MdRaid {
+            level: "raid1".to_string(),
+            raid_disks: 2,
+            array_state: Some("clean".to_string()),
+            degraded: Some(1),
+            sync_action: Some("recover".to_string()),
+            sync_speed: Some(104857),
+            mismatch_cnt: Some(0),
+            members: vec![
+                MdMember { name: "sda1".to_string(), state: vec!["in_sync".to_string()], slot: Some(0), errors: Some(0) },
+                MdMember { name: "sdb1".to_string(), state: vec!["faulty".to_string(), "write_mostly".to_string()], slot: None, errors: Some(12) },
+            ],
+        });
+        assert!(md.is_degraded());
+        assert_eq!(md.faulty_members(), vec!["sdb1"]);
+    }
+    #[test]
+    fn create_sys_block_loop_devices() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_block_device(&test_path, "loop0");
+        create_mock_block_device(&test_path, "loop1");
+        create_dir_all(format!("{}/block/loop0/loop", test_path)).expect("Error creating mock sysfs directories.");
+        for (file, contents) in [("backing_file", "/var/lib/snapd/snaps/core22_1380.snap"), ("offset", "0"), ("sizelimit", "0"), ("autoclear", "1"), ("partscan", "0"), ("dio", "0")] {
+            write(format!("{}/block/loop0/loop/{}", test_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("error writing to mock sysfs loop/{} file.", file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.device("loop0").unwrap().loop_device, Some(LoopDevice {
+            backing_file: "/var/lib/snapd/snaps/core22_1380.snap".to_string(),
+            offset: 0,
+            sizelimit: 0,
+            autoclear: true,
+            partscan: false,
+            dio: Some(false),
+        }));
+        assert_eq!(result.device("loop1").unwrap().loop_device, None);
+    }
+    #[test]
+    fn create_sys_block_devices_with_missing_files() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_block_device(&test_path, "sda");
+        create_dir_all(format!("{}/block/md0", test_path)).expect("Error creating mock sysfs directories.");
+        write(format!("{}/block/md0/dev", test_path), "9:0\n").expect("error writing to mock sysfs dev file.");
+        write(format!("{}/block/md0/size", test_path), "not a number\n").expect("error writing to mock sysfs size file.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(&test_path).unwrap();
+
+        assert_eq!(result.device("sda").unwrap().warnings, Vec::<String>::new());
+        let md0 = result.device("md0").unwrap();
+        assert_eq!((md0.dev_block_major, md0.dev_block_minor, md0.size), (9, 0, 0));
+        assert!(md0.warnings.iter().any(|warning| warning.contains(&format!("{}/block/md0/queue/dax", test_path))));
+        assert!(md0.warnings.contains(&format!("Error during parsing string to integer in file {}/block/md0/size", test_path)));
+    }
+    #[test]
+    fn create_sys_block_device_and_read_selected_groups() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_block_device(&test_path, "sda");
+        write(format!("{}/block/sda/inflight", test_path), "       1        2\n").expect("error writing to mock sysfs inflight file.");
+        // an unreadable queue file is not noticed if the queue group is not read.
+        remove_file(format!("{}/block/sda/queue/rotational", test_path)).expect("error removing mock sysfs queue/rotational file.");
+
+        let result = Builder::new().path(&test_path).groups(&[FieldGroup::Stat, FieldGroup::Inflight]).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        let device = &result.block_devices[0];
+        assert_eq!(device.device_name, "sda");
+        assert_eq!((device.dev_block_major, device.dev_block_minor), (9, 0));
+        assert_eq!(device.stat_reads_completed_success, 9718);
+        assert_eq!((device.inflight_reads, device.inflight_writes), (1, 2));
+        assert_eq!(device.size, 0);
+        assert_eq!(device.queue_nr_requests, 0);
+        assert_eq!(device.queue_scheduler, "");
+        assert!(device.warnings.is_empty());
+    }
+    #[test]
+    fn classify_block_devices_by_device_path() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/block", test_path)).expect("Error creating mock sysfs directories.");
+        for (device, device_directory) in [
+            ("sda", "devices/pci0000:00/0000:00:1f.2/ata1/host0/target0:0:0/0:0:0:0"),
+            ("sdb", "devices/pci0000:00/0000:00:05.0/virtio2/host1/target1:0:0/1:0:0:0"),
+            ("vda", "devices/pci0000:00/0000:00:04.0/virtio1"),
+            ("nvme0n1", "devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0"),
+            ("dm-0", "devices/virtual"),
+            ("md0", "devices/virtual"),
+            ("loop0", "devices/virtual"),
+            ("zram0", "devices/virtual"),
+            ("sr0", "devices/pci0000:00/0000:00:1f.2/ata2/host1/target1:0:0/1:0:0:0"),
+            ("nbd0", "devices/virtual"),
+            ("nvme1n1", "devices/virtual/nvme-subsystem/nvme-subsys1"),
+            ("nvme2n1", "devices/virtual/nvme-fabrics/ctl/nvme2"),
+        ] {
+            create_mock_block_device(&format!("{}/{}", test_path, device_directory), device);
+            symlink(format!("../{}/block/{}", device_directory, device), format!("{}/block/{}", test_path, device)).expect("Error creating mock symlink.");
+        }
+
+        let result = Builder::new().path(&test_path).regex("").read().unwrap();
+
+        remove_dir_all(&test_path).unwrap();
+
+        for (device, device_class) in [("sda", DeviceClass::Scsi), ("sdb", DeviceClass::Scsi), ("vda", DeviceClass::Virtio), ("nvme0n1", DeviceClass::Nvme), ("dm-0", DeviceClass::DeviceMapper),
+            ("md0", DeviceClass::Md), ("loop0", DeviceClass::Loop), ("zram0", DeviceClass::Ram), ("sr0", DeviceClass::Scsi), ("nbd0", DeviceClass::Other),
+            ("nvme1n1", DeviceClass::Nvme), ("nvme2n1", DeviceClass::Nvme)] {
+            assert_eq!(result.device(device).unwrap().device_class, device_class, "device {}", device);
+        }
+        assert_eq!(result.device("vda").unwrap().device_path, Some(format!("{}/devices/pci0000:00/0000:00:04.0/virtio1/block/vda", test_path)));
+    }
+    #[test]
+    fn zoned_block_devices() {
+        let regular = BlockDevice { queue_zoned: Some("none".to_string()), queue_nr_zones: Some(0), queue_chunk_sectors: Some(0), ..Default::default() };
+        let smr = BlockDevice { queue_zoned: Some("host-managed".to_string()), queue_nr_zones: Some(55880), queue_chunk_sectors: Some(524288), ..Default::default() };
+        assert!(!regular.is_zoned());
+        assert_eq!(regular.zone_size_bytes(), None);
+        assert!(smr.is_zoned());
+        assert_eq!(smr.zone_size_bytes(), Some(268435456));
+    }
+    #[test]
+    fn follow_slaves_to_bottom_devices() {
+        let sysblock = SysBlock { block_devices: vec![
+            BlockDevice { device_name: "dm-0".to_string(), slaves: vec!["md0".to_string()], ..Default::default() },
+            BlockDevice { device_name: "md0".to_string(), slaves: vec!["sda1".to_string(), "sdb1".to_string()], holders: vec!["dm-0".to_string()], ..Default::default() },
+            BlockDevice { device_name: "sda".to_string(), ..Default::default() },
+        ] };
+        assert_eq!(sysblock.bottom_devices("dm-0"), vec!["sda1".to_string(), "sdb1".to_string()]);
+        assert_eq!(sysblock.bottom_devices("sda"), vec!["sda".to_string()]);
+    }
+    #[test]
     fn create_sys_block_device_test_filter() {
         let alignment_offset = "0";
         let cache_type = "write back";
@@ -1078,11 +2029,20 @@ mod tests {
         write(format!("{}/block/dm-0/ro", test_path), ro).expect("error writing to mock sysfs ro file.");
         write(format!("{}/block/dm-0/size", test_path), size).expect("error writing to mock sysfs size file.");
         write(format!("{}/block/dm-0/stat", test_path), stat).expect("error writing to mock sysfs stat file.");
+        create_dir_all(format!("{}/block/dm-0/dm", test_path)).expect("Error creating mock sysfs directories.");
+        write(format!("{}/block/dm-0/dm/name", test_path), "vg0-lvroot\n").expect("error writing to mock sysfs dm/name file.");
+        write(format!("{}/block/dm-0/dm/uuid", test_path), "LVM-kvvKzXYl2nqcE5ZqL5ovLTXNsiZWyxM7\n").expect("error writing to mock sysfs dm/uuid file.");
 
         let result = Builder::new().path(&test_path).read().unwrap();
+        let result_filtered = Builder::new().path(&test_path).regex("^dm-").read().unwrap();
 
         remove_dir_all(test_path).unwrap();
 
-        assert_eq!(result, SysBlock { block_devices: vec![] });
+        assert_eq!(result_filtered, SysBlock { block_devices: vec![] });
+        // dm devices are read by default.
+        let dm_device = result.device_by_dm_name("vg0-lvroot").unwrap();
+        assert_eq!(dm_device.device_name, "dm-0");
+        assert_eq!(dm_device.display_name(), "vg0-lvroot");
+        assert_eq!(dm_device.dm_uuid, Some("LVM-kvvKzXYl2nqcE5ZqL5ovLTXNsiZWyxM7".to_string()));
     }
 }
@@ -0,0 +1,315 @@
+/*!
+Read the bonding state from `/sys/class/net/<bond>/bonding` into the struct [`SysBonding`].
+
+A bond is a virtual interface that combines multiple interfaces, the slaves, for redundancy and/or throughput.
+The sysfs directory `bonding` is only present for bond interfaces, and shows the configuration and state of the bond;
+the directory `bonding_slave` of every slave interface shows the state of the slave in the bond.
+Attributes that do not apply to the mode of the bond, such as the 802.3ad (LACP) attributes for an active-backup
+bond, cannot be read or are empty, and are None.
+
+The sysfs attributes contain the same information as `/proc/net/bonding/<bond>`, but in a form that is stable and
+easy to parse.
+
+Documentation: <https://docs.kernel.org/networking/bonding.html>
+
+Here is an example obtaining the data from `/sys/class/net/<bond>/bonding`:
+```no_run
+use proc_sys_parser::bonding;
+
+let sys_bonding = bonding::read();
+
+println!("{:#?}", sys_bonding);
+```
+Example output:
+```text
+SysBonding {
+    bonds: [
+        Bond {
+            name: "bond0",
+            mode: "802.3ad",
+            mode_nr: 4,
+            mii_status: "up",
+            miimon: 100,
+            updelay: 0,
+            downdelay: 0,
+            active_slave: None,
+            primary: None,
+            xmit_hash_policy: Some("layer3+4"),
+            lacp_rate: Some("fast"),
+            ad_select: Some("stable"),
+            ad_aggregator: Some(1),
+            ad_num_ports: Some(2),
+            ad_actor_key: Some(15),
+            ad_partner_key: Some(32779),
+            ad_partner_mac: Some("00:1c:73:aa:bb:cc"),
+            slaves: [
+                BondSlave { name: "eth0", state: "active", mii_status: "up", link_failure_count: 0, perm_hwaddr: "52:54:00:12:34:56", ad_aggregator_id: Some(1) },
+                BondSlave { name: "eth1", state: "active", mii_status: "up", link_failure_count: 1, perm_hwaddr: "52:54:00:12:34:57", ad_aggregator_id: Some(1) },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysBonding`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::bonding::Builder;
+
+let sys_bonding = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_string, read_file_u64};
+
+/// Struct for holding the state of all bonds
+#[derive(Debug, PartialEq, Default)]
+pub struct SysBonding {
+    pub bonds: Vec<Bond>,
+}
+
+/// Struct for holding the state of a single bond
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Bond {
+    pub name: String,
+    /// The bonding mode name, such as balance-rr, active-backup or 802.3ad.
+    pub mode: String,
+    /// The bonding mode number, 0 to 6.
+    pub mode_nr: u64,
+    /// The link state of the bond: up or down.
+    pub mii_status: String,
+    /// The link monitoring interval in milliseconds, 0 means disabled.
+    pub miimon: u64,
+    /// The time in milliseconds to wait before enabling a slave after link up.
+    pub updelay: u64,
+    /// The time in milliseconds to wait before disabling a slave after link down.
+    pub downdelay: u64,
+    /// The slave currently active, only for active-backup, balance-tlb and balance-alb.
+    pub active_slave: Option<String>,
+    /// The preferred slave, only for active-backup, balance-tlb and balance-alb.
+    pub primary: Option<String>,
+    /// The transmit hash policy, only for balance-xor, 802.3ad and balance-tlb.
+    pub xmit_hash_policy: Option<String>,
+    /// The LACPDU rate requested from the partner: slow or fast, only for 802.3ad.
+    pub lacp_rate: Option<String>,
+    /// The aggregation selection logic, only for 802.3ad.
+    pub ad_select: Option<String>,
+    /// The id of the active aggregator, only for 802.3ad.
+    pub ad_aggregator: Option<u64>,
+    /// The number of ports in the active aggregator, only for 802.3ad.
+    pub ad_num_ports: Option<u64>,
+    pub ad_actor_key: Option<u64>,
+    pub ad_partner_key: Option<u64>,
+    /// The mac address of the link partner (switch), only for 802.3ad.
+    pub ad_partner_mac: Option<String>,
+    pub slaves: Vec<BondSlave>,
+}
+
+/// Struct for holding the state of a slave of a bond from `/sys/class/net/<slave>/bonding_slave`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BondSlave {
+    pub name: String,
+    /// The state of the slave in the bond: active or backup.
+    pub state: String,
+    /// The link state of the slave: up, going down, down or going back.
+    pub mii_status: String,
+    /// The number of times the link of the slave went down.
+    pub link_failure_count: u64,
+    /// The permanent hardware address of the slave, which can be different from the address in use.
+    pub perm_hwaddr: String,
+    /// The aggregator the slave belongs to, only for 802.3ad.
+    pub ad_aggregator_id: Option<u64>,
+}
+
+/// Builder pattern for [`SysBonding`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysBonding, ProcSysParserError> {
+        SysBonding::read_sys_bonding(format!("{}/class/net", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysBonding`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysBonding, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Bond {
+    /// The slaves of which the link is not up.
+    pub fn failed_slaves(&self) -> Vec<&BondSlave> {
+        self.slaves.iter().filter(|slave| slave.mii_status != "up").collect()
+    }
+}
+
+impl SysBonding {
+    pub fn new() -> SysBonding {
+        SysBonding::default()
+    }
+    /// The state of a bond by name, None if the bond is not found.
+    pub fn bond(&self, name: &str) -> Option<&Bond> {
+        self.bonds.iter().find(|bond| bond.name == name)
+    }
+    /// Read an attribute that is empty or cannot be read when it does not apply to the bonding mode.
+    fn read_attribute(file: &Path) -> Option<String> {
+        read_file_option_string(file).filter(|contents| !contents.trim().is_empty())
+    }
+    /// Read an attribute with the format `<name> <number>`, such as `802.3ad 4`, and return the name.
+    fn read_attribute_name(file: &Path) -> Option<String> {
+        SysBonding::read_attribute(file).and_then(|contents| contents.split_whitespace().next().map(|name| name.to_string()))
+    }
+    fn read_attribute_u64(file: &Path) -> Result<Option<u64>, ProcSysParserError> {
+        SysBonding::read_attribute(file)
+            .map(|contents| contents.trim().parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
+            .transpose()
+    }
+    fn read_bond_slave(slave_path: &Path, name: &str) -> Result<BondSlave, ProcSysParserError> {
+        Ok(BondSlave {
+            name: name.to_string(),
+            state: read_file_string(&slave_path.join("state"))?,
+            mii_status: read_file_string(&slave_path.join("mii_status"))?,
+            link_failure_count: read_file_u64(&slave_path.join("link_failure_count"))?,
+            perm_hwaddr: read_file_string(&slave_path.join("perm_hwaddr"))?,
+            ad_aggregator_id: SysBonding::read_attribute_u64(&slave_path.join("ad_aggregator_id"))?,
+        })
+    }
+    fn read_bond(sys_class_net_path: &Path, bonding_path: &Path, name: &str) -> Result<Bond, ProcSysParserError> {
+        let mode = read_file_string(&bonding_path.join("mode"))?;
+        let mut mode_fields = mode.split_whitespace();
+
+        let mut bond = Bond {
+            name: name.to_string(),
+            mode: mode_fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError {item: "bonding mode".to_string() })?
+                .to_string(),
+            mode_nr: mode_fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError {item: "bonding mode_nr".to_string() })?
+                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            mii_status: read_file_string(&bonding_path.join("mii_status"))?,
+            miimon: read_file_u64(&bonding_path.join("miimon"))?,
+            updelay: read_file_u64(&bonding_path.join("updelay"))?,
+            downdelay: read_file_u64(&bonding_path.join("downdelay"))?,
+            active_slave: SysBonding::read_attribute(&bonding_path.join("active_slave")),
+            primary: SysBonding::read_attribute(&bonding_path.join("primary")),
+            xmit_hash_policy: SysBonding::read_attribute_name(&bonding_path.join("xmit_hash_policy")),
+            lacp_rate: SysBonding::read_attribute_name(&bonding_path.join("lacp_rate")),
+            ad_select: SysBonding::read_attribute_name(&bonding_path.join("ad_select")),
+            ad_aggregator: SysBonding::read_attribute_u64(&bonding_path.join("ad_aggregator"))?,
+            ad_num_ports: SysBonding::read_attribute_u64(&bonding_path.join("ad_num_ports"))?,
+            ad_actor_key: SysBonding::read_attribute_u64(&bonding_path.join("ad_actor_key"))?,
+            ad_partner_key: SysBonding::read_attribute_u64(&bonding_path.join("ad_partner_key"))?,
+            ad_partner_mac: SysBonding::read_attribute(&bonding_path.join("ad_partner_mac")),
+            slaves: Vec::new(),
+        };
+        for slave in read_file_string(&bonding_path.join("slaves"))?.split_whitespace() {
+            bond.slaves.push(SysBonding::read_bond_slave(&sys_class_net_path.join(slave).join("bonding_slave"), slave)?);
+        }
+        Ok(bond)
+    }
+    pub fn read_sys_bonding(sys_class_net_path: &str) -> Result<SysBonding, ProcSysParserError> {
+        let mut sysbonding = SysBonding::new();
+
+        let interface_directories = read_dir(sys_class_net_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_net_path.to_string(), error })?;
+
+        for interface in interface_directories.filter_map(|entry| entry.ok()) {
+            let bonding_path = interface.path().join("bonding");
+            if !bonding_path.is_dir() { continue };
+            sysbonding.bonds.push(SysBonding::read_bond(Path::new(sys_class_net_path), &bonding_path, &interface.file_name().to_string_lossy())?);
+        }
+        sysbonding.bonds.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysbonding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_bonding_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/net/bond0/bonding", test_path), &[
+            ("mode", "active-backup 1"),
+            ("mii_status", "up"),
+            ("miimon", "100"),
+            ("updelay", "0"),
+            ("downdelay", "0"),
+            ("active_slave", "eth0"),
+            ("primary", ""),
+            ("slaves", "eth0 eth1"),
+        ]);
+        create_mock_files(&format!("{}/class/net/eth0/bonding_slave", test_path), &[
+            ("state", "active"),
+            ("mii_status", "up"),
+            ("link_failure_count", "0"),
+            ("perm_hwaddr", "52:54:00:12:34:56"),
+        ]);
+        create_mock_files(&format!("{}/class/net/eth1/bonding_slave", test_path), &[
+            ("state", "backup"),
+            ("mii_status", "down"),
+            ("link_failure_count", "3"),
+            ("perm_hwaddr", "52:54:00:12:34:57"),
+        ]);
+        // an interface that is not a bond
+        create_dir_all(format!("{}/class/net/eth2", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysBonding { bonds: vec![
+            Bond {
+                name: "bond0".to_string(),
+                mode: "active-backup".to_string(),
+                mode_nr: 1,
+                mii_status: "up".to_string(),
+                miimon: 100,
+                updelay: 0,
+                downdelay: 0,
+                active_slave: Some("eth0".to_string()),
+                primary: None,
+                xmit_hash_policy: None,
+                lacp_rate: None,
+                ad_select: None,
+                ad_aggregator: None,
+                ad_num_ports: None,
+                ad_actor_key: None,
+                ad_partner_key: None,
+                ad_partner_mac: None,
+                slaves: vec![
+                    BondSlave { name: "eth0".to_string(), state: "active".to_string(), mii_status: "up".to_string(), link_failure_count: 0, perm_hwaddr: "52:54:00:12:34:56".to_string(), ad_aggregator_id: None },
+                    BondSlave { name: "eth1".to_string(), state: "backup".to_string(), mii_status: "down".to_string(), link_failure_count: 3, perm_hwaddr: "52:54:00:12:34:57".to_string(), ad_aggregator_id: None },
+                ],
+            }
+        ]});
+        assert_eq!(result.bond("bond0").unwrap().failed_slaves()[0].name, "eth1");
+    }
+}
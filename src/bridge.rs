@@ -0,0 +1,287 @@
+/*!
+Read the bridge configuration and ports from `/sys/class/net/<bridge>/bridge` and `/sys/class/net/<bridge>/brif`
+into the struct [`SysBridge`].
+
+A bridge is a virtual switch connecting interfaces, which is commonly used on virtualization hosts to connect the
+interfaces of virtual machines and containers to the network. The `bridge` directory of a bridge interface shows the
+configuration of the bridge and the spanning tree protocol (STP) state; the `brif` directory contains a directory for
+every port (member interface) of the bridge, showing the STP state of the port.
+
+The timers (forward_delay, hello_time, max_age and ageing_time) are shown in clock ticks in the files, and are
+converted into milliseconds using the `CLK_TCK` sysconf variable, just like the stat module does for the cpu times.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net> and
+<https://docs.kernel.org/networking/bridge.html>
+
+Here is an example obtaining the data from `/sys/class/net/<bridge>/bridge`:
+```no_run
+use proc_sys_parser::bridge;
+
+let sys_bridge = bridge::read();
+
+println!("{:#?}", sys_bridge);
+```
+Example output:
+```text
+SysBridge {
+    bridges: [
+        Bridge {
+            name: "virbr0",
+            bridge_id: "8000.525400a1b2c3",
+            root_id: "8000.525400a1b2c3",
+            stp_state: 1,
+            priority: 32768,
+            forward_delay: 15000,
+            hello_time: 2000,
+            max_age: 20000,
+            ageing_time: 300000,
+            vlan_filtering: 0,
+            multicast_snooping: Some(1),
+            ports: [
+                BridgePort { name: "vnet0", port_no: 1, state: Forwarding, path_cost: 100, priority: 32 },
+            ],
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysBridge`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::bridge::Builder;
+
+let sys_bridge = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use nix::unistd::{sysconf, SysconfVar};
+use crate::ProcSysParserError;
+use crate::utils::{read_file_hex_u64, read_file_option_u64, read_file_string, read_file_u64};
+
+/// Struct for holding all bridges
+#[derive(Debug, PartialEq, Default)]
+pub struct SysBridge {
+    pub bridges: Vec<Bridge>,
+}
+
+/// Struct for holding the configuration and ports of a single bridge
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Bridge {
+    pub name: String,
+    /// The bridge id: priority and mac address.
+    pub bridge_id: String,
+    /// The bridge id of the root bridge of the spanning tree.
+    pub root_id: String,
+    /// The STP state: 0 is disabled, 1 is kernel STP, 2 is user space STP.
+    pub stp_state: u64,
+    pub priority: u64,
+    /// The forward delay in milliseconds.
+    pub forward_delay: u64,
+    /// The hello time in milliseconds.
+    pub hello_time: u64,
+    /// The maximal message age in milliseconds.
+    pub max_age: u64,
+    /// The time in milliseconds a mac address is kept in the forwarding database.
+    pub ageing_time: u64,
+    /// 1 if vlan filtering is enabled.
+    pub vlan_filtering: u64,
+    /// 1 if IGMP/MLD snooping is enabled, None if the kernel is built without multicast snooping support.
+    pub multicast_snooping: Option<u64>,
+    pub ports: Vec<BridgePort>,
+}
+
+/// Struct for holding a single port of a bridge
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BridgePort {
+    pub name: String,
+    pub port_no: u64,
+    pub state: BridgePortState,
+    pub path_cost: u64,
+    pub priority: u64,
+}
+
+/// The STP state of a bridge port
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub enum BridgePortState {
+    #[default]
+    Disabled,
+    Listening,
+    Learning,
+    Forwarding,
+    Blocking,
+    Unknown(u64),
+}
+
+/// Builder pattern for [`SysBridge`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysBridge, ProcSysParserError> {
+        SysBridge::read_sys_bridge(format!("{}/class/net", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysBridge`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysBridge, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl From<u64> for BridgePortState {
+    fn from(state: u64) -> Self {
+        match state {
+            0 => BridgePortState::Disabled,
+            1 => BridgePortState::Listening,
+            2 => BridgePortState::Learning,
+            3 => BridgePortState::Forwarding,
+            4 => BridgePortState::Blocking,
+            state => BridgePortState::Unknown(state),
+        }
+    }
+}
+
+impl SysBridge {
+    pub fn new() -> SysBridge {
+        SysBridge::default()
+    }
+    /// A bridge by name, None if the bridge is not found.
+    pub fn bridge(&self, name: &str) -> Option<&Bridge> {
+        self.bridges.iter().find(|bridge| bridge.name == name)
+    }
+    fn read_bridge_port(brport_path: &Path, name: &str) -> Result<BridgePort, ProcSysParserError> {
+        Ok(BridgePort {
+            name: name.to_string(),
+            port_no: read_file_hex_u64(&brport_path.join("port_no"))?,
+            state: BridgePortState::from(read_file_u64(&brport_path.join("state"))?),
+            path_cost: read_file_u64(&brport_path.join("path_cost"))?,
+            priority: read_file_u64(&brport_path.join("priority"))?,
+        })
+    }
+    fn read_bridge(interface_path: &Path, name: &str) -> Result<Bridge, ProcSysParserError> {
+        // Note: the timers are in clock ticks, CLK_TCK is set by CONFIG_HZ and is 100 on most enterprise linuxes.
+        let clock_time = sysconf(SysconfVar::CLK_TCK).unwrap_or(Some(100)).unwrap_or(100) as u64;
+        let to_milliseconds = |ticks: u64| ticks * 1000 / clock_time;
+        let bridge_path = interface_path.join("bridge");
+
+        let mut bridge = Bridge {
+            name: name.to_string(),
+            bridge_id: read_file_string(&bridge_path.join("bridge_id"))?,
+            root_id: read_file_string(&bridge_path.join("root_id"))?,
+            stp_state: read_file_u64(&bridge_path.join("stp_state"))?,
+            priority: read_file_u64(&bridge_path.join("priority"))?,
+            forward_delay: to_milliseconds(read_file_u64(&bridge_path.join("forward_delay"))?),
+            hello_time: to_milliseconds(read_file_u64(&bridge_path.join("hello_time"))?),
+            max_age: to_milliseconds(read_file_u64(&bridge_path.join("max_age"))?),
+            ageing_time: to_milliseconds(read_file_u64(&bridge_path.join("ageing_time"))?),
+            vlan_filtering: read_file_u64(&bridge_path.join("vlan_filtering"))?,
+            multicast_snooping: read_file_option_u64(&bridge_path.join("multicast_snooping"))?,
+            ports: Vec::new(),
+        };
+        let brif_path = interface_path.join("brif");
+        let brif_directories = read_dir(&brif_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: brif_path.to_string_lossy().to_string(), error })?;
+        for port in brif_directories.filter_map(|entry| entry.ok()) {
+            bridge.ports.push(SysBridge::read_bridge_port(&port.path(), &port.file_name().to_string_lossy())?);
+        }
+        bridge.ports.sort_by_key(|port| port.port_no);
+        Ok(bridge)
+    }
+    pub fn read_sys_bridge(sys_class_net_path: &str) -> Result<SysBridge, ProcSysParserError> {
+        let mut sysbridge = SysBridge::new();
+
+        let interface_directories = read_dir(sys_class_net_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_net_path.to_string(), error })?;
+
+        for interface in interface_directories.filter_map(|entry| entry.ok()) {
+            if !interface.path().join("bridge").is_dir() { continue };
+            sysbridge.bridges.push(SysBridge::read_bridge(&interface.path(), &interface.file_name().to_string_lossy())?);
+        }
+        sysbridge.bridges.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysbridge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    // the timers are in clock ticks, which are defined in the getconf value CLK_TCK.
+    // the common value of CLK_TCK is 100, which is a hard assumption here.
+    #[test]
+    fn create_sys_bridge_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/net/br0/bridge", test_path), &[
+            ("bridge_id", "8000.525400a1b2c3"),
+            ("root_id", "8000.525400a1b2c3"),
+            ("stp_state", "0"),
+            ("priority", "32768"),
+            ("forward_delay", "1500"),
+            ("hello_time", "200"),
+            ("max_age", "2000"),
+            ("ageing_time", "30000"),
+            ("vlan_filtering", "1"),
+        ]);
+        create_mock_files(&format!("{}/class/net/br0/brif/vnet1", test_path), &[
+            ("port_no", "0x2"),
+            ("state", "4"),
+            ("path_cost", "100"),
+            ("priority", "32"),
+        ]);
+        create_mock_files(&format!("{}/class/net/br0/brif/vnet0", test_path), &[
+            ("port_no", "0x1"),
+            ("state", "3"),
+            ("path_cost", "2"),
+            ("priority", "32"),
+        ]);
+        create_dir_all(format!("{}/class/net/eth0", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysBridge { bridges: vec![
+            Bridge {
+                name: "br0".to_string(),
+                bridge_id: "8000.525400a1b2c3".to_string(),
+                root_id: "8000.525400a1b2c3".to_string(),
+                stp_state: 0,
+                priority: 32768,
+                forward_delay: 15000,
+                hello_time: 2000,
+                max_age: 20000,
+                ageing_time: 300000,
+                vlan_filtering: 1,
+                multicast_snooping: None,
+                ports: vec![
+                    BridgePort { name: "vnet0".to_string(), port_no: 1, state: BridgePortState::Forwarding, path_cost: 2, priority: 32 },
+                    BridgePort { name: "vnet1".to_string(), port_no: 2, state: BridgePortState::Blocking, path_cost: 100, priority: 32 },
+                ],
+            }
+        ]});
+        assert!(result.bridge("eth0").is_none());
+    }
+}
@@ -0,0 +1,246 @@
+/*!
+Read the cgroup v1 blkio controller files of a cgroup into the struct [`CgroupBlkio`].
+
+The blkio controller of cgroup v1 shows the IO of the processes in a cgroup per device, with lines containing the
+major:minor number of the device, the operation and the value:
+```text
+8:0 Read 2150400
+8:0 Write 1105920
+8:0 Sync 2920448
+8:0 Async 335872
+8:0 Total 3256320
+Total 3256320
+```
+The following files are read:
+- `blkio.throttle.io_service_bytes`: the number of bytes read and written.
+- `blkio.throttle.io_serviced`: the number of read and write requests.
+- `blkio.io_wait_time`: the time requests waited in the scheduler queue in nanoseconds. This is only available with the
+  CFQ scheduler, which was removed in linux 5.0, so these are None on most systems.
+
+The `throttle` files are used, because these account the IO of all schedulers, including the IO of devices without
+a scheduler (`none`), unlike `blkio.io_service_bytes`. The Discard operation is shown since linux 4.19.
+
+On a host that only uses cgroup v2, the IO is shown in `io.stat` of the io controller.
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v1/blkio-controller.html>
+
+Here is an example obtaining the blkio data of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::blkio;
+
+let cgroup_blkio = blkio::read();
+
+println!("{:#?}", cgroup_blkio);
+```
+Example output:
+```text
+CgroupBlkio {
+    path: "/system.slice/docker-4f1c.scope",
+    devices: [
+        BlkioDevice {
+            block_major: 8,
+            block_minor: 0,
+            read_bytes: 2150400,
+            write_bytes: 1105920,
+            discard_bytes: Some(0),
+            read_ios: 73,
+            write_ios: 22,
+            discard_ios: Some(0),
+            io_wait_time_read_ns: None,
+            io_wait_time_write_ns: None,
+        },
+    ],
+}
+```
+
+If you want to read another cgroup, and/or change the path that is read for the mounts and the cgroup of the
+process, which is `/proc` by default, or set the mount point of the blkio hierarchy, use:
+```no_run
+use proc_sys_parser::cgroup::blkio::Builder;
+
+let cgroup_blkio = Builder::new().path("/myproc").cgroup("/system.slice/sshd.service").read();
+let cgroup_blkio = Builder::new().mount_point("/mysys/fs/cgroup/blkio").cgroup("/system.slice").read();
+```
+*/
+use std::collections::BTreeMap;
+use crate::ProcSysParserError;
+use crate::cgroup::Cgroup;
+
+/// Struct for holding the blkio data of a cgroup v1 cgroup
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupBlkio {
+    /// The path of the cgroup relative to the mount point.
+    pub path: String,
+    /// The devices the cgroup did IO to, sorted by major and minor number.
+    pub devices: Vec<BlkioDevice>,
+}
+
+/// Struct for holding the IO of a cgroup to a single device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct BlkioDevice {
+    pub block_major: u64,
+    pub block_minor: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    /// Kernel 4.19+
+    pub discard_bytes: Option<u64>,
+    pub read_ios: u64,
+    pub write_ios: u64,
+    /// Kernel 4.19+
+    pub discard_ios: Option<u64>,
+    /// CFQ scheduler only.
+    pub io_wait_time_read_ns: Option<u64>,
+    /// CFQ scheduler only.
+    pub io_wait_time_write_ns: Option<u64>,
+}
+
+/// The values of a blkio file by major and minor number, and by operation.
+type BlkioValues = BTreeMap<(u64, u64), BTreeMap<String, u64>>;
+
+/// Builder pattern for [`CgroupBlkio`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the blkio hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    /// The path of the cgroup; if empty, it is the cgroup of the process itself.
+    pub cgroup_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            cgroup_path: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    pub fn cgroup(mut self, cgroup_path: &str) -> Builder {
+        self.cgroup_path = cgroup_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupBlkio, ProcSysParserError> {
+        CgroupBlkio::read_cgroup_blkio(&Cgroup::resolve_v1(&self.proc_path, "blkio", &self.mount_point, &self.cgroup_path)?)
+    }
+}
+
+/// The main function for building a [`CgroupBlkio`] struct with current data of the cgroup of the process itself.
+/// This uses the Builder pattern, which allows settings such as the cgroup to specified.
+pub fn read() -> Result<CgroupBlkio, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Read the cgroup v1 blkio controller files of the cgroup, which must be a cgroup of the blkio hierarchy.
+    pub fn blkio(&self) -> Result<CgroupBlkio, ProcSysParserError> {
+        CgroupBlkio::read_cgroup_blkio(self)
+    }
+}
+
+impl CgroupBlkio {
+    pub fn new() -> CgroupBlkio {
+        CgroupBlkio::default()
+    }
+    /// The device with the major and minor number.
+    pub fn device(&self, major: u64, minor: u64) -> Option<&BlkioDevice> {
+        self.devices.iter().find(|device| (device.block_major, device.block_minor) == (major, minor))
+    }
+    /// Parse a blkio file with `major:minor operation value` lines. The `Total` line of all devices is skipped.
+    pub fn parse_blkio_file(blkio_file: &str, file: &str) -> Result<BlkioValues, ProcSysParserError> {
+        let mut values = BlkioValues::new();
+
+        for line in blkio_file.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 3 { continue };
+            let (major, minor) = fields[0].split_once(':')
+                .ok_or(ProcSysParserError::FindItemError { item: format!("major:minor in '{}' in {}", line, file) })?;
+            let parse_u64 = |value: &str| value.parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("'{}' in {}", line, file), error });
+            values.entry((parse_u64(major)?, parse_u64(minor)?))
+                .or_default()
+                .insert(fields[1].to_string(), parse_u64(fields[2])?);
+        }
+        Ok(values)
+    }
+    pub fn read_cgroup_blkio(cgroup: &Cgroup) -> Result<CgroupBlkio, ProcSysParserError> {
+        let read_blkio_file = |file: &str| CgroupBlkio::parse_blkio_file(&cgroup.read_file(file)?, &cgroup.file_path(file));
+        let service_bytes = read_blkio_file("blkio.throttle.io_service_bytes")?;
+        let serviced = read_blkio_file("blkio.throttle.io_serviced")?;
+        let wait_time = if cgroup.has_file("blkio.io_wait_time") { read_blkio_file("blkio.io_wait_time")? } else { BlkioValues::new() };
+
+        let mut cgroupblkio = CgroupBlkio::new();
+        cgroupblkio.path = cgroup.path.clone();
+
+        let mut devices: Vec<(u64, u64)> = service_bytes.keys().chain(serviced.keys()).copied().collect();
+        devices.sort();
+        devices.dedup();
+        for (major, minor) in devices {
+            let value = |values: &BlkioValues, operation: &str| values.get(&(major, minor)).and_then(|operations| operations.get(operation).copied());
+            cgroupblkio.devices.push(BlkioDevice {
+                block_major: major,
+                block_minor: minor,
+                read_bytes: value(&service_bytes, "Read").unwrap_or_default(),
+                write_bytes: value(&service_bytes, "Write").unwrap_or_default(),
+                discard_bytes: value(&service_bytes, "Discard"),
+                read_ios: value(&serviced, "Read").unwrap_or_default(),
+                write_ios: value(&serviced, "Write").unwrap_or_default(),
+                discard_ios: value(&serviced, "Discard"),
+                io_wait_time_read_ns: value(&wait_time, "Read"),
+                io_wait_time_write_ns: value(&wait_time, "Write"),
+            });
+        }
+        Ok(cgroupblkio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_corrupted_blkio_file() {
+        assert!(CgroupBlkio::parse_blkio_file("8-0 Read 100\n", "blkio.throttle.io_serviced").is_err());
+        assert!(CgroupBlkio::parse_blkio_file("8:0 Read x\n", "blkio.throttle.io_serviced").is_err());
+        assert_eq!(CgroupBlkio::parse_blkio_file("Total 0\n", "blkio.throttle.io_serviced").unwrap(), BlkioValues::new());
+    }
+
+    #[test]
+    fn create_cgroup_blkio_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup/blkio", test_path);
+        let cgroup_directory = format!("{}/system.slice", mount_point);
+        create_dir_all(&cgroup_directory).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("blkio.throttle.io_service_bytes", "253:0 Read 2150400\n253:0 Write 1105920\n253:0 Sync 2920448\n253:0 Async 335872\n253:0 Total 3256320\n8:0 Read 4096\n8:0 Write 0\n8:0 Sync 4096\n8:0 Async 0\n8:0 Total 4096\nTotal 3260416"),
+            ("blkio.throttle.io_serviced", "253:0 Read 73\n253:0 Write 22\n253:0 Sync 90\n253:0 Async 5\n253:0 Total 95\n8:0 Read 1\n8:0 Write 0\n8:0 Sync 1\n8:0 Async 0\n8:0 Total 1\nTotal 96"),
+        ] {
+            write(format!("{}/{}", cgroup_directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", cgroup_directory, file));
+        }
+
+        let result = Builder::new().mount_point(&mount_point).cgroup("/system.slice").read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, CgroupBlkio {
+            path: "/system.slice".to_string(),
+            devices: vec![
+                BlkioDevice { block_major: 8, block_minor: 0, read_bytes: 4096, write_bytes: 0, discard_bytes: None, read_ios: 1, write_ios: 0, discard_ios: None, io_wait_time_read_ns: None, io_wait_time_write_ns: None },
+                BlkioDevice { block_major: 253, block_minor: 0, read_bytes: 2150400, write_bytes: 1105920, discard_bytes: None, read_ios: 73, write_ios: 22, discard_ios: None, io_wait_time_read_ns: None, io_wait_time_write_ns: None },
+            ],
+        });
+        assert_eq!(result.device(253, 0).unwrap().read_ios, 73);
+    }
+}
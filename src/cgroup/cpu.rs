@@ -0,0 +1,257 @@
+/*!
+Read the cpu and cpuset controller interface files of a cgroup v2 cgroup into the struct [`CgroupCpu`].
+
+The cpu controller limits the cpu time of a cgroup with `cpu.max`, which contains the quota and the period in
+microseconds: the cgroup can use at most quota microseconds of cpu time every period, so a quota of 200000 with a
+period of 100000 limits the cgroup to 2 cpus. A quota of `max` means no limit. `cpu.weight` is the relative share of
+cpu time between sibling cgroups when the cpus are contended (1-10000, default 100).
+
+The cpuset controller restricts the cpus and memory nodes a cgroup can use. `cpuset.cpus` and `cpuset.mems` are the
+requested cpus and nodes, which are empty if not set, and `cpuset.cpus.effective` and `cpuset.mems.effective` are the
+cpus and nodes that are actually granted by the parent. The lists, such as `0-3,8`, are expanded into the numbers.
+
+The usage is shown in `cpu.stat`: the cpu time of the cgroup, and if the cpu controller is enabled, the number of
+periods in which the cgroup was throttled.
+
+The files of a controller are only available if the controller is enabled for the cgroup; these are None otherwise.
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files>
+
+Here is an example obtaining the cpu data of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::cpu;
+
+let cgroup_cpu = cpu::read();
+
+println!("{:#?}", cgroup_cpu);
+```
+Example output:
+```text
+CgroupCpu {
+    path: "/system.slice/docker-4f1c.scope",
+    max: Some(CpuMax { quota_us: Some(200000), period_us: 100000 }),
+    weight: Some(100),
+    cpuset_cpus: Some([]),
+    cpuset_cpus_effective: Some([0, 1, 2, 3, 4, 5, 6, 7]),
+    cpuset_mems: Some([]),
+    cpuset_mems_effective: Some([0]),
+    stat: CgroupCpuStat { usage_usec: 81929221, user_usec: 60722114, system_usec: 21207107, nr_periods: Some(18221), nr_throttled: Some(411), throttled_usec: Some(20911034) },
+}
+```
+
+If you want to read another cgroup, and/or change the path that is read for the mounts and the cgroup of the
+process, which is `/proc` by default, or set the mount point of the unified hierarchy, use:
+```no_run
+use proc_sys_parser::cgroup::cpu::Builder;
+
+let cgroup_cpu = Builder::new().path("/myproc").cgroup("/system.slice/sshd.service").read();
+let cgroup_cpu = Builder::new().mount_point("/mysys/fs/cgroup").cgroup("/system.slice").read();
+```
+*/
+use crate::ProcSysParserError;
+use crate::cgroup::{parse_flat_keyed, parse_limit, Cgroup};
+use crate::utils::parse_cpu_list;
+
+/// Struct for holding the cpu and cpuset data of a cgroup
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupCpu {
+    /// The path of the cgroup relative to the mount point.
+    pub path: String,
+    /// `cpu.max`: None if the cpu controller is not enabled.
+    pub max: Option<CpuMax>,
+    /// `cpu.weight`: None if the cpu controller is not enabled.
+    pub weight: Option<u64>,
+    /// `cpuset.cpus`: empty if not set. None if the cpuset controller is not enabled.
+    pub cpuset_cpus: Option<Vec<u32>>,
+    /// `cpuset.cpus.effective`: None if the cpuset controller is not enabled.
+    pub cpuset_cpus_effective: Option<Vec<u32>>,
+    /// `cpuset.mems`: empty if not set. None if the cpuset controller is not enabled.
+    pub cpuset_mems: Option<Vec<u32>>,
+    /// `cpuset.mems.effective`: None if the cpuset controller is not enabled.
+    pub cpuset_mems_effective: Option<Vec<u32>>,
+    pub stat: CgroupCpuStat,
+}
+
+/// Struct for holding `cpu.max`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuMax {
+    /// None if `max` (no limit).
+    pub quota_us: Option<u64>,
+    pub period_us: u64,
+}
+
+/// Struct for holding `cpu.stat`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupCpuStat {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    /// The number of periods, None if the cpu controller is not enabled.
+    pub nr_periods: Option<u64>,
+    /// The number of periods in which the cgroup used its quota and was throttled.
+    pub nr_throttled: Option<u64>,
+    pub throttled_usec: Option<u64>,
+}
+
+/// Builder pattern for [`CgroupCpu`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the unified hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    /// The path of the cgroup; if empty, it is the cgroup of the process itself.
+    pub cgroup_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            cgroup_path: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    pub fn cgroup(mut self, cgroup_path: &str) -> Builder {
+        self.cgroup_path = cgroup_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupCpu, ProcSysParserError> {
+        CgroupCpu::read_cgroup_cpu(&Cgroup::resolve(&self.proc_path, &self.mount_point, &self.cgroup_path)?)
+    }
+}
+
+/// The main function for building a [`CgroupCpu`] struct with current data of the cgroup of the process itself.
+/// This uses the Builder pattern, which allows settings such as the cgroup to specified.
+pub fn read() -> Result<CgroupCpu, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Read the cpu and cpuset controller interface files of the cgroup.
+    pub fn cpu(&self) -> Result<CgroupCpu, ProcSysParserError> {
+        CgroupCpu::read_cgroup_cpu(self)
+    }
+}
+
+impl CpuMax {
+    /// The quota as a number of cpus, such as 2.0 for a quota of 200000 and a period of 100000; None if no limit.
+    pub fn cpus(&self) -> Option<f64> {
+        if self.period_us == 0 { return None };
+        self.quota_us.map(|quota_us| quota_us as f64 / self.period_us as f64)
+    }
+}
+
+impl CgroupCpu {
+    pub fn new() -> CgroupCpu {
+        CgroupCpu::default()
+    }
+    pub fn parse_cpu_max(cpu_max: &str) -> Result<CpuMax, ProcSysParserError> {
+        let mut fields = cpu_max.split_whitespace();
+        Ok(CpuMax {
+            quota_us: parse_limit(fields.next().ok_or(ProcSysParserError::IteratorItemError { item: "cgroup cpu.max quota".to_string() })?, "cpu.max quota")?,
+            period_us: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: "cgroup cpu.max period".to_string() })?
+                .parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: "cpu.max period".to_string(), error })?,
+        })
+    }
+    pub fn parse_cpu_stat(cpu_stat: &str) -> Result<CgroupCpuStat, ProcSysParserError> {
+        let mut entries = parse_flat_keyed(cpu_stat, "cpu.stat")?;
+        let mut entry = |name: &str| entries.remove(name);
+
+        Ok(CgroupCpuStat {
+            usage_usec: entry("usage_usec").ok_or(ProcSysParserError::FindItemError { item: "cpu.stat usage_usec".to_string() })?,
+            user_usec: entry("user_usec").unwrap_or_default(),
+            system_usec: entry("system_usec").unwrap_or_default(),
+            nr_periods: entry("nr_periods"),
+            nr_throttled: entry("nr_throttled"),
+            throttled_usec: entry("throttled_usec"),
+        })
+    }
+    pub fn read_cgroup_cpu(cgroup: &Cgroup) -> Result<CgroupCpu, ProcSysParserError> {
+        let read_option_list = |file: &str| -> Result<Option<Vec<u32>>, ProcSysParserError> {
+            if !cgroup.has_file(file) { return Ok(None) };
+            parse_cpu_list(&cgroup.read_file(file)?).map(Some)
+        };
+
+        Ok(CgroupCpu {
+            path: cgroup.path.clone(),
+            max: if cgroup.has_file("cpu.max") { Some(CgroupCpu::parse_cpu_max(&cgroup.read_file("cpu.max")?)?) } else { None },
+            weight: if cgroup.has_file("cpu.weight") {
+                Some(cgroup.read_file("cpu.weight")?.trim().parse::<u64>()
+                    .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path("cpu.weight"), error })?)
+            } else {
+                None
+            },
+            cpuset_cpus: read_option_list("cpuset.cpus")?,
+            cpuset_cpus_effective: read_option_list("cpuset.cpus.effective")?,
+            cpuset_mems: read_option_list("cpuset.mems")?,
+            cpuset_mems_effective: read_option_list("cpuset.mems.effective")?,
+            stat: CgroupCpu::parse_cpu_stat(&cgroup.read_file("cpu.stat")?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_cgroup_cpu_max() {
+        assert_eq!(CgroupCpu::parse_cpu_max("200000 100000").unwrap(), CpuMax { quota_us: Some(200000), period_us: 100000 });
+        assert_eq!(CgroupCpu::parse_cpu_max("max 100000\n").unwrap().cpus(), None);
+        assert_eq!(CgroupCpu::parse_cpu_max("150000 100000").unwrap().cpus(), Some(1.5));
+        assert!(CgroupCpu::parse_cpu_max("max").is_err());
+    }
+
+    #[test]
+    fn create_cgroup_cpu_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup", test_path);
+        create_dir_all(format!("{}/system.slice", mount_point)).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("cpu.stat", "usage_usec 81929221\nuser_usec 60722114\nsystem_usec 21207107"),
+            ("system.slice/cpu.max", "200000 100000"),
+            ("system.slice/cpu.weight", "100"),
+            ("system.slice/cpuset.cpus", ""),
+            ("system.slice/cpuset.cpus.effective", "0-3,6"),
+            ("system.slice/cpuset.mems", ""),
+            ("system.slice/cpuset.mems.effective", "0"),
+            ("system.slice/cpu.stat", "usage_usec 81929221\nuser_usec 60722114\nsystem_usec 21207107\nnr_periods 18221\nnr_throttled 411\nthrottled_usec 20911034"),
+        ] {
+            write(format!("{}/{}", mount_point, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", mount_point, file));
+        }
+
+        let result = Builder::new().mount_point(&mount_point).cgroup("/system.slice").read().unwrap();
+        let result_root = Builder::new().mount_point(&mount_point).cgroup("/").read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, CgroupCpu {
+            path: "/system.slice".to_string(),
+            max: Some(CpuMax { quota_us: Some(200000), period_us: 100000 }),
+            weight: Some(100),
+            cpuset_cpus: Some(vec![]),
+            cpuset_cpus_effective: Some(vec![0, 1, 2, 3, 6]),
+            cpuset_mems: Some(vec![]),
+            cpuset_mems_effective: Some(vec![0]),
+            stat: CgroupCpuStat { usage_usec: 81929221, user_usec: 60722114, system_usec: 21207107, nr_periods: Some(18221), nr_throttled: Some(411), throttled_usec: Some(20911034) },
+        });
+        assert_eq!(result_root.max, None);
+        assert_eq!(result_root.cpuset_cpus_effective, None);
+        assert_eq!(result_root.stat.nr_periods, None);
+    }
+}
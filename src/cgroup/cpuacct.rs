@@ -0,0 +1,191 @@
+/*!
+Read the cgroup v1 cpuacct controller files of a cgroup into the struct [`CgroupCpuacct`].
+
+The cpuacct (cpu accounting) controller of cgroup v1 shows the cpu time used by the processes in a cgroup and its
+descendants:
+- `cpuacct.usage`: the total cpu time in nanoseconds.
+- `cpuacct.usage_percpu`: the cpu time in nanoseconds per cpu.
+- `cpuacct.stat`: the user and system cpu time in clock ticks (`USER_HZ`).
+
+The user and system time are converted to milliseconds, like the cpu times in [`crate::stat::ProcStat`], by taking the
+`CLK_TCK` sysconf variable.
+
+The cpuacct controller is mostly mounted together with the cpu controller, at `/sys/fs/cgroup/cpu,cpuacct`. The
+mount point is found in `/proc/mounts`, and the cgroup of the process in `/proc/self/cgroup`. On a host that only uses
+cgroup v2, the cpu time is in `cpu.stat`, see [`crate::cgroup::cpu`].
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v1/cpuacct.html>
+
+Here is an example obtaining the cpuacct data of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::cpuacct;
+
+let cgroup_cpuacct = cpuacct::read();
+
+println!("{:#?}", cgroup_cpuacct);
+```
+Example output:
+```text
+CgroupCpuacct {
+    path: "/system.slice/sshd.service",
+    usage_ns: 4471265402,
+    usage_percpu_ns: [
+        1204611730,
+        1022381921,
+        1317265812,
+        927005939,
+    ],
+    user_ms: 2730,
+    system_ms: 1490,
+}
+```
+
+If you want to read another cgroup, and/or change the path that is read for the mounts and the cgroup of the
+process, which is `/proc` by default, or set the mount point of the cpuacct hierarchy, use:
+```no_run
+use proc_sys_parser::cgroup::cpuacct::Builder;
+
+let cgroup_cpuacct = Builder::new().path("/myproc").cgroup("/system.slice/sshd.service").read();
+let cgroup_cpuacct = Builder::new().mount_point("/mysys/fs/cgroup/cpu,cpuacct").cgroup("/system.slice").read();
+```
+*/
+use nix::unistd::{sysconf, SysconfVar};
+use crate::ProcSysParserError;
+use crate::cgroup::{parse_flat_keyed, Cgroup};
+
+/// Struct for holding the cpuacct data of a cgroup v1 cgroup
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupCpuacct {
+    /// The path of the cgroup relative to the mount point.
+    pub path: String,
+    /// `cpuacct.usage`: the total cpu time in nanoseconds.
+    pub usage_ns: u64,
+    /// `cpuacct.usage_percpu`: the cpu time in nanoseconds per cpu, indexed by the cpu number.
+    pub usage_percpu_ns: Vec<u64>,
+    /// `cpuacct.stat` user: the user cpu time in milliseconds.
+    pub user_ms: u64,
+    /// `cpuacct.stat` system: the system cpu time in milliseconds.
+    pub system_ms: u64,
+}
+
+/// Builder pattern for [`CgroupCpuacct`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the cpuacct hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    /// The path of the cgroup; if empty, it is the cgroup of the process itself.
+    pub cgroup_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            cgroup_path: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    pub fn cgroup(mut self, cgroup_path: &str) -> Builder {
+        self.cgroup_path = cgroup_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupCpuacct, ProcSysParserError> {
+        CgroupCpuacct::read_cgroup_cpuacct(&Cgroup::resolve_v1(&self.proc_path, "cpuacct", &self.mount_point, &self.cgroup_path)?)
+    }
+}
+
+/// The main function for building a [`CgroupCpuacct`] struct with current data of the cgroup of the process itself.
+/// This uses the Builder pattern, which allows settings such as the cgroup to specified.
+pub fn read() -> Result<CgroupCpuacct, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Read the cgroup v1 cpuacct controller files of the cgroup, which must be a cgroup of the cpuacct hierarchy.
+    pub fn cpuacct(&self) -> Result<CgroupCpuacct, ProcSysParserError> {
+        CgroupCpuacct::read_cgroup_cpuacct(self)
+    }
+}
+
+impl CgroupCpuacct {
+    pub fn new() -> CgroupCpuacct {
+        CgroupCpuacct::default()
+    }
+    pub fn parse_cpuacct_usage_percpu(cpuacct_usage_percpu: &str) -> Result<Vec<u64>, ProcSysParserError> {
+        cpuacct_usage_percpu.split_whitespace()
+            .map(|usage| usage.parse::<u64>().map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: "cpuacct.usage_percpu".to_string(), error }))
+            .collect()
+    }
+    /// Parse `cpuacct.stat`, and return the user and system time in milliseconds.
+    pub fn parse_cpuacct_stat(cpuacct_stat: &str) -> Result<(u64, u64), ProcSysParserError> {
+        // Note: time in clock ticks, must be divided by CLK_TCK to show time in seconds.
+        let clock_time = sysconf(SysconfVar::CLK_TCK).unwrap_or(Some(100)).unwrap_or(100) as u64;
+        let entries = parse_flat_keyed(cpuacct_stat, "cpuacct.stat")?;
+        let entry = |name: &str| entries.get(name).copied()
+            .ok_or(ProcSysParserError::FindItemError { item: format!("cpuacct.stat {}", name) });
+
+        Ok((entry("user")? * 1000 / clock_time, entry("system")? * 1000 / clock_time))
+    }
+    pub fn read_cgroup_cpuacct(cgroup: &Cgroup) -> Result<CgroupCpuacct, ProcSysParserError> {
+        let (user_ms, system_ms) = CgroupCpuacct::parse_cpuacct_stat(&cgroup.read_file("cpuacct.stat")?)?;
+
+        Ok(CgroupCpuacct {
+            path: cgroup.path.clone(),
+            usage_ns: cgroup.read_file("cpuacct.usage")?.trim().parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path("cpuacct.usage"), error })?,
+            usage_percpu_ns: CgroupCpuacct::parse_cpuacct_usage_percpu(&cgroup.read_file("cpuacct.usage_percpu")?)?,
+            user_ms,
+            system_ms,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_cgroup_cpuacct_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup/cpu,cpuacct", test_path);
+        let cgroup_directory = format!("{}/system.slice/sshd.service", mount_point);
+        create_dir_all(format!("{}/self", test_path)).expect("Error creating mock directory.");
+        create_dir_all(&cgroup_directory).expect("Error creating mock directory.");
+        for (file, contents) in [
+            (format!("{}/mounts", test_path), format!("cgroup {} cgroup rw,nosuid,nodev,noexec,relatime,cpu,cpuacct 0 0", mount_point)),
+            (format!("{}/self/cgroup", test_path), "4:cpu,cpuacct:/system.slice/sshd.service\n1:name=systemd:/system.slice/sshd.service".to_string()),
+            (format!("{}/cpuacct.usage", cgroup_directory), "4471265402".to_string()),
+            (format!("{}/cpuacct.usage_percpu", cgroup_directory), "1204611730 1022381921 1317265812 927005939 ".to_string()),
+            (format!("{}/cpuacct.stat", cgroup_directory), "user 273\nsystem 149".to_string()),
+        ] {
+            write(&file, format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}", file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        let clock_time = sysconf(SysconfVar::CLK_TCK).unwrap_or(Some(100)).unwrap_or(100) as u64;
+        assert_eq!(result, CgroupCpuacct {
+            path: "/system.slice/sshd.service".to_string(),
+            usage_ns: 4471265402,
+            usage_percpu_ns: vec![1204611730, 1022381921, 1317265812, 927005939],
+            user_ms: 273 * 1000 / clock_time,
+            system_ms: 149 * 1000 / clock_time,
+        });
+    }
+}
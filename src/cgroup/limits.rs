@@ -0,0 +1,312 @@
+/*!
+Read the cpu and memory limits of the cgroup of the process itself into the struct [`CgroupLimits`].
+
+Inside a container, `/proc/stat` and `/proc/meminfo` show the cpus and the memory of the host, while the container
+can be limited to a fraction of these by its cgroup. An application that sizes a thread pool on the number of cpus
+in [`crate::stat::ProcStat`], or a cache on the memory total in [`crate::meminfo::ProcMemInfo`], then takes too much.
+
+[`CgroupLimits`] contains the host capacity and the limits of the cgroup, and calculates the effective capacity:
+- cpus: the cpu quota (cgroup v2 `cpu.max`, cgroup v1 `cpu.cfs_quota_us` and `cpu.cfs_period_us`) as a number of
+  cpus, and the number of cpus of the cpuset (cgroup v2 `cpuset.cpus.effective`, cgroup v1 `cpuset.effective_cpus`).
+- memory: the memory limit (cgroup v2 `memory.max`, cgroup v1 `memory.limit_in_bytes`).
+
+A limit of a cgroup also applies to the cgroups below it, so the quota and the memory limit are the lowest limit of
+the cgroup of the process and its ancestors. Inside a cgroup namespace, the ancestors outside the namespace are not
+visible.
+
+The cgroup version is found in `/proc/self/cgroup`: if the cpu or memory controller is bound to a cgroup v1
+hierarchy, which is also the case on hosts with a hybrid hierarchy, the cgroup v1 hierarchies are used, otherwise the
+unified hierarchy. If no cgroup hierarchy is found, the version is None and the effective capacity is the host
+capacity.
+
+Here is an example obtaining the limits of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::limits;
+
+let cgroup_limits = limits::read().unwrap();
+
+println!("{:#?}", cgroup_limits);
+println!("threads: {}, memory: {} kB", cgroup_limits.effective_cpu_count(), cgroup_limits.effective_mem_total());
+```
+Example output:
+```text
+CgroupLimits {
+    version: Some(
+        V2,
+    ),
+    cpu_quota: Some(
+        1.5,
+    ),
+    cpuset_cpus: Some(
+        8,
+    ),
+    memory_max: Some(
+        536870912,
+    ),
+    host_cpu_count: 8,
+    host_mem_total: 3997876,
+}
+threads: 2, memory: 524288 kB
+```
+
+If you want to change the path that is read for `stat`, `meminfo`, the mounts and the cgroup of the process, which is
+`/proc` by default, use:
+```no_run
+use proc_sys_parser::cgroup::limits::Builder;
+
+let cgroup_limits = Builder::new().path("/myproc").read();
+```
+*/
+use crate::ProcSysParserError;
+use crate::cgroup::{parse_limit, Cgroup};
+use crate::cgroup::cpu::CgroupCpu;
+use crate::cgroup::memory_v1::UNLIMITED;
+use crate::process::cgroup::ProcPidCgroup;
+use crate::{meminfo, stat};
+use crate::utils::parse_cpu_list;
+
+/// The cgroup version of the hierarchies the limits are read from
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CgroupVersion {
+    V1,
+    V2,
+}
+
+/// Struct for holding the cpu and memory limits of a cgroup and the capacity of the host
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupLimits {
+    /// None if no cgroup hierarchy is found.
+    pub version: Option<CgroupVersion>,
+    /// The lowest cpu quota as a number of cpus, such as 1.5; None if no quota is set.
+    pub cpu_quota: Option<f64>,
+    /// The number of cpus the cgroup can run on; None if the cpuset controller is not enabled.
+    pub cpuset_cpus: Option<usize>,
+    /// The lowest memory limit in bytes; None if no limit is set.
+    pub memory_max: Option<u64>,
+    /// The number of cpus in `/proc/stat`.
+    pub host_cpu_count: usize,
+    /// MemTotal in `/proc/meminfo` in kilobytes (kB).
+    pub host_mem_total: u64,
+}
+
+/// Builder pattern for [`CgroupLimits`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupLimits, ProcSysParserError> {
+        CgroupLimits::read_cgroup_limits(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`CgroupLimits`] struct with current data of the cgroup of the process itself.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<CgroupLimits, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl CgroupLimits {
+    pub fn new() -> CgroupLimits {
+        CgroupLimits::default()
+    }
+    /// The number of cpus the cgroup can use: the lowest of the host cpus, the cpu quota and the cpuset cpus.
+    pub fn effective_cpus(&self) -> f64 {
+        [self.cpu_quota, self.cpuset_cpus.map(|cpus| cpus as f64)]
+            .into_iter()
+            .flatten()
+            .fold(self.host_cpu_count as f64, f64::min)
+    }
+    /// The effective cpus rounded up to a whole number of cpus, and at least 1, for sizing a thread pool.
+    pub fn effective_cpu_count(&self) -> usize {
+        (self.effective_cpus().ceil() as usize).max(1)
+    }
+    /// The memory the cgroup can use in kilobytes (kB), like MemTotal: the lowest of MemTotal and the memory limit.
+    pub fn effective_mem_total(&self) -> u64 {
+        self.memory_max.map_or(self.host_mem_total, |memory_max| self.host_mem_total.min(memory_max / 1024))
+    }
+    /// The lowest limit of the cgroup and its ancestors.
+    fn lineage_minimum<T: PartialOrd + Copy>(
+        cgroup: &Cgroup,
+        limit: impl Fn(&Cgroup) -> Result<Option<T>, ProcSysParserError>,
+    ) -> Result<Option<T>, ProcSysParserError> {
+        let mut minimum: Option<T> = None;
+        let mut current = Some(cgroup.clone());
+        while let Some(cgroup) = current {
+            if let Some(value) = limit(&cgroup)? {
+                if minimum.filter(|minimum| *minimum <= value).is_none() { minimum = Some(value) };
+            }
+            current = cgroup.parent();
+        }
+        Ok(minimum)
+    }
+    fn read_u64(cgroup: &Cgroup, file: &str) -> Result<u64, ProcSysParserError> {
+        cgroup.read_file(file)?.trim().parse::<u64>()
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path(file), error })
+    }
+    fn read_cpuset_cpus(cgroup: &Cgroup, files: &[&str]) -> Result<Option<usize>, ProcSysParserError> {
+        match files.iter().find(|file| cgroup.has_file(file)) {
+            Some(file) => Ok(Some(parse_cpu_list(&cgroup.read_file(file)?)?.len()).filter(|cpus| *cpus > 0)),
+            None => Ok(None),
+        }
+    }
+    fn read_limits_v2(&mut self, cgroup: &Cgroup) -> Result<(), ProcSysParserError> {
+        self.cpu_quota = CgroupLimits::lineage_minimum(cgroup, |cgroup| {
+            if !cgroup.has_file("cpu.max") { return Ok(None) };
+            Ok(CgroupCpu::parse_cpu_max(&cgroup.read_file("cpu.max")?)?.cpus())
+        })?;
+        self.memory_max = CgroupLimits::lineage_minimum(cgroup, |cgroup| {
+            if !cgroup.has_file("memory.max") { return Ok(None) };
+            parse_limit(&cgroup.read_file("memory.max")?, &cgroup.file_path("memory.max"))
+        })?;
+        // cpuset.cpus.effective is only shown if the cpuset controller is enabled, which might be for an ancestor only.
+        let mut current = Some(cgroup.clone());
+        while let Some(cgroup) = current {
+            if cgroup.has_file("cpuset.cpus.effective") {
+                self.cpuset_cpus = CgroupLimits::read_cpuset_cpus(&cgroup, &["cpuset.cpus.effective"])?;
+                break;
+            }
+            current = cgroup.parent();
+        }
+        Ok(())
+    }
+    fn read_limits_v1(&mut self, proc_path: &str) -> Result<(), ProcSysParserError> {
+        if let Ok(cgroup) = Cgroup::resolve_v1(proc_path, "cpu", "", "") {
+            self.cpu_quota = CgroupLimits::lineage_minimum(&cgroup, |cgroup| {
+                if !cgroup.has_file("cpu.cfs_quota_us") { return Ok(None) };
+                // the quota is -1 if not set.
+                let quota_us = cgroup.read_file("cpu.cfs_quota_us")?.trim().parse::<i64>()
+                    .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path("cpu.cfs_quota_us"), error })?;
+                let period_us = CgroupLimits::read_u64(cgroup, "cpu.cfs_period_us")?;
+                Ok(if quota_us < 0 || period_us == 0 { None } else { Some(quota_us as f64 / period_us as f64) })
+            })?;
+        }
+        if let Ok(cgroup) = Cgroup::resolve_v1(proc_path, "memory", "", "") {
+            self.memory_max = CgroupLimits::lineage_minimum(&cgroup, |cgroup| {
+                if !cgroup.has_file("memory.limit_in_bytes") { return Ok(None) };
+                Ok(Some(CgroupLimits::read_u64(cgroup, "memory.limit_in_bytes")?).filter(|limit| *limit < UNLIMITED))
+            })?;
+        }
+        if let Ok(cgroup) = Cgroup::resolve_v1(proc_path, "cpuset", "", "") {
+            self.cpuset_cpus = CgroupLimits::read_cpuset_cpus(&cgroup, &["cpuset.effective_cpus", "cpuset.cpus"])?;
+        }
+        Ok(())
+    }
+    pub fn read_cgroup_limits(proc_path: &str) -> Result<CgroupLimits, ProcSysParserError> {
+        let mut cgroup_limits = CgroupLimits {
+            host_cpu_count: stat::Builder::new().path(proc_path).read()?.cpu_individual.len(),
+            host_mem_total: meminfo::Builder::new().path(proc_path).read()?.memtotal,
+            ..Default::default()
+        };
+
+        let Ok(proc_self_cgroup) = ProcPidCgroup::read_proc_pid_cgroup(format!("{}/self/cgroup", proc_path).as_str(), "self") else {
+            return Ok(cgroup_limits);
+        };
+        if proc_self_cgroup.controller_path("cpu").is_some() || proc_self_cgroup.controller_path("memory").is_some() {
+            cgroup_limits.version = Some(CgroupVersion::V1);
+            cgroup_limits.read_limits_v1(proc_path)?;
+        } else if let Ok(cgroup) = Cgroup::resolve(proc_path, "", "") {
+            cgroup_limits.version = Some(CgroupVersion::V2);
+            cgroup_limits.read_limits_v2(&cgroup)?;
+        }
+        Ok(cgroup_limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_proc_files(test_path: &str, mounts: &str, self_cgroup: &str) {
+        create_dir_all(format!("{}/self", test_path)).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("stat", "cpu  1000 0 1000 10000 0 0 0 0 0 0\ncpu0 250 0 250 2500 0 0 0 0 0 0\ncpu1 250 0 250 2500 0 0 0 0 0 0\ncpu2 250 0 250 2500 0 0 0 0 0 0\ncpu3 250 0 250 2500 0 0 0 0 0 0\nctxt 1000\nbtime 1700000000\nprocesses 100\nprocs_running 1\nprocs_blocked 0"),
+            ("meminfo", "MemTotal:        3997876 kB\nMemFree:         2415136 kB"),
+            ("mounts", mounts),
+            ("self/cgroup", self_cgroup),
+        ] {
+            write(format!("{}/{}", test_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", test_path, file));
+        }
+    }
+
+    #[test]
+    fn create_cgroup_v2_limits_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup", test_path);
+        create_proc_files(&test_path, &format!("cgroup2 {} cgroup2 rw,nosuid,nodev,noexec,relatime 0 0", mount_point), "0::/system.slice/app.service");
+        create_dir_all(format!("{}/system.slice/app.service", mount_point)).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("system.slice/cpu.max", "150000 100000"),
+            ("system.slice/memory.max", "1073741824"),
+            ("system.slice/cpuset.cpus.effective", "0-3"),
+            ("system.slice/app.service/cpu.max", "max 100000"),
+            ("system.slice/app.service/memory.max", "536870912"),
+        ] {
+            write(format!("{}/{}", mount_point, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", mount_point, file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, CgroupLimits {
+            version: Some(CgroupVersion::V2),
+            cpu_quota: Some(1.5),
+            cpuset_cpus: Some(4),
+            memory_max: Some(536870912),
+            host_cpu_count: 4,
+            host_mem_total: 3997876,
+        });
+        assert_eq!(result.effective_cpus(), 1.5);
+        assert_eq!(result.effective_cpu_count(), 2);
+        assert_eq!(result.effective_mem_total(), 524288);
+    }
+
+    #[test]
+    fn create_cgroup_v1_limits_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let cpu_mount_point = format!("{}/sys/fs/cgroup/cpu,cpuacct", test_path);
+        let memory_mount_point = format!("{}/sys/fs/cgroup/memory", test_path);
+        create_proc_files(
+            &test_path,
+            &format!("cgroup {} cgroup rw,nosuid,nodev,noexec,relatime,cpu,cpuacct 0 0\ncgroup {} cgroup rw,nosuid,nodev,noexec,relatime,memory 0 0", cpu_mount_point, memory_mount_point),
+            "5:memory:/docker/4f1c\n4:cpu,cpuacct:/docker/4f1c\n1:name=systemd:/docker/4f1c\n0::/docker/4f1c",
+        );
+        create_dir_all(format!("{}/docker/4f1c", cpu_mount_point)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/docker/4f1c", memory_mount_point)).expect("Error creating mock directory.");
+        for (file, contents) in [
+            (format!("{}/docker/4f1c/cpu.cfs_quota_us", cpu_mount_point), "-1"),
+            (format!("{}/docker/4f1c/cpu.cfs_period_us", cpu_mount_point), "100000"),
+            (format!("{}/docker/cpu.cfs_quota_us", cpu_mount_point), "300000"),
+            (format!("{}/docker/cpu.cfs_period_us", cpu_mount_point), "100000"),
+            (format!("{}/docker/4f1c/memory.limit_in_bytes", memory_mount_point), "9223372036854771712"),
+        ] {
+            write(&file, format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}", file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.version, Some(CgroupVersion::V1));
+        assert_eq!((result.cpu_quota, result.cpuset_cpus, result.memory_max), (Some(3.0), None, None));
+        assert_eq!(result.effective_cpu_count(), 3);
+        assert_eq!(result.effective_mem_total(), 3997876);
+    }
+}
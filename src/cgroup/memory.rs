@@ -0,0 +1,410 @@
+/*!
+Read the memory controller interface files of a cgroup v2 cgroup into the struct [`CgroupMemory`].
+
+The memory controller shows the memory usage of the cgroup and its descendants in `memory.current`, and the limits
+in `memory.min`, `memory.low`, `memory.high` and `memory.max`:
+- `memory.min`: memory that is never reclaimed (hard protection).
+- `memory.low`: memory that is only reclaimed if no unprotected memory can be reclaimed (best-effort protection).
+- `memory.high`: the usage above which the cgroup is throttled and put under heavy reclaim.
+- `memory.max`: the hard limit; if the usage cannot be reduced below it, the OOM killer is invoked in the cgroup.
+
+A limit can be `max`, which means no limit; this is None. The number of times a limit was hit and the number of OOM
+events are in `memory.events`, and the breakdown of the usage is in `memory.stat`. All values are in bytes, except
+the event counters of `memory.stat`, such as pgfault and the workingset counters.
+
+The root cgroup does not have the memory interface files, except `memory.stat`; reading it returns an error.
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v2.html#memory-interface-files>
+
+Here is an example obtaining the memory data of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::memory;
+
+let cgroup_memory = memory::read();
+
+println!("{:#?}", cgroup_memory);
+```
+Example output:
+```text
+CgroupMemory {
+    path: "/system.slice/docker-4f1c.scope",
+    current: 219656192,
+    peak: Some(307232768),
+    min: Some(0),
+    low: Some(0),
+    high: None,
+    max: Some(536870912),
+    events: CgroupMemoryEvents { low: 0, high: 0, max: 12, oom: 1, oom_kill: 1, oom_group_kill: Some(0) },
+    stat: CgroupMemoryStat {
+        anon: 151924736,
+        file: 55025664,
+        kernel: Some(11866112),
+        kernel_stack: 491520,
+        pagetables: 1019904,
+        sock: 0,
+        shmem: 0,
+        file_mapped: 20480000,
+        file_dirty: 4096,
+        file_writeback: 0,
+        anon_thp: 48234496,
+        inactive_anon: 151863296,
+        active_anon: 61440,
+        inactive_file: 36306944,
+        active_file: 18718720,
+        unevictable: 0,
+        slab_reclaimable: 8734720,
+        slab_unreclaimable: 1302528,
+        slab: Some(10037248),
+        workingset_refault_anon: Some(0),
+        workingset_refault_file: Some(1129),
+        workingset_activate_anon: Some(0),
+        workingset_activate_file: Some(83),
+        workingset_restore_anon: Some(0),
+        workingset_restore_file: Some(35),
+        workingset_nodereclaim: 0,
+        pgfault: 1045723,
+        pgmajfault: 231,
+        other: {
+            "file_thp": 0,
+            "percpu": 107520,
+            "pgscan": 2511,
+            "pgsteal": 2489,
+            "shmem_thp": 0,
+            "vmalloc": 0,
+            ..
+        },
+    },
+    v1: None,
+}
+```
+(edited for readability)
+
+If you want to read another cgroup, and/or change the path that is read for the mounts and the cgroup of the
+process, which is `/proc` by default, or set the mount point of the unified hierarchy, use:
+```no_run
+use proc_sys_parser::cgroup::memory::Builder;
+
+let cgroup_memory = Builder::new().path("/myproc").cgroup("/system.slice/sshd.service").read();
+let cgroup_memory = Builder::new().mount_point("/mysys/fs/cgroup").cgroup("/system.slice").read();
+```
+*/
+use std::collections::BTreeMap;
+use crate::ProcSysParserError;
+use crate::cgroup::{parse_flat_keyed, parse_limit, Cgroup};
+
+/// Struct for holding the memory data of a cgroup
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupMemory {
+    /// The path of the cgroup relative to the mount point.
+    pub path: String,
+    /// `memory.current`: the memory usage of the cgroup and its descendants.
+    pub current: u64,
+    /// `memory.peak`: the maximal memory usage. Kernel 5.19+
+    pub peak: Option<u64>,
+    /// `memory.min`: None if `max`.
+    pub min: Option<u64>,
+    /// `memory.low`: None if `max`.
+    pub low: Option<u64>,
+    /// `memory.high`: None if `max` (no limit).
+    pub high: Option<u64>,
+    /// `memory.max`: None if `max` (no limit).
+    pub max: Option<u64>,
+    pub events: CgroupMemoryEvents,
+    pub stat: CgroupMemoryStat,
+    /// The cgroup v1 data that has no cgroup v2 equivalent, None for cgroup v2, see [`crate::cgroup::memory_v1`].
+    pub v1: Option<CgroupMemoryV1>,
+}
+
+/// Struct for holding the cgroup v1 memory data that has no cgroup v2 equivalent
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupMemoryV1 {
+    /// `memory.failcnt`: the number of times the usage hit the limit.
+    pub failcnt: u64,
+    /// `memory.oom_control` oom_kill_disable: the OOM killer is disabled, and processes hang until memory is freed.
+    pub oom_kill_disable: bool,
+    /// `memory.oom_control` under_oom: the cgroup is out of memory, and processes hang if the OOM killer is disabled.
+    pub under_oom: bool,
+}
+
+/// Struct for holding `memory.events`: the number of times the events happened in the cgroup and its descendants
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupMemoryEvents {
+    /// The cgroup was reclaimed while below memory.low.
+    pub low: u64,
+    /// The cgroup was throttled because it exceeded memory.high.
+    pub high: u64,
+    /// The usage was about to exceed memory.max.
+    pub max: u64,
+    /// The usage reached memory.max and allocation failed.
+    pub oom: u64,
+    /// A process was killed by the OOM killer.
+    pub oom_kill: u64,
+    /// The cgroup was killed as a whole. Kernel 5.17+
+    pub oom_group_kill: Option<u64>,
+}
+
+/// Struct for holding `memory.stat`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupMemoryStat {
+    /// Anonymous memory, such as the heap and stack of processes.
+    pub anon: u64,
+    /// The page cache.
+    pub file: u64,
+    /// The kernel memory, including kernel_stack, pagetables, slab and sock. Kernel 5.18+
+    pub kernel: Option<u64>,
+    pub kernel_stack: u64,
+    pub pagetables: u64,
+    /// Network transmission buffers.
+    pub sock: u64,
+    /// Shared memory and tmpfs, which is part of file.
+    pub shmem: u64,
+    pub file_mapped: u64,
+    pub file_dirty: u64,
+    pub file_writeback: u64,
+    /// Anonymous transparent hugepages.
+    pub anon_thp: u64,
+    pub inactive_anon: u64,
+    pub active_anon: u64,
+    pub inactive_file: u64,
+    pub active_file: u64,
+    pub unevictable: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    /// Kernel 5.9+
+    pub slab: Option<u64>,
+    /// The number of refaults of evicted anonymous pages. Kernel 5.9+
+    pub workingset_refault_anon: Option<u64>,
+    /// The number of refaults of evicted file pages. Kernel 5.9+
+    pub workingset_refault_file: Option<u64>,
+    /// Kernel 5.9+
+    pub workingset_activate_anon: Option<u64>,
+    /// Kernel 5.9+
+    pub workingset_activate_file: Option<u64>,
+    /// Kernel 5.9+
+    pub workingset_restore_anon: Option<u64>,
+    /// Kernel 5.9+
+    pub workingset_restore_file: Option<u64>,
+    pub workingset_nodereclaim: u64,
+    pub pgfault: u64,
+    pub pgmajfault: u64,
+    /// All other statistics by name, which differ per linux version.
+    pub other: BTreeMap<String, u64>,
+}
+
+/// Builder pattern for [`CgroupMemory`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the unified hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    /// The path of the cgroup; if empty, it is the cgroup of the process itself.
+    pub cgroup_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            cgroup_path: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    pub fn cgroup(mut self, cgroup_path: &str) -> Builder {
+        self.cgroup_path = cgroup_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupMemory, ProcSysParserError> {
+        CgroupMemory::read_cgroup_memory(&Cgroup::resolve(&self.proc_path, &self.mount_point, &self.cgroup_path)?)
+    }
+}
+
+/// The main function for building a [`CgroupMemory`] struct with current data of the cgroup of the process itself.
+/// This uses the Builder pattern, which allows settings such as the cgroup to specified.
+pub fn read() -> Result<CgroupMemory, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Read the memory controller interface files of the cgroup.
+    pub fn memory(&self) -> Result<CgroupMemory, ProcSysParserError> {
+        CgroupMemory::read_cgroup_memory(self)
+    }
+}
+
+impl CgroupMemory {
+    pub fn new() -> CgroupMemory {
+        CgroupMemory::default()
+    }
+    /// The memory that can be used before memory.max is reached, None if there is no limit.
+    pub fn headroom(&self) -> Option<u64> {
+        self.max.map(|max| max.saturating_sub(self.current))
+    }
+    pub fn parse_memory_events(memory_events: &str) -> Result<CgroupMemoryEvents, ProcSysParserError> {
+        let mut entries = parse_flat_keyed(memory_events, "memory.events")?;
+        let mut entry = |name: &str| entries.remove(name)
+            .ok_or(ProcSysParserError::FindItemError { item: format!("memory.events {}", name) });
+
+        Ok(CgroupMemoryEvents {
+            low: entry("low")?,
+            high: entry("high")?,
+            max: entry("max")?,
+            oom: entry("oom")?,
+            oom_kill: entry("oom_kill")?,
+            oom_group_kill: entry("oom_group_kill").ok(),
+        })
+    }
+    pub fn parse_memory_stat(memory_stat: &str) -> Result<CgroupMemoryStat, ProcSysParserError> {
+        let mut entries = parse_flat_keyed(memory_stat, "memory.stat")?;
+        let mut entry = |name: &str| entries.remove(name);
+
+        let mut memory_stat = CgroupMemoryStat {
+            anon: entry("anon").unwrap_or_default(),
+            file: entry("file").unwrap_or_default(),
+            kernel: entry("kernel"),
+            kernel_stack: entry("kernel_stack").unwrap_or_default(),
+            pagetables: entry("pagetables").unwrap_or_default(),
+            sock: entry("sock").unwrap_or_default(),
+            shmem: entry("shmem").unwrap_or_default(),
+            file_mapped: entry("file_mapped").unwrap_or_default(),
+            file_dirty: entry("file_dirty").unwrap_or_default(),
+            file_writeback: entry("file_writeback").unwrap_or_default(),
+            anon_thp: entry("anon_thp").unwrap_or_default(),
+            inactive_anon: entry("inactive_anon").unwrap_or_default(),
+            active_anon: entry("active_anon").unwrap_or_default(),
+            inactive_file: entry("inactive_file").unwrap_or_default(),
+            active_file: entry("active_file").unwrap_or_default(),
+            unevictable: entry("unevictable").unwrap_or_default(),
+            slab_reclaimable: entry("slab_reclaimable").unwrap_or_default(),
+            slab_unreclaimable: entry("slab_unreclaimable").unwrap_or_default(),
+            slab: entry("slab"),
+            workingset_refault_anon: entry("workingset_refault_anon"),
+            workingset_refault_file: entry("workingset_refault_file"),
+            workingset_activate_anon: entry("workingset_activate_anon"),
+            workingset_activate_file: entry("workingset_activate_file"),
+            workingset_restore_anon: entry("workingset_restore_anon"),
+            workingset_restore_file: entry("workingset_restore_file"),
+            workingset_nodereclaim: entry("workingset_nodereclaim").unwrap_or_default(),
+            pgfault: entry("pgfault").unwrap_or_default(),
+            pgmajfault: entry("pgmajfault").unwrap_or_default(),
+            ..Default::default()
+        };
+        memory_stat.other = entries;
+        Ok(memory_stat)
+    }
+    pub fn read_cgroup_memory(cgroup: &Cgroup) -> Result<CgroupMemory, ProcSysParserError> {
+        let read_u64 = |file: &str| -> Result<u64, ProcSysParserError> {
+            cgroup.read_file(file)?.trim().parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path(file), error })
+        };
+        let read_limit = |file: &str| -> Result<Option<u64>, ProcSysParserError> {
+            parse_limit(&cgroup.read_file(file)?, &cgroup.file_path(file))
+        };
+
+        Ok(CgroupMemory {
+            path: cgroup.path.clone(),
+            current: read_u64("memory.current")?,
+            peak: if cgroup.has_file("memory.peak") { Some(read_u64("memory.peak")?) } else { None },
+            min: read_limit("memory.min")?,
+            low: read_limit("memory.low")?,
+            high: read_limit("memory.high")?,
+            max: read_limit("memory.max")?,
+            events: CgroupMemory::parse_memory_events(&cgroup.read_file("memory.events")?)?,
+            stat: CgroupMemory::parse_memory_stat(&cgroup.read_file("memory.stat")?)?,
+            v1: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_cgroup_memory_stat() {
+        let memory_stat = "anon 151924736
+file 55025664
+kernel_stack 491520
+pagetables 1019904
+percpu 107520
+sock 0
+shmem 0
+file_mapped 20480000
+file_dirty 4096
+file_writeback 0
+anon_thp 48234496
+inactive_anon 151863296
+active_anon 61440
+inactive_file 36306944
+active_file 18718720
+unevictable 0
+slab_reclaimable 8734720
+slab_unreclaimable 1302528
+slab 10037248
+workingset_refault_anon 0
+workingset_refault_file 1129
+workingset_activate_anon 0
+workingset_activate_file 83
+workingset_restore_anon 0
+workingset_restore_file 35
+workingset_nodereclaim 0
+pgfault 1045723
+pgmajfault 231
+";
+        let result = CgroupMemory::parse_memory_stat(memory_stat).unwrap();
+        assert_eq!(result.anon, 151924736);
+        assert_eq!(result.kernel, None);
+        assert_eq!(result.slab, Some(10037248));
+        assert_eq!(result.workingset_refault_file, Some(1129));
+        assert_eq!(result.pgmajfault, 231);
+        assert_eq!(result.other.into_iter().collect::<Vec<_>>(), vec![("percpu".to_string(), 107520)]);
+    }
+
+    #[test]
+    fn create_cgroup_memory_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let cgroup_directory = format!("{}/sys/fs/cgroup/system.slice", test_path);
+        create_dir_all(&cgroup_directory).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("memory.current", "219656192"),
+            ("memory.min", "0"),
+            ("memory.low", "max"),
+            ("memory.high", "max"),
+            ("memory.max", "536870912"),
+            ("memory.events", "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1"),
+            ("memory.stat", "anon 151924736\nfile 55025664"),
+        ] {
+            write(format!("{}/{}", cgroup_directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", cgroup_directory, file));
+        }
+
+        let result = Builder::new().mount_point(&format!("{}/sys/fs/cgroup", test_path)).cgroup("/system.slice").read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, CgroupMemory {
+            path: "/system.slice".to_string(),
+            current: 219656192,
+            peak: None,
+            min: Some(0),
+            low: None,
+            high: None,
+            max: Some(536870912),
+            events: CgroupMemoryEvents { low: 0, high: 0, max: 12, oom: 1, oom_kill: 1, oom_group_kill: None },
+            stat: CgroupMemoryStat { anon: 151924736, file: 55025664, ..Default::default() },
+            v1: None,
+        });
+        assert_eq!(result.headroom(), Some(317214720));
+    }
+}
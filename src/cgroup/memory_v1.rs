@@ -0,0 +1,224 @@
+/*!
+Read the cgroup v1 memory controller files of a cgroup into the struct [`CgroupMemory`].
+
+The cgroup v1 memory controller shows mostly the same data as the cgroup v2 memory controller, in other files and
+with other names. To make the memory of a cgroup comparable between cgroup v1 and v2 hosts, the data is read into the
+same struct as [`crate::cgroup::memory`]:
+
+| cgroup v1                        | [`CgroupMemory`]                                              |
+|----------------------------------|---------------------------------------------------------------|
+| `memory.usage_in_bytes`          | current                                                       |
+| `memory.max_usage_in_bytes`      | peak                                                          |
+| `memory.soft_limit_in_bytes`     | low                                                           |
+| `memory.limit_in_bytes`          | max                                                           |
+| `memory.failcnt`                 | events.max and v1.failcnt                                     |
+| `memory.oom_control` oom_kill    | events.oom_kill (linux 4.13+)                                 |
+| `memory.oom_control`             | v1.oom_kill_disable and v1.under_oom                          |
+| `memory.stat` total_rss          | stat.anon                                                     |
+| `memory.stat` total_cache        | stat.file                                                     |
+| `memory.stat` total_rss_huge     | stat.anon_thp                                                 |
+| `memory.stat` total_mapped_file  | stat.file_mapped                                              |
+| `memory.stat` total_dirty        | stat.file_dirty                                               |
+| `memory.stat` total_writeback    | stat.file_writeback                                           |
+
+cgroup v1 has no memory.min and memory.high, so min is 0 (no protection) and high is None (no limit). A limit that
+is not set is shown as a very large number (9223372036854771712 on 64 bit systems), which is None, like `max` in
+cgroup v2. The `total_` statistics of `memory.stat` include the descendants of the cgroup, like the statistics of
+cgroup v2; the statistics without `total_` are for the cgroup itself and are stored in stat.other. The names that are
+equal in cgroup v1 and v2, such as shmem, active_anon and pgfault, are taken from their `total_` statistic too.
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v1/memory.html>
+
+Here is an example obtaining the memory data of the cgroup of the process itself:
+```no_run
+use proc_sys_parser::cgroup::memory_v1;
+
+let cgroup_memory = memory_v1::read();
+
+println!("{:#?}", cgroup_memory);
+```
+Example output:
+```text
+CgroupMemory {
+    path: "/system.slice/sshd.service",
+    current: 6533120,
+    peak: Some(17014784),
+    min: Some(0),
+    low: None,
+    high: None,
+    max: Some(536870912),
+    events: CgroupMemoryEvents { low: 0, high: 0, max: 0, oom: 0, oom_kill: 0, oom_group_kill: None },
+    stat: CgroupMemoryStat { anon: 2490368, file: 3784704, .. },
+    v1: Some(CgroupMemoryV1 { failcnt: 0, oom_kill_disable: false, under_oom: false }),
+}
+```
+(edited for readability)
+
+If you want to read another cgroup, and/or change the path that is read for the mounts and the cgroup of the
+process, which is `/proc` by default, or set the mount point of the memory hierarchy, use:
+```no_run
+use proc_sys_parser::cgroup::memory_v1::Builder;
+
+let cgroup_memory = Builder::new().path("/myproc").cgroup("/system.slice/sshd.service").read();
+let cgroup_memory = Builder::new().mount_point("/mysys/fs/cgroup/memory").cgroup("/system.slice").read();
+```
+*/
+use crate::ProcSysParserError;
+use crate::cgroup::{parse_flat_keyed, Cgroup};
+use crate::cgroup::memory::{CgroupMemory, CgroupMemoryEvents, CgroupMemoryStat, CgroupMemoryV1};
+
+/// Limits at or above this value are not set; the kernel shows the maximal number of pages in bytes.
+pub(crate) const UNLIMITED: u64 = 1 << 62;
+
+/// Builder pattern for [`CgroupMemory`] of a cgroup v1 cgroup
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the memory hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    /// The path of the cgroup; if empty, it is the cgroup of the process itself.
+    pub cgroup_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            cgroup_path: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    pub fn cgroup(mut self, cgroup_path: &str) -> Builder {
+        self.cgroup_path = cgroup_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<CgroupMemory, ProcSysParserError> {
+        CgroupMemory::read_cgroup_memory_v1(&Cgroup::resolve_v1(&self.proc_path, "memory", &self.mount_point, &self.cgroup_path)?)
+    }
+}
+
+/// The main function for building a [`CgroupMemory`] struct with current data of the cgroup v1 cgroup of the process
+/// itself. This uses the Builder pattern, which allows settings such as the cgroup to specified.
+pub fn read() -> Result<CgroupMemory, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Read the cgroup v1 memory controller files of the cgroup, which must be a cgroup of the memory hierarchy.
+    pub fn memory_v1(&self) -> Result<CgroupMemory, ProcSysParserError> {
+        CgroupMemory::read_cgroup_memory_v1(self)
+    }
+}
+
+impl CgroupMemory {
+    pub fn parse_memory_v1_stat(memory_stat: &str) -> Result<CgroupMemoryStat, ProcSysParserError> {
+        let mut entries = parse_flat_keyed(memory_stat, "memory.stat")?;
+        // take the hierarchical total_ statistic, or the statistic of the cgroup itself if it is not shown.
+        let mut entry = |name: &str| entries.remove(&format!("total_{}", name))
+            .or_else(|| entries.get(name).copied())
+            .unwrap_or_default();
+
+        let mut memory_stat = CgroupMemoryStat {
+            anon: entry("rss"),
+            file: entry("cache"),
+            shmem: entry("shmem"),
+            file_mapped: entry("mapped_file"),
+            file_dirty: entry("dirty"),
+            file_writeback: entry("writeback"),
+            anon_thp: entry("rss_huge"),
+            inactive_anon: entry("inactive_anon"),
+            active_anon: entry("active_anon"),
+            inactive_file: entry("inactive_file"),
+            active_file: entry("active_file"),
+            unevictable: entry("unevictable"),
+            pgfault: entry("pgfault"),
+            pgmajfault: entry("pgmajfault"),
+            ..Default::default()
+        };
+        memory_stat.other = entries;
+        Ok(memory_stat)
+    }
+    pub fn read_cgroup_memory_v1(cgroup: &Cgroup) -> Result<CgroupMemory, ProcSysParserError> {
+        let read_u64 = |file: &str| -> Result<u64, ProcSysParserError> {
+            cgroup.read_file(file)?.trim().parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: cgroup.file_path(file), error })
+        };
+        let read_limit = |file: &str| -> Result<Option<u64>, ProcSysParserError> {
+            read_u64(file).map(|limit| if limit >= UNLIMITED { None } else { Some(limit) })
+        };
+        let oom_control = cgroup.read_flat_keyed_file("memory.oom_control")?;
+        let failcnt = read_u64("memory.failcnt")?;
+
+        Ok(CgroupMemory {
+            path: cgroup.path.clone(),
+            current: read_u64("memory.usage_in_bytes")?,
+            peak: Some(read_u64("memory.max_usage_in_bytes")?),
+            min: Some(0),
+            low: read_limit("memory.soft_limit_in_bytes")?,
+            high: None,
+            max: read_limit("memory.limit_in_bytes")?,
+            events: CgroupMemoryEvents {
+                max: failcnt,
+                oom_kill: oom_control.get("oom_kill").copied().unwrap_or_default(),
+                ..Default::default()
+            },
+            stat: CgroupMemory::parse_memory_v1_stat(&cgroup.read_file("memory.stat")?)?,
+            v1: Some(CgroupMemoryV1 {
+                failcnt,
+                oom_kill_disable: oom_control.get("oom_kill_disable") == Some(&1),
+                under_oom: oom_control.get("under_oom") == Some(&1),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_cgroup_memory_v1_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup/memory", test_path);
+        let cgroup_directory = format!("{}/system.slice", mount_point);
+        create_dir_all(&cgroup_directory).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("memory.usage_in_bytes", "6533120"),
+            ("memory.max_usage_in_bytes", "17014784"),
+            ("memory.soft_limit_in_bytes", "9223372036854771712"),
+            ("memory.limit_in_bytes", "536870912"),
+            ("memory.failcnt", "3"),
+            ("memory.oom_control", "oom_kill_disable 0\nunder_oom 0\noom_kill 1"),
+            ("memory.stat", "cache 1000\nrss 2000\nshmem 0\nmapped_file 500\npgfault 70\ntotal_cache 3784704\ntotal_rss 2490368\ntotal_mapped_file 1200\ntotal_pgfault 9120\nhierarchical_memory_limit 536870912"),
+        ] {
+            write(format!("{}/{}", cgroup_directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", cgroup_directory, file));
+        }
+
+        let result = Builder::new().mount_point(&mount_point).cgroup("/system.slice").read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.current, 6533120);
+        assert_eq!(result.peak, Some(17014784));
+        assert_eq!((result.min, result.low, result.high, result.max), (Some(0), None, None, Some(536870912)));
+        assert_eq!(result.events, CgroupMemoryEvents { max: 3, oom_kill: 1, ..Default::default() });
+        assert_eq!((result.stat.anon, result.stat.file, result.stat.file_mapped, result.stat.shmem, result.stat.pgfault), (2490368, 3784704, 1200, 0, 9120));
+        assert_eq!(result.stat.other.get("cache"), Some(&1000));
+        assert_eq!(result.stat.other.get("hierarchical_memory_limit"), Some(&536870912));
+        assert_eq!(result.v1, Some(CgroupMemoryV1 { failcnt: 3, oom_kill_disable: false, under_oom: false }));
+        assert_eq!(result.headroom(), Some(530337792));
+    }
+}
@@ -0,0 +1,485 @@
+/*!
+Walk the cgroup v2 unified hierarchy into the struct [`SysCgroup`].
+
+The unified hierarchy is mounted with filesystem type `cgroup2`, normally at `/sys/fs/cgroup`. The mount point is
+found in `/proc/mounts`. Every directory in the hierarchy is a cgroup, which contains the interface files of the
+controllers that are enabled for it, such as `memory.current` or `cpu.stat`.
+
+The hierarchy is walked from the root, or from the cgroup set with `root()`, and every cgroup is stored as a
+[`Cgroup`], with its path relative to the mount point, its depth below the root of the walk and its enabled
+controllers. The [`Cgroup`] is a handle from which the interface files of the cgroup can be read.
+
+Documentation: <https://docs.kernel.org/admin-guide/cgroup-v2.html>
+
+Here is an example obtaining the cgroup hierarchy:
+```no_run
+use proc_sys_parser::cgroup;
+
+let sys_cgroup = cgroup::read();
+
+println!("{:#?}", sys_cgroup);
+```
+Example output:
+```text
+SysCgroup {
+    mount_point: "/sys/fs/cgroup",
+    cgroups: [
+        Cgroup {
+            path: "/",
+            directory: "/sys/fs/cgroup",
+            depth: 0,
+            controllers: [
+                "cpuset",
+                "cpu",
+                "io",
+                "memory",
+                "pids",
+            ],
+        },
+        Cgroup {
+            path: "/system.slice",
+            directory: "/sys/fs/cgroup/system.slice",
+            depth: 1,
+            controllers: [
+                "cpu",
+                "io",
+                "memory",
+                "pids",
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+A host can have thousands of cgroups. The walk can be limited to a subtree with `root()`, to a number of levels below
+the root with `max_depth()`, and cgroups can be excluded with a regex filter on the path; an excluded cgroup is not
+walked into:
+```no_run
+use proc_sys_parser::cgroup::Builder;
+
+let sys_cgroup = Builder::new().root("/system.slice").max_depth(1).filter(r"\.mount$").read();
+```
+
+If you want to change the path that is read for the mounts, which is `/proc` by default, or set the mount point of
+the unified hierarchy instead of finding it in the mounts, use:
+```no_run
+use proc_sys_parser::cgroup::Builder;
+
+let sys_cgroup = Builder::new().path("/myproc").read();
+let sys_cgroup = Builder::new().mount_point("/mysys/fs/cgroup").read();
+```
+
+The submodules of `cgroup` parse the interface files of a controller. By default these read the cgroup of the
+process itself, as found in `/proc/self/cgroup`, which inside a container is the cgroup of the container. Use the
+`cgroup()` function of the Builder of the submodule to read another cgroup, or call the function of the submodule on
+a [`Cgroup`] from the walk, such as [`Cgroup::memory`].
+
+On hosts that still use cgroup v1, every controller (or group of controllers, such as `cpu,cpuacct`) has its own
+hierarchy, mounted with filesystem type `cgroup`. The submodules for cgroup v1 controllers, such as `cpuacct`, find
+the hierarchy of the controller and the cgroup of the process in that hierarchy with [`Cgroup::resolve_v1`].
+*/
+use std::collections::BTreeMap;
+use std::fs::{read_dir, read_to_string};
+use std::io::ErrorKind;
+use std::path::Path;
+use regex::Regex;
+use crate::ProcSysParserError;
+use crate::pressure::ProcPressure;
+use crate::process::cgroup::ProcPidCgroup;
+use crate::utils::read_file_string;
+
+pub mod blkio;
+pub mod cpu;
+pub mod cpuacct;
+pub mod limits;
+pub mod memory;
+pub mod memory_v1;
+
+/// Struct for holding the cgroups of the unified hierarchy
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCgroup {
+    /// The mount point of the unified hierarchy.
+    pub mount_point: String,
+    /// The cgroups, sorted by path.
+    pub cgroups: Vec<Cgroup>,
+}
+
+/// Struct for holding a single cgroup
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Cgroup {
+    /// The path of the cgroup relative to the mount point, such as `/system.slice/sshd.service`, which is the path
+    /// shown in `/proc/<pid>/cgroup`. The root cgroup is `/`.
+    pub path: String,
+    /// The directory of the cgroup.
+    pub directory: String,
+    /// The depth below the root of the walk, which is 0 for the root.
+    pub depth: usize,
+    /// `cgroup.controllers`: the controllers that are available to the cgroup.
+    pub controllers: Vec<String>,
+}
+
+/// Builder pattern for [`SysCgroup`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    /// The mount point of the unified hierarchy; if empty, it is found in `/proc/mounts`.
+    pub mount_point : String,
+    pub root : String,
+    pub max_depth : Option<usize>,
+    pub filter : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            mount_point: "".to_string(),
+            root: "/".to_string(),
+            max_depth: None,
+            filter: "".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn mount_point(mut self, mount_point: &str) -> Builder {
+        self.mount_point = mount_point.to_string();
+        self
+    }
+    /// The path of the cgroup to start the walk, such as `/system.slice`.
+    pub fn root(mut self, root: &str) -> Builder {
+        self.root = root.to_string();
+        self
+    }
+    /// The number of levels below the root that are walked.
+    pub fn max_depth(mut self, max_depth: usize) -> Builder {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// A regex that is matched against the path of a cgroup; matching cgroups are excluded.
+    pub fn filter(mut self, filter: &str) -> Builder {
+        self.filter = filter.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCgroup, ProcSysParserError> {
+        let mount_point = if self.mount_point.is_empty() {
+            SysCgroup::find_mount_point(format!("{}/mounts", &self.proc_path).as_str(), "cgroup2", None)?
+        } else {
+            self.mount_point
+        };
+        SysCgroup::read_sys_cgroup(&mount_point, &self.root, self.max_depth, &self.filter)
+    }
+}
+
+/// The main function for building a [`SysCgroup`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the root of the walk to specified.
+pub fn read() -> Result<SysCgroup, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Cgroup {
+    /// Create a handle for the cgroup with the path relative to the mount point.
+    pub fn new(mount_point: &str, path: &str) -> Cgroup {
+        let path = format!("/{}", path.trim_matches('/'));
+        Cgroup {
+            directory: format!("{}{}", mount_point.trim_end_matches('/'), if path == "/" { "" } else { &path }),
+            path,
+            depth: 0,
+            controllers: Vec::new(),
+        }
+    }
+    /// The parent cgroup, None for the root cgroup of the hierarchy.
+    pub fn parent(&self) -> Option<Cgroup> {
+        if self.path == "/" { return None };
+        let mount_point = self.directory.strip_suffix(&self.path)?;
+        let (parent_path, _) = self.path.rsplit_once('/')?;
+        Some(Cgroup::new(mount_point, parent_path))
+    }
+    /// The path of an interface file of the cgroup, such as `memory.current`.
+    pub fn file_path(&self, file: &str) -> String {
+        format!("{}/{}", self.directory, file)
+    }
+    /// Does the cgroup contain the interface file?
+    pub fn has_file(&self, file: &str) -> bool {
+        Path::new(&self.file_path(file)).exists()
+    }
+    /// Read an interface file of the cgroup, without the trailing newline.
+    pub fn read_file(&self, file: &str) -> Result<String, ProcSysParserError> {
+        read_file_string(Path::new(&self.file_path(file)))
+    }
+    /// Create a handle for a cgroup of the unified hierarchy. If `mount_point` is empty, it is found in
+    /// `<proc_path>/mounts`; if `cgroup_path` is empty, it is the cgroup of the process itself from
+    /// `<proc_path>/self/cgroup`.
+    pub fn resolve(proc_path: &str, mount_point: &str, cgroup_path: &str) -> Result<Cgroup, ProcSysParserError> {
+        let mount_point = if mount_point.is_empty() {
+            SysCgroup::find_mount_point(format!("{}/mounts", proc_path).as_str(), "cgroup2", None)?
+        } else {
+            mount_point.to_string()
+        };
+        if !cgroup_path.is_empty() { return Ok(Cgroup::new(&mount_point, cgroup_path)) };
+
+        let proc_self_cgroup = ProcPidCgroup::read_proc_pid_cgroup(format!("{}/self/cgroup", proc_path).as_str(), "self")?;
+        let unified_path = proc_self_cgroup.unified_path()
+            .ok_or(ProcSysParserError::FindItemError { item: format!("cgroup v2 path in {}/self/cgroup", proc_path) })?;
+        Ok(Cgroup::new(&mount_point, unified_path))
+    }
+    /// Create a handle for a cgroup of the cgroup v1 hierarchy of `controller`. If `mount_point` is empty, the
+    /// hierarchy the controller is mounted with is found in `<proc_path>/mounts`; if `cgroup_path` is empty, it is the
+    /// cgroup of the process itself in the hierarchy from `<proc_path>/self/cgroup`.
+    pub fn resolve_v1(proc_path: &str, controller: &str, mount_point: &str, cgroup_path: &str) -> Result<Cgroup, ProcSysParserError> {
+        let mount_point = if mount_point.is_empty() {
+            SysCgroup::find_mount_point(format!("{}/mounts", proc_path).as_str(), "cgroup", Some(controller))?
+        } else {
+            mount_point.to_string()
+        };
+        if !cgroup_path.is_empty() { return Ok(Cgroup::new(&mount_point, cgroup_path)) };
+
+        let proc_self_cgroup = ProcPidCgroup::read_proc_pid_cgroup(format!("{}/self/cgroup", proc_path).as_str(), "self")?;
+        let controller_path = proc_self_cgroup.controller_path(controller)
+            .ok_or(ProcSysParserError::FindItemError { item: format!("cgroup v1 {} path in {}/self/cgroup", controller, proc_path) })?;
+        Ok(Cgroup::new(&mount_point, controller_path))
+    }
+    /// Read an interface file of the cgroup that contains `key value` lines, such as `memory.stat` or `cpu.stat`.
+    pub fn read_flat_keyed_file(&self, file: &str) -> Result<BTreeMap<String, u64>, ProcSysParserError> {
+        parse_flat_keyed(&self.read_file(file)?, &self.file_path(file))
+    }
+    /// Read the pressure files of the cgroup, see [`ProcPressure::read_cgroup_pressure`].
+    pub fn pressure(&self) -> Result<ProcPressure, ProcSysParserError> {
+        ProcPressure::read_cgroup_pressure(&self.directory)
+    }
+    /// `cgroup.procs`: the pids of the processes in the cgroup.
+    pub fn procs(&self) -> Result<Vec<u32>, ProcSysParserError> {
+        self.read_file("cgroup.procs")?
+            .lines()
+            .map(|pid| pid.trim().parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError))
+            .collect()
+    }
+}
+
+/// Parse the `key value` lines of a flat keyed file.
+pub(crate) fn parse_flat_keyed(contents: &str, file: &str) -> Result<BTreeMap<String, u64>, ProcSysParserError> {
+    contents.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (key, value) = line.split_once(' ')
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("value of '{}' in {}", line, file) })?;
+            let value = value.trim().parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("{} in {}", key, file), error })?;
+            Ok((key.to_string(), value))
+        })
+        .collect()
+}
+/// Parse a limit, which is a number or `max` for no limit, which results in None.
+pub(crate) fn parse_limit(value: &str, file: &str) -> Result<Option<u64>, ProcSysParserError> {
+    match value.trim() {
+        "max" => Ok(None),
+        value => value.parse::<u64>()
+            .map(Some)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: file.to_string(), error }),
+    }
+}
+
+impl SysCgroup {
+    pub fn new() -> SysCgroup {
+        SysCgroup::default()
+    }
+    /// The cgroup with the path, such as `/system.slice`.
+    pub fn cgroup(&self, path: &str) -> Option<&Cgroup> {
+        self.cgroups.iter().find(|cgroup| cgroup.path == path)
+    }
+    /// The cgroups directly below the cgroup with the path.
+    pub fn children(&self, path: &str) -> Vec<&Cgroup> {
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        self.cgroups.iter()
+            .filter(|cgroup| cgroup.path.strip_prefix(&prefix).is_some_and(|name| !name.is_empty() && !name.contains('/')))
+            .collect()
+    }
+    /// Find the mount point of a filesystem type in a mounts file (`/proc/mounts`). For the cgroup v1 type `cgroup`,
+    /// `option` selects the hierarchy by a mount option, which is the controller name, such as `memory`.
+    pub fn find_mount_point(proc_mounts_file: &str, filesystem_type: &str, option: Option<&str>) -> Result<String, ProcSysParserError> {
+        let proc_mounts = read_to_string(proc_mounts_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_mounts_file.to_string(), error })?;
+
+        for line in proc_mounts.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 || fields[2] != filesystem_type { continue };
+            if option.is_some_and(|option| !fields[3].split(',').any(|mount_option| mount_option == option)) { continue };
+            // spaces in the mount point are shown as \040.
+            return Ok(fields[1].replace("\\040", " "));
+        }
+        Err(ProcSysParserError::FindItemError { item: format!("{} mount {}in {}", filesystem_type, option.map(|option| format!("for {} ", option)).unwrap_or_default(), proc_mounts_file) })
+    }
+    fn walk(
+        sys_cgroup: &mut SysCgroup,
+        cgroup: Cgroup,
+        max_depth: Option<usize>,
+        filter_regex: &Regex,
+    ) -> Result<(), ProcSysParserError> {
+        let entries = read_dir(&cgroup.directory)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: cgroup.directory.clone(), error })?;
+        let mut children: Vec<Cgroup> = Vec::new();
+
+        if cgroup.depth < max_depth.unwrap_or(usize::MAX) {
+            for entry in entries.flatten().filter(|entry| entry.path().is_dir()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let mut child = Cgroup::new(&sys_cgroup.mount_point, &format!("{}/{}", cgroup.path.trim_end_matches('/'), name));
+                if !filter_regex.as_str().is_empty() && filter_regex.is_match(&child.path) { continue };
+                child.depth = cgroup.depth + 1;
+                children.push(child);
+            }
+        }
+        sys_cgroup.cgroups.push(Cgroup {
+            controllers: cgroup.read_file("cgroup.controllers")
+                .map(|controllers| controllers.split_whitespace().map(|controller| controller.to_string()).collect())
+                .unwrap_or_default(),
+            ..cgroup
+        });
+        for child in children {
+            SysCgroup::walk_child(sys_cgroup, child, max_depth, filter_regex)?;
+        }
+        Ok(())
+    }
+    /// Walk a child cgroup. A child that was removed after its parent was read, such as a transient scope, is skipped;
+    /// only the root of the walk must exist.
+    fn walk_child(
+        sys_cgroup: &mut SysCgroup,
+        cgroup: Cgroup,
+        max_depth: Option<usize>,
+        filter_regex: &Regex,
+    ) -> Result<(), ProcSysParserError> {
+        match SysCgroup::walk(sys_cgroup, cgroup, max_depth, filter_regex) {
+            Err(ProcSysParserError::DirectoryReadError { error, .. }) if error.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+    pub fn read_sys_cgroup(
+        mount_point: &str,
+        root: &str,
+        max_depth: Option<usize>,
+        filter: &str,
+    ) -> Result<SysCgroup, ProcSysParserError> {
+        let mut syscgroup = SysCgroup::new();
+        syscgroup.mount_point = mount_point.to_string();
+
+        let filter_regex = Regex::new(filter)
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: filter.to_string() })?;
+
+        SysCgroup::walk(&mut syscgroup, Cgroup::new(mount_point, root), max_depth, &filter_regex)?;
+        syscgroup.cgroups.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(syscgroup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_cgroup_hierarchy_and_walk() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mount_point = format!("{}/sys/fs/cgroup", test_path);
+        create_mock_files(&format!("{}/proc", test_path), &[("mounts", &format!("sysfs /sys sysfs rw,nosuid,nodev,noexec,relatime 0 0\ncgroup2 {} cgroup2 rw,nosuid,nodev,noexec,relatime,nsdelegate 0 0", mount_point))]);
+        create_mock_files(&mount_point, &[("cgroup.controllers", "cpuset cpu io memory pids"), ("cgroup.procs", "1\n2")]);
+        create_mock_files(&format!("{}/system.slice", mount_point), &[("cgroup.controllers", "cpu io memory pids")]);
+        create_mock_files(&format!("{}/system.slice/sshd.service", mount_point), &[("cgroup.controllers", ""), ("cgroup.procs", "812")]);
+        create_mock_files(&format!("{}/system.slice/boot.mount", mount_point), &[("cgroup.controllers", "")]);
+        create_mock_files(&format!("{}/user.slice", mount_point), &[("cgroup.controllers", "memory pids")]);
+
+        let result = Builder::new().path(&format!("{}/proc", test_path)).read().unwrap();
+        let result_filtered = Builder::new().mount_point(&mount_point).root("/system.slice").filter(r"\.mount$").read().unwrap();
+        let result_depth = Builder::new().mount_point(&mount_point).max_depth(1).read().unwrap();
+        let procs = result.cgroup("/").unwrap().procs().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.mount_point, mount_point);
+        assert_eq!(result.cgroups.iter().map(|cgroup| (cgroup.path.as_str(), cgroup.depth)).collect::<Vec<_>>(), vec![
+            ("/", 0),
+            ("/system.slice", 1),
+            ("/system.slice/boot.mount", 2),
+            ("/system.slice/sshd.service", 2),
+            ("/user.slice", 1),
+        ]);
+        assert_eq!(result.cgroup("/user.slice"), Some(&Cgroup {
+            path: "/user.slice".to_string(),
+            directory: format!("{}/user.slice", mount_point),
+            depth: 1,
+            controllers: vec!["memory".to_string(), "pids".to_string()],
+        }));
+        assert_eq!(procs, vec![1, 2]);
+        assert_eq!(result.children("/system.slice").len(), 2);
+        assert_eq!(result.children("/").len(), 2);
+        assert_eq!(result_filtered.cgroups.iter().map(|cgroup| (cgroup.path.as_str(), cgroup.depth)).collect::<Vec<_>>(), vec![
+            ("/system.slice", 0),
+            ("/system.slice/sshd.service", 1),
+        ]);
+        assert_eq!(result_depth.cgroups.len(), 3);
+    }
+
+    #[test]
+    fn walk_removed_cgroup() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let mount_point = format!("/tmp/test.{}", directory_suffix);
+        let filter_regex = Regex::new("").unwrap();
+        let mut sys_cgroup = SysCgroup { mount_point: mount_point.clone(), ..Default::default() };
+
+        // a child that was removed after its parent was read is skipped, a root that does not exist is an error.
+        let child = SysCgroup::walk_child(&mut sys_cgroup, Cgroup::new(&mount_point, "/system.slice/run-r1.scope"), None, &filter_regex);
+        let root = SysCgroup::read_sys_cgroup(&mount_point, "/", None, "");
+
+        assert!(child.is_ok());
+        assert!(sys_cgroup.cgroups.is_empty());
+        assert!(matches!(root, Err(ProcSysParserError::DirectoryReadError { .. })));
+    }
+
+    #[test]
+    fn resolve_cgroup_of_process_and_parse_flat_keyed() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&test_path, &[("mounts", "cgroup2 /sys/fs/cgroup cgroup2 rw,nosuid,nodev,noexec,relatime 0 0")]);
+        create_mock_files(&format!("{}/self", test_path), &[("cgroup", "0::/system.slice/docker-4f1c.scope")]);
+
+        let result = Cgroup::resolve(&test_path, "", "");
+        let result_path = Cgroup::resolve(&test_path, "/mysys/fs/cgroup/", "/user.slice/");
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.unwrap().directory, "/sys/fs/cgroup/system.slice/docker-4f1c.scope");
+        assert_eq!(result_path.unwrap(), Cgroup { path: "/user.slice".to_string(), directory: "/mysys/fs/cgroup/user.slice".to_string(), depth: 0, controllers: vec![] });
+        assert_eq!(parse_flat_keyed("anon 1024\nfile 2048\n", "memory.stat").unwrap().get("file"), Some(&2048));
+        assert!(parse_flat_keyed("anon\n", "memory.stat").is_err());
+        assert_eq!(parse_limit("max", "memory.max").unwrap(), None);
+        assert_eq!(parse_limit("1073741824", "memory.max").unwrap(), Some(1073741824));
+    }
+    #[test]
+    fn find_mount_point_of_cgroup_v1_controller() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&test_path, &[("mounts", "tmpfs /sys/fs/cgroup tmpfs ro,nosuid,nodev,noexec,mode=755 0 0
+cgroup /sys/fs/cgroup/cpu,cpuacct cgroup rw,nosuid,nodev,noexec,relatime,cpu,cpuacct 0 0
+cgroup /sys/fs/cgroup/memory cgroup rw,nosuid,nodev,noexec,relatime,memory 0 0")]);
+
+        let mounts_file = format!("{}/mounts", test_path);
+        let cpuacct = SysCgroup::find_mount_point(&mounts_file, "cgroup", Some("cpuacct"));
+        let unified = SysCgroup::find_mount_point(&mounts_file, "cgroup2", None);
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(cpuacct.unwrap(), "/sys/fs/cgroup/cpu,cpuacct");
+        assert!(unified.is_err());
+    }
+}
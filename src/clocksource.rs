@@ -0,0 +1,124 @@
+/*!
+Read the clocksource from `/sys/devices/system/clocksource/clocksource0` into the struct [`SysClockSource`].
+
+The clocksource is the hardware counter the kernel uses for timekeeping. On x86 the preferred clocksource is `tsc`.
+If the kernel finds the tsc unstable, for example because of an unreliable tsc in a virtual machine, it switches to
+a slower clocksource such as `hpet` or `acpi_pm`, which makes reading the time (gettimeofday, clock_gettime) much
+more expensive. In virtual machines the paravirtualized clocksources `kvm-clock`, `xen` or `hyperv_clocksource_tsc_page`
+are common.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-devices-system-clocksource>
+
+Here is an example obtaining the data from `/sys/devices/system/clocksource/clocksource0`:
+```no_run
+use proc_sys_parser::clocksource;
+
+let sys_clocksource = clocksource::read();
+
+println!("{:#?}", sys_clocksource);
+```
+Example output:
+```text
+SysClockSource {
+    current_clocksource: "tsc",
+    available_clocksource: [
+        "tsc",
+        "kvm-clock",
+        "hpet",
+        "acpi_pm",
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysClockSource`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::clocksource::Builder;
+
+let sys_clocksource = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::read_file_string;
+
+/// Struct for holding the clocksource data
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct SysClockSource {
+    /// The clocksource currently in use.
+    pub current_clocksource: String,
+    /// The clocksources that can be used, in the order of the file.
+    pub available_clocksource: Vec<String>,
+}
+
+/// Builder pattern for [`SysClockSource`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysClockSource, ProcSysParserError> {
+        SysClockSource::read_sys_clocksource(format!("{}/devices/system/clocksource/clocksource0", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysClockSource`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysClockSource, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysClockSource {
+    pub fn new() -> SysClockSource {
+        SysClockSource::default()
+    }
+    /// True if the tsc is available, but another clocksource is in use, which often means the tsc was found unstable.
+    pub fn tsc_available_but_not_used(&self) -> bool {
+        self.current_clocksource != "tsc" && self.available_clocksource.iter().any(|clocksource| clocksource == "tsc")
+    }
+    pub fn read_sys_clocksource(sys_clocksource_path: &str) -> Result<SysClockSource, ProcSysParserError> {
+        let sys_clocksource_path = Path::new(sys_clocksource_path);
+        Ok(SysClockSource {
+            current_clocksource: read_file_string(&sys_clocksource_path.join("current_clocksource"))?.trim().to_string(),
+            available_clocksource: read_file_string(&sys_clocksource_path.join("available_clocksource"))?
+                .split_whitespace()
+                .map(|clocksource| clocksource.to_string())
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_clocksource_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let clocksource_path = format!("{}/devices/system/clocksource/clocksource0", test_path);
+        create_dir_all(&clocksource_path).expect("Error creating mock directory.");
+        write(format!("{}/current_clocksource", clocksource_path), "hpet\n").unwrap_or_else(|_| panic!("Error writing to {}/current_clocksource", clocksource_path));
+        write(format!("{}/available_clocksource", clocksource_path), "tsc hpet acpi_pm \n").unwrap_or_else(|_| panic!("Error writing to {}/available_clocksource", clocksource_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysClockSource { current_clocksource: "hpet".to_string(), available_clocksource: vec!["tsc".to_string(), "hpet".to_string(), "acpi_pm".to_string()] });
+        assert!(result.tsc_available_but_not_used());
+    }
+}
@@ -0,0 +1,219 @@
+/*!
+Read the cpu cache hierarchy from `/sys/devices/system/cpu/cpu<nr>/cache/index<nr>` into the struct [`SysCpuCache`].
+
+Every index directory of a cpu describes a cache the cpu uses: the level, the type (Data, Instruction or Unified),
+the size, the line size, the associativity and the cpus sharing the cache.
+The size is shown in the file with a unit suffix (such as `48K`), and is converted to bytes.
+
+Because caches shared by multiple cpus are shown in the directories of every cpu sharing it, use
+[`SysCpuCache::unique_caches`] to obtain every cache once, for example to calculate the total L3 size.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-devices-system-cpu>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu/cpu<nr>/cache`:
+```no_run
+use proc_sys_parser::cpu::cache;
+
+let sys_cpu_cache = cache::read();
+
+println!("{:#?}", sys_cpu_cache);
+```
+Example output:
+```text
+SysCpuCache {
+    cpus: [
+        CpuCache {
+            cpu_nr: 0,
+            caches: [
+                CacheIndex { index: 0, id: Some(0), level: 1, cache_type: "Data", size: 49152, coherency_line_size: 64, ways_of_associativity: Some(12), number_of_sets: Some(64), shared_cpu_list: [0, 4] },
+                CacheIndex { index: 1, id: Some(0), level: 1, cache_type: "Instruction", size: 32768, coherency_line_size: 64, ways_of_associativity: Some(8), number_of_sets: Some(64), shared_cpu_list: [0, 4] },
+                CacheIndex { index: 2, id: Some(0), level: 2, cache_type: "Unified", size: 1310720, coherency_line_size: 64, ways_of_associativity: Some(10), number_of_sets: Some(2048), shared_cpu_list: [0, 4] },
+                CacheIndex { index: 3, id: Some(0), level: 3, cache_type: "Unified", size: 12582912, coherency_line_size: 64, ways_of_associativity: Some(12), number_of_sets: Some(16384), shared_cpu_list: [0, 1, 2, 3, 4, 5, 6, 7] },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysCpuCache`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::cache::Builder;
+
+let sys_cpu_cache = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::cpu::read_cpus;
+use crate::utils::{parse_cpu_list, read_file_option_u64, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the caches of all cpus
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuCache {
+    pub cpus: Vec<CpuCache>,
+}
+
+/// Struct for holding the caches of a single cpu
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuCache {
+    pub cpu_nr: u32,
+    pub caches: Vec<CacheIndex>,
+}
+
+/// Struct for holding a single cache index directory
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CacheIndex {
+    /// The number of the index directory.
+    pub index: u32,
+    /// The id of the cache, unique among the caches with the same level and type. Kernel 4.11+
+    pub id: Option<u64>,
+    pub level: u64,
+    /// Data, Instruction or Unified.
+    pub cache_type: String,
+    /// The size in bytes.
+    pub size: u64,
+    /// The line size in bytes.
+    pub coherency_line_size: u64,
+    pub ways_of_associativity: Option<u64>,
+    pub number_of_sets: Option<u64>,
+    /// The cpus sharing this cache.
+    pub shared_cpu_list: Vec<u32>,
+}
+
+/// Builder pattern for [`SysCpuCache`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuCache, ProcSysParserError> {
+        SysCpuCache::read_sys_cpu_cache(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuCache`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuCache, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysCpuCache {
+    pub fn new() -> SysCpuCache {
+        SysCpuCache::default()
+    }
+    /// Every cache once, ordered by level and type. Caches are considered equal when the level, type and shared cpus
+    /// are equal.
+    pub fn unique_caches(&self) -> Vec<&CacheIndex> {
+        let mut caches: Vec<&CacheIndex> = Vec::new();
+
+        for cache in self.cpus.iter().flat_map(|cpu| cpu.caches.iter()) {
+            if !caches.iter().any(|known| known.level == cache.level && known.cache_type == cache.cache_type && known.shared_cpu_list == cache.shared_cpu_list) {
+                caches.push(cache);
+            }
+        }
+        caches.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.cache_type.cmp(&b.cache_type)));
+        caches
+    }
+    /// The total size in bytes of all distinct caches of a level, such as the total L3 size of the system.
+    pub fn total_size_of_level(&self, level: u64) -> u64 {
+        self.unique_caches().iter()
+            .filter(|cache| cache.level == level)
+            .map(|cache| cache.size)
+            .sum()
+    }
+    /// Convert a size with a unit suffix such as `48K` into bytes.
+    fn parse_size(size: &str) -> Result<u64, ProcSysParserError> {
+        let size = size.trim();
+        let (number, multiplier) = match size.chars().last() {
+            Some('K') => (&size[..size.len() - 1], 1024),
+            Some('M') => (&size[..size.len() - 1], 1024 * 1024),
+            Some('G') => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+            _ => (size, 1),
+        };
+        Ok(number.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)? * multiplier)
+    }
+    fn read_cache_index(index_path: &Path, index: u32) -> Result<CacheIndex, ProcSysParserError> {
+        Ok(CacheIndex {
+            index,
+            id: read_file_option_u64(&index_path.join("id"))?,
+            level: read_file_u64(&index_path.join("level"))?,
+            cache_type: read_file_string(&index_path.join("type"))?,
+            size: SysCpuCache::parse_size(&read_file_string(&index_path.join("size"))?)?,
+            coherency_line_size: read_file_u64(&index_path.join("coherency_line_size"))?,
+            ways_of_associativity: read_file_option_u64(&index_path.join("ways_of_associativity"))?,
+            number_of_sets: read_file_option_u64(&index_path.join("number_of_sets"))?,
+            shared_cpu_list: parse_cpu_list(&read_file_string(&index_path.join("shared_cpu_list"))?)?,
+        })
+    }
+    pub fn read_sys_cpu_cache(sys_cpu_path: &str) -> Result<SysCpuCache, ProcSysParserError> {
+        let mut syscpucache = SysCpuCache::new();
+
+        for cpu_nr in read_cpus(sys_cpu_path)? {
+            let cache_path = Path::new(sys_cpu_path).join(format!("cpu{}/cache", cpu_nr));
+            if !cache_path.is_dir() { continue };
+            let mut cpucache = CpuCache { cpu_nr, caches: Vec::new() };
+            for index in read_numbered_entries(&cache_path, "index")? {
+                cpucache.caches.push(SysCpuCache::read_cache_index(&cache_path.join(format!("index{}", index)), index)?);
+            }
+            syscpucache.cpus.push(cpucache);
+        }
+        Ok(syscpucache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_cache(test_path: &str, cpu_nr: u32, index: u32, level: u64, cache_type: &str, size: &str, shared_cpu_list: &str) {
+        let index_path = format!("{}/devices/system/cpu/cpu{}/cache/index{}", test_path, cpu_nr, index);
+        create_dir_all(&index_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("level", level.to_string()),
+            ("type", cache_type.to_string()),
+            ("size", size.to_string()),
+            ("coherency_line_size", "64".to_string()),
+            ("ways_of_associativity", "8".to_string()),
+            ("shared_cpu_list", shared_cpu_list.to_string()),
+        ] {
+            write(format!("{}/{}", index_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", index_path, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_cpu_cache_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_cache(&test_path, 0, 0, 1, "Data", "32K", "0");
+        create_mock_cache(&test_path, 0, 1, 2, "Unified", "1024K", "0");
+        create_mock_cache(&test_path, 0, 2, 3, "Unified", "16M", "0-1");
+        create_mock_cache(&test_path, 1, 0, 1, "Data", "32K", "1");
+        create_mock_cache(&test_path, 1, 1, 2, "Unified", "1024K", "1");
+        create_mock_cache(&test_path, 1, 2, 3, "Unified", "16M", "0-1");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.cpus.len(), 2);
+        assert_eq!(result.cpus[1].caches[2], CacheIndex { index: 2, id: None, level: 3, cache_type: "Unified".to_string(), size: 16777216, coherency_line_size: 64, ways_of_associativity: Some(8), number_of_sets: None, shared_cpu_list: vec![0, 1] });
+        assert_eq!(result.unique_caches().len(), 5);
+        assert_eq!(result.total_size_of_level(1), 65536);
+        assert_eq!(result.total_size_of_level(3), 16777216);
+    }
+}
@@ -0,0 +1,198 @@
+/*!
+Read the cpu frequency scaling data from `/sys/devices/system/cpu/cpu<nr>/cpufreq` into the struct [`SysCpuFreq`].
+
+The cpufreq directory of a cpu contains the frequency limits of the hardware, the limits and current frequency set by
+the scaling driver, and the governor that determines the frequency. All frequencies are in kHz.
+
+A cpu without a cpufreq directory, which is common in virtual machines, is not included.
+
+Documentation: <https://docs.kernel.org/admin-guide/pm/cpufreq.html>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu/cpu<nr>/cpufreq`:
+```no_run
+use proc_sys_parser::cpu::cpufreq;
+
+let sys_cpufreq = cpufreq::read();
+
+println!("{:#?}", sys_cpufreq);
+```
+Example output:
+```text
+SysCpuFreq {
+    cpus: [
+        CpuFreq {
+            cpu_nr: 0,
+            cpuinfo_min_freq: 400000,
+            cpuinfo_max_freq: 4700000,
+            scaling_min_freq: 400000,
+            scaling_max_freq: 4700000,
+            scaling_cur_freq: 1332018,
+            scaling_governor: "powersave",
+            scaling_driver: "intel_pstate",
+            scaling_available_governors: [
+                "performance",
+                "powersave",
+            ],
+            scaling_available_frequencies: [],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysCpuFreq`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::cpufreq::Builder;
+
+let sys_cpufreq = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::cpu::read_cpus;
+use crate::utils::{read_file_option_string, read_file_string, read_file_u64};
+
+/// Struct for holding the cpufreq data of all cpus
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuFreq {
+    pub cpus: Vec<CpuFreq>,
+}
+
+/// Struct for holding the cpufreq data of a single cpu, frequencies are in kHz
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuFreq {
+    pub cpu_nr: u32,
+    /// Minimal frequency the hardware can run at.
+    pub cpuinfo_min_freq: u64,
+    /// Maximal frequency the hardware can run at.
+    pub cpuinfo_max_freq: u64,
+    /// Minimal frequency the governor may choose.
+    pub scaling_min_freq: u64,
+    /// Maximal frequency the governor may choose.
+    pub scaling_max_freq: u64,
+    /// Current frequency as determined by the governor and the scaling driver.
+    pub scaling_cur_freq: u64,
+    pub scaling_governor: String,
+    pub scaling_driver: String,
+    /// The governors that can be set, empty if the file does not exist.
+    pub scaling_available_governors: Vec<String>,
+    /// The frequencies that can be set, empty if the scaling driver does not provide the list, such as intel_pstate.
+    pub scaling_available_frequencies: Vec<u64>,
+}
+
+/// Builder pattern for [`SysCpuFreq`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuFreq, ProcSysParserError> {
+        SysCpuFreq::read_sys_cpufreq(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuFreq`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuFreq, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysCpuFreq {
+    pub fn new() -> SysCpuFreq {
+        SysCpuFreq::default()
+    }
+    fn read_cpufreq(cpufreq_path: &Path, cpu_nr: u32) -> Result<CpuFreq, ProcSysParserError> {
+        Ok(CpuFreq {
+            cpu_nr,
+            cpuinfo_min_freq: read_file_u64(&cpufreq_path.join("cpuinfo_min_freq"))?,
+            cpuinfo_max_freq: read_file_u64(&cpufreq_path.join("cpuinfo_max_freq"))?,
+            scaling_min_freq: read_file_u64(&cpufreq_path.join("scaling_min_freq"))?,
+            scaling_max_freq: read_file_u64(&cpufreq_path.join("scaling_max_freq"))?,
+            scaling_cur_freq: read_file_u64(&cpufreq_path.join("scaling_cur_freq"))?,
+            scaling_governor: read_file_string(&cpufreq_path.join("scaling_governor"))?,
+            scaling_driver: read_file_string(&cpufreq_path.join("scaling_driver"))?,
+            scaling_available_governors: read_file_option_string(&cpufreq_path.join("scaling_available_governors"))
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(|governor| governor.to_string())
+                .collect(),
+            scaling_available_frequencies: read_file_option_string(&cpufreq_path.join("scaling_available_frequencies"))
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(|frequency| frequency.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
+                .collect::<Result<Vec<u64>, ProcSysParserError>>()?,
+        })
+    }
+    pub fn read_sys_cpufreq(sys_cpu_path: &str) -> Result<SysCpuFreq, ProcSysParserError> {
+        let mut syscpufreq = SysCpuFreq::new();
+
+        for cpu_nr in read_cpus(sys_cpu_path)? {
+            let cpufreq_path = Path::new(sys_cpu_path).join(format!("cpu{}/cpufreq", cpu_nr));
+            if !cpufreq_path.is_dir() { continue };
+            syscpufreq.cpus.push(SysCpuFreq::read_cpufreq(&cpufreq_path, cpu_nr)?);
+        }
+        Ok(syscpufreq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_cpufreq_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let cpufreq_path = format!("{}/devices/system/cpu/cpu0/cpufreq", test_path);
+        create_dir_all(&cpufreq_path).expect("Error creating mock directory.");
+        // a cpu without cpufreq directory and a non-cpu directory should be ignored
+        create_dir_all(format!("{}/devices/system/cpu/cpu1", test_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/devices/system/cpu/cpufreq", test_path)).expect("Error creating mock directory.");
+
+        for (file, contents) in [
+            ("cpuinfo_min_freq", "800000\n"),
+            ("cpuinfo_max_freq", "3500000\n"),
+            ("scaling_min_freq", "800000\n"),
+            ("scaling_max_freq", "3500000\n"),
+            ("scaling_cur_freq", "1996000\n"),
+            ("scaling_governor", "schedutil\n"),
+            ("scaling_driver", "acpi-cpufreq\n"),
+            ("scaling_available_governors", "conservative ondemand userspace powersave performance schedutil \n"),
+            ("scaling_available_frequencies", "3500000 2800000 2200000 800000 \n"),
+        ] {
+            write(format!("{}/{}", cpufreq_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", cpufreq_path, file));
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysCpuFreq { cpus: vec![
+            CpuFreq {
+                cpu_nr: 0,
+                cpuinfo_min_freq: 800000,
+                cpuinfo_max_freq: 3500000,
+                scaling_min_freq: 800000,
+                scaling_max_freq: 3500000,
+                scaling_cur_freq: 1996000,
+                scaling_governor: "schedutil".to_string(),
+                scaling_driver: "acpi-cpufreq".to_string(),
+                scaling_available_governors: vec!["conservative".to_string(), "ondemand".to_string(), "userspace".to_string(), "powersave".to_string(), "performance".to_string(), "schedutil".to_string()],
+                scaling_available_frequencies: vec![3500000, 2800000, 2200000, 800000],
+            }
+        ]});
+    }
+}
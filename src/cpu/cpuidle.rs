@@ -0,0 +1,204 @@
+/*!
+Read the cpu idle states from `/sys/devices/system/cpu/cpu<nr>/cpuidle/state<nr>` into the struct [`SysCpuIdle`].
+
+When a cpu has no work, the cpuidle governor selects an idle state for the cpu. Deeper idle states save more power,
+but take longer to exit, which adds latency to the wakeup of a task. Per state, the statistics show how often the state
+was entered and how long the cpu spent in it, which shows if latency sensitive workloads suffer from deep idle states.
+
+The exit latency, the target residency and the time in the state are in microseconds.
+
+A cpu without a cpuidle directory, which is common in virtual machines, is not included.
+
+Documentation: <https://docs.kernel.org/admin-guide/pm/cpuidle.html>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu/cpu<nr>/cpuidle`:
+```no_run
+use proc_sys_parser::cpu::cpuidle;
+
+let sys_cpuidle = cpuidle::read();
+
+println!("{:#?}", sys_cpuidle);
+```
+Example output:
+```text
+SysCpuIdle {
+    current_driver: Some("intel_idle"),
+    current_governor: Some("menu"),
+    cpus: [
+        CpuIdle {
+            cpu_nr: 0,
+            states: [
+                CpuIdleState { state_nr: 0, name: "POLL", desc: "CPUIDLE CORE POLL IDLE", latency: 0, residency: 0, usage: 3046, time: 63012, disable: 0, above: Some(0), below: Some(1402) },
+                CpuIdleState { state_nr: 1, name: "C1E", desc: "MWAIT 0x01", latency: 2, residency: 4, usage: 102554, time: 48630312, disable: 0, above: Some(2), below: Some(5217) },
+                CpuIdleState { state_nr: 2, name: "C6", desc: "MWAIT 0x20", latency: 133, residency: 400, usage: 210992, time: 1723062911, disable: 0, above: Some(4170), below: Some(0) },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysCpuIdle`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::cpuidle::Builder;
+
+let sys_cpuidle = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::cpu::read_cpus;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the idle states of all cpus
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuIdle {
+    /// The cpuidle driver, None if cpuidle is not available.
+    pub current_driver: Option<String>,
+    /// The cpuidle governor, None if cpuidle is not available.
+    pub current_governor: Option<String>,
+    pub cpus: Vec<CpuIdle>,
+}
+
+/// Struct for holding the idle states of a single cpu
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuIdle {
+    pub cpu_nr: u32,
+    pub states: Vec<CpuIdleState>,
+}
+
+/// Struct for holding a single idle state of a cpu
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuIdleState {
+    pub state_nr: u32,
+    pub name: String,
+    pub desc: String,
+    /// The exit latency in microseconds.
+    pub latency: u64,
+    /// The minimal time in microseconds the cpu should stay in the state to be worth entering it.
+    pub residency: u64,
+    /// The number of times the state was entered.
+    pub usage: u64,
+    /// The total time in microseconds spent in the state.
+    pub time: u64,
+    /// 1 if the state is disabled.
+    pub disable: u64,
+    /// The number of times the state was entered, but the idle time turned out to be too short. Kernel 5.1+
+    pub above: Option<u64>,
+    /// The number of times the state was entered, but a deeper state would have fitted the idle time. Kernel 5.1+
+    pub below: Option<u64>,
+}
+
+/// Builder pattern for [`SysCpuIdle`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuIdle, ProcSysParserError> {
+        SysCpuIdle::read_sys_cpuidle(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuIdle`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuIdle, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysCpuIdle {
+    pub fn new() -> SysCpuIdle {
+        SysCpuIdle::default()
+    }
+    fn read_cpuidle_state(state_path: &Path, state_nr: u32) -> Result<CpuIdleState, ProcSysParserError> {
+        Ok(CpuIdleState {
+            state_nr,
+            name: read_file_string(&state_path.join("name"))?,
+            desc: read_file_string(&state_path.join("desc"))?,
+            latency: read_file_u64(&state_path.join("latency"))?,
+            residency: read_file_u64(&state_path.join("residency"))?,
+            usage: read_file_u64(&state_path.join("usage"))?,
+            time: read_file_u64(&state_path.join("time"))?,
+            disable: read_file_u64(&state_path.join("disable"))?,
+            above: read_file_option_u64(&state_path.join("above"))?,
+            below: read_file_option_u64(&state_path.join("below"))?,
+        })
+    }
+    pub fn read_sys_cpuidle(sys_cpu_path: &str) -> Result<SysCpuIdle, ProcSysParserError> {
+        let mut syscpuidle = SysCpuIdle::new();
+
+        syscpuidle.current_driver = read_file_option_string(&Path::new(sys_cpu_path).join("cpuidle/current_driver"));
+        syscpuidle.current_governor = read_file_option_string(&Path::new(sys_cpu_path).join("cpuidle/current_governor"))
+            .or_else(|| read_file_option_string(&Path::new(sys_cpu_path).join("cpuidle/current_governor_ro")));
+
+        for cpu_nr in read_cpus(sys_cpu_path)? {
+            let cpuidle_path = Path::new(sys_cpu_path).join(format!("cpu{}/cpuidle", cpu_nr));
+            if !cpuidle_path.is_dir() { continue };
+            let mut cpuidle = CpuIdle { cpu_nr, states: Vec::new() };
+            for state_nr in read_numbered_entries(&cpuidle_path, "state")? {
+                cpuidle.states.push(SysCpuIdle::read_cpuidle_state(&cpuidle_path.join(format!("state{}", state_nr)), state_nr)?);
+            }
+            syscpuidle.cpus.push(cpuidle);
+        }
+        Ok(syscpuidle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_state(test_path: &str, cpu_nr: u32, state_nr: u32, name: &str, latency: u64, time: u64) {
+        let state_path = format!("{}/devices/system/cpu/cpu{}/cpuidle/state{}", test_path, cpu_nr, state_nr);
+        create_dir_all(&state_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("name", name.to_string()),
+            ("desc", format!("ACPI {}", name)),
+            ("latency", latency.to_string()),
+            ("residency", (latency * 3).to_string()),
+            ("usage", "1000".to_string()),
+            ("time", time.to_string()),
+            ("disable", "0".to_string()),
+            ("above", "1".to_string()),
+            ("below", "2".to_string()),
+        ] {
+            write(format!("{}/{}", state_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", state_path, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_cpuidle_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_state(&test_path, 0, 0, "POLL", 0, 100);
+        create_mock_state(&test_path, 0, 1, "C1", 1, 5000);
+        create_mock_state(&test_path, 1, 0, "POLL", 0, 200);
+        create_dir_all(format!("{}/devices/system/cpu/cpuidle", test_path)).expect("Error creating mock directory.");
+        write(format!("{}/devices/system/cpu/cpuidle/current_driver", test_path), "acpi_idle\n").unwrap_or_else(|_| panic!("Error writing to {}/devices/system/cpu/cpuidle/current_driver", test_path));
+        write(format!("{}/devices/system/cpu/cpuidle/current_governor_ro", test_path), "menu\n").unwrap_or_else(|_| panic!("Error writing to {}/devices/system/cpu/cpuidle/current_governor_ro", test_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.current_driver, Some("acpi_idle".to_string()));
+        assert_eq!(result.current_governor, Some("menu".to_string()));
+        assert_eq!(result.cpus.len(), 2);
+        assert_eq!(result.cpus[0].states[1], CpuIdleState { state_nr: 1, name: "C1".to_string(), desc: "ACPI C1".to_string(), latency: 1, residency: 3, usage: 1000, time: 5000, disable: 0, above: Some(1), below: Some(2) });
+        assert_eq!(result.cpus[1].states.len(), 1);
+    }
+}
@@ -0,0 +1,184 @@
+/*!
+Read the cpu lists `online`, `offline`, `possible`, `present` and `isolated` from `/sys/devices/system/cpu` into the
+struct [`SysCpuMasks`].
+
+The cpu lists use the kernel list format, which is a comma separated list of cpu numbers and ranges, such as `0-3,5`.
+The lists are expanded into vectors of cpu numbers using [`parse_cpu_list`], which can be used for any other file
+in this format too, such as the `cpulist` of a numa node. Files that show cpus as a hexadecimal mask, such as
+`/proc/irq/<nr>/smp_affinity`, can be converted using [`parse_cpu_mask`].
+
+- possible: the cpus that can ever be available in the system, including cpus that can be hotplugged.
+- present: the cpus that are currently physically present.
+- online: the cpus that are currently online and being scheduled.
+- offline: the cpus that are not online, because they are set offline or are not present but possible.
+- isolated: the cpus isolated with the isolcpus boot parameter, and thus not used by the scheduler for normal tasks.
+
+The numbering of online cpus is not necessarily contiguous: the cpu lines in `/proc/stat` and `/proc/schedstat` are only
+shown for online cpus, so the position of a cpu in those lists should not be taken as the cpu number.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-devices-system-cpu>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu`:
+```no_run
+use proc_sys_parser::cpu::masks;
+
+let sys_cpu_masks = masks::read();
+
+println!("{:#?}", sys_cpu_masks);
+```
+Example output:
+```text
+SysCpuMasks {
+    online: [0, 1, 2, 3, 5],
+    offline: [4, 6, 7],
+    possible: [0, 1, 2, 3, 4, 5, 6, 7],
+    present: [0, 1, 2, 3, 4, 5],
+    isolated: [],
+    kernel_max: Some(8191),
+}
+```
+
+If you want to change the path that is read for [`SysCpuMasks`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::masks::Builder;
+
+let sys_cpu_masks = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_file_string};
+
+pub use crate::utils::{parse_cpu_list, parse_cpu_mask};
+
+/// Struct for holding the cpu lists
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct SysCpuMasks {
+    pub online: Vec<u32>,
+    pub offline: Vec<u32>,
+    pub possible: Vec<u32>,
+    pub present: Vec<u32>,
+    /// Empty if the file does not exist.
+    pub isolated: Vec<u32>,
+    /// The maximum cpu index allowed by the kernel configuration.
+    pub kernel_max: Option<u64>,
+}
+
+/// Builder pattern for [`SysCpuMasks`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuMasks, ProcSysParserError> {
+        SysCpuMasks::read_sys_cpu_masks(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuMasks`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuMasks, ProcSysParserError> {
+   Builder::new().read()
+}
+
+/// Convert a list of cpu numbers into a bitmask of 64 bit words, where cpu 0 is the lowest bit of the first word.
+pub fn cpu_list_to_bitmask(cpus: &[u32]) -> Vec<u64> {
+    let mut bitmask = vec![0_u64; cpus.iter().max().map(|max| *max as usize / 64 + 1).unwrap_or_default()];
+    for cpu in cpus {
+        bitmask[*cpu as usize / 64] |= 1 << (cpu % 64);
+    }
+    bitmask
+}
+
+/// Convert a bitmask of 64 bit words, where cpu 0 is the lowest bit of the first word, into a list of cpu numbers.
+pub fn bitmask_to_cpu_list(bitmask: &[u64]) -> Vec<u32> {
+    bitmask.iter()
+        .enumerate()
+        .flat_map(|(word_nr, word)| (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| word_nr as u32 * 64 + bit))
+        .collect()
+}
+
+impl SysCpuMasks {
+    pub fn new() -> SysCpuMasks {
+        SysCpuMasks::default()
+    }
+    pub fn is_online(&self, cpu_nr: u32) -> bool {
+        self.online.contains(&cpu_nr)
+    }
+    pub fn is_isolated(&self, cpu_nr: u32) -> bool {
+        self.isolated.contains(&cpu_nr)
+    }
+    /// True if the online cpus are not numbered 0 up to the number of online cpus, which means per cpu statistics
+    /// must be matched by cpu number, not by position.
+    pub fn is_sparse(&self) -> bool {
+        self.online.iter().enumerate().any(|(position, cpu_nr)| position as u32 != *cpu_nr)
+    }
+    /// The present cpus that are not online.
+    pub fn present_offline(&self) -> Vec<u32> {
+        self.present.iter().filter(|cpu_nr| !self.is_online(**cpu_nr)).copied().collect()
+    }
+    pub fn read_sys_cpu_masks(sys_cpu_path: &str) -> Result<SysCpuMasks, ProcSysParserError> {
+        let sys_cpu_path = Path::new(sys_cpu_path);
+        Ok(SysCpuMasks {
+            online: parse_cpu_list(&read_file_string(&sys_cpu_path.join("online"))?)?,
+            offline: parse_cpu_list(&read_file_string(&sys_cpu_path.join("offline"))?)?,
+            possible: parse_cpu_list(&read_file_string(&sys_cpu_path.join("possible"))?)?,
+            present: parse_cpu_list(&read_file_string(&sys_cpu_path.join("present"))?)?,
+            isolated: parse_cpu_list(&read_file_option_string(&sys_cpu_path.join("isolated")).unwrap_or_default())?,
+            kernel_max: read_file_option_u64(&sys_cpu_path.join("kernel_max"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn convert_cpu_list_and_bitmask() {
+        assert_eq!(cpu_list_to_bitmask(&[0, 1, 3, 64]), vec![11, 1]);
+        assert_eq!(cpu_list_to_bitmask(&[]), Vec::<u64>::new());
+        assert_eq!(bitmask_to_cpu_list(&[11, 1]), vec![0, 1, 3, 64]);
+    }
+
+    #[test]
+    fn create_sys_cpu_masks_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let cpu_path = format!("{}/devices/system/cpu", test_path);
+        create_dir_all(&cpu_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("online", "0-3,5\n"),
+            ("offline", "4,6-7\n"),
+            ("possible", "0-7\n"),
+            ("present", "0-5\n"),
+            ("isolated", "\n"),
+            ("kernel_max", "8191\n"),
+        ] {
+            write(format!("{}/{}", cpu_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", cpu_path, file));
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysCpuMasks { online: vec![0, 1, 2, 3, 5], offline: vec![4, 6, 7], possible: vec![0, 1, 2, 3, 4, 5, 6, 7], present: vec![0, 1, 2, 3, 4, 5], isolated: vec![], kernel_max: Some(8191) });
+        assert!(result.is_online(5));
+        assert!(!result.is_online(4));
+        assert!(result.is_sparse());
+        assert_eq!(result.present_offline(), vec![4]);
+    }
+}
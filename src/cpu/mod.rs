@@ -0,0 +1,25 @@
+/*!
+Read data from the cpu directories in `/sys/devices/system/cpu`.
+
+Every submodule in `cpu` parses a group of files found in `/sys/devices/system/cpu/cpu<nr>`, for all cpus.
+By default the submodules read `/sys`; use the `path()` function of the Builder of the submodule to read another path.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-devices-system-cpu>
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::read_numbered_entries;
+
+pub mod cache;
+pub mod cpufreq;
+pub mod cpuidle;
+pub mod masks;
+pub mod pstate;
+pub mod topology;
+pub mod vulnerabilities;
+
+/// List the numbers of the cpus in `sys_cpu_path` (normally `/sys/devices/system/cpu`), sorted.
+/// This takes all directories `cpu<nr>`.
+pub fn read_cpus(sys_cpu_path: &str) -> Result<Vec<u32>, ProcSysParserError> {
+    read_numbered_entries(Path::new(sys_cpu_path), "cpu")
+}
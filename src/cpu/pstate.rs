@@ -0,0 +1,219 @@
+/*!
+Read the configuration of the intel_pstate and amd_pstate scaling drivers from `/sys/devices/system/cpu/intel_pstate`
+and `/sys/devices/system/cpu/amd_pstate` into the struct [`SysCpuPstate`].
+
+The pstate drivers are the cpufreq scaling drivers of modern Intel and AMD cpus. Their global configuration determines
+the operation mode of the driver, and for intel_pstate the limits of the performance, which override the limits
+set via the cpufreq policies. The per-cpu frequency data is read with [`crate::cpu::cpufreq`].
+
+If a driver is not active, its directory does not exist, and the driver field is None. If the intel_pstate driver is
+loaded but its status is `off`, the performance files cannot be read, and their fields are None.
+
+Documentation:
+- intel_pstate: <https://docs.kernel.org/admin-guide/pm/intel_pstate.html>
+- amd_pstate: <https://docs.kernel.org/admin-guide/pm/amd-pstate.html>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu`:
+```no_run
+use proc_sys_parser::cpu::pstate;
+
+let sys_cpu_pstate = pstate::read();
+
+println!("{:#?}", sys_cpu_pstate);
+```
+Example output:
+```text
+SysCpuPstate {
+    intel_pstate: Some(
+        IntelPstate {
+            status: "active",
+            no_turbo: Some(0),
+            min_perf_pct: Some(9),
+            max_perf_pct: Some(100),
+            num_pstates: Some(40),
+            turbo_pct: Some(24),
+            hwp_dynamic_boost: Some(0),
+        },
+    ),
+    amd_pstate: None,
+}
+```
+
+If you want to change the path that is read for [`SysCpuPstate`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::pstate::Builder;
+
+let sys_cpu_pstate = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_file_string};
+
+/// Struct for holding the pstate driver configuration
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuPstate {
+    pub intel_pstate: Option<IntelPstate>,
+    pub amd_pstate: Option<AmdPstate>,
+}
+
+/// Struct for holding the intel_pstate driver configuration
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct IntelPstate {
+    /// The operation mode: active, passive or off.
+    pub status: String,
+    /// 1 if turbo is disabled, None if the status is off.
+    pub no_turbo: Option<u64>,
+    /// The minimal performance as percentage of the maximal available performance, None if the status is off.
+    pub min_perf_pct: Option<u64>,
+    /// The maximal performance as percentage of the maximal available performance, None if the status is off.
+    pub max_perf_pct: Option<u64>,
+    /// The number of pstates, not available in passive mode with HWP.
+    pub num_pstates: Option<u64>,
+    /// The percentage of the performance range that is in the turbo range.
+    pub turbo_pct: Option<u64>,
+    /// 1 if HWP dynamic boost is enabled, only available with HWP.
+    pub hwp_dynamic_boost: Option<u64>,
+}
+
+/// Struct for holding the amd_pstate driver configuration
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct AmdPstate {
+    /// The operation mode: active, passive, guided or disable.
+    pub status: String,
+    /// The preferred core support, enabled or disabled. Kernel 6.9+
+    pub prefcore: Option<String>,
+}
+
+/// Builder pattern for [`SysCpuPstate`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuPstate, ProcSysParserError> {
+        SysCpuPstate::read_sys_cpu_pstate(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuPstate`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuPstate, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysCpuPstate {
+    pub fn new() -> SysCpuPstate {
+        SysCpuPstate::default()
+    }
+    fn read_intel_pstate(intel_pstate_path: &Path) -> Result<IntelPstate, ProcSysParserError> {
+        let status = read_file_string(&intel_pstate_path.join("status"))?;
+        // with status off, reading the performance files fails with EAGAIN.
+        if status == "off" {
+            return Ok(IntelPstate { status, ..Default::default() });
+        }
+
+        Ok(IntelPstate {
+            status,
+            no_turbo: read_file_option_u64(&intel_pstate_path.join("no_turbo"))?,
+            min_perf_pct: read_file_option_u64(&intel_pstate_path.join("min_perf_pct"))?,
+            max_perf_pct: read_file_option_u64(&intel_pstate_path.join("max_perf_pct"))?,
+            num_pstates: read_file_option_u64(&intel_pstate_path.join("num_pstates"))?,
+            turbo_pct: read_file_option_u64(&intel_pstate_path.join("turbo_pct"))?,
+            hwp_dynamic_boost: read_file_option_u64(&intel_pstate_path.join("hwp_dynamic_boost"))?,
+        })
+    }
+    fn read_amd_pstate(amd_pstate_path: &Path) -> Result<AmdPstate, ProcSysParserError> {
+        Ok(AmdPstate {
+            status: read_file_string(&amd_pstate_path.join("status"))?,
+            prefcore: read_file_option_string(&amd_pstate_path.join("prefcore")),
+        })
+    }
+    pub fn read_sys_cpu_pstate(sys_cpu_path: &str) -> Result<SysCpuPstate, ProcSysParserError> {
+        let mut syscpupstate = SysCpuPstate::new();
+
+        let intel_pstate_path = Path::new(sys_cpu_path).join("intel_pstate");
+        if intel_pstate_path.is_dir() {
+            syscpupstate.intel_pstate = Some(SysCpuPstate::read_intel_pstate(&intel_pstate_path)?);
+        }
+        let amd_pstate_path = Path::new(sys_cpu_path).join("amd_pstate");
+        if amd_pstate_path.is_dir() {
+            syscpupstate.amd_pstate = Some(SysCpuPstate::read_amd_pstate(&amd_pstate_path)?);
+        }
+        Ok(syscpupstate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_cpu_intel_pstate_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let intel_pstate_path = format!("{}/devices/system/cpu/intel_pstate", test_path);
+        create_dir_all(&intel_pstate_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("status", "passive\n"),
+            ("no_turbo", "1\n"),
+            ("min_perf_pct", "20\n"),
+            ("max_perf_pct", "80\n"),
+            ("num_pstates", "32\n"),
+            ("turbo_pct", "24\n"),
+        ] {
+            write(format!("{}/{}", intel_pstate_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", intel_pstate_path, file));
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysCpuPstate { intel_pstate: Some(IntelPstate { status: "passive".to_string(), no_turbo: Some(1), min_perf_pct: Some(20), max_perf_pct: Some(80), num_pstates: Some(32), turbo_pct: Some(24), hwp_dynamic_boost: None }), amd_pstate: None });
+    }
+
+    #[test]
+    fn create_sys_cpu_intel_pstate_status_off_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let intel_pstate_path = format!("{}/devices/system/cpu/intel_pstate", test_path);
+        create_dir_all(&intel_pstate_path).expect("Error creating mock directory.");
+        // with status off, the performance files cannot be read.
+        write(format!("{}/status", intel_pstate_path), "off\n").unwrap_or_else(|_| panic!("Error writing to {}/status", intel_pstate_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysCpuPstate { intel_pstate: Some(IntelPstate { status: "off".to_string(), no_turbo: None, min_perf_pct: None, max_perf_pct: None, num_pstates: None, turbo_pct: None, hwp_dynamic_boost: None }), amd_pstate: None });
+    }
+
+    #[test]
+    fn create_sys_cpu_amd_pstate_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let amd_pstate_path = format!("{}/devices/system/cpu/amd_pstate", test_path);
+        create_dir_all(&amd_pstate_path).expect("Error creating mock directory.");
+        write(format!("{}/status", amd_pstate_path), "active\n").unwrap_or_else(|_| panic!("Error writing to {}/status", amd_pstate_path));
+        write(format!("{}/prefcore", amd_pstate_path), "enabled\n").unwrap_or_else(|_| panic!("Error writing to {}/prefcore", amd_pstate_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysCpuPstate { intel_pstate: None, amd_pstate: Some(AmdPstate { status: "active".to_string(), prefcore: Some("enabled".to_string()) }) });
+    }
+}
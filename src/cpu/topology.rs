@@ -0,0 +1,232 @@
+/*!
+Read the cpu topology from `/sys/devices/system/cpu/cpu<nr>/topology` into the struct [`SysCpuTopology`].
+
+The topology directory of a cpu describes where the (logical) cpu is placed: the physical package (socket), the die
+inside the package, the cluster inside the die, and the core. The thread siblings are the logical cpus sharing the
+same core, which means more than one thread sibling indicates SMT (hyperthreading).
+
+Offline cpus do not have a topology directory, and are not included.
+
+With the topology, per cpu statistics such as the ones in `/proc/stat` can be aggregated per core or per socket.
+
+Documentation: <https://docs.kernel.org/admin-guide/cputopology.html>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu/cpu<nr>/topology`:
+```no_run
+use proc_sys_parser::cpu::topology;
+
+let sys_cpu_topology = topology::read();
+
+println!("{:#?}", sys_cpu_topology);
+```
+Example output:
+```text
+SysCpuTopology {
+    cpus: [
+        CpuTopology {
+            cpu_nr: 0,
+            physical_package_id: 0,
+            die_id: Some(0),
+            cluster_id: Some(0),
+            core_id: 0,
+            thread_siblings_list: [0, 4],
+        },
+        CpuTopology {
+            cpu_nr: 1,
+            physical_package_id: 0,
+            die_id: Some(0),
+            cluster_id: Some(0),
+            core_id: 1,
+            thread_siblings_list: [1, 5],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysCpuTopology`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::topology::Builder;
+
+let sys_cpu_topology = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::cpu::read_cpus;
+use crate::utils::{parse_cpu_list, read_file_i64, read_file_option_i64, read_file_string};
+
+/// Struct for holding the topology of all online cpus
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuTopology {
+    pub cpus: Vec<CpuTopology>,
+}
+
+/// Struct for holding the topology of a single cpu
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuTopology {
+    pub cpu_nr: u32,
+    /// The physical package (socket) number, -1 if unknown.
+    pub physical_package_id: i64,
+    /// The die number inside the package. Kernel 5.2+
+    pub die_id: Option<i64>,
+    /// The cluster number, -1 if unknown. Kernel 5.16+
+    pub cluster_id: Option<i64>,
+    /// The core number, which is only unique within the package.
+    pub core_id: i64,
+    /// The logical cpus sharing the core with this cpu, including the cpu itself.
+    pub thread_siblings_list: Vec<u32>,
+}
+
+/// Builder pattern for [`SysCpuTopology`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuTopology, ProcSysParserError> {
+        SysCpuTopology::read_sys_cpu_topology(format!("{}/devices/system/cpu", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuTopology`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuTopology, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysCpuTopology {
+    pub fn new() -> SysCpuTopology {
+        SysCpuTopology::default()
+    }
+    /// The topology of a cpu, None if the cpu is not found.
+    pub fn cpu(&self, cpu_nr: u32) -> Option<&CpuTopology> {
+        self.cpus.iter().find(|cpu| cpu.cpu_nr == cpu_nr)
+    }
+    /// The socket (physical package) of a cpu.
+    pub fn socket_of(&self, cpu_nr: u32) -> Option<i64> {
+        self.cpu(cpu_nr).map(|cpu| cpu.physical_package_id)
+    }
+    /// The core of a cpu as (physical_package_id, core_id), because the core id is only unique within a package.
+    pub fn core_of(&self, cpu_nr: u32) -> Option<(i64, i64)> {
+        self.cpu(cpu_nr).map(|cpu| (cpu.physical_package_id, cpu.core_id))
+    }
+    /// The distinct sockets, sorted.
+    pub fn sockets(&self) -> Vec<i64> {
+        let mut sockets: Vec<i64> = self.cpus.iter().map(|cpu| cpu.physical_package_id).collect();
+        sockets.sort();
+        sockets.dedup();
+        sockets
+    }
+    /// The distinct cores as (physical_package_id, core_id), sorted.
+    pub fn cores(&self) -> Vec<(i64, i64)> {
+        let mut cores: Vec<(i64, i64)> = self.cpus.iter().map(|cpu| (cpu.physical_package_id, cpu.core_id)).collect();
+        cores.sort();
+        cores.dedup();
+        cores
+    }
+    /// The cpus in a socket.
+    pub fn cpus_in_socket(&self, physical_package_id: i64) -> Vec<u32> {
+        self.cpus.iter()
+            .filter(|cpu| cpu.physical_package_id == physical_package_id)
+            .map(|cpu| cpu.cpu_nr)
+            .collect()
+    }
+    /// The cpus in a core.
+    pub fn cpus_in_core(&self, physical_package_id: i64, core_id: i64) -> Vec<u32> {
+        self.cpus.iter()
+            .filter(|cpu| cpu.physical_package_id == physical_package_id && cpu.core_id == core_id)
+            .map(|cpu| cpu.cpu_nr)
+            .collect()
+    }
+    /// True if two different cpus are thread siblings, which means they share a core.
+    pub fn is_smt_sibling(&self, cpu_nr: u32, other_cpu_nr: u32) -> bool {
+        cpu_nr != other_cpu_nr && self.cpu(cpu_nr).is_some_and(|cpu| cpu.thread_siblings_list.contains(&other_cpu_nr))
+    }
+    /// True if any core has more than one thread, which means SMT is active.
+    pub fn smt_active(&self) -> bool {
+        self.cpus.iter().any(|cpu| cpu.thread_siblings_list.len() > 1)
+    }
+    fn read_cpu_topology(topology_path: &Path, cpu_nr: u32) -> Result<CpuTopology, ProcSysParserError> {
+        Ok(CpuTopology {
+            cpu_nr,
+            physical_package_id: read_file_i64(&topology_path.join("physical_package_id"))?,
+            die_id: read_file_option_i64(&topology_path.join("die_id"))?,
+            cluster_id: read_file_option_i64(&topology_path.join("cluster_id"))?,
+            core_id: read_file_i64(&topology_path.join("core_id"))?,
+            thread_siblings_list: parse_cpu_list(&read_file_string(&topology_path.join("thread_siblings_list"))?)?,
+        })
+    }
+    pub fn read_sys_cpu_topology(sys_cpu_path: &str) -> Result<SysCpuTopology, ProcSysParserError> {
+        let mut syscputopology = SysCpuTopology::new();
+
+        for cpu_nr in read_cpus(sys_cpu_path)? {
+            let topology_path = Path::new(sys_cpu_path).join(format!("cpu{}/topology", cpu_nr));
+            if !topology_path.is_dir() { continue };
+            syscputopology.cpus.push(SysCpuTopology::read_cpu_topology(&topology_path, cpu_nr)?);
+        }
+        Ok(syscputopology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_cpu(test_path: &str, cpu_nr: u32, physical_package_id: i64, core_id: i64, thread_siblings_list: &str) {
+        let topology_path = format!("{}/devices/system/cpu/cpu{}/topology", test_path, cpu_nr);
+        create_dir_all(&topology_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("physical_package_id", physical_package_id.to_string()),
+            ("die_id", "0".to_string()),
+            ("core_id", core_id.to_string()),
+            ("thread_siblings_list", thread_siblings_list.to_string()),
+        ] {
+            write(format!("{}/{}", topology_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", topology_path, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_cpu_topology_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        // two sockets with one core each, with two threads per core.
+        create_mock_cpu(&test_path, 0, 0, 0, "0,2");
+        create_mock_cpu(&test_path, 1, 1, 0, "1,3");
+        create_mock_cpu(&test_path, 2, 0, 0, "0,2");
+        create_mock_cpu(&test_path, 3, 1, 0, "1,3");
+        // an offline cpu has no topology directory
+        create_dir_all(format!("{}/devices/system/cpu/cpu4", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.cpus.len(), 4);
+        assert_eq!(result.cpu(1), Some(&CpuTopology { cpu_nr: 1, physical_package_id: 1, die_id: Some(0), cluster_id: None, core_id: 0, thread_siblings_list: vec![1, 3] }));
+        assert_eq!(result.sockets(), vec![0, 1]);
+        assert_eq!(result.cores(), vec![(0, 0), (1, 0)]);
+        assert_eq!(result.socket_of(3), Some(1));
+        assert_eq!(result.core_of(4), None);
+        assert_eq!(result.cpus_in_socket(0), vec![0, 2]);
+        assert_eq!(result.cpus_in_core(1, 0), vec![1, 3]);
+        assert!(result.is_smt_sibling(0, 2));
+        assert!(!result.is_smt_sibling(0, 1));
+        assert!(!result.is_smt_sibling(0, 0));
+        assert!(result.smt_active());
+    }
+}
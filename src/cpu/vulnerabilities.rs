@@ -0,0 +1,199 @@
+/*!
+Read the cpu vulnerability status from `/sys/devices/system/cpu/vulnerabilities` into the struct [`SysCpuVulnerabilities`].
+
+Every file in the vulnerabilities directory is named after a hardware vulnerability, such as meltdown, spectre_v2 or mds,
+and contains a single line with the status for the system:
+- `Not affected`: the cpu is not affected by the vulnerability.
+- `Vulnerable`: the cpu is affected and no mitigation is active. This can be followed by a colon and a description.
+- `Mitigation: <description>`: the cpu is affected and the described mitigation is active.
+- `Unknown: <description>`: the kernel cannot determine the status, for example in a virtual machine.
+
+The status is parsed into [`VulnerabilityStatus`]. Because new vulnerabilities get added over time, every file in the
+directory is read, and the vulnerabilities are sorted by name.
+
+Documentation: <https://docs.kernel.org/admin-guide/hw-vuln/index.html>
+
+Here is an example obtaining the data from `/sys/devices/system/cpu/vulnerabilities`:
+```no_run
+use proc_sys_parser::cpu::vulnerabilities;
+
+let sys_cpu_vulnerabilities = vulnerabilities::read();
+
+println!("{:#?}", sys_cpu_vulnerabilities);
+```
+Example output:
+```text
+SysCpuVulnerabilities {
+    vulnerabilities: [
+        Vulnerability { name: "itlb_multihit", status: NotAffected },
+        Vulnerability { name: "l1tf", status: NotAffected },
+        Vulnerability { name: "mds", status: Vulnerable(Some("Clear CPU buffers attempted, no microcode; SMT Host state unknown")) },
+        Vulnerability { name: "meltdown", status: NotAffected },
+        Vulnerability { name: "spectre_v1", status: Mitigation("usercopy/swapgs barriers and __user pointer sanitization") },
+        Vulnerability { name: "spectre_v2", status: Mitigation("Enhanced / Automatic IBRS; IBPB: conditional; PBRSB-eIBRS: SW sequence") },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysCpuVulnerabilities`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::cpu::vulnerabilities::Builder;
+
+let sys_cpu_vulnerabilities = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use crate::ProcSysParserError;
+use crate::utils::read_file_string;
+
+/// Struct for holding the status of all cpu vulnerabilities
+#[derive(Debug, PartialEq, Default)]
+pub struct SysCpuVulnerabilities {
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+/// Struct for holding the status of a single vulnerability
+#[derive(Debug, PartialEq, Clone)]
+pub struct Vulnerability {
+    /// The name of the file, such as `spectre_v2`.
+    pub name: String,
+    pub status: VulnerabilityStatus,
+}
+
+/// The status of a vulnerability
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VulnerabilityStatus {
+    NotAffected,
+    /// The cpu is vulnerable, with the description if it is provided.
+    Vulnerable(Option<String>),
+    /// The cpu is affected, and the described mitigation is active.
+    Mitigation(String),
+    /// The kernel cannot determine the status, with the description.
+    Unknown(String),
+    /// The contents of the file do not have one of the formats above.
+    Other(String),
+}
+
+/// Builder pattern for [`SysCpuVulnerabilities`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysCpuVulnerabilities, ProcSysParserError> {
+        SysCpuVulnerabilities::read_sys_cpu_vulnerabilities(format!("{}/devices/system/cpu/vulnerabilities", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysCpuVulnerabilities`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysCpuVulnerabilities, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl VulnerabilityStatus {
+    pub fn parse(status: &str) -> VulnerabilityStatus {
+        let status = status.trim();
+        if status == "Not affected" {
+            VulnerabilityStatus::NotAffected
+        } else if status == "Vulnerable" {
+            VulnerabilityStatus::Vulnerable(None)
+        } else if let Some(description) = status.strip_prefix("Vulnerable:") {
+            VulnerabilityStatus::Vulnerable(Some(description.trim().to_string()))
+        } else if let Some(description) = status.strip_prefix("Mitigation:") {
+            VulnerabilityStatus::Mitigation(description.trim().to_string())
+        } else if let Some(description) = status.strip_prefix("Unknown:") {
+            VulnerabilityStatus::Unknown(description.trim().to_string())
+        } else {
+            VulnerabilityStatus::Other(status.to_string())
+        }
+    }
+}
+
+impl SysCpuVulnerabilities {
+    pub fn new() -> SysCpuVulnerabilities {
+        SysCpuVulnerabilities::default()
+    }
+    /// The status of a vulnerability by name, None if the kernel does not know the vulnerability.
+    pub fn get(&self, name: &str) -> Option<&VulnerabilityStatus> {
+        self.vulnerabilities.iter()
+            .find(|vulnerability| vulnerability.name == name)
+            .map(|vulnerability| &vulnerability.status)
+    }
+    /// The vulnerabilities for which the cpu is vulnerable without mitigation.
+    pub fn vulnerable(&self) -> Vec<&Vulnerability> {
+        self.vulnerabilities.iter()
+            .filter(|vulnerability| matches!(vulnerability.status, VulnerabilityStatus::Vulnerable(_)))
+            .collect()
+    }
+    pub fn read_sys_cpu_vulnerabilities(sys_vulnerabilities_path: &str) -> Result<SysCpuVulnerabilities, ProcSysParserError> {
+        let mut syscpuvulnerabilities = SysCpuVulnerabilities::new();
+
+        let vulnerability_files = read_dir(sys_vulnerabilities_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_vulnerabilities_path.to_string(), error })?;
+
+        for vulnerability_file in vulnerability_files.filter_map(|entry| entry.ok()) {
+            if !vulnerability_file.path().is_file() { continue };
+            syscpuvulnerabilities.vulnerabilities.push(Vulnerability {
+                name: vulnerability_file.file_name().to_string_lossy().to_string(),
+                status: VulnerabilityStatus::parse(&read_file_string(&vulnerability_file.path())?),
+            });
+        }
+        syscpuvulnerabilities.vulnerabilities.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(syscpuvulnerabilities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_vulnerability_status() {
+        assert_eq!(VulnerabilityStatus::parse("Not affected\n"), VulnerabilityStatus::NotAffected);
+        assert_eq!(VulnerabilityStatus::parse("Vulnerable\n"), VulnerabilityStatus::Vulnerable(None));
+        assert_eq!(VulnerabilityStatus::parse("Vulnerable: Clear CPU buffers attempted, no microcode\n"), VulnerabilityStatus::Vulnerable(Some("Clear CPU buffers attempted, no microcode".to_string())));
+        assert_eq!(VulnerabilityStatus::parse("Mitigation: PTI\n"), VulnerabilityStatus::Mitigation("PTI".to_string()));
+        assert_eq!(VulnerabilityStatus::parse("Unknown: Dependent on hypervisor status\n"), VulnerabilityStatus::Unknown("Dependent on hypervisor status".to_string()));
+        assert_eq!(VulnerabilityStatus::parse("Processor vulnerable\n"), VulnerabilityStatus::Other("Processor vulnerable".to_string()));
+    }
+
+    #[test]
+    fn create_sys_cpu_vulnerabilities_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let vulnerabilities_path = format!("{}/devices/system/cpu/vulnerabilities", test_path);
+        create_dir_all(&vulnerabilities_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("meltdown", "Mitigation: PTI\n"),
+            ("mds", "Vulnerable: Clear CPU buffers attempted, no microcode; SMT Host state unknown\n"),
+            ("l1tf", "Not affected\n"),
+        ] {
+            write(format!("{}/{}", vulnerabilities_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", vulnerabilities_path, file));
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.vulnerabilities.iter().map(|vulnerability| vulnerability.name.as_str()).collect::<Vec<_>>(), vec!["l1tf", "mds", "meltdown"]);
+        assert_eq!(result.get("meltdown"), Some(&VulnerabilityStatus::Mitigation("PTI".to_string())));
+        assert_eq!(result.get("spectre_v1"), None);
+        assert_eq!(result.vulnerable().len(), 1);
+        assert_eq!(result.vulnerable()[0].name, "mds");
+    }
+}
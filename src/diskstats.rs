@@ -53,6 +53,13 @@ Example output:
 ```
 (edited for readability)
 
+The number of fields in `/proc/diskstats` depends on the linux version: 11 statistics up to linux 4.18, 15 with the
+discard statistics since linux 4.18 and 17 with the flush statistics since linux 5.5. Together with the major number,
+minor number and device name, the latter is 20 fields. The detected layout is stored in [`ProcDiskStats::layout`].
+
+The sector counts are in sectors of 512 bytes, independent of the sector size of the device. Use
+[`DiskStats::reads_bytes`], [`DiskStats::writes_bytes`] and [`DiskStats::discards_bytes`] to get these in bytes.
+
 If you want to change the path and/or file that is read for [`ProcDiskStats`], which is `/proc/diskstats`
 by default, use:
 ```no_run
@@ -61,14 +68,69 @@ use proc_sys_parser::{diskstats, diskstats::{ProcDiskStats, Builder}};
 let proc_diskstats = Builder::new().path("/myproc").read();
 ```
 
+To find the statistics of a device, use [`ProcDiskStats::by_name`] or [`ProcDiskStats::by_dev`] with the major and
+minor number. The disk of a partition is found with [`ProcDiskStats::parent_of`].
+
+The statistics are counters since boot. The iostat metrics, such as IOPS, MB/s, await and utilization, are calculated
+from two readings with [`ProcDiskStats::delta`], which takes the number of seconds between the readings:
+```no_run
+use std::{thread::sleep, time::Duration};
+use proc_sys_parser::diskstats;
+
+let previous = diskstats::read().unwrap();
+sleep(Duration::from_secs(1));
+let current = diskstats::read().unwrap();
+
+for delta in current.delta(&previous, 1.) {
+    println!("{} r/s: {:.1} w/s: {:.1} util: {:.1}%", delta.device_name, delta.reads_per_second, delta.writes_per_second, delta.utilization_percent);
+}
+```
+
+A system can have hundreds of loop, ram and zram devices. These can be excluded when parsing with a regex filter on
+the device name, which is empty (no filter) by default:
+```no_run
+use proc_sys_parser::{diskstats, diskstats::{ProcDiskStats, Builder}};
+
+let proc_diskstats = Builder::new().filter("^(loop|ram|zram)").read();
+```
+
 */
 use std::fs::read_to_string;
+use regex::Regex;
 use crate::ProcSysParserError;
 
 /// Struct for holding `/proc/diskstats` statistics
 #[derive(Debug, PartialEq, Default)]
 pub struct ProcDiskStats {
-    pub disk_stats: Vec<DiskStats>
+    pub disk_stats: Vec<DiskStats>,
+    /// The layout of the lines, None if no device was read.
+    pub layout: Option<DiskStatsLayout>,
+}
+
+/// The layout of `/proc/diskstats`, by the number of statistics fields after the device name
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DiskStatsLayout {
+    /// Up to linux 4.18: the read, write and io statistics.
+    Fields11,
+    /// linux 4.18+: with the discard statistics.
+    Fields15,
+    /// linux 5.5+: with the flush statistics.
+    Fields17,
+}
+
+impl DiskStatsLayout {
+    /// The number of statistics fields.
+    pub fn statistics_fields(&self) -> usize {
+        match self {
+            DiskStatsLayout::Fields11 => 11,
+            DiskStatsLayout::Fields15 => 15,
+            DiskStatsLayout::Fields17 => 17,
+        }
+    }
+    /// The number of fields in a line, which includes the major number, minor number and device name.
+    pub fn fields(&self) -> usize {
+        self.statistics_fields() + 3
+    }
 }
 
 /// Builder pattern for [`ProcDiskStats`]
@@ -76,6 +138,7 @@ pub struct ProcDiskStats {
 pub struct Builder {
     pub proc_path : String,
     pub proc_file : String,
+    pub proc_filter : String,
 }
 
 impl Builder {
@@ -83,6 +146,7 @@ impl Builder {
         Builder { 
             proc_path: "/proc".to_string(),
             proc_file: "diskstats".to_string(),
+            proc_filter: "".to_string(),
         }
     }
 
@@ -94,8 +158,13 @@ impl Builder {
         self.proc_file = proc_file.to_string();
         self
     }
+    /// A regex that is matched against the device name; matching devices are excluded.
+    pub fn filter(mut self, proc_filter: &str) -> Builder {
+        self.proc_filter = proc_filter.to_string();
+        self
+    }
     pub fn read(self) -> Result<ProcDiskStats, ProcSysParserError> {
-        ProcDiskStats::read_proc_diskstats(format!("{}/{}", &self.proc_path, &self.proc_file).as_str())
+        ProcDiskStats::read_proc_diskstats(format!("{}/{}", &self.proc_path, &self.proc_file).as_str(), self.proc_filter.as_str())
     }
 }
 
@@ -136,92 +205,225 @@ pub struct DiskStats {
     pub flush_requests_time_spent_ms: Option<u64>,
 }
 
+/// Struct for holding the iostat metrics of a device between two `/proc/diskstats` readings
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct DiskStatsDelta {
+    pub block_major: u64,
+    pub block_minor: u64,
+    pub device_name: String,
+    pub reads_per_second: f64,
+    pub writes_per_second: f64,
+    pub reads_merged_per_second: f64,
+    pub writes_merged_per_second: f64,
+    /// MB is 1024*1024 bytes, like iostat.
+    pub read_mb_per_second: f64,
+    pub write_mb_per_second: f64,
+    /// The average time of a read request, including the time in the queue (r_await).
+    pub read_await_ms: f64,
+    /// The average time of a write request, including the time in the queue (w_await).
+    pub write_await_ms: f64,
+    /// The average time of a read or write request.
+    pub await_ms: f64,
+    /// The busy time divided by the number of reads and writes. The device can serve requests in parallel, so this is
+    /// not the real service time; iostat removed svctm for that reason.
+    pub svctm_ms: f64,
+    /// The percentage of time the device had requests in flight. For devices that serve requests in parallel, such as
+    /// SSDs and RAID arrays, 100% does not mean the device is saturated.
+    pub utilization_percent: f64,
+    /// The average number of requests in flight (aqu-sz).
+    pub average_queue_size: f64,
+    /// kernel 4.18+
+    pub discards_per_second: Option<f64>,
+    /// kernel 4.18+
+    pub discard_mb_per_second: Option<f64>,
+    /// kernel 4.18+
+    pub discard_await_ms: Option<f64>,
+    /// kernel 5.5+
+    pub flushes_per_second: Option<f64>,
+    /// kernel 5.5+
+    pub flush_await_ms: Option<f64>,
+}
+
+/// The size of a sector in `/proc/diskstats`, which is always 512 bytes, independent of the sector size of the device.
+pub const SECTOR_SIZE: u64 = 512;
+
+/// Convert a number of sectors from `/proc/diskstats` to bytes.
+pub fn sectors_to_bytes(sectors: u64) -> u64 {
+    sectors * SECTOR_SIZE
+}
+
+impl DiskStats {
+    pub fn reads_bytes(&self) -> u64 {
+        sectors_to_bytes(self.reads_sectors)
+    }
+    pub fn writes_bytes(&self) -> u64 {
+        sectors_to_bytes(self.writes_sectors)
+    }
+    /// kernel 4.18+
+    pub fn discards_bytes(&self) -> Option<u64> {
+        self.discards_sectors.map(sectors_to_bytes)
+    }
+    /// The layout of the line the statistics are parsed from.
+    pub fn layout(&self) -> DiskStatsLayout {
+        if self.flush_requests_completed_success.is_some() {
+            DiskStatsLayout::Fields17
+        } else if self.discards_completed_success.is_some() {
+            DiskStatsLayout::Fields15
+        } else {
+            DiskStatsLayout::Fields11
+        }
+    }
+}
+
+impl DiskStatsDelta {
+    /// Calculate the metrics of a device from a previous and a current reading, which are `interval_seconds` apart.
+    /// A counter that is lower in the current reading, because the device was removed and added, counts as zero.
+    pub fn new(previous: &DiskStats, current: &DiskStats, interval_seconds: f64) -> DiskStatsDelta {
+        let per_second = |difference: u64| if interval_seconds > 0. { difference as f64 / interval_seconds } else { 0. };
+        let mb_per_second = |sectors: u64| per_second(sectors_to_bytes(sectors)) / (1024. * 1024.);
+        let average = |time_ms: u64, count: u64| if count > 0 { time_ms as f64 / count as f64 } else { 0. };
+        let option_difference = |previous: Option<u64>, current: Option<u64>| Some(current?.saturating_sub(previous?));
+
+        let reads = current.reads_completed_success.saturating_sub(previous.reads_completed_success);
+        let writes = current.writes_completed_success.saturating_sub(previous.writes_completed_success);
+        let reads_time_ms = current.reads_time_spent_ms.saturating_sub(previous.reads_time_spent_ms);
+        let writes_time_ms = current.writes_time_spent_ms.saturating_sub(previous.writes_time_spent_ms);
+        let ios_time_ms = current.ios_time_spent_ms.saturating_sub(previous.ios_time_spent_ms);
+        let discards = option_difference(previous.discards_completed_success, current.discards_completed_success);
+        let flushes = option_difference(previous.flush_requests_completed_success, current.flush_requests_completed_success);
+
+        DiskStatsDelta {
+            block_major: current.block_major,
+            block_minor: current.block_minor,
+            device_name: current.device_name.clone(),
+            reads_per_second: per_second(reads),
+            writes_per_second: per_second(writes),
+            reads_merged_per_second: per_second(current.reads_merged.saturating_sub(previous.reads_merged)),
+            writes_merged_per_second: per_second(current.writes_merged.saturating_sub(previous.writes_merged)),
+            read_mb_per_second: mb_per_second(current.reads_sectors.saturating_sub(previous.reads_sectors)),
+            write_mb_per_second: mb_per_second(current.writes_sectors.saturating_sub(previous.writes_sectors)),
+            read_await_ms: average(reads_time_ms, reads),
+            write_await_ms: average(writes_time_ms, writes),
+            await_ms: average(reads_time_ms + writes_time_ms, reads + writes),
+            svctm_ms: average(ios_time_ms, reads + writes),
+            utilization_percent: if interval_seconds > 0. { ios_time_ms as f64 / (interval_seconds * 10.) } else { 0. },
+            average_queue_size: if interval_seconds > 0. { current.ios_weighted_time_spent_ms.saturating_sub(previous.ios_weighted_time_spent_ms) as f64 / (interval_seconds * 1000.) } else { 0. },
+            discards_per_second: discards.map(per_second),
+            discard_mb_per_second: option_difference(previous.discards_sectors, current.discards_sectors).map(mb_per_second),
+            discard_await_ms: discards.zip(option_difference(previous.discards_time_spent_ms, current.discards_time_spent_ms))
+                .map(|(discards, time_ms)| average(time_ms, discards)),
+            flushes_per_second: flushes.map(per_second),
+            flush_await_ms: flushes.zip(option_difference(previous.flush_requests_time_spent_ms, current.flush_requests_time_spent_ms))
+                .map(|(flushes, time_ms)| average(time_ms, flushes)),
+        }
+    }
+}
+
 impl ProcDiskStats {
     pub fn new() -> ProcDiskStats {
         ProcDiskStats::default() 
     }
     pub fn parse_proc_diskstats(
         proc_diskstats: &str,
+        filter: &str,
     ) -> Result<ProcDiskStats, ProcSysParserError> {
         let mut procdiskstats = ProcDiskStats::new();
+        let filter_regex = Regex::new(filter)
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: filter.to_string() })?;
         for line in proc_diskstats.lines() {
+            // the device name is the third field.
+            if !filter_regex.as_str().is_empty() && line.split_whitespace().nth(2).is_some_and(|device_name| filter_regex.is_match(device_name)) { continue };
             procdiskstats.disk_stats.push(ProcDiskStats::parse_proc_diskstats_line(line)?);
         }
+        procdiskstats.layout = procdiskstats.disk_stats.first().map(DiskStats::layout);
         Ok(procdiskstats)
     }
+    /// The statistics of the device with the given name, such as "sda".
+    pub fn by_name(&self, device_name: &str) -> Option<&DiskStats> {
+        self.disk_stats.iter().find(|disk_stats| disk_stats.device_name == device_name)
+    }
+    /// The statistics of the device with the given major and minor number.
+    pub fn by_dev(&self, major: u64, minor: u64) -> Option<&DiskStats> {
+        self.disk_stats.iter().find(|disk_stats| (disk_stats.block_major, disk_stats.block_minor) == (major, minor))
+    }
+    /// The statistics of the disk of a partition, such as "sda" for "sda1" and "nvme0n1" for "nvme0n1p1".
+    /// `/proc/diskstats` does not show the relation between a partition and its disk, so this uses the kernel
+    /// naming of partitions: the disk name followed by the partition number, with a 'p' in between if the disk name
+    /// ends with a digit. None if the device is not a partition, or the disk is not found.
+    pub fn parent_of(&self, partition_name: &str) -> Option<&DiskStats> {
+        let without_number = partition_name.trim_end_matches(|character: char| character.is_ascii_digit());
+        if without_number.len() == partition_name.len() { return None };
+        let parent_name = match without_number.strip_suffix('p') {
+            Some(parent_name) if parent_name.ends_with(|character: char| character.is_ascii_digit()) => parent_name,
+            _ => without_number,
+        };
+        self.by_name(parent_name)
+    }
+    /// The iostat metrics of every device between a previous reading and this reading, which are `interval_seconds`
+    /// apart. Devices that are not in the previous reading are skipped.
+    pub fn delta(&self, previous: &ProcDiskStats, interval_seconds: f64) -> Vec<DiskStatsDelta> {
+        self.disk_stats.iter()
+            .filter_map(|current| previous.by_name(&current.device_name).map(|previous| DiskStatsDelta::new(previous, current, interval_seconds)))
+            .collect()
+    }
+    /// The statistics of the partitions of a disk.
+    pub fn partitions_of(&self, device_name: &str) -> Vec<&DiskStats> {
+        self.disk_stats.iter()
+            .filter(|disk_stats| disk_stats.device_name != device_name)
+            .filter(|disk_stats| self.parent_of(&disk_stats.device_name).is_some_and(|parent| parent.device_name == device_name))
+            .collect()
+    }
     fn parse_proc_diskstats_line(proc_diskstats_line: &str) -> Result<DiskStats, ProcSysParserError> {
-        let mut fields = proc_diskstats_line.split_whitespace();
-
-        let parse_next_and_conversion_into_option_u64 = |result: Option<&str>| -> Option<u64> {
-            match result {
-                None => None,
-                Some(value) => {
-                    match value.parse::<u64>() {
-                        Err(_) => None,
-                        Ok(number) => Some(number),
-                    }
-                },
-            }
+        let fields: Vec<&str> = proc_diskstats_line.split_whitespace().collect();
+
+        // every error names the field and the line, so a changed or corrupted line can be found.
+        let line = proc_diskstats_line.trim();
+        let field = |index: usize, name: &str| -> Result<&str, ProcSysParserError> {
+            fields.get(index).copied()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("diskstats {} in line '{}'", name, line) })
+        };
+        let parse_u64 = |name: &str, value: &str| -> Result<u64, ProcSysParserError> {
+            value.parse::<u64>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("diskstats {} in line '{}'", name, line), error })
+        };
+        let field_u64 = |index: usize, name: &str| -> Result<u64, ProcSysParserError> {
+            parse_u64(name, field(index, name)?)
+        };
+        // the discard (linux 4.18+) and flush (linux 5.5+) fields are optional.
+        let field_option_u64 = |index: usize, name: &str| -> Result<Option<u64>, ProcSysParserError> {
+            fields.get(index).map(|value| parse_u64(name, value)).transpose()
         };
 
         Ok(
             DiskStats {
-            block_major: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats block_major".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            block_minor: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats block_minor".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            device_name: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats device_name".to_string() })?
-                .to_string(),
-            reads_completed_success: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats reads_completed_success".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            reads_merged: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats reads_merged".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            reads_sectors: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats reads_sectors".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            reads_time_spent_ms: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats reads_time_spent_ms".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            writes_completed_success: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats writes_completed_success".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            writes_merged: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats writes_merged".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            writes_sectors: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats writes_sectors".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            writes_time_spent_ms: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats writes_time_spent_ms".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            ios_in_progress: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats ios_in_progress".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            ios_time_spent_ms: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats ios_time_spent_ms".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            ios_weighted_time_spent_ms: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError { item: "diskstats ios_weighted_time_spent_ms".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            discards_completed_success: parse_next_and_conversion_into_option_u64(fields.next()),
-            discards_merged: parse_next_and_conversion_into_option_u64(fields.next()),
-            discards_sectors: parse_next_and_conversion_into_option_u64(fields.next()),
-            discards_time_spent_ms: parse_next_and_conversion_into_option_u64(fields.next()),
-            flush_requests_completed_success: parse_next_and_conversion_into_option_u64(fields.next()),
-            flush_requests_time_spent_ms: parse_next_and_conversion_into_option_u64(fields.next()),
+            block_major: field_u64(0, "block_major")?,
+            block_minor: field_u64(1, "block_minor")?,
+            device_name: field(2, "device_name")?.to_string(),
+            reads_completed_success: field_u64(3, "reads_completed_success")?,
+            reads_merged: field_u64(4, "reads_merged")?,
+            reads_sectors: field_u64(5, "reads_sectors")?,
+            reads_time_spent_ms: field_u64(6, "reads_time_spent_ms")?,
+            writes_completed_success: field_u64(7, "writes_completed_success")?,
+            writes_merged: field_u64(8, "writes_merged")?,
+            writes_sectors: field_u64(9, "writes_sectors")?,
+            writes_time_spent_ms: field_u64(10, "writes_time_spent_ms")?,
+            ios_in_progress: field_u64(11, "ios_in_progress")?,
+            ios_time_spent_ms: field_u64(12, "ios_time_spent_ms")?,
+            ios_weighted_time_spent_ms: field_u64(13, "ios_weighted_time_spent_ms")?,
+            discards_completed_success: field_option_u64(14, "discards_completed_success")?,
+            discards_merged: field_option_u64(15, "discards_merged")?,
+            discards_sectors: field_option_u64(16, "discards_sectors")?,
+            discards_time_spent_ms: field_option_u64(17, "discards_time_spent_ms")?,
+            flush_requests_completed_success: field_option_u64(18, "flush_requests_completed_success")?,
+            flush_requests_time_spent_ms: field_option_u64(19, "flush_requests_time_spent_ms")?,
         })
     }
 
-    pub fn read_proc_diskstats(proc_diskstats_file: &str) -> Result<ProcDiskStats, ProcSysParserError> {
+    pub fn read_proc_diskstats(proc_diskstats_file: &str, proc_diskstats_filter: &str) -> Result<ProcDiskStats, ProcSysParserError> {
         let proc_diskstats_output = read_to_string(proc_diskstats_file)
             .map_err(|error| ProcSysParserError::FileReadError { file: proc_diskstats_file.to_string(), error })?;
 
-        ProcDiskStats::parse_proc_diskstats(&proc_diskstats_output)
+        ProcDiskStats::parse_proc_diskstats(&proc_diskstats_output, proc_diskstats_filter)
     }
 }
 
@@ -235,7 +437,7 @@ mod tests {
     #[test]
     fn parse_proc_diskstats_line() {
         let diskstats_line = "   7       0 loop0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17";
-        let result = ProcDiskStats::parse_proc_diskstats_line(&diskstats_line).unwrap();
+        let result = ProcDiskStats::parse_proc_diskstats_line(diskstats_line).unwrap();
         assert_eq!(result, DiskStats { block_major: 7,
             block_minor: 0,
             device_name: "loop0".to_string(),
@@ -261,7 +463,7 @@ mod tests {
     #[test]
     fn parse_proc_diskstats_line_before_linux_4_18() {
         let diskstats_line = "   7       0 loop0 1 2 3 4 5 6 7 8 9 10 11";
-        let result = ProcDiskStats::parse_proc_diskstats_line(&diskstats_line).unwrap();
+        let result = ProcDiskStats::parse_proc_diskstats_line(diskstats_line).unwrap();
         assert_eq!(result, DiskStats { block_major: 7,
             block_minor: 0,
             device_name: "loop0".to_string(),
@@ -285,6 +487,13 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parse_proc_diskstats_line_errors_name_field_and_line() {
+        let result = ProcDiskStats::parse_proc_diskstats_line("   7       0 loop0 1 2 3 4 5 6 x 8 9 10 11");
+        assert_eq!(result.unwrap_err().to_string(), "Error invalid digit found in string during parsing diskstats writes_sectors in line '7       0 loop0 1 2 3 4 5 6 x 8 9 10 11' to integer.");
+        let result = ProcDiskStats::parse_proc_diskstats_line("   7       0 loop0 1 2 3");
+        assert_eq!(result.unwrap_err().to_string(), "Expected item is not found on iterator: diskstats reads_time_spent_ms in line '7       0 loop0 1 2 3'.");
+    }
     #[test]
     fn parse_full_proc_diskstats_file() {
         let proc_diskstats = "   7       0 loop0 11 0 28 0 0 0 0 0 0 4 0 0 0 0 0 0 0
@@ -300,7 +509,9 @@ mod tests {
  253      15 vda15 136 1547 9919 20 1 0 1 0 0 52 21 1 0 186691 0 0 0
  259       0 vda16 159 15 10711 31 20 22 242 12 0 108 46 27 0 1630688 1 0 0
   11       0 sr0 291 0 75108 68 0 0 0 0 0 156 68 0 0 0 0 0 0";
-        let result = ProcDiskStats::parse_proc_diskstats(proc_diskstats).unwrap();
+        let result = ProcDiskStats::parse_proc_diskstats(proc_diskstats, "").unwrap();
+        assert_eq!(result.layout, Some(DiskStatsLayout::Fields17));
+        assert_eq!(result.layout.unwrap().fields(), 20);
         assert_eq!(result, ProcDiskStats {
             disk_stats: vec![
                 DiskStats { block_major: 7, block_minor: 0, device_name: "loop0".to_string(), reads_completed_success: 11, reads_merged: 0, reads_sectors: 28, reads_time_spent_ms: 0, writes_completed_success: 0, writes_merged: 0, writes_sectors: 0, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 4, ios_weighted_time_spent_ms: 0, discards_completed_success: Some(0), discards_merged: Some(0), discards_sectors: Some(0), discards_time_spent_ms: Some(0), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) },
@@ -316,7 +527,8 @@ mod tests {
                 DiskStats { block_major: 253, block_minor: 15, device_name: "vda15".to_string(), reads_completed_success: 136, reads_merged: 1547, reads_sectors: 9919, reads_time_spent_ms: 20, writes_completed_success: 1, writes_merged: 0, writes_sectors: 1, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 52, ios_weighted_time_spent_ms: 21, discards_completed_success: Some(1), discards_merged: Some(0), discards_sectors: Some(186691), discards_time_spent_ms: Some(0), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) },
                 DiskStats { block_major: 259, block_minor: 0, device_name: "vda16".to_string(), reads_completed_success: 159, reads_merged: 15, reads_sectors: 10711, reads_time_spent_ms: 31, writes_completed_success: 20, writes_merged: 22, writes_sectors: 242, writes_time_spent_ms: 12, ios_in_progress: 0, ios_time_spent_ms: 108, ios_weighted_time_spent_ms: 46, discards_completed_success: Some(27), discards_merged: Some(0), discards_sectors: Some(1630688), discards_time_spent_ms: Some(1), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) },
                 DiskStats { block_major: 11, block_minor: 0, device_name: "sr0".to_string(), reads_completed_success: 291, reads_merged: 0, reads_sectors: 75108, reads_time_spent_ms: 68, writes_completed_success: 0, writes_merged: 0, writes_sectors: 0, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 156, ios_weighted_time_spent_ms: 68, discards_completed_success: Some(0), discards_merged: Some(0), discards_sectors: Some(0), discards_time_spent_ms: Some(0), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) }
-            ]
+            ],
+            layout: Some(DiskStatsLayout::Fields17),
         });
     }
 
@@ -339,7 +551,7 @@ mod tests {
         let test_path = format!("/tmp/test.{}", directory_suffix);
 
         create_dir_all(test_path.clone()).expect("Error creating mock sysfs directories.");
-        write(format!("{}/diskstats", test_path), proc_diskstats).expect(format!("Error writing to {}/diskstats", test_path).as_str());
+        write(format!("{}/diskstats", test_path), proc_diskstats).unwrap_or_else(|_| panic!("Error writing to {}/diskstats", test_path));
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
 
@@ -357,7 +569,93 @@ mod tests {
             DiskStats { block_major: 253, block_minor: 15, device_name: "vda15".to_string(), reads_completed_success: 136, reads_merged: 1547, reads_sectors: 9919, reads_time_spent_ms: 20, writes_completed_success: 1, writes_merged: 0, writes_sectors: 1, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 52, ios_weighted_time_spent_ms: 21, discards_completed_success: Some(1), discards_merged: Some(0), discards_sectors: Some(186691), discards_time_spent_ms: Some(0), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) },
             DiskStats { block_major: 259, block_minor: 0, device_name: "vda16".to_string(), reads_completed_success: 159, reads_merged: 15, reads_sectors: 10711, reads_time_spent_ms: 31, writes_completed_success: 20, writes_merged: 22, writes_sectors: 242, writes_time_spent_ms: 12, ios_in_progress: 0, ios_time_spent_ms: 108, ios_weighted_time_spent_ms: 46, discards_completed_success: Some(27), discards_merged: Some(0), discards_sectors: Some(1630688), discards_time_spent_ms: Some(1), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) },
             DiskStats { block_major: 11, block_minor: 0, device_name: "sr0".to_string(), reads_completed_success: 291, reads_merged: 0, reads_sectors: 75108, reads_time_spent_ms: 68, writes_completed_success: 0, writes_merged: 0, writes_sectors: 0, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 156, ios_weighted_time_spent_ms: 68, discards_completed_success: Some(0), discards_merged: Some(0), discards_sectors: Some(0), discards_time_spent_ms: Some(0), flush_requests_completed_success: Some(0), flush_requests_time_spent_ms: Some(0) }
-        ]});
+        ], layout: Some(DiskStatsLayout::Fields17) });
+    }
+    #[test]
+    fn create_proc_diskstats_file_and_read_with_filter() {
+        let proc_diskstats = "   7       0 loop0 11 0 28 0 0 0 0 0 0 4 0 0 0 0 0 0 0
+   1       0 ram0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 252       0 zram0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 253       0 vda 13534 4237 1645451 3763 10172 10577 1730555 12701 0 23356 18881 7179 0 89620507 396 3929 2019
+ 253       1 vda1 13192 2675 1623109 3692 10151 10555 1730312 12688 0 23324 16775 7151 0 87803128 394 0 0";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+
+        create_dir_all(test_path.clone()).expect("Error creating mock sysfs directories.");
+        write(format!("{}/diskstats", test_path), proc_diskstats).unwrap_or_else(|_| panic!("Error writing to {}/diskstats", test_path));
+        let result = Builder::new().path(&test_path).filter("^(loop|ram|zram)").read().unwrap();
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.disk_stats.iter().map(|disk_stats| disk_stats.device_name.as_str()).collect::<Vec<_>>(), vec!["vda", "vda1"]);
+    }
+    #[test]
+    fn lookup_proc_diskstats_by_name_dev_and_parent() {
+        let proc_diskstats = "   7       0 loop0 11 0 28 0 0 0 0 0 0 4 0 0 0 0 0 0 0
+ 259       0 nvme0n1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 259       1 nvme0n1p1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 259       2 nvme0n1p2 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+   8       0 sda 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+   8       1 sda1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+   9       0 md0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0";
+        let result = ProcDiskStats::parse_proc_diskstats(proc_diskstats, "").unwrap();
+
+        assert_eq!(result.by_name("sda1").map(|disk_stats| (disk_stats.block_major, disk_stats.block_minor)), Some((8, 1)));
+        assert_eq!(result.by_dev(259, 2).map(|disk_stats| disk_stats.device_name.as_str()), Some("nvme0n1p2"));
+        assert_eq!(result.by_name("sdb"), None);
+        assert_eq!(result.parent_of("nvme0n1p1").map(|disk_stats| disk_stats.device_name.as_str()), Some("nvme0n1"));
+        assert_eq!(result.parent_of("sda1").map(|disk_stats| disk_stats.device_name.as_str()), Some("sda"));
+        assert_eq!(result.parent_of("nvme0n1"), None);
+        assert_eq!(result.parent_of("md0"), None);
+        assert_eq!(result.partitions_of("nvme0n1").iter().map(|disk_stats| disk_stats.device_name.as_str()).collect::<Vec<_>>(), vec!["nvme0n1p1", "nvme0n1p2"]);
+    }
+    #[test]
+    fn detect_proc_diskstats_layout_and_convert_sectors_to_bytes() {
+        let result = ProcDiskStats::parse_proc_diskstats(" 253       0 vda 13534 4237 1645451 3763 10172 10577 1730555 12701 0 23356 18881 7179 0 89620507 396", "").unwrap();
+
+        assert_eq!(result.layout, Some(DiskStatsLayout::Fields15));
+        assert_eq!(result.layout.unwrap().statistics_fields(), 15);
+        assert_eq!(result.disk_stats[0].reads_bytes(), 842470912);
+        assert_eq!(result.disk_stats[0].writes_bytes(), 886044160);
+        assert_eq!(result.disk_stats[0].discards_bytes(), Some(45885699584));
+        assert_eq!(ProcDiskStats::parse_proc_diskstats("", "").unwrap().layout, None);
+    }
+    #[test]
+    fn calculate_proc_diskstats_delta() {
+        let previous = ProcDiskStats::parse_proc_diskstats(" 253       0 vda 1000 10 20000 500 2000 20 40000 3000 0 1000 3500 10 0 800 20 5 10
+   8       0 sda 100 0 800 10 0 0 0 0 0 50 10", "").unwrap();
+        let current = ProcDiskStats::parse_proc_diskstats(" 253       0 vda 1200 30 24096 900 2300 50 42048 3600 2 1500 4500 20 0 1600 40 10 30
+   8       0 sda 150 0 1000 20 0 0 0 0 0 60 20
+   8      16 sdb 10 0 80 1 0 0 0 0 0 1 1", "").unwrap();
+
+        let result = current.delta(&previous, 2.);
+
+        // sdb is not in the previous reading.
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], DiskStatsDelta {
+            block_major: 253,
+            block_minor: 0,
+            device_name: "vda".to_string(),
+            reads_per_second: 100.,
+            writes_per_second: 150.,
+            reads_merged_per_second: 10.,
+            writes_merged_per_second: 15.,
+            read_mb_per_second: 1.,
+            write_mb_per_second: 0.5,
+            read_await_ms: 2.,
+            write_await_ms: 2.,
+            await_ms: 2.,
+            svctm_ms: 1.,
+            utilization_percent: 25.,
+            average_queue_size: 0.5,
+            discards_per_second: Some(5.),
+            discard_mb_per_second: Some(0.1953125),
+            discard_await_ms: Some(2.),
+            flushes_per_second: Some(2.5),
+            flush_await_ms: Some(4.),
+        });
+        assert_eq!(result[1].reads_per_second, 25.);
+        assert_eq!(result[1].discards_per_second, None);
+        assert_eq!(result[1].write_await_ms, 0.);
     }
     #[test]
     fn create_proc_diskstats_file_with_pre_kernel_4_18_fields_removed_and_read() {
@@ -369,7 +667,7 @@ mod tests {
         let test_path = format!("/tmp/test.{}", directory_suffix);
 
         create_dir_all(test_path.clone()).expect("Error creating mock sysfs directories.");
-        write(format!("{}/diskstats", test_path), proc_diskstats).expect(format!("Error writing to {}/diskstats", test_path).as_str());
+        write(format!("{}/diskstats", test_path), proc_diskstats).unwrap_or_else(|_| panic!("Error writing to {}/diskstats", test_path));
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
 
@@ -378,7 +676,7 @@ mod tests {
             DiskStats { block_major: 253, block_minor: 1, device_name: "vda1".to_string(), reads_completed_success: 13192, reads_merged: 2675, reads_sectors: 1623109, reads_time_spent_ms: 3692, writes_completed_success: 10151, writes_merged: 10555, writes_sectors: 1730312, writes_time_spent_ms: 12688, ios_in_progress: 0, ios_time_spent_ms: 23324, ios_weighted_time_spent_ms: 16775, discards_completed_success: None, discards_merged: None, discards_sectors: None, discards_time_spent_ms: None, flush_requests_completed_success: None, flush_requests_time_spent_ms: None },
             DiskStats { block_major: 253, block_minor: 15, device_name: "vda15".to_string(), reads_completed_success: 136, reads_merged: 1547, reads_sectors: 9919, reads_time_spent_ms: 20, writes_completed_success: 1, writes_merged: 0, writes_sectors: 1, writes_time_spent_ms: 0, ios_in_progress: 0, ios_time_spent_ms: 52, ios_weighted_time_spent_ms: 21, discards_completed_success: None, discards_merged: None, discards_sectors: None, discards_time_spent_ms: None, flush_requests_completed_success: None, flush_requests_time_spent_ms: None },
             DiskStats { block_major: 259, block_minor: 0, device_name: "vda16".to_string(), reads_completed_success: 159, reads_merged: 15, reads_sectors: 10711, reads_time_spent_ms: 31, writes_completed_success: 20, writes_merged: 22, writes_sectors: 242, writes_time_spent_ms: 12, ios_in_progress: 0, ios_time_spent_ms: 108, ios_weighted_time_spent_ms: 46, discards_completed_success: None, discards_merged: None, discards_sectors: None, discards_time_spent_ms: None, flush_requests_completed_success: None, flush_requests_time_spent_ms: None },
-        ]});
+        ], layout: Some(DiskStatsLayout::Fields11) });
     }
 }
 
@@ -0,0 +1,195 @@
+/*!
+Read the DMI/SMBIOS identity of the system from `/sys/class/dmi/id` into the struct [`SysDmi`].
+
+The DMI identity shows the vendor and name of the system, the motherboard and the chassis, and the BIOS version,
+as provided by the firmware. This is the same data that is shown by `dmidecode -t system,baseboard,chassis,bios`
+for these fields, without requiring root to read `/dev/mem`.
+
+The serial numbers and the product uuid are only readable by root; these are None when reading as another user.
+Systems without DMI, such as most ARM systems, do not have `/sys/class/dmi/id`, in which case all fields are None.
+The values are trimmed, because the firmware often pads them with spaces.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-dmi-id>
+
+Here is an example obtaining the data from `/sys/class/dmi/id`:
+```no_run
+use proc_sys_parser::dmi;
+
+let sys_dmi = dmi::read();
+
+println!("{:#?}", sys_dmi);
+```
+Example output:
+```text
+SysDmi {
+    sys_vendor: Some("Dell Inc."),
+    product_name: Some("PowerEdge R640"),
+    product_version: Some(""),
+    product_serial: Some("7XJ2K93"),
+    product_uuid: Some("4c4c4544-0058-4a10-8032-b7c04f4b3933"),
+    product_family: None,
+    product_sku: Some("SKU=NotProvided;ModelName=PowerEdge R640"),
+    board_vendor: Some("Dell Inc."),
+    board_name: Some("0H28RR"),
+    board_version: Some("A04"),
+    board_serial: Some(".7XJ2K93.CNFCP0099R0093."),
+    board_asset_tag: None,
+    chassis_vendor: Some("Dell Inc."),
+    chassis_type: Some(23),
+    chassis_serial: Some("7XJ2K93"),
+    chassis_asset_tag: Some(""),
+    bios_vendor: Some("Dell Inc."),
+    bios_version: Some("2.17.1"),
+    bios_date: Some("11/14/2022"),
+    bios_release: Some("2.17"),
+}
+```
+
+If you want to change the path that is read for [`SysDmi`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::dmi::Builder;
+
+let sys_dmi = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_option_u64};
+
+/// Struct for holding the DMI identity
+#[derive(Debug, PartialEq, Default)]
+pub struct SysDmi {
+    pub sys_vendor: Option<String>,
+    pub product_name: Option<String>,
+    pub product_version: Option<String>,
+    /// Only readable by root.
+    pub product_serial: Option<String>,
+    /// Only readable by root.
+    pub product_uuid: Option<String>,
+    pub product_family: Option<String>,
+    pub product_sku: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub board_version: Option<String>,
+    /// Only readable by root.
+    pub board_serial: Option<String>,
+    pub board_asset_tag: Option<String>,
+    pub chassis_vendor: Option<String>,
+    /// The SMBIOS chassis type number, such as 3 (desktop), 10 (notebook), 17 (main server chassis),
+    /// 23 (rack mount chassis) or 1 (other, which is used by most virtual machines).
+    pub chassis_type: Option<u64>,
+    /// Only readable by root.
+    pub chassis_serial: Option<String>,
+    pub chassis_asset_tag: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+    pub bios_release: Option<String>,
+}
+
+/// Builder pattern for [`SysDmi`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysDmi, ProcSysParserError> {
+        SysDmi::read_sys_dmi(format!("{}/class/dmi/id", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysDmi`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysDmi, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysDmi {
+    pub fn new() -> SysDmi {
+        SysDmi::default()
+    }
+    fn read_dmi_string(file: &Path) -> Option<String> {
+        read_file_option_string(file).map(|contents| contents.trim().to_string())
+    }
+    pub fn read_sys_dmi(sys_class_dmi_id_path: &str) -> Result<SysDmi, ProcSysParserError> {
+        let path = Path::new(sys_class_dmi_id_path);
+
+        Ok(SysDmi {
+            sys_vendor: SysDmi::read_dmi_string(&path.join("sys_vendor")),
+            product_name: SysDmi::read_dmi_string(&path.join("product_name")),
+            product_version: SysDmi::read_dmi_string(&path.join("product_version")),
+            product_serial: SysDmi::read_dmi_string(&path.join("product_serial")),
+            product_uuid: SysDmi::read_dmi_string(&path.join("product_uuid")),
+            product_family: SysDmi::read_dmi_string(&path.join("product_family")),
+            product_sku: SysDmi::read_dmi_string(&path.join("product_sku")),
+            board_vendor: SysDmi::read_dmi_string(&path.join("board_vendor")),
+            board_name: SysDmi::read_dmi_string(&path.join("board_name")),
+            board_version: SysDmi::read_dmi_string(&path.join("board_version")),
+            board_serial: SysDmi::read_dmi_string(&path.join("board_serial")),
+            board_asset_tag: SysDmi::read_dmi_string(&path.join("board_asset_tag")),
+            chassis_vendor: SysDmi::read_dmi_string(&path.join("chassis_vendor")),
+            chassis_type: read_file_option_u64(&path.join("chassis_type"))?,
+            chassis_serial: SysDmi::read_dmi_string(&path.join("chassis_serial")),
+            chassis_asset_tag: SysDmi::read_dmi_string(&path.join("chassis_asset_tag")),
+            bios_vendor: SysDmi::read_dmi_string(&path.join("bios_vendor")),
+            bios_version: SysDmi::read_dmi_string(&path.join("bios_version")),
+            bios_date: SysDmi::read_dmi_string(&path.join("bios_date")),
+            bios_release: SysDmi::read_dmi_string(&path.join("bios_release")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_dmi_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let dmi_path = format!("{}/class/dmi/id", test_path);
+        create_dir_all(&dmi_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("sys_vendor", "QEMU"),
+            ("product_name", "Standard PC (Q35 + ICH9, 2009)"),
+            ("product_version", "pc-q35-8.2"),
+            ("board_vendor", "  Intel Corporation  "),
+            ("chassis_type", "1"),
+            ("bios_vendor", "SeaBIOS"),
+            ("bios_version", "1.16.3-debian-1.16.3-2"),
+            ("bios_date", "04/01/2014"),
+        ] {
+            write(format!("{}/{}", dmi_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", dmi_path, file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysDmi {
+            sys_vendor: Some("QEMU".to_string()),
+            product_name: Some("Standard PC (Q35 + ICH9, 2009)".to_string()),
+            product_version: Some("pc-q35-8.2".to_string()),
+            board_vendor: Some("Intel Corporation".to_string()),
+            chassis_type: Some(1),
+            bios_vendor: Some("SeaBIOS".to_string()),
+            bios_version: Some("1.16.3-debian-1.16.3-2".to_string()),
+            bios_date: Some("04/01/2014".to_string()),
+            ..Default::default()
+        });
+    }
+}
@@ -0,0 +1,301 @@
+/*!
+Read the EDAC memory error counters from `/sys/devices/system/edac/mc` into the struct [`SysEdac`].
+
+EDAC (Error Detection And Correction) drivers report the errors found by the memory controllers of ECC memory.
+Every memory controller has a directory `mc<nr>`, which shows:
+- `mc_name`: the name of the driver, such as `Skylake Socket#0 IMC#0`.
+- `size_mb`: the size of the memory managed by the controller.
+- `seconds_since_reset`: the time since the counters were reset.
+- `ce_count` and `ue_count`: the number of correctable and uncorrectable errors.
+- `ce_noinfo_count` and `ue_noinfo_count`: the errors that could not be attributed to a dimm.
+
+Below the memory controller, the memory is shown in two ways, depending on the driver:
+- `csrow<nr>`: the legacy chip select rows, with `ce_count`, `ue_count`, `size_mb` and per channel `ch<nr>_ce_count`
+  and `ch<nr>_dimm_label`.
+- `dimm<nr>` or `rank<nr>`: the dimms or ranks, with `dimm_label`, `dimm_location`, `size` (MB), `dimm_mem_type` and,
+  in later linux versions, `dimm_ce_count` and `dimm_ue_count`.
+
+A correctable error is corrected by ECC and does no harm, but an increasing number of correctable errors of a dimm
+predicts that dimm will fail, so trending the counters shows which dimm needs to be replaced.
+
+If the host has no EDAC driver loaded, such as most virtual machines, the directory does not exist and `controllers` is empty.
+
+Documentation: <https://docs.kernel.org/admin-guide/ras.html>
+
+Here is an example obtaining the EDAC counters:
+```no_run
+use proc_sys_parser::edac;
+
+let sys_edac = edac::read();
+
+println!("{:#?}", sys_edac);
+```
+Example output:
+```text
+SysEdac {
+    controllers: [
+        MemoryController {
+            mc_nr: 0,
+            mc_name: "Skylake Socket#0 IMC#0",
+            size_mb: Some(65536),
+            seconds_since_reset: Some(2750893),
+            ce_count: 3,
+            ce_noinfo_count: Some(0),
+            ue_count: 0,
+            ue_noinfo_count: Some(0),
+            csrows: [],
+            dimms: [
+                Dimm {
+                    dimm_nr: 0,
+                    rank: false,
+                    label: Some("CPU_SrcID#0_MC#0_Chan#0_DIMM#0"),
+                    location: Some("channel 0 slot 0"),
+                    size_mb: Some(32768),
+                    mem_type: Some("Registered-DDR4"),
+                    ce_count: Some(3),
+                    ue_count: Some(0),
+                },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysEdac`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::edac::Builder;
+
+let sys_edac = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the EDAC memory controllers
+#[derive(Debug, PartialEq, Default)]
+pub struct SysEdac {
+    /// The memory controllers, sorted by number.
+    pub controllers: Vec<MemoryController>,
+}
+
+/// Struct for holding a single memory controller
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct MemoryController {
+    pub mc_nr: u32,
+    pub mc_name: String,
+    pub size_mb: Option<u64>,
+    pub seconds_since_reset: Option<u64>,
+    /// counter: the number of correctable errors
+    pub ce_count: u64,
+    pub ce_noinfo_count: Option<u64>,
+    /// counter: the number of uncorrectable errors
+    pub ue_count: u64,
+    pub ue_noinfo_count: Option<u64>,
+    pub csrows: Vec<Csrow>,
+    pub dimms: Vec<Dimm>,
+}
+
+/// Struct for holding a legacy chip select row
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Csrow {
+    pub csrow_nr: u32,
+    pub size_mb: Option<u64>,
+    pub mem_type: Option<String>,
+    pub edac_mode: Option<String>,
+    pub ce_count: u64,
+    pub ue_count: u64,
+    pub channels: Vec<CsrowChannel>,
+}
+
+/// Struct for holding a channel of a chip select row
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CsrowChannel {
+    pub channel_nr: u32,
+    pub ce_count: u64,
+    pub dimm_label: Option<String>,
+}
+
+/// Struct for holding a dimm or rank
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Dimm {
+    pub dimm_nr: u32,
+    /// True if the driver shows ranks (`rank<nr>`) instead of dimms (`dimm<nr>`).
+    pub rank: bool,
+    pub label: Option<String>,
+    pub location: Option<String>,
+    pub size_mb: Option<u64>,
+    pub mem_type: Option<String>,
+    /// Not shown by older kernels.
+    pub ce_count: Option<u64>,
+    /// Not shown by older kernels.
+    pub ue_count: Option<u64>,
+}
+
+/// Builder pattern for [`SysEdac`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysEdac, ProcSysParserError> {
+        SysEdac::read_sys_edac(format!("{}/devices/system/edac/mc", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysEdac`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysEdac, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl MemoryController {
+    /// The dimms with correctable errors; empty if the kernel does not count errors per dimm.
+    pub fn dimms_with_ce(&self) -> Vec<&Dimm> {
+        self.dimms.iter().filter(|dimm| dimm.ce_count.is_some_and(|ce_count| ce_count > 0)).collect()
+    }
+}
+
+impl SysEdac {
+    pub fn new() -> SysEdac {
+        SysEdac::default()
+    }
+    /// The number of correctable errors of all memory controllers.
+    pub fn ce_count(&self) -> u64 {
+        self.controllers.iter().map(|controller| controller.ce_count).sum()
+    }
+    /// The number of uncorrectable errors of all memory controllers.
+    pub fn ue_count(&self) -> u64 {
+        self.controllers.iter().map(|controller| controller.ue_count).sum()
+    }
+    fn read_csrow(csrow_path: &Path, csrow_nr: u32) -> Result<Csrow, ProcSysParserError> {
+        let mut channel_nrs: Vec<u32> = read_dir(csrow_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: csrow_path.to_string_lossy().to_string(), error })?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_string_lossy()
+                .strip_prefix("ch")
+                .and_then(|name| name.strip_suffix("_ce_count"))
+                .and_then(|channel_nr| channel_nr.parse::<u32>().ok()))
+            .collect();
+        channel_nrs.sort();
+
+        Ok(Csrow {
+            csrow_nr,
+            size_mb: read_file_option_u64(&csrow_path.join("size_mb"))?,
+            mem_type: read_file_option_string(&csrow_path.join("mem_type")),
+            edac_mode: read_file_option_string(&csrow_path.join("edac_mode")),
+            ce_count: read_file_u64(&csrow_path.join("ce_count"))?,
+            ue_count: read_file_u64(&csrow_path.join("ue_count"))?,
+            channels: channel_nrs.into_iter()
+                .map(|channel_nr| Ok(CsrowChannel {
+                    channel_nr,
+                    ce_count: read_file_u64(&csrow_path.join(format!("ch{}_ce_count", channel_nr)))?,
+                    dimm_label: read_file_option_string(&csrow_path.join(format!("ch{}_dimm_label", channel_nr))),
+                }))
+                .collect::<Result<Vec<_>, ProcSysParserError>>()?,
+        })
+    }
+    fn read_dimm(dimm_path: &Path, dimm_nr: u32, rank: bool) -> Result<Dimm, ProcSysParserError> {
+        Ok(Dimm {
+            dimm_nr,
+            rank,
+            label: read_file_option_string(&dimm_path.join("dimm_label")),
+            location: read_file_option_string(&dimm_path.join("dimm_location")).map(|location| location.trim().to_string()),
+            size_mb: read_file_option_u64(&dimm_path.join("size"))?,
+            mem_type: read_file_option_string(&dimm_path.join("dimm_mem_type")),
+            ce_count: read_file_option_u64(&dimm_path.join("dimm_ce_count"))?,
+            ue_count: read_file_option_u64(&dimm_path.join("dimm_ue_count"))?,
+        })
+    }
+    pub fn read_sys_edac(sys_edac_mc_path: &str) -> Result<SysEdac, ProcSysParserError> {
+        let mut sysedac = SysEdac::new();
+
+        let sys_edac_mc_path = Path::new(sys_edac_mc_path);
+        if !sys_edac_mc_path.exists() { return Ok(sysedac) };
+
+        for mc_nr in read_numbered_entries(sys_edac_mc_path, "mc")? {
+            let mc_path = sys_edac_mc_path.join(format!("mc{}", mc_nr));
+            let mut controller = MemoryController {
+                mc_nr,
+                mc_name: read_file_string(&mc_path.join("mc_name"))?,
+                size_mb: read_file_option_u64(&mc_path.join("size_mb"))?,
+                seconds_since_reset: read_file_option_u64(&mc_path.join("seconds_since_reset"))?,
+                ce_count: read_file_u64(&mc_path.join("ce_count"))?,
+                ce_noinfo_count: read_file_option_u64(&mc_path.join("ce_noinfo_count"))?,
+                ue_count: read_file_u64(&mc_path.join("ue_count"))?,
+                ue_noinfo_count: read_file_option_u64(&mc_path.join("ue_noinfo_count"))?,
+                ..Default::default()
+            };
+            for csrow_nr in read_numbered_entries(&mc_path, "csrow")? {
+                controller.csrows.push(SysEdac::read_csrow(&mc_path.join(format!("csrow{}", csrow_nr)), csrow_nr)?);
+            }
+            for (prefix, rank) in [("dimm", false), ("rank", true)] {
+                for dimm_nr in read_numbered_entries(&mc_path, prefix)? {
+                    controller.dimms.push(SysEdac::read_dimm(&mc_path.join(format!("{}{}", prefix, dimm_nr)), dimm_nr, rank)?);
+                }
+            }
+            sysedac.controllers.push(controller);
+        }
+        Ok(sysedac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_edac_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let mc_path = format!("{}/devices/system/edac/mc/mc0", test_path);
+        create_mock_files(&mc_path, &[("mc_name", "Skylake Socket#0 IMC#0"), ("size_mb", "65536"), ("seconds_since_reset", "2750893"), ("ce_count", "3"), ("ce_noinfo_count", "0"), ("ue_count", "0"), ("ue_noinfo_count", "0")]);
+        create_mock_files(&format!("{}/csrow0", mc_path), &[("size_mb", "32768"), ("mem_type", "Registered-DDR4"), ("edac_mode", "SECDED"), ("ce_count", "3"), ("ue_count", "0"), ("ch0_ce_count", "3"), ("ch0_dimm_label", "CPU_SrcID#0_MC#0_Chan#0_DIMM#0"), ("ch1_ce_count", "0"), ("ch1_dimm_label", "CPU_SrcID#0_MC#0_Chan#1_DIMM#0")]);
+        create_mock_files(&format!("{}/dimm0", mc_path), &[("dimm_label", "CPU_SrcID#0_MC#0_Chan#0_DIMM#0"), ("dimm_location", "channel 0 slot 0 "), ("size", "32768"), ("dimm_mem_type", "Registered-DDR4"), ("dimm_ce_count", "3"), ("dimm_ue_count", "0")]);
+        create_mock_files(&format!("{}/dimm1", mc_path), &[("dimm_label", "CPU_SrcID#0_MC#0_Chan#1_DIMM#0"), ("dimm_location", "channel 1 slot 0 "), ("size", "32768"), ("dimm_mem_type", "Registered-DDR4")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.ce_count(), 3);
+        assert_eq!(result.ue_count(), 0);
+        let controller = &result.controllers[0];
+        assert_eq!(controller.csrows[0].channels, vec![
+            CsrowChannel { channel_nr: 0, ce_count: 3, dimm_label: Some("CPU_SrcID#0_MC#0_Chan#0_DIMM#0".to_string()) },
+            CsrowChannel { channel_nr: 1, ce_count: 0, dimm_label: Some("CPU_SrcID#0_MC#0_Chan#1_DIMM#0".to_string()) },
+        ]);
+        assert_eq!(controller.dimms[0], Dimm { dimm_nr: 0, rank: false, label: Some("CPU_SrcID#0_MC#0_Chan#0_DIMM#0".to_string()), location: Some("channel 0 slot 0".to_string()), size_mb: Some(32768), mem_type: Some("Registered-DDR4".to_string()), ce_count: Some(3), ue_count: Some(0) });
+        assert_eq!(controller.dimms[1].ce_count, None);
+        assert_eq!(controller.dimms_with_ce().len(), 1);
+    }
+
+    #[test]
+    fn read_nonexistent_edac_directory() {
+        let result = Builder::new().path("/xxxxxxxxxxxx").read().unwrap();
+        assert_eq!(result, SysEdac::new());
+    }
+}
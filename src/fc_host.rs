@@ -0,0 +1,285 @@
+/*!
+Read the Fibre Channel host adapters from `/sys/class/fc_host` into the struct [`SysFcHost`].
+
+Every Fibre Channel HBA port is a SCSI host, and shows the Fibre Channel properties in `/sys/class/fc_host/host<nr>`:
+the world wide names (WWN) of the port and the node, the port state, the link speed, and the `statistics` directory
+with the frame, error and FCP request counters of the port, which are essential for SAN monitoring.
+
+The statistics are shown as hexadecimal numbers in the files. Statistics that are not supported by the driver are
+shown as all bits set (`0xffffffffffffffff`), or cannot be read, and are None.
+
+Documentation: <https://www.kernel.org/doc/Documentation/scsi/scsi_fc_transport.rst>
+
+Here is an example obtaining the data from `/sys/class/fc_host`:
+```no_run
+use proc_sys_parser::fc_host;
+
+let sys_fc_host = fc_host::read();
+
+println!("{:#?}", sys_fc_host);
+```
+Example output:
+```text
+SysFcHost {
+    hosts: [
+        FcHost {
+            name: "host1",
+            port_name: "0x21000024ff7a1b2c",
+            node_name: "0x20000024ff7a1b2c",
+            port_id: Some("0x010a00"),
+            port_state: "Online",
+            port_type: Some("NPort (fabric via point-to-point)"),
+            speed: "16 Gbit",
+            fabric_name: Some("0x100000051e7a1b2c"),
+            statistics: FcHostStatistics {
+                seconds_since_last_reset: Some(2591304),
+                tx_frames: Some(128401923),
+                rx_frames: Some(210394812),
+                ..
+                link_failure_count: Some(2),
+                loss_of_sync_count: Some(5),
+                ..
+            },
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysFcHost`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::fc_host::Builder;
+
+let sys_fc_host = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_string};
+
+/// Struct for holding all Fibre Channel hosts
+#[derive(Debug, PartialEq, Default)]
+pub struct SysFcHost {
+    pub hosts: Vec<FcHost>,
+}
+
+/// Struct for holding a single Fibre Channel host
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct FcHost {
+    /// The SCSI host name, such as `host1`.
+    pub name: String,
+    /// The world wide port name (WWPN).
+    pub port_name: String,
+    /// The world wide node name (WWNN).
+    pub node_name: String,
+    /// The Fibre Channel address of the port.
+    pub port_id: Option<String>,
+    /// The port state, such as Online or Linkdown.
+    pub port_state: String,
+    /// The port type, such as `NPort (fabric via point-to-point)`.
+    pub port_type: Option<String>,
+    /// The link speed, such as `16 Gbit`, or `unknown` when the link is down.
+    pub speed: String,
+    /// The world wide name of the fabric.
+    pub fabric_name: Option<String>,
+    pub statistics: FcHostStatistics,
+}
+
+/// Struct for holding `/sys/class/fc_host/host<nr>/statistics`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct FcHostStatistics {
+    /// Seconds since the statistics were last reset.
+    pub seconds_since_last_reset: Option<u64>,
+    /// Frames transmitted.
+    pub tx_frames: Option<u64>,
+    /// Frames received.
+    pub rx_frames: Option<u64>,
+    /// Words transmitted.
+    pub tx_words: Option<u64>,
+    /// Words received.
+    pub rx_words: Option<u64>,
+    /// Loop initialization primitives.
+    pub lip_count: Option<u64>,
+    /// Not operational sequences.
+    pub nos_count: Option<u64>,
+    /// Frames received with an error.
+    pub error_frames: Option<u64>,
+    /// Frames dropped.
+    pub dumped_frames: Option<u64>,
+    /// Link failures.
+    pub link_failure_count: Option<u64>,
+    /// Losses of synchronization.
+    pub loss_of_sync_count: Option<u64>,
+    /// Losses of signal.
+    pub loss_of_signal_count: Option<u64>,
+    /// Primitive sequence protocol errors.
+    pub prim_seq_protocol_err_count: Option<u64>,
+    /// Invalid transmission words received.
+    pub invalid_tx_word_count: Option<u64>,
+    /// Frames received with an invalid CRC.
+    pub invalid_crc_count: Option<u64>,
+    /// FCP read requests.
+    pub fcp_input_requests: Option<u64>,
+    /// FCP write requests.
+    pub fcp_output_requests: Option<u64>,
+    /// FCP control requests.
+    pub fcp_control_requests: Option<u64>,
+    /// FCP megabytes read.
+    pub fcp_input_megabytes: Option<u64>,
+    /// FCP megabytes written.
+    pub fcp_output_megabytes: Option<u64>,
+}
+
+/// Builder pattern for [`SysFcHost`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysFcHost, ProcSysParserError> {
+        SysFcHost::read_sys_fc_host(format!("{}/class/fc_host", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysFcHost`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysFcHost, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl FcHost {
+    /// True if the port state is Online.
+    pub fn is_online(&self) -> bool {
+        self.port_state == "Online"
+    }
+}
+
+impl SysFcHost {
+    pub fn new() -> SysFcHost {
+        SysFcHost::default()
+    }
+    /// Read a hexadecimal statistic, None if the file cannot be read or the statistic is not supported.
+    fn read_statistic(file: &Path) -> Result<Option<u64>, ProcSysParserError> {
+        let Some(contents) = read_file_option_string(file) else { return Ok(None) };
+        let contents = contents.trim();
+        let value = u64::from_str_radix(contents.strip_prefix("0x").unwrap_or(contents), 16)
+            .map_err(ProcSysParserError::ParseToIntegerError)?;
+        Ok(if value == u64::MAX { None } else { Some(value) })
+    }
+    fn read_fc_host_statistics(statistics_path: &Path) -> Result<FcHostStatistics, ProcSysParserError> {
+        Ok(FcHostStatistics {
+            seconds_since_last_reset: SysFcHost::read_statistic(&statistics_path.join("seconds_since_last_reset"))?,
+            tx_frames: SysFcHost::read_statistic(&statistics_path.join("tx_frames"))?,
+            rx_frames: SysFcHost::read_statistic(&statistics_path.join("rx_frames"))?,
+            tx_words: SysFcHost::read_statistic(&statistics_path.join("tx_words"))?,
+            rx_words: SysFcHost::read_statistic(&statistics_path.join("rx_words"))?,
+            lip_count: SysFcHost::read_statistic(&statistics_path.join("lip_count"))?,
+            nos_count: SysFcHost::read_statistic(&statistics_path.join("nos_count"))?,
+            error_frames: SysFcHost::read_statistic(&statistics_path.join("error_frames"))?,
+            dumped_frames: SysFcHost::read_statistic(&statistics_path.join("dumped_frames"))?,
+            link_failure_count: SysFcHost::read_statistic(&statistics_path.join("link_failure_count"))?,
+            loss_of_sync_count: SysFcHost::read_statistic(&statistics_path.join("loss_of_sync_count"))?,
+            loss_of_signal_count: SysFcHost::read_statistic(&statistics_path.join("loss_of_signal_count"))?,
+            prim_seq_protocol_err_count: SysFcHost::read_statistic(&statistics_path.join("prim_seq_protocol_err_count"))?,
+            invalid_tx_word_count: SysFcHost::read_statistic(&statistics_path.join("invalid_tx_word_count"))?,
+            invalid_crc_count: SysFcHost::read_statistic(&statistics_path.join("invalid_crc_count"))?,
+            fcp_input_requests: SysFcHost::read_statistic(&statistics_path.join("fcp_input_requests"))?,
+            fcp_output_requests: SysFcHost::read_statistic(&statistics_path.join("fcp_output_requests"))?,
+            fcp_control_requests: SysFcHost::read_statistic(&statistics_path.join("fcp_control_requests"))?,
+            fcp_input_megabytes: SysFcHost::read_statistic(&statistics_path.join("fcp_input_megabytes"))?,
+            fcp_output_megabytes: SysFcHost::read_statistic(&statistics_path.join("fcp_output_megabytes"))?,
+        })
+    }
+    fn read_fc_host(host_path: &Path, name: &str) -> Result<FcHost, ProcSysParserError> {
+        Ok(FcHost {
+            name: name.to_string(),
+            port_name: read_file_string(&host_path.join("port_name"))?,
+            node_name: read_file_string(&host_path.join("node_name"))?,
+            port_id: read_file_option_string(&host_path.join("port_id")),
+            port_state: read_file_string(&host_path.join("port_state"))?,
+            port_type: read_file_option_string(&host_path.join("port_type")),
+            speed: read_file_string(&host_path.join("speed"))?,
+            fabric_name: read_file_option_string(&host_path.join("fabric_name")),
+            statistics: SysFcHost::read_fc_host_statistics(&host_path.join("statistics"))?,
+        })
+    }
+    pub fn read_sys_fc_host(sys_class_fc_host_path: &str) -> Result<SysFcHost, ProcSysParserError> {
+        let mut sysfchost = SysFcHost::new();
+
+        let host_directories = read_dir(sys_class_fc_host_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_fc_host_path.to_string(), error })?;
+
+        for host in host_directories.filter_map(|entry| entry.ok()) {
+            sysfchost.hosts.push(SysFcHost::read_fc_host(&host.path(), &host.file_name().to_string_lossy())?);
+        }
+        sysfchost.hosts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysfchost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_fc_host_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let host_path = format!("{}/class/fc_host/host1", test_path);
+        create_mock_files(&host_path, &[
+            ("port_name", "0x21000024ff7a1b2c"),
+            ("node_name", "0x20000024ff7a1b2c"),
+            ("port_id", "0x010a00"),
+            ("port_state", "Online"),
+            ("speed", "16 Gbit"),
+        ]);
+        create_mock_files(&format!("{}/statistics", host_path), &[
+            ("tx_frames", "0x7a73f03"),
+            ("rx_frames", "0xc8a5abc"),
+            ("link_failure_count", "0x2"),
+            ("loss_of_sync_count", "0x5"),
+            ("lip_count", "0xffffffffffffffff"),
+        ]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysFcHost { hosts: vec![
+            FcHost {
+                name: "host1".to_string(),
+                port_name: "0x21000024ff7a1b2c".to_string(),
+                node_name: "0x20000024ff7a1b2c".to_string(),
+                port_id: Some("0x010a00".to_string()),
+                port_state: "Online".to_string(),
+                port_type: None,
+                speed: "16 Gbit".to_string(),
+                fabric_name: None,
+                statistics: FcHostStatistics { tx_frames: Some(128401155), rx_frames: Some(210393788), link_failure_count: Some(2), loss_of_sync_count: Some(5), lip_count: None, ..Default::default() },
+            }
+        ]});
+        assert!(result.hosts[0].is_online());
+    }
+}
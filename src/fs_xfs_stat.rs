@@ -151,7 +151,7 @@ qm 0 0 0 0 0 0 0 0 0
 xpc 1839985078272 1924118614718 41117984770168
 defer_relog 0
 debug 0";
-        let result = ProcFsXfsStat::parse_proc_fs_xfs_stat(&proc_fs_xfs_stat_file);
+        let result = ProcFsXfsStat::parse_proc_fs_xfs_stat(proc_fs_xfs_stat_file);
         assert_eq!(
             result,
             ProcFsXfsStat {
@@ -200,7 +200,7 @@ debug 0";
         create_dir_all(format!("{}/fs/xfs", test_path)).expect("Error creating mock directory.");
 
         write(format!("{}/fs/xfs/stat", test_path), proc_fs_xfs_stat_file)
-            .expect(format!("Error writing to {}/fs/xfs/stat", test_path).as_str());
+            .unwrap_or_else(|_| panic!("Error writing to {}/fs/xfs/stat", test_path));
         let result = Builder::new().path(&test_path).read();
 
         remove_dir_all(test_path).unwrap();
@@ -258,7 +258,7 @@ refcntbt 220 12 2 2 0 0 0 0 0 0 0 0 0 0 0
 qm 0 0 0 0 0 0 0 0 0
 defer_relog 0
 debug 0";
-        let result = ProcFsXfsStat::parse_proc_fs_xfs_stat(&proc_fs_xfs_stat_file);
+        let result = ProcFsXfsStat::parse_proc_fs_xfs_stat(proc_fs_xfs_stat_file);
         assert_eq!(
             result,
             ProcFsXfsStat {
@@ -0,0 +1,239 @@
+/*!
+Read the hugetlbfs huge page pools of every huge page size from `/sys/kernel/mm/hugepages` into the struct [`SysHugepages`].
+
+`/proc/meminfo` shows the HugePages_Total, HugePages_Free, HugePages_Rsvd and HugePages_Surp of the default huge
+page size only. On systems with multiple huge page sizes, such as 2MB and 1GB on x86_64, every size has its own pool
+in `/sys/kernel/mm/hugepages/hugepages-<size>kB`, which shows:
+- `nr_hugepages`: the number of huge pages in the pool.
+- `free_hugepages`: the number of huge pages in the pool that are not allocated.
+- `resv_hugepages`: the number of huge pages that are reserved (committed) but not allocated yet.
+- `surplus_hugepages`: the number of huge pages allocated above nr_hugepages via overcommit.
+- `nr_overcommit_hugepages`: the maximal number of surplus huge pages.
+
+For every pool the per-NUMA-node values are read from `/sys/devices/system/node/node<nr>/hugepages`, which show the
+nr, free and surplus huge pages per node. These are empty for a kernel built without NUMA support.
+
+Documentation: <https://www.kernel.org/doc/Documentation/admin-guide/mm/hugetlbpage.rst>
+
+Here is an example obtaining the data from `/sys/kernel/mm/hugepages`:
+```no_run
+use proc_sys_parser::hugepages;
+
+let sys_hugepages = hugepages::read();
+
+println!("{:#?}", sys_hugepages);
+```
+Example output:
+```text
+SysHugepages {
+    pools: [
+        HugepagePool {
+            size_kb: 2048,
+            nr_hugepages: 1024,
+            free_hugepages: 512,
+            resv_hugepages: 12,
+            surplus_hugepages: 0,
+            nr_overcommit_hugepages: 0,
+            nodes: [
+                HugepagePoolNode { node_nr: 0, nr_hugepages: 512, free_hugepages: 256, surplus_hugepages: 0 },
+                HugepagePoolNode { node_nr: 1, nr_hugepages: 512, free_hugepages: 256, surplus_hugepages: 0 },
+            ],
+        },
+        HugepagePool {
+            size_kb: 1048576,
+            nr_hugepages: 0,
+            free_hugepages: 0,
+            resv_hugepages: 0,
+            surplus_hugepages: 0,
+            nr_overcommit_hugepages: 0,
+            nodes: [
+                HugepagePoolNode { node_nr: 0, nr_hugepages: 0, free_hugepages: 0, surplus_hugepages: 0 },
+                HugepagePoolNode { node_nr: 1, nr_hugepages: 0, free_hugepages: 0, surplus_hugepages: 0 },
+            ],
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysHugepages`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::hugepages::Builder;
+
+let sys_hugepages = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::numa::SysNuma;
+use crate::utils::{read_file_u64, read_numbered_entries};
+
+/// Struct for holding the huge page pools
+#[derive(Debug, PartialEq, Default)]
+pub struct SysHugepages {
+    /// The pools, sorted by huge page size.
+    pub pools: Vec<HugepagePool>,
+}
+
+/// Struct for holding the huge page pool of a single huge page size
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct HugepagePool {
+    pub size_kb: u64,
+    pub nr_hugepages: u64,
+    pub free_hugepages: u64,
+    pub resv_hugepages: u64,
+    pub surplus_hugepages: u64,
+    pub nr_overcommit_hugepages: u64,
+    pub nodes: Vec<HugepagePoolNode>,
+}
+
+/// Struct for holding the huge page pool of a single huge page size on a single NUMA node
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct HugepagePoolNode {
+    pub node_nr: u32,
+    pub nr_hugepages: u64,
+    pub free_hugepages: u64,
+    pub surplus_hugepages: u64,
+}
+
+/// Builder pattern for [`SysHugepages`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysHugepages, ProcSysParserError> {
+        SysHugepages::read_sys_hugepages(
+            format!("{}/kernel/mm/hugepages", &self.sys_path).as_str(),
+            format!("{}/devices/system/node", &self.sys_path).as_str(),
+        )
+    }
+}
+
+/// The main function for building a [`SysHugepages`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysHugepages, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl HugepagePool {
+    /// The total size of the pool in kilobytes.
+    pub fn total_kb(&self) -> u64 {
+        self.nr_hugepages * self.size_kb
+    }
+    /// The size of the huge pages in the pool that are neither allocated nor reserved in kilobytes.
+    pub fn available_kb(&self) -> u64 {
+        self.free_hugepages.saturating_sub(self.resv_hugepages) * self.size_kb
+    }
+}
+
+impl SysHugepages {
+    pub fn new() -> SysHugepages {
+        SysHugepages::default()
+    }
+    /// The pool of the given huge page size.
+    pub fn pool(&self, size_kb: u64) -> Option<&HugepagePool> {
+        self.pools.iter().find(|pool| pool.size_kb == size_kb)
+    }
+    pub fn read_sys_hugepages(sys_hugepages_path: &str, sys_node_path: &str) -> Result<SysHugepages, ProcSysParserError> {
+        let mut syshugepages = SysHugepages::new();
+
+        let pool_directories = read_dir(sys_hugepages_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_hugepages_path.to_string(), error })?;
+        for pool_directory in pool_directories.filter_map(|entry| entry.ok()) {
+            let directory_name = pool_directory.file_name().to_string_lossy().to_string();
+            let Some(size_kb) = directory_name.strip_prefix("hugepages-").and_then(|size| size.strip_suffix("kB")) else { continue };
+            let pool_path = pool_directory.path();
+            syshugepages.pools.push(HugepagePool {
+                size_kb: size_kb.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                nr_hugepages: read_file_u64(&pool_path.join("nr_hugepages"))?,
+                free_hugepages: read_file_u64(&pool_path.join("free_hugepages"))?,
+                resv_hugepages: read_file_u64(&pool_path.join("resv_hugepages"))?,
+                surplus_hugepages: read_file_u64(&pool_path.join("surplus_hugepages"))?,
+                nr_overcommit_hugepages: read_file_u64(&pool_path.join("nr_overcommit_hugepages"))?,
+                nodes: Vec::new(),
+            });
+        }
+        syshugepages.pools.sort_by_key(|pool| pool.size_kb);
+
+        // the node directory does not exist if the kernel is built without NUMA support.
+        let sys_node_path = Path::new(sys_node_path);
+        let node_nrs = if sys_node_path.exists() { read_numbered_entries(sys_node_path, "node")? } else { Vec::new() };
+        for node_nr in node_nrs {
+            for node_hugepages in SysNuma::read_node_hugepages(&sys_node_path.join(format!("node{}/hugepages", node_nr)))? {
+                let Some(pool) = syshugepages.pools.iter_mut().find(|pool| pool.size_kb == node_hugepages.size_kb) else { continue };
+                pool.nodes.push(HugepagePoolNode {
+                    node_nr,
+                    nr_hugepages: node_hugepages.nr_hugepages,
+                    free_hugepages: node_hugepages.free_hugepages,
+                    surplus_hugepages: node_hugepages.surplus_hugepages,
+                });
+            }
+        }
+        Ok(syshugepages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_hugepages_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        for (size, nr, free, resv) in [("2048", "1024", "512", "12"), ("1048576", "4", "4", "0")] {
+            create_mock_files(&format!("{}/kernel/mm/hugepages/hugepages-{}kB", test_path, size), &[
+                ("nr_hugepages", nr),
+                ("nr_hugepages_mempolicy", nr),
+                ("free_hugepages", free),
+                ("resv_hugepages", resv),
+                ("surplus_hugepages", "0"),
+                ("nr_overcommit_hugepages", "0"),
+            ]);
+        }
+        for node in ["node0", "node1"] {
+            create_mock_files(&format!("{}/devices/system/node/{}/hugepages/hugepages-2048kB", test_path, node), &[
+                ("nr_hugepages", "512"),
+                ("free_hugepages", "256"),
+                ("surplus_hugepages", "0"),
+            ]);
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.pools.iter().map(|pool| pool.size_kb).collect::<Vec<_>>(), vec![2048, 1048576]);
+        let pool = result.pool(2048).unwrap();
+        assert_eq!(pool.resv_hugepages, 12);
+        assert_eq!(pool.total_kb(), 2097152);
+        assert_eq!(pool.available_kb(), 500 * 2048);
+        assert_eq!(pool.nodes, vec![
+            HugepagePoolNode { node_nr: 0, nr_hugepages: 512, free_hugepages: 256, surplus_hugepages: 0 },
+            HugepagePoolNode { node_nr: 1, nr_hugepages: 512, free_hugepages: 256, surplus_hugepages: 0 },
+        ]);
+        assert_eq!(result.pool(1048576).unwrap().nodes, vec![]);
+    }
+}
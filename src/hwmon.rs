@@ -0,0 +1,303 @@
+/*!
+Read the hardware monitoring sensors from `/sys/class/hwmon` into the struct [`SysHwmon`].
+
+Every hardware monitoring chip in `/sys/class/hwmon/hwmon<nr>` has a name (the driver, such as `coretemp`, `k10temp`,
+`nct6775` or `nvme`) and a number of sensors, which are shown as files named `<type><index>_<item>`, such as
+`temp1_input`, `temp1_label` and `temp1_max`. This is the same data that is shown by the `sensors` utility of
+lm-sensors, without its configuration (which can rename, scale or hide sensors).
+
+The following sensor types are read, with the unit in which the values are shown by the kernel:
+- `temp`: temperature in millidegree Celsius.
+- `fan`: fan speed in RPM.
+- `in`: voltage in millivolt.
+- `curr`: current in milliampere.
+- `power`: power in microwatt. If `power<index>_input` does not exist, `power<index>_average` is used.
+
+For every sensor the input, label, min, max, crit and alarm files are read if they exist. Reading a sensor can
+fail, for example when the device is not powered; the value then is None.
+
+Older drivers put the sensor files in the `device` directory of the hwmon chip instead of the chip directory itself;
+these are read too.
+
+Documentation: <https://www.kernel.org/doc/Documentation/hwmon/sysfs-interface.rst>
+
+Here is an example obtaining the data from `/sys/class/hwmon`:
+```no_run
+use proc_sys_parser::hwmon;
+
+let sys_hwmon = hwmon::read();
+
+println!("{:#?}", sys_hwmon);
+```
+Example output:
+```text
+SysHwmon {
+    chips: [
+        HwmonChip {
+            hwmon_nr: 0,
+            name: "coretemp",
+            sensors: [
+                HwmonSensor { sensor_type: Temp, index: 1, label: Some("Package id 0"), input: Some(45000), min: None, max: Some(80000), crit: Some(100000), alarm: Some(false) },
+                HwmonSensor { sensor_type: Temp, index: 2, label: Some("Core 0"), input: Some(43000), min: None, max: Some(80000), crit: Some(100000), alarm: Some(false) },
+            ],
+        },
+        HwmonChip {
+            hwmon_nr: 1,
+            name: "nct6775",
+            sensors: [
+                HwmonSensor { sensor_type: Fan, index: 2, label: None, input: Some(1171), min: Some(0), max: None, crit: None, alarm: Some(false) },
+                HwmonSensor { sensor_type: In, index: 0, label: None, input: Some(872), min: Some(0), max: Some(1744), crit: None, alarm: Some(false) },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysHwmon`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::hwmon::Builder;
+
+let sys_hwmon = Builder::new().path("/mysys").read();
+```
+*/
+use std::collections::BTreeSet;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_numbered_entries};
+
+/// Struct for holding all hardware monitoring chips
+#[derive(Debug, PartialEq, Default)]
+pub struct SysHwmon {
+    pub chips: Vec<HwmonChip>,
+}
+
+/// Struct for holding a single hardware monitoring chip
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct HwmonChip {
+    pub hwmon_nr: u32,
+    /// The name of the chip, which is the driver name, such as `coretemp`.
+    pub name: String,
+    /// The sensors, sorted by type and index.
+    pub sensors: Vec<HwmonSensor>,
+}
+
+/// Struct for holding a single sensor
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct HwmonSensor {
+    pub sensor_type: SensorType,
+    pub index: u32,
+    pub label: Option<String>,
+    pub input: Option<i64>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub crit: Option<i64>,
+    pub alarm: Option<bool>,
+}
+
+/// The type of a sensor
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy)]
+pub enum SensorType {
+    /// Temperature in millidegree Celsius.
+    #[default]
+    Temp,
+    /// Fan speed in RPM.
+    Fan,
+    /// Voltage in millivolt.
+    In,
+    /// Current in milliampere.
+    Curr,
+    /// Power in microwatt.
+    Power,
+}
+
+/// Builder pattern for [`SysHwmon`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysHwmon, ProcSysParserError> {
+        SysHwmon::read_sys_hwmon(format!("{}/class/hwmon", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysHwmon`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysHwmon, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SensorType {
+    fn from_prefix(prefix: &str) -> Option<SensorType> {
+        match prefix {
+            "temp" => Some(SensorType::Temp),
+            "fan" => Some(SensorType::Fan),
+            "in" => Some(SensorType::In),
+            "curr" => Some(SensorType::Curr),
+            "power" => Some(SensorType::Power),
+            _ => None,
+        }
+    }
+    fn prefix(&self) -> &'static str {
+        match self {
+            SensorType::Temp => "temp",
+            SensorType::Fan => "fan",
+            SensorType::In => "in",
+            SensorType::Curr => "curr",
+            SensorType::Power => "power",
+        }
+    }
+}
+
+impl HwmonChip {
+    /// The sensors of the given type.
+    pub fn sensors_of_type(&self, sensor_type: SensorType) -> impl Iterator<Item = &HwmonSensor> {
+        self.sensors.iter().filter(move |sensor| sensor.sensor_type == sensor_type)
+    }
+    /// The sensor with the given label, such as `Package id 0`.
+    pub fn sensor_by_label(&self, label: &str) -> Option<&HwmonSensor> {
+        self.sensors.iter().find(|sensor| sensor.label.as_deref() == Some(label))
+    }
+}
+
+impl SysHwmon {
+    pub fn new() -> SysHwmon {
+        SysHwmon::default()
+    }
+    /// The chips with the given name, such as `coretemp`; there is a chip for every socket for some drivers.
+    pub fn chips_by_name(&self, name: &str) -> impl Iterator<Item = &HwmonChip> {
+        let name = name.to_string();
+        self.chips.iter().filter(move |chip| chip.name == name)
+    }
+    // sensor values are read leniently, because reading a sensor that is not available returns an error
+    // such as EIO or ENODATA.
+    fn read_sensor_value(file: &Path) -> Option<i64> {
+        read_file_option_string(file).and_then(|value| value.trim().parse::<i64>().ok())
+    }
+    fn read_sensor(sensor_path: &Path, sensor_type: SensorType, index: u32) -> HwmonSensor {
+        let file = |item: &str| sensor_path.join(format!("{}{}_{}", sensor_type.prefix(), index, item));
+
+        HwmonSensor {
+            sensor_type,
+            index,
+            label: read_file_option_string(&file("label")).map(|label| label.trim().to_string()),
+            input: SysHwmon::read_sensor_value(&file("input"))
+                .or_else(|| if sensor_type == SensorType::Power { SysHwmon::read_sensor_value(&file("average")) } else { None }),
+            min: SysHwmon::read_sensor_value(&file("min")),
+            max: SysHwmon::read_sensor_value(&file("max")),
+            crit: SysHwmon::read_sensor_value(&file("crit")),
+            alarm: SysHwmon::read_sensor_value(&file("alarm")).map(|alarm| alarm != 0),
+        }
+    }
+    fn read_hwmon_chip(chip_path: &Path, hwmon_nr: u32, sensor_regex: &Regex) -> Result<HwmonChip, ProcSysParserError> {
+        // older drivers have the name and sensor files in the device directory.
+        let sensor_path: PathBuf = if chip_path.join("name").exists() { chip_path.to_path_buf() } else { chip_path.join("device") };
+
+        let mut sensors: BTreeSet<(SensorType, u32)> = BTreeSet::new();
+        let sensor_files = read_dir(&sensor_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sensor_path.to_string_lossy().to_string(), error })?;
+        for sensor_file in sensor_files.filter_map(|entry| entry.ok()) {
+            let file_name = sensor_file.file_name().to_string_lossy().to_string();
+            let Some(captures) = sensor_regex.captures(&file_name) else { continue };
+            let Some(sensor_type) = SensorType::from_prefix(&captures[1]) else { continue };
+            let Ok(index) = captures[2].parse::<u32>() else { continue };
+            sensors.insert((sensor_type, index));
+        }
+
+        Ok(HwmonChip {
+            hwmon_nr,
+            name: read_file_option_string(&sensor_path.join("name")).unwrap_or_default(),
+            sensors: sensors.into_iter().map(|(sensor_type, index)| SysHwmon::read_sensor(&sensor_path, sensor_type, index)).collect(),
+        })
+    }
+    pub fn read_sys_hwmon(sys_class_hwmon_path: &str) -> Result<SysHwmon, ProcSysParserError> {
+        let mut syshwmon = SysHwmon::new();
+        let sys_class_hwmon_path = Path::new(sys_class_hwmon_path);
+        let sensor_regex = Regex::new(r"^(temp|fan|in|curr|power)(\d+)_(input|average)$")
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: "hwmon sensor".to_string() })?;
+
+        for hwmon_nr in read_numbered_entries(sys_class_hwmon_path, "hwmon")? {
+            syshwmon.chips.push(SysHwmon::read_hwmon_chip(&sys_class_hwmon_path.join(format!("hwmon{}", hwmon_nr)), hwmon_nr, &sensor_regex)?);
+        }
+        Ok(syshwmon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_hwmon_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/hwmon/hwmon0", test_path), &[
+            ("name", "coretemp"),
+            ("temp1_input", "45000"),
+            ("temp1_label", "Package id 0"),
+            ("temp1_max", "80000"),
+            ("temp1_crit", "100000"),
+            ("temp1_crit_alarm", "0"),
+            ("temp2_input", "43000"),
+            ("temp2_label", "Core 0"),
+        ]);
+        create_mock_files(&format!("{}/class/hwmon/hwmon1", test_path), &[
+            ("name", "nct6775"),
+            ("fan2_input", "1171"),
+            ("fan2_min", "0"),
+            ("fan2_alarm", "1"),
+            ("in0_input", "872"),
+            ("in0_max", "1744"),
+            ("power1_average", "35000000"),
+            // a sensor that is not available cannot be read.
+            ("temp7_input", "N/A"),
+            ("pwm2", "128"),
+        ]);
+        // an older driver with the files in the device directory.
+        create_mock_files(&format!("{}/class/hwmon/hwmon2/device", test_path), &[("name", "it87"), ("curr1_input", "1500")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.chips.len(), 3);
+        let coretemp = result.chips_by_name("coretemp").next().unwrap();
+        assert_eq!(coretemp.sensors, vec![
+            HwmonSensor { sensor_type: SensorType::Temp, index: 1, label: Some("Package id 0".to_string()), input: Some(45000), min: None, max: Some(80000), crit: Some(100000), alarm: None },
+            HwmonSensor { sensor_type: SensorType::Temp, index: 2, label: Some("Core 0".to_string()), input: Some(43000), min: None, max: None, crit: None, alarm: None },
+        ]);
+        assert_eq!(coretemp.sensor_by_label("Core 0").unwrap().index, 2);
+        let nct6775 = &result.chips[1];
+        assert_eq!(nct6775.sensors.iter().map(|sensor| (sensor.sensor_type, sensor.index)).collect::<Vec<_>>(),
+            vec![(SensorType::Temp, 7), (SensorType::Fan, 2), (SensorType::In, 0), (SensorType::Power, 1)]);
+        assert_eq!(nct6775.sensors[0].input, None);
+        assert_eq!(nct6775.sensors[1].alarm, Some(true));
+        assert_eq!(nct6775.sensors_of_type(SensorType::Power).next().unwrap().input, Some(35000000));
+        assert_eq!(result.chips[2].name, "it87");
+        assert_eq!(result.chips[2].sensors[0], HwmonSensor { sensor_type: SensorType::Curr, index: 1, input: Some(1500), ..Default::default() });
+    }
+}
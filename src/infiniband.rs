@@ -0,0 +1,260 @@
+/*!
+Read the InfiniBand and RoCE devices, ports and counters from `/sys/class/infiniband` into the struct [`SysInfiniband`].
+
+Every RDMA device in `/sys/class/infiniband/<device>` has one or more ports in `ports/<nr>`. For every port the state,
+the rate, the link layer and the counters are read:
+- `counters`: the standard port counters defined by the InfiniBand specification, such as port_rcv_data,
+  port_xmit_data, symbol_error and link_downed.
+- `hw_counters`: the driver specific counters, such as out_of_buffer and duplicate_request for mlx5.
+
+Because the available counters are different per device and driver, the counters are stored by name.
+Counters that cannot be read or do not contain a number, which some drivers show for unsupported counters, are skipped.
+
+Please mind port_rcv_data and port_xmit_data are counted in units of 4 bytes (octets divided by 4); use
+[`IbPort::rcv_data_bytes`] and [`IbPort::xmit_data_bytes`] to obtain bytes.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/stable/sysfs-class-infiniband>
+
+Here is an example obtaining the data from `/sys/class/infiniband`:
+```no_run
+use proc_sys_parser::infiniband;
+
+let sys_infiniband = infiniband::read();
+
+println!("{:#?}", sys_infiniband);
+```
+Example output:
+```text
+SysInfiniband {
+    devices: [
+        IbDevice {
+            name: "mlx5_0",
+            fw_ver: Some("16.35.2000"),
+            node_guid: Some("0c42:a103:0065:1a2b"),
+            ports: [
+                IbPort {
+                    port_nr: 1,
+                    state: "4: ACTIVE",
+                    phys_state: "5: LinkUp",
+                    rate: "100 Gb/sec (4X EDR)",
+                    link_layer: Some("InfiniBand"),
+                    counters: {
+                        "link_downed": 0,
+                        "port_rcv_data": 5429118733,
+                        "port_xmit_data": 4821196512,
+                        "symbol_error": 0,
+                        ..
+                    },
+                    hw_counters: {
+                        "out_of_buffer": 0,
+                        ..
+                    },
+                },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysInfiniband`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::infiniband::Builder;
+
+let sys_infiniband = Builder::new().path("/mysys").read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_string, read_numbered_entries};
+
+/// Struct for holding all RDMA devices
+#[derive(Debug, PartialEq, Default)]
+pub struct SysInfiniband {
+    pub devices: Vec<IbDevice>,
+}
+
+/// Struct for holding a single RDMA device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct IbDevice {
+    pub name: String,
+    /// The firmware version.
+    pub fw_ver: Option<String>,
+    pub node_guid: Option<String>,
+    pub ports: Vec<IbPort>,
+}
+
+/// Struct for holding a single port of an RDMA device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct IbPort {
+    pub port_nr: u32,
+    /// The logical state, such as `4: ACTIVE`.
+    pub state: String,
+    /// The physical state, such as `5: LinkUp`.
+    pub phys_state: String,
+    /// The link rate, such as `100 Gb/sec (4X EDR)`.
+    pub rate: String,
+    /// InfiniBand or Ethernet (RoCE).
+    pub link_layer: Option<String>,
+    /// The standard port counters by name.
+    pub counters: BTreeMap<String, u64>,
+    /// The driver specific counters by name.
+    pub hw_counters: BTreeMap<String, u64>,
+}
+
+/// Builder pattern for [`SysInfiniband`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysInfiniband, ProcSysParserError> {
+        SysInfiniband::read_sys_infiniband(format!("{}/class/infiniband", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysInfiniband`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysInfiniband, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl IbPort {
+    /// A standard port counter by name, None if the counter is not available.
+    pub fn counter(&self, name: &str) -> Option<u64> {
+        self.counters.get(name).copied()
+    }
+    /// The received data in bytes, which is port_rcv_data multiplied by 4.
+    pub fn rcv_data_bytes(&self) -> Option<u64> {
+        self.counter("port_rcv_data").map(|words| words * 4)
+    }
+    /// The transmitted data in bytes, which is port_xmit_data multiplied by 4.
+    pub fn xmit_data_bytes(&self) -> Option<u64> {
+        self.counter("port_xmit_data").map(|words| words * 4)
+    }
+    /// True if the logical state is ACTIVE.
+    pub fn is_active(&self) -> bool {
+        self.state.ends_with("ACTIVE")
+    }
+}
+
+impl SysInfiniband {
+    pub fn new() -> SysInfiniband {
+        SysInfiniband::default()
+    }
+    /// Read all files in a counters directory, an absent directory results in no counters.
+    fn read_counters(counters_path: &Path) -> BTreeMap<String, u64> {
+        let Ok(counter_files) = read_dir(counters_path) else { return BTreeMap::new() };
+
+        counter_files.filter_map(|entry| entry.ok())
+            .filter_map(|counter| {
+                let value = read_file_option_string(&counter.path())?.trim().parse::<u64>().ok()?;
+                Some((counter.file_name().to_string_lossy().to_string(), value))
+            })
+            .collect()
+    }
+    fn read_ib_port(port_path: &Path, port_nr: u32) -> Result<IbPort, ProcSysParserError> {
+        Ok(IbPort {
+            port_nr,
+            state: read_file_string(&port_path.join("state"))?,
+            phys_state: read_file_string(&port_path.join("phys_state"))?,
+            rate: read_file_string(&port_path.join("rate"))?,
+            link_layer: read_file_option_string(&port_path.join("link_layer")),
+            counters: SysInfiniband::read_counters(&port_path.join("counters")),
+            hw_counters: SysInfiniband::read_counters(&port_path.join("hw_counters")),
+        })
+    }
+    fn read_ib_device(device_path: &Path, name: &str) -> Result<IbDevice, ProcSysParserError> {
+        let mut ibdevice = IbDevice {
+            name: name.to_string(),
+            fw_ver: read_file_option_string(&device_path.join("fw_ver")),
+            node_guid: read_file_option_string(&device_path.join("node_guid")),
+            ports: Vec::new(),
+        };
+        let ports_path = device_path.join("ports");
+        for port_nr in read_numbered_entries(&ports_path, "")? {
+            ibdevice.ports.push(SysInfiniband::read_ib_port(&ports_path.join(port_nr.to_string()), port_nr)?);
+        }
+        Ok(ibdevice)
+    }
+    pub fn read_sys_infiniband(sys_class_infiniband_path: &str) -> Result<SysInfiniband, ProcSysParserError> {
+        let mut sysinfiniband = SysInfiniband::new();
+
+        let device_directories = read_dir(sys_class_infiniband_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_infiniband_path.to_string(), error })?;
+
+        for device in device_directories.filter_map(|entry| entry.ok()) {
+            sysinfiniband.devices.push(SysInfiniband::read_ib_device(&device.path(), &device.file_name().to_string_lossy())?);
+        }
+        sysinfiniband.devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(sysinfiniband)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_infiniband_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let device_path = format!("{}/class/infiniband/mlx5_0", test_path);
+        create_mock_files(&device_path, &[("fw_ver", "16.35.2000")]);
+        create_mock_files(&format!("{}/ports/1", device_path), &[
+            ("state", "4: ACTIVE"),
+            ("phys_state", "5: LinkUp"),
+            ("rate", "100 Gb/sec (4X EDR)"),
+            ("link_layer", "InfiniBand"),
+        ]);
+        create_mock_files(&format!("{}/ports/1/counters", device_path), &[
+            ("port_rcv_data", "1000"),
+            ("port_xmit_data", "2000"),
+            ("symbol_error", "3"),
+            ("link_downed", "1"),
+            ("unsupported_counter", "N/A"),
+        ]);
+        create_mock_files(&format!("{}/ports/1/hw_counters", device_path), &[("out_of_buffer", "12")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.devices.len(), 1);
+        assert_eq!(result.devices[0].fw_ver, Some("16.35.2000".to_string()));
+        assert_eq!(result.devices[0].node_guid, None);
+        let port = &result.devices[0].ports[0];
+        assert_eq!(port.port_nr, 1);
+        assert!(port.is_active());
+        assert_eq!(port.link_layer, Some("InfiniBand".to_string()));
+        assert_eq!(port.counters.len(), 4);
+        assert_eq!(port.counter("symbol_error"), Some(3));
+        assert_eq!(port.counter("unsupported_counter"), None);
+        assert_eq!(port.rcv_data_bytes(), Some(4000));
+        assert_eq!(port.xmit_data_bytes(), Some(8000));
+        assert_eq!(port.hw_counters.get("out_of_buffer"), Some(&12));
+    }
+}
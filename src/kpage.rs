@@ -0,0 +1,249 @@
+/*!
+Read the page frame statistics from `/proc/kpagecount` and `/proc/kpageflags` into the struct [`ProcKPage`].
+
+`/proc/kpagecount` and `/proc/kpageflags` are binary files with a 64 bit number for every page frame (PFN) of
+physical memory, at offset PFN * 8:
+- `kpagecount`: the number of times the page is mapped, which shows the sharing of a page between processes.
+- `kpageflags`: the flags of the page, such as whether the page is anonymous, on the LRU, dirty, part of a
+  transparent huge page or merged by KSM. [`KPage::flag_names`] decodes the flags.
+
+The files are as large as physical memory divided by the page size times 8, so only a range of page frames is read,
+which by default are the first 1024 page frames. A large range can be sampled by only reading every `step`th page
+frame. Page frames beyond the end of physical memory are not read, and holes in physical memory show the NOPAGE flag.
+
+Reading the files requires CAP_SYS_ADMIN, and the kernel must be built with CONFIG_PROC_PAGE_MONITOR.
+The numbers are in the byte order of the host.
+
+Documentation: <https://docs.kernel.org/admin-guide/mm/pagemap.html>
+
+Here is an example obtaining the data of the first 1024 page frames:
+```no_run
+use proc_sys_parser::kpage;
+
+let proc_kpage = kpage::read();
+
+println!("{:#?}", proc_kpage);
+```
+Example output:
+```text
+ProcKPage {
+    pages: [
+        KPage { pfn: 0, count: 0, flags: 1024 },
+        KPage { pfn: 1, count: 1, flags: 2097260 },
+        ..
+    ],
+}
+```
+(edited for readability)
+
+Here is an example sampling every 16th page frame of the first 4GB of physical memory with 4k pages, and counting the
+pages that are shared between processes:
+```no_run
+use proc_sys_parser::kpage::Builder;
+
+let proc_kpage = Builder::new().range(0, 1048576).step(16).read().unwrap();
+
+println!("shared: {} of {} sampled pages", proc_kpage.shared_pages(), proc_kpage.pages.len());
+for page in proc_kpage.pages.iter().filter(|page| page.is_ksm()).take(10) {
+    println!("{} {} {:?}", page.pfn, page.count, page.flag_names());
+}
+```
+
+If you want to change the path that is read for [`ProcKPage`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::kpage::Builder;
+
+let proc_kpage = Builder::new().path("/myproc").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::read_file_u64_entries;
+
+/// The names of the bits of `/proc/kpageflags`, by bit number.
+pub const KPAGEFLAGS: [&str; 27] = [
+    "LOCKED", "ERROR", "REFERENCED", "UPTODATE", "DIRTY", "LRU", "ACTIVE", "SLAB", "WRITEBACK", "RECLAIM", "BUDDY",
+    "MMAP", "ANON", "SWAPCACHE", "SWAPBACKED", "COMPOUND_HEAD", "COMPOUND_TAIL", "HUGE", "UNEVICTABLE", "HWPOISON",
+    "NOPAGE", "KSM", "THP", "OFFLINE", "ZERO_PAGE", "IDLE", "PGTABLE",
+];
+
+/// Struct for holding the statistics of a range of page frames
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcKPage {
+    pub pages: Vec<KPage>,
+}
+
+/// Struct for holding the statistics of a single page frame
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct KPage {
+    /// The page frame number.
+    pub pfn: u64,
+    /// The number of times the page is mapped.
+    pub count: u64,
+    /// The flags of the page, see [`KPAGEFLAGS`].
+    pub flags: u64,
+}
+
+/// Builder pattern for [`ProcKPage`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub start_pfn : u64,
+    pub pages : u64,
+    pub step : u64,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            start_pfn: 0,
+            pages: 1024,
+            step: 1,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    /// Read `pages` page frames starting at page frame `start_pfn`.
+    pub fn range(mut self, start_pfn: u64, pages: u64) -> Builder {
+        self.start_pfn = start_pfn;
+        self.pages = pages;
+        self
+    }
+    /// Only read every `step`th page frame of the range. A step of 0 counts as 1.
+    pub fn step(mut self, step: u64) -> Builder {
+        self.step = step.max(1);
+        self
+    }
+    pub fn read(self) -> Result<ProcKPage, ProcSysParserError> {
+        ProcKPage::read_proc_kpage(&self.proc_path, self.start_pfn, self.pages, self.step)
+    }
+}
+
+/// The main function for building a [`ProcKPage`] struct with current data of the first 1024 page frames.
+/// This uses the Builder pattern, which allows settings such as the range to specified.
+pub fn read() -> Result<ProcKPage, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl KPage {
+    /// True if bit `bit` of the flags is set, such as 12 for ANON.
+    pub fn is_set(&self, bit: usize) -> bool {
+        bit < 64 && self.flags & (1 << bit) != 0
+    }
+    /// The names of the flags that are set. Bits without a name are shown as their bit number.
+    pub fn flag_names(&self) -> Vec<String> {
+        (0..64)
+            .filter(|bit| self.is_set(*bit))
+            .map(|bit| KPAGEFLAGS.get(bit).map(|name| name.to_string()).unwrap_or_else(|| bit.to_string()))
+            .collect()
+    }
+    pub fn is_anon(&self) -> bool {
+        self.is_set(12)
+    }
+    /// The page is free.
+    pub fn is_buddy(&self) -> bool {
+        self.is_set(10)
+    }
+    /// The page is merged by KSM.
+    pub fn is_ksm(&self) -> bool {
+        self.is_set(21)
+    }
+    /// The page is part of a transparent huge page.
+    pub fn is_thp(&self) -> bool {
+        self.is_set(22)
+    }
+    /// There is no page frame at this address.
+    pub fn is_nopage(&self) -> bool {
+        self.is_set(20)
+    }
+}
+
+impl ProcKPage {
+    pub fn new() -> ProcKPage {
+        ProcKPage::default()
+    }
+    /// The number of pages that are mapped at least once.
+    pub fn mapped_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.count > 0).count()
+    }
+    /// The number of pages that are mapped more than once, which are shared.
+    pub fn shared_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.count > 1).count()
+    }
+    /// Read the 64 bit numbers of the page frames of a range from a kpage file. Stops at the end of the file.
+    pub fn read_kpage_file(kpage_file: &str, start_pfn: u64, pages: u64, step: u64) -> Result<Vec<u64>, ProcSysParserError> {
+        read_file_u64_entries(Path::new(kpage_file), start_pfn, pages, step)
+    }
+    pub fn read_proc_kpage(proc_path: &str, start_pfn: u64, pages: u64, step: u64) -> Result<ProcKPage, ProcSysParserError> {
+        let counts = ProcKPage::read_kpage_file(&format!("{}/kpagecount", proc_path), start_pfn, pages, step)?;
+        let flags = ProcKPage::read_kpage_file(&format!("{}/kpageflags", proc_path), start_pfn, pages, step)?;
+
+        Ok(ProcKPage {
+            pages: counts.into_iter()
+                .zip(flags)
+                .enumerate()
+                .map(|(index, (count, flags))| KPage { pfn: start_pfn + index as u64 * step.max(1), count, flags })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn decode_kpage_flags() {
+        // REFERENCED, UPTODATE, LRU, ACTIVE, MMAP, ANON, SWAPBACKED and THP.
+        let page = KPage { pfn: 1, count: 2, flags: (1 << 2) | (1 << 3) | (1 << 5) | (1 << 6) | (1 << 11) | (1 << 12) | (1 << 14) | (1 << 22) | (1 << 40) };
+        assert_eq!(page.flag_names(), vec!["REFERENCED", "UPTODATE", "LRU", "ACTIVE", "MMAP", "ANON", "SWAPBACKED", "THP", "40"]);
+        assert!(page.is_anon());
+        assert!(page.is_thp());
+        assert!(!page.is_ksm());
+        assert!(!page.is_set(64));
+    }
+
+    #[test]
+    fn create_proc_kpage_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(&test_path).expect("Error creating mock directory.");
+
+        // 100 page frames: the count is the pfn modulo 3, the flags are BUDDY for the page frames with count 0.
+        let counts: Vec<u8> = (0..100_u64).flat_map(|pfn| (pfn % 3).to_ne_bytes()).collect();
+        let flags: Vec<u8> = (0..100_u64).flat_map(|pfn| if pfn % 3 == 0 { 1_u64 << 10 } else { 1_u64 << 12 }.to_ne_bytes()).collect();
+        write(format!("{}/kpagecount", test_path), counts).unwrap_or_else(|_| panic!("Error writing to {}/kpagecount", test_path));
+        write(format!("{}/kpageflags", test_path), flags).unwrap_or_else(|_| panic!("Error writing to {}/kpageflags", test_path));
+
+        let result = Builder::new().path(&test_path).range(10, 6).read().unwrap();
+        let sampled = Builder::new().path(&test_path).range(0, 1000).step(10).read().unwrap();
+        let beyond = Builder::new().path(&test_path).range(200, 10).read().unwrap();
+        let overflow = Builder::new().path(&test_path).range(u64::MAX - 5, 10).step(2).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.pages, vec![
+            KPage { pfn: 10, count: 1, flags: 4096 },
+            KPage { pfn: 11, count: 2, flags: 4096 },
+            KPage { pfn: 12, count: 0, flags: 1024 },
+            KPage { pfn: 13, count: 1, flags: 4096 },
+            KPage { pfn: 14, count: 2, flags: 4096 },
+            KPage { pfn: 15, count: 0, flags: 1024 },
+        ]);
+        assert_eq!(result.mapped_pages(), 4);
+        assert_eq!(result.shared_pages(), 2);
+        assert!(result.pages[2].is_buddy());
+        // the file ends at page frame 100.
+        assert_eq!(sampled.pages.iter().map(|page| page.pfn).collect::<Vec<_>>(), vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(sampled.pages[1].count, 1);
+        assert_eq!(beyond.pages, vec![]);
+        assert_eq!(overflow.pages, vec![]);
+    }
+}
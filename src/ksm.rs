@@ -0,0 +1,197 @@
+/*!
+Read the kernel samepage merging (KSM) settings and statistics from `/sys/kernel/mm/ksm` into the struct [`SysKsm`].
+
+KSM scans memory regions that are marked as mergeable (with madvise MADV_MERGEABLE, which for example qemu/KVM does
+for guest memory) and merges identical pages into a single write-protected page. The most important statistics are:
+- `pages_shared`: the number of merged pages that are in use.
+- `pages_sharing`: the number of pages that are mapped to the merged pages, which is the number of pages saved.
+- `pages_unshared`: the number of pages that are unique, but are repeatedly checked for merging.
+- `pages_volatile`: the number of pages that are changing too fast to be merged.
+- `general_profit`: the number of bytes saved by KSM minus the memory used by KSM itself (linux 6.1+), which can
+  be negative.
+
+The statistics added in later linux versions are Option.
+
+Documentation: <https://www.kernel.org/doc/Documentation/admin-guide/mm/ksm.rst>
+
+Here is an example obtaining the data from `/sys/kernel/mm/ksm`:
+```no_run
+use proc_sys_parser::ksm;
+
+let sys_ksm = ksm::read();
+
+println!("{:#?}", sys_ksm);
+```
+Example output:
+```text
+SysKsm {
+    run: 1,
+    pages_to_scan: 100,
+    sleep_millisecs: 20,
+    merge_across_nodes: Some(1),
+    max_page_sharing: Some(256),
+    use_zero_pages: Some(0),
+    full_scans: 1437,
+    pages_shared: 110822,
+    pages_sharing: 1203394,
+    pages_unshared: 2201340,
+    pages_volatile: 31022,
+    pages_scanned: Some(1129344812),
+    pages_skipped: Some(0),
+    stable_node_chains: Some(12),
+    stable_node_dups: Some(1094),
+    general_profit: Some(4694318360),
+    ksm_zero_pages: Some(0),
+}
+```
+
+If you want to change the path that is read for [`SysKsm`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::ksm::Builder;
+
+let sys_ksm = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_i64, read_file_option_u64, read_file_u64};
+
+/// Struct for holding the KSM settings and statistics
+#[derive(Debug, PartialEq, Default)]
+pub struct SysKsm {
+    /// 0: stopped, 1: running, 2: stopped and all merged pages unmerged.
+    pub run: u64,
+    pub pages_to_scan: u64,
+    pub sleep_millisecs: u64,
+    pub merge_across_nodes: Option<u64>,
+    pub max_page_sharing: Option<u64>,
+    pub use_zero_pages: Option<u64>,
+    pub full_scans: u64,
+    pub pages_shared: u64,
+    pub pages_sharing: u64,
+    pub pages_unshared: u64,
+    pub pages_volatile: u64,
+    pub pages_scanned: Option<u64>,
+    pub pages_skipped: Option<u64>,
+    pub stable_node_chains: Option<u64>,
+    pub stable_node_dups: Option<u64>,
+    /// The bytes saved minus the memory used by KSM itself.
+    pub general_profit: Option<i64>,
+    /// The number of empty pages merged with the zero page when use_zero_pages is set.
+    pub ksm_zero_pages: Option<u64>,
+}
+
+/// Builder pattern for [`SysKsm`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysKsm, ProcSysParserError> {
+        SysKsm::read_sys_ksm(format!("{}/kernel/mm/ksm", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysKsm`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysKsm, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysKsm {
+    pub fn new() -> SysKsm {
+        SysKsm::default()
+    }
+    pub fn is_running(&self) -> bool {
+        self.run == 1
+    }
+    /// The number of pages mapping a merged page per merged page; a high ratio means KSM is effective.
+    pub fn sharing_ratio(&self) -> Option<f64> {
+        if self.pages_shared == 0 { return None };
+        Some(self.pages_sharing as f64 / self.pages_shared as f64)
+    }
+    pub fn read_sys_ksm(sys_ksm_path: &str) -> Result<SysKsm, ProcSysParserError> {
+        let path = Path::new(sys_ksm_path);
+
+        Ok(SysKsm {
+            run: read_file_u64(&path.join("run"))?,
+            pages_to_scan: read_file_u64(&path.join("pages_to_scan"))?,
+            sleep_millisecs: read_file_u64(&path.join("sleep_millisecs"))?,
+            merge_across_nodes: read_file_option_u64(&path.join("merge_across_nodes"))?,
+            max_page_sharing: read_file_option_u64(&path.join("max_page_sharing"))?,
+            use_zero_pages: read_file_option_u64(&path.join("use_zero_pages"))?,
+            full_scans: read_file_u64(&path.join("full_scans"))?,
+            pages_shared: read_file_u64(&path.join("pages_shared"))?,
+            pages_sharing: read_file_u64(&path.join("pages_sharing"))?,
+            pages_unshared: read_file_u64(&path.join("pages_unshared"))?,
+            pages_volatile: read_file_u64(&path.join("pages_volatile"))?,
+            pages_scanned: read_file_option_u64(&path.join("pages_scanned"))?,
+            pages_skipped: read_file_option_u64(&path.join("pages_skipped"))?,
+            stable_node_chains: read_file_option_u64(&path.join("stable_node_chains"))?,
+            stable_node_dups: read_file_option_u64(&path.join("stable_node_dups"))?,
+            general_profit: read_file_option_i64(&path.join("general_profit"))?,
+            ksm_zero_pages: read_file_option_u64(&path.join("ksm_zero_pages"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_ksm_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let ksm_path = format!("{}/kernel/mm/ksm", test_path);
+        create_dir_all(&ksm_path).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("run", "1"),
+            ("pages_to_scan", "100"),
+            ("sleep_millisecs", "20"),
+            ("merge_across_nodes", "1"),
+            ("full_scans", "1437"),
+            ("pages_shared", "1000"),
+            ("pages_sharing", "5000"),
+            ("pages_unshared", "200"),
+            ("pages_volatile", "30"),
+            ("general_profit", "-4096"),
+        ] {
+            write(format!("{}/{}", ksm_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", ksm_path, file));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysKsm {
+            run: 1,
+            pages_to_scan: 100,
+            sleep_millisecs: 20,
+            merge_across_nodes: Some(1),
+            full_scans: 1437,
+            pages_shared: 1000,
+            pages_sharing: 5000,
+            pages_unshared: 200,
+            pages_volatile: 30,
+            general_profit: Some(-4096),
+            ..Default::default()
+        });
+        assert!(result.is_running());
+        assert_eq!(result.sharing_ratio(), Some(5_f64));
+    }
+}
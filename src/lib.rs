@@ -25,13 +25,13 @@ println!("{:#?}", proc_stat);
 Example output:
 ```text
 ProcStat {
-    cpu_total: CpuStat { name: "cpu", user: 8570, nice: 0, system: 7530, idle: 1710040, iowait: 2780, irq: 0, softirq: 150, steal: 0, guest: 0, guest_nice: 0 },
-    cpu_individual: [CpuStat { name: "cpu0", user: 1800, nice: 0, system: 1450, idle: 283400, iowait: 460, irq: 0, softirq: 120, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu1", user: 1720, nice: 0, system: 1320, idle: 284780, iowait: 580, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu2", user: 1060, nice: 0, system: 1220, idle: 285410, iowait: 510, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu3", user: 890, nice: 0, system: 990, idle: 286130, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu4", user: 1400, nice: 0, system: 1280, idle: 285260, iowait: 310, irq: 0, softirq: 30, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu5", user: 1680, nice: 0, system: 1250, idle: 285020, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 }],
+    cpu_total: CpuStat { name: "cpu", cpu_id: None, user: 8570, nice: 0, system: 7530, idle: 1710040, iowait: 2780, irq: 0, softirq: 150, steal: 0, guest: 0, guest_nice: 0 },
+    cpu_individual: [CpuStat { name: "cpu0", cpu_id: Some(0), user: 1800, nice: 0, system: 1450, idle: 283400, iowait: 460, irq: 0, softirq: 120, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu1", cpu_id: Some(1), user: 1720, nice: 0, system: 1320, idle: 284780, iowait: 580, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu2", cpu_id: Some(2), user: 1060, nice: 0, system: 1220, idle: 285410, iowait: 510, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu3", cpu_id: Some(3), user: 890, nice: 0, system: 990, idle: 286130, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu4", cpu_id: Some(4), user: 1400, nice: 0, system: 1280, idle: 285260, iowait: 310, irq: 0, softirq: 30, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu5", cpu_id: Some(5), user: 1680, nice: 0, system: 1250, idle: 285020, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 }],
     interrupts: [184655, 0, 4500, 60546, 0, 0, 0, 2, 0, 0, 0, 70138, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 548, 0, 0, 0, 0, 0, 2, 0, 3410, 2927, 4739, 5542, 1595, 1913, 0, 0, 0, 79, 154, 208, 282, 43, 52, 0, 14842, 11679, 0, 0, 17, 0, 0, 0, 0, 0, 0, 0, 1437, 0, 0, 0, 0, 0, 0],
     context_switches: 275716,
     boot_time: 1702127060,
@@ -62,18 +62,18 @@ Example output:
 ProcSchedStat {
     version: 15,
     timestamp: 4294964691,
-    cpu: [[0, 0, 0, 0, 0, 0, 0, 40178371330, 4778820750, 26299],
-          [1, 0, 0, 0, 0, 0, 0, 35526916030, 3606934630, 20919],
-          [2, 0, 0, 0, 0, 0, 0, 29224692150, 5614007710, 28163],
-          [3, 0, 0, 0, 0, 0, 0, 23848255950, 2265375620, 26240],
-          [4, 0, 0, 0, 0, 0, 0, 33846671420, 2990792870, 25605],
-          [5, 0, 0, 0, 0, 0, 0, 34565043670, 2885580430, 22629]],
-    domain: [[0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]],
+    cpu: [CpuSchedStat { cpu_nr: 0, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 40178371330, wait_time_ns: 4778820750, timeslices: 26299 },
+          CpuSchedStat { cpu_nr: 1, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 35526916030, wait_time_ns: 3606934630, timeslices: 20919 },
+          CpuSchedStat { cpu_nr: 2, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 29224692150, wait_time_ns: 5614007710, timeslices: 28163 },
+          CpuSchedStat { cpu_nr: 3, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 23848255950, wait_time_ns: 2265375620, timeslices: 26240 },
+          CpuSchedStat { cpu_nr: 4, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 33846671420, wait_time_ns: 2990792870, timeslices: 25605 },
+          CpuSchedStat { cpu_nr: 5, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 34565043670, wait_time_ns: 2885580430, timeslices: 22629 }],
+    domain: [Domain { cpu_nr: 0, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 1, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 2, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 3, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 4, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 5, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. }],
 }
 ```
 (edited for readability)
@@ -162,6 +162,15 @@ ProcMemInfo {
     hugepages_surp: 0,
     hugepagesize: 2048,
     hugetlb: 0,
+    directmap4k: None,
+    directmap2m: None,
+    directmap4m: None,
+    directmap1g: None,
+    unaccepted: None,
+    earlymemtestbad: None,
+    quicklists: None,
+    mmapcopy: None,
+    other: {},
 }
 ```
 (edited for readability)
@@ -217,8 +226,8 @@ Example output:
 ```text
 ProcNetDev {
     interface: [
-        InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 },
-        InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 }
+        InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None },
+        InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
     ]
 }
 ```
@@ -349,6 +358,14 @@ pub enum ProcSysParserError {
     /// into an integer.
     #[error("Error during parsing string to integer")]
     ParseToIntegerError(#[from] ParseIntError),
+    /// This error means a named field that is expected to be an integer cannot be parsed into an integer.
+    /// The item contains the field and the line it is found in.
+    #[error("Error {error} during parsing {item} to integer.")]
+    ParseItemToIntegerError { item: String, error: ParseIntError },
+    /// This error means a named field that is expected to be a float cannot be parsed into a float.
+    /// The item contains the field and the line it is found in.
+    #[error("Error {error} during parsing {item} to float.")]
+    ParseItemToFloatError { item: String, error: ParseFloatError },
     /// This error means the file to be read cannot be found or is unreadable.
     #[error("Error {error} during reading file {file}.")]
     FileReadError { file: String, error: std::io::Error },
@@ -372,13 +389,45 @@ impl From<ParseFloatError> for ProcSysParserError {
 }
 */
 
+pub mod bcache;
+pub mod bdi;
 pub mod block;
+pub mod bonding;
+pub mod bridge;
+pub mod cgroup;
+pub mod clocksource;
+pub mod cpu;
 pub mod diskstats;
+pub mod dmi;
+pub mod edac;
+pub mod fc_host;
 pub mod fs_xfs_stat;
+pub mod hugepages;
+pub mod hwmon;
+pub mod infiniband;
+pub mod kpage;
+pub mod ksm;
 pub mod loadavg;
 pub mod meminfo;
+pub mod memory;
+pub mod memory_hotplug;
+pub mod net_conntrack;
 pub mod net_dev;
+pub mod net_dev_snmp6;
+pub mod net_if_inet6;
+pub mod net_psched;
+pub mod net_socket;
+pub mod numa;
+pub mod nvme;
+pub mod power_supply;
 pub mod pressure;
+pub mod process;
 pub mod schedstat;
+pub mod scsi;
 pub mod stat;
+pub mod sys_net;
+pub mod thermal;
+pub mod transparent_hugepage;
 pub mod vmstat;
+pub mod zswap;
+mod utils;
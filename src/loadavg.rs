@@ -31,14 +31,28 @@ ProcLoadavg {
 If you want to change the path and/or file that is read for [`ProcLoadavg`], which is `/proc/loadavg`
 by default, use:
 ```no_run
-use proc_sys_parser::{meminfo, meminfo::Builder};
+use proc_sys_parser::loadavg::Builder;
 
 let proc_loadavg = Builder::new().path("/myproc").read();
+let proc_loadavg = Builder::new().path("/myproc").file("myloadavg").read();
+```
+
+The load is the number of tasks that are running, waiting to run or waiting in uninterruptible sleep, which is mostly
+waiting for IO. A load of 4 means the cpus are fully used on a host with 4 cpus, but means there are tasks waiting on a
+host with 1 cpu, so the load only makes sense when divided by the number of cpus. [`ProcLoadavg::normalized`] divides
+the load by the number of online cpus from [`crate::cpu::masks::SysCpuMasks`]:
+```no_run
+use proc_sys_parser::{loadavg, cpu::masks};
+
+let normalized_loadavg = loadavg::read().unwrap().normalized(&masks::read().unwrap());
+
+println!("load per cpu over 1 minute: {:.2}", normalized_loadavg.load_1);
 ```
 
 */
 use std::fs::read_to_string;
 use crate::ProcSysParserError;
+use crate::cpu::masks::SysCpuMasks;
 
 /// Struct for holding `/proc/loadavg` statistics
 #[derive(Debug, PartialEq, Default)]
@@ -51,6 +65,15 @@ pub struct ProcLoadavg {
     pub last_pid: u64,
 }
 
+/// Struct for holding the load averages divided by the number of online cpus
+#[derive(Debug, PartialEq, Default)]
+pub struct NormalizedLoadavg {
+    pub online_cpus: usize,
+    pub load_1: f64,
+    pub load_5: f64,
+    pub load_15: f64,
+}
+
 /// Builder pattern for [`ProcLoadavg`]
 #[derive(Default)]
 pub struct Builder {
@@ -74,15 +97,13 @@ impl Builder {
         self.proc_file = proc_file.to_string();
         self
     }
-    //pub fn read(self) -> Result<ProcLoadavg, ProcSysParserError> {
     pub fn read(self) -> Result<ProcLoadavg, ProcSysParserError> {
         ProcLoadavg::read_proc_loadavg(format!("{}/{}", &self.proc_path, &self.proc_file).as_str())
     }
 }
 
-/// The main function for building a [`ProcMemInfo`] struct with current data.
+/// The main function for building a [`ProcLoadavg`] struct with current data.
 /// This uses the Builder pattern, which allows settings such as the filename to specified.
-//pub fn read() -> Result<()> {
 pub fn read() -> Result<ProcLoadavg, ProcSysParserError> {
    Builder::new().read()
 }
@@ -91,37 +112,45 @@ impl ProcLoadavg {
     pub fn new() -> ProcLoadavg {
         ProcLoadavg::default()
     }
-    pub fn parse_proc_loadavg( proc_loadavg: &str,) -> Result<ProcLoadavg, ProcSysParserError>
-    {
-        let mut fields = proc_loadavg.split_whitespace();
-        let mut fields_copy = fields.clone();
+    /// The load averages divided by the number of online cpus.
+    pub fn normalized(&self, sys_cpu_masks: &SysCpuMasks) -> NormalizedLoadavg {
+        self.normalized_by(sys_cpu_masks.online.len())
+    }
+    /// The load averages divided by a number of cpus, such as the cpus a cgroup can use. A number of 0 counts as 1.
+    pub fn normalized_by(&self, online_cpus: usize) -> NormalizedLoadavg {
+        let cpus = online_cpus.max(1) as f64;
+        NormalizedLoadavg {
+            online_cpus,
+            load_1: self.load_1 / cpus,
+            load_5: self.load_5 / cpus,
+            load_15: self.load_15 / cpus,
+        }
+    }
+    pub fn parse_proc_loadavg(proc_loadavg: &str) -> Result<ProcLoadavg, ProcSysParserError> {
+        let line = proc_loadavg.trim();
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        // every error names the field and the line, so a changed or corrupted line can be found.
+        let field = |index: usize, name: &str| -> Result<&str, ProcSysParserError> {
+            fields.get(index).copied()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("loadavg {} in '{}'", name, line) })
+        };
+        let parse_f64 = |value: &str, name: &str| value.parse::<f64>()
+            .map_err(|error| ProcSysParserError::ParseItemToFloatError { item: format!("loadavg {} in '{}'", name, line), error });
+        let parse_u64 = |value: &str, name: &str| value.parse::<u64>()
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("loadavg {} in '{}'", name, line), error });
+        let (current_runnable, total) = field(3, "runnable/total")?.split_once('/')
+            .ok_or(ProcSysParserError::FindItemError { item: format!("loadavg '/' in '{}'", line) })?;
 
         Ok(ProcLoadavg {
-            //load_1: fields.next().ok_or(ProcSysParserError::IteratorItemError)?.parse::<f64>().unwrap(),
-            load_1: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg load_1".to_string() })?
-                .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?,
-            load_5: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg load_5".to_string() })?
-                .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?,
-            load_15: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg load_15". to_string() })?
-                .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?,
-            current_runnable: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg current_runnable".to_string() })?
-                .split('/').next().ok_or(ProcSysParserError::IteratorItemError {item: "loadavg current_runnable split".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            total: fields_copy.nth(3)
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg total".to_string() })?
-                .split('/').nth(1).ok_or(ProcSysParserError::IteratorItemError {item: "loadavg total split".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
-            last_pid: fields.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "loadavg last_pid".to_string() })?
-                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            load_1: parse_f64(field(0, "load_1")?, "load_1")?,
+            load_5: parse_f64(field(1, "load_5")?, "load_5")?,
+            load_15: parse_f64(field(2, "load_15")?, "load_15")?,
+            current_runnable: parse_u64(current_runnable, "current_runnable")?,
+            total: parse_u64(total, "total")?,
+            last_pid: parse_u64(field(4, "last_pid")?, "last_pid")?,
         })
     }
-    pub fn read_proc_loadavg(proc_loadavg: &str) -> Result<ProcLoadavg, ProcSysParserError>
-    {
+    pub fn read_proc_loadavg(proc_loadavg: &str) -> Result<ProcLoadavg, ProcSysParserError> {
         let proc_loadavg_output = read_to_string(proc_loadavg)
             .map_err(|error| ProcSysParserError::FileReadError { file: proc_loadavg.to_string(), error })?;
 
@@ -138,20 +167,20 @@ mod tests {
 
     #[test]
     fn parse_proc_loadavg_line() {
-        let loadavg_line = format!("0.05 0.19 0.13 1/161 7\n");
+        let loadavg_line = "0.05 0.19 0.13 1/161 7\n".to_string();
         let result = ProcLoadavg::parse_proc_loadavg(&loadavg_line).unwrap();
         assert_eq!(result, ProcLoadavg { load_1: 0.05, load_5: 0.19, load_15: 0.13, current_runnable: 1, total: 161, last_pid: 7 });
     }
 
     #[test]
     fn create_proc_loadavg_file_and_read() {
-        let proc_loadavg = format!("0.05 0.19 0.13 1/161 7\n");
+        let proc_loadavg = "0.05 0.19 0.13 1/161 7\n".to_string();
 
         let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
         let test_path = format!("/tmp/test.{}", directory_suffix);
-        create_dir_all(format!("{}", test_path)).expect("Error creating mock directory.");
+        create_dir_all(&test_path).expect("Error creating mock directory.");
 
-        write(format!("{}/loadavg", test_path), proc_loadavg).expect(format!("Error writing to {}/loadavg", test_path).as_str());
+        write(format!("{}/loadavg", test_path), proc_loadavg).unwrap_or_else(|_| panic!("Error writing to {}/loadavg", test_path));
         let result = Builder::new().path(&test_path).read().unwrap();
 
         remove_dir_all(test_path).unwrap();
@@ -162,22 +191,33 @@ mod tests {
    fn read_nonexistent_loadavg_file() -> Result<(), ProcSysParserError> {
         // uncomment to see the error message
         //let _result = Builder::new().path("/xxxxxxxxxxxx").read()?;
-        Ok(assert!(Builder::new().path("/xxxxxxxxxxxx").read().is_err()))
+        assert!(Builder::new().path("/xxxxxxxxxxxx").read().is_err());
+        Ok(())
     }
 
     #[test]
     fn parse_corrupted_loadavg_line_missing_entries() -> Result<(), ProcSysParserError> {
-        let loadavg_line = format!("0.05 0.19\n");
+        let loadavg_line = "0.05 0.19\n".to_string();
         //let result = ProcLoadavg::parse_proc_loadavg(&loadavg_line)?;
         let result = ProcLoadavg::parse_proc_loadavg(&loadavg_line);
-        Ok(assert!(result.is_err()))
+        assert!(result.is_err());
+        Ok(())
     }
     #[test]
     fn parse_corrupted_loadavg_line_wrong_entry() -> Result<(), ProcSysParserError> {
-        let loadavg_line = format!("AAA 0.19 0.13 1/161 7\n");
+        let loadavg_line = "AAA 0.19 0.13 1/161 7\n".to_string();
         //let result = ProcLoadavg::parse_proc_loadavg(&loadavg_line)?;
         let result = ProcLoadavg::parse_proc_loadavg(&loadavg_line);
-        Ok(assert!(result.is_err()))
+        assert!(matches!(result, Err(ProcSysParserError::ParseItemToFloatError { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_loadavg_per_online_cpu() {
+        let proc_loadavg = ProcLoadavg { load_1: 6.0, load_5: 4.0, load_15: 2.0, current_runnable: 1, total: 161, last_pid: 7 };
+        let sys_cpu_masks = SysCpuMasks { online: vec![0, 1, 2, 3], ..Default::default() };
+        assert_eq!(proc_loadavg.normalized(&sys_cpu_masks), NormalizedLoadavg { online_cpus: 4, load_1: 1.5, load_5: 1.0, load_15: 0.5 });
+        assert_eq!(proc_loadavg.normalized_by(0).load_1, 6.0);
     }
 }
 
@@ -69,11 +69,41 @@ ProcMemInfo {
     hugepages_rsvd: 0,
     hugepages_surp: 0,
     hugepagesize: 2048,
-    hugetlb: 0
+    hugetlb: 0,
+    directmap4k: Some(
+        171520,
+    ),
+    directmap2m: Some(
+        4022272,
+    ),
+    directmap4m: None,
+    directmap1g: None,
+    unaccepted: None,
+    earlymemtestbad: None,
+    quicklists: None,
+    mmapcopy: None,
+    other: {},
 }
 ```
 (edited for readability)
 
+The values are kept in kilobytes as they are in `/proc/meminfo`, except for the `HugePages_*` fields, which are a number
+of huge pages of `Hugepagesize`. To prevent mixing up units, the main fields have an accessor returning bytes, and the
+`HugePages_*` accessors return the number of pages multiplied by the huge page size:
+```no_run
+use proc_sys_parser::meminfo;
+
+let proc_meminfo = meminfo::read().unwrap();
+
+println!("memory available: {} bytes", proc_meminfo.memavailable_bytes());
+println!("huge pages free: {} bytes", proc_meminfo.hugepages_free_bytes());
+```
+Any other field can be converted with [`kb_to_bytes`].
+
+Lines that depend on the linux version, the architecture or the kernel configuration are Options, which are None if the
+line is not present. Any line that is not known is stored by its name in the `other` map, so no line is lost. An unknown
+line that does not contain a number is skipped with a warning, so it cannot fail reading all statistics.
+
 If you want to change the path and/or file that is read for [`ProcMemInfo`], which is `/proc/meminfo`
 by default, use:
 ```no_run
@@ -83,9 +113,10 @@ let proc_meminfo = Builder::new().path("/myproc").read();
 ```
 
 */
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
-use crate::ProcSysParserError;
 use log::warn;
+use crate::ProcSysParserError;
 
 /// Struct for holding `/proc/meminfo` statistics
 #[derive(Debug, PartialEq, Default)]
@@ -146,6 +177,25 @@ pub struct ProcMemInfo {
     pub hugetlb: u64,
     pub directmap4k: Option<u64>,
     pub directmap2m: Option<u64>,
+    /// x86 32 bit without PAE
+    pub directmap4m: Option<u64>,
+    /// x86 with 1G pages
+    pub directmap1g: Option<u64>,
+    /// Kernel 6.5+ with CONFIG_UNACCEPTED_MEMORY
+    pub unaccepted: Option<u64>,
+    /// With CONFIG_MEMTEST and the memtest boot parameter
+    pub earlymemtestbad: Option<u64>,
+    /// Kernels before 5.5 with quicklists
+    pub quicklists: Option<u64>,
+    /// Kernels without a MMU
+    pub mmapcopy: Option<u64>,
+    /// All other statistics by name, which differ per linux version and architecture.
+    pub other: BTreeMap<String, u64>,
+}
+
+/// Convert a value from `/proc/meminfo` in kilobytes to bytes.
+pub fn kb_to_bytes(kb: u64) -> u64 {
+    kb * 1024
 }
 
 /// Builder pattern for [`ProcMemInfo`]
@@ -188,6 +238,86 @@ impl ProcMemInfo {
     pub fn new() -> ProcMemInfo {
         ProcMemInfo::default()
     }
+    pub fn memtotal_bytes(&self) -> u64 {
+        kb_to_bytes(self.memtotal)
+    }
+    pub fn memfree_bytes(&self) -> u64 {
+        kb_to_bytes(self.memfree)
+    }
+    pub fn memavailable_bytes(&self) -> u64 {
+        kb_to_bytes(self.memavailable)
+    }
+    pub fn buffers_bytes(&self) -> u64 {
+        kb_to_bytes(self.buffers)
+    }
+    pub fn cached_bytes(&self) -> u64 {
+        kb_to_bytes(self.cached)
+    }
+    pub fn swapcached_bytes(&self) -> u64 {
+        kb_to_bytes(self.swapcached)
+    }
+    pub fn swaptotal_bytes(&self) -> u64 {
+        kb_to_bytes(self.swaptotal)
+    }
+    pub fn swapfree_bytes(&self) -> u64 {
+        kb_to_bytes(self.swapfree)
+    }
+    pub fn dirty_bytes(&self) -> u64 {
+        kb_to_bytes(self.dirty)
+    }
+    pub fn writeback_bytes(&self) -> u64 {
+        kb_to_bytes(self.writeback)
+    }
+    pub fn anonpages_bytes(&self) -> u64 {
+        kb_to_bytes(self.anonpages)
+    }
+    pub fn mapped_bytes(&self) -> u64 {
+        kb_to_bytes(self.mapped)
+    }
+    pub fn shmem_bytes(&self) -> u64 {
+        kb_to_bytes(self.shmem)
+    }
+    pub fn slab_bytes(&self) -> u64 {
+        kb_to_bytes(self.slab)
+    }
+    pub fn sreclaimable_bytes(&self) -> u64 {
+        kb_to_bytes(self.sreclaimable)
+    }
+    pub fn sunreclaim_bytes(&self) -> u64 {
+        kb_to_bytes(self.sunreclaim)
+    }
+    pub fn pagetables_bytes(&self) -> u64 {
+        kb_to_bytes(self.pagetables)
+    }
+    pub fn commitlimit_bytes(&self) -> u64 {
+        kb_to_bytes(self.commitlimit)
+    }
+    pub fn committed_as_bytes(&self) -> u64 {
+        kb_to_bytes(self.committed_as)
+    }
+    pub fn anonhugepages_bytes(&self) -> u64 {
+        kb_to_bytes(self.anonhugepages)
+    }
+    pub fn hugetlb_bytes(&self) -> u64 {
+        kb_to_bytes(self.hugetlb)
+    }
+    /// The size of a default huge page in bytes.
+    pub fn hugepagesize_bytes(&self) -> u64 {
+        kb_to_bytes(self.hugepagesize)
+    }
+    /// The HugePages_* fields are a number of pages of the default huge page size, not kilobytes.
+    pub fn hugepages_total_bytes(&self) -> u64 {
+        self.hugepages_total * self.hugepagesize_bytes()
+    }
+    pub fn hugepages_free_bytes(&self) -> u64 {
+        self.hugepages_free * self.hugepagesize_bytes()
+    }
+    pub fn hugepages_rsvd_bytes(&self) -> u64 {
+        self.hugepages_rsvd * self.hugepagesize_bytes()
+    }
+    pub fn hugepages_surp_bytes(&self) -> u64 {
+        self.hugepages_surp * self.hugepagesize_bytes()
+    }
     pub fn parse_proc_meminfo_output(proc_meminfo: &str) -> Result<ProcMemInfo, ProcSysParserError> {
         let mut procmeminfo = ProcMemInfo::new();
         for line in proc_meminfo.lines() {
@@ -362,7 +492,33 @@ impl ProcMemInfo {
                 line if line.starts_with("DirectMap2M:") => {
                     procmeminfo.directmap2m = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
                 }
-                _ => warn!("meminfo: unknown entry found: {}", line),
+                line if line.starts_with("DirectMap4M:") => {
+                    procmeminfo.directmap4m = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.starts_with("DirectMap1G:") => {
+                    procmeminfo.directmap1g = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.starts_with("Unaccepted:") => {
+                    procmeminfo.unaccepted = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.starts_with("EarlyMemtestBad:") => {
+                    procmeminfo.earlymemtestbad = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.starts_with("Quicklists:") => {
+                    procmeminfo.quicklists = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.starts_with("MmapCopy:") => {
+                    procmeminfo.mmapcopy = Some(ProcMemInfo::parse_proc_meminfo_line(line)?)
+                }
+                line if line.trim().is_empty() => {},
+                line => {
+                    let name = line.split(':').next()
+                        .ok_or(ProcSysParserError::IteratorItemError {item: "meminfo name".to_string() })?;
+                    match ProcMemInfo::parse_proc_meminfo_line(line) {
+                        Ok(value) => { procmeminfo.other.insert(name.to_string(), value); },
+                        Err(_) => warn!("meminfo: unparsable entry found: {}", line),
+                    }
+                },
             }
         }
         Ok(procmeminfo)
@@ -391,10 +547,38 @@ mod tests {
     #[test]
     fn parse_meminfo_line() {
         let meminfo_line = "MemTotal:        3997876 kB";
-        let result = ProcMemInfo::parse_proc_meminfo_line(&meminfo_line).unwrap();
+        let result = ProcMemInfo::parse_proc_meminfo_line(meminfo_line).unwrap();
         assert_eq!(result, 3997876_u64);
     }
 
+    #[test]
+    fn parse_optional_and_unknown_meminfo_lines() {
+        let proc_meminfo = "MemTotal:        3997876 kB
+DirectMap4k:      171520 kB
+DirectMap2M:     4022272 kB
+DirectMap1G:     2097152 kB
+Unaccepted:            0 kB
+EarlyMemtestBad:       0 kB
+KFENCE:              256 kB
+Unparsable:          n/a
+";
+        let result = ProcMemInfo::parse_proc_meminfo_output(proc_meminfo).unwrap();
+        assert_eq!(result.directmap1g, Some(2097152));
+        assert_eq!(result.directmap4m, None);
+        assert_eq!(result.unaccepted, Some(0));
+        assert_eq!(result.earlymemtestbad, Some(0));
+        assert_eq!(result.other.into_iter().collect::<Vec<_>>(), vec![("KFENCE".to_string(), 256)]);
+    }
+
+    #[test]
+    fn meminfo_bytes() {
+        let proc_meminfo = ProcMemInfo { memtotal: 3997876, hugepages_total: 4, hugepages_free: 3, hugepagesize: 2048, ..Default::default() };
+        assert_eq!(proc_meminfo.memtotal_bytes(), 4093825024);
+        assert_eq!(proc_meminfo.hugepagesize_bytes(), 2097152);
+        assert_eq!(proc_meminfo.hugepages_total_bytes(), 8388608);
+        assert_eq!(proc_meminfo.hugepages_free_bytes(), 6291456);
+    }
+
     #[test]
     fn parse_full_proc_meminfo_file() {
         let proc_meminfo = "MemTotal:        3997876 kB
@@ -511,6 +695,7 @@ Hugetlb:               0 kB";
                 hugetlb: 0,
                 directmap4k: None,
                 directmap2m: None,
+                ..Default::default()
             }
         );
     }
@@ -577,10 +762,10 @@ Hugetlb:               0 kB";
             .map(char::from)
             .collect();
         let test_path = format!("/tmp/test.{}", directory_suffix);
-        create_dir_all(format!("{}", test_path)).expect("Error creating mock directory.");
+        create_dir_all(&test_path).expect("Error creating mock directory.");
 
         write(format!("{}/meminfo", test_path), proc_meminfo)
-            .expect(format!("Error writing to {}/meminfo", test_path).as_str());
+            .unwrap_or_else(|_| panic!("Error writing to {}/meminfo", test_path));
         let result = Builder::new().path(&test_path).read().unwrap();
 
         remove_dir_all(test_path).unwrap();
@@ -644,6 +829,7 @@ Hugetlb:               0 kB";
                 hugetlb: 0,
                 directmap4k: None,
                 directmap2m: None,
+                ..Default::default()
             }
         );
     }
@@ -0,0 +1,258 @@
+/*!
+Read `/proc/meminfo` and `/proc/vmstat` into the consolidated struct [`MemorySummary`].
+
+[`crate::meminfo`] and [`crate::vmstat`] show the raw values of the kernel. The tools `free` and `vmstat` combine these
+into a few columns, and [`MemorySummary`] does the same, so the numbers are the ones people know:
+- total, used, free, shared, buffers, cache and available, which are the columns of `free`:
+  - shared is `Shmem`.
+  - cache is `Cached` plus `SReclaimable`, like `free` since procps-ng 3.3.10.
+  - used is total minus available, like `free` since procps-ng 4.0.1.
+  - available is `MemAvailable`, or free plus buffers plus cache for kernels before 3.14.
+- swap total, used and free.
+- slab, dirty and writeback.
+- the swap in and swap out counters (si and so of `vmstat`), and the paged in and paged out counters (bi and bo).
+  These are counters since boot, take the difference between two readings for the activity of an interval,
+  or use [`crate::vmstat::VmStatDelta`].
+
+All values are in kilobytes, except the counters.
+
+`/proc/meminfo` and `/proc/vmstat` show several of the same statistics, in kilobytes and in pages. Because the files
+are read one after the other, and the kernel calculates some values per cpu, the values are never exactly equal.
+If a statistic differs more than 1% of the total memory between the two files, it is added to `inconsistencies`, which
+mostly means memory changed fast between reading the files.
+
+Here is an example obtaining the memory summary:
+```no_run
+use proc_sys_parser::memory;
+
+let memory_summary = memory::read();
+
+println!("{:#?}", memory_summary);
+```
+Example output:
+```text
+MemorySummary {
+    total: 3997876,
+    used: 343780,
+    free: 2415136,
+    shared: 5020,
+    buffers: 37492,
+    cache: 1362248,
+    available: 3654096,
+    swap_total: 2097148,
+    swap_used: 0,
+    swap_free: 2097148,
+    slab: 93916,
+    dirty: 0,
+    writeback: 0,
+    swap_in_pages: 0,
+    swap_out_pages: 0,
+    paged_in_kb: 569048,
+    paged_out_kb: 264157,
+    inconsistencies: [],
+}
+```
+
+If you want to change the path that is read for [`MemorySummary`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::memory::Builder;
+
+let memory_summary = Builder::new().path("/myproc").read();
+```
+*/
+use nix::unistd::{sysconf, SysconfVar};
+use crate::ProcSysParserError;
+use crate::meminfo::ProcMemInfo;
+use crate::vmstat::ProcVmStat;
+
+/// Struct for holding the memory summary of `free` and `vmstat`
+#[derive(Debug, PartialEq, Default)]
+pub struct MemorySummary {
+    pub total: u64,
+    /// total - available
+    pub used: u64,
+    pub free: u64,
+    pub shared: u64,
+    pub buffers: u64,
+    /// Cached + SReclaimable
+    pub cache: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    pub swap_free: u64,
+    pub slab: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    /// counter: the number of pages swapped in (si)
+    pub swap_in_pages: u64,
+    /// counter: the number of pages swapped out (so)
+    pub swap_out_pages: u64,
+    /// counter: the number of kilobytes paged in from disk (bi)
+    pub paged_in_kb: u64,
+    /// counter: the number of kilobytes paged out to disk (bo)
+    pub paged_out_kb: u64,
+    /// The statistics that differ between `/proc/meminfo` and `/proc/vmstat`.
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+/// Struct for holding a statistic that differs between `/proc/meminfo` and `/proc/vmstat`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Inconsistency {
+    /// The name in `/proc/meminfo`, such as "Dirty".
+    pub meminfo_name: String,
+    /// The name in `/proc/vmstat`, such as "nr_dirty".
+    pub vmstat_name: String,
+    pub meminfo_kb: u64,
+    /// The value of `/proc/vmstat` converted from pages to kilobytes.
+    pub vmstat_kb: u64,
+}
+
+/// Builder pattern for [`MemorySummary`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<MemorySummary, ProcSysParserError> {
+        MemorySummary::read_memory_summary(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`MemorySummary`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<MemorySummary, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl MemorySummary {
+    pub fn new() -> MemorySummary {
+        MemorySummary::default()
+    }
+    /// Combine the statistics of `/proc/meminfo` and `/proc/vmstat`, which should be read at the same moment.
+    pub fn summarize(meminfo: &ProcMemInfo, vmstat: &ProcVmStat) -> MemorySummary {
+        let page_size_kb = sysconf(SysconfVar::PAGE_SIZE).unwrap_or(Some(4096)).unwrap_or(4096) as u64 / 1024;
+        MemorySummary::summarize_with_page_size(meminfo, vmstat, page_size_kb)
+    }
+    fn summarize_with_page_size(meminfo: &ProcMemInfo, vmstat: &ProcVmStat, page_size_kb: u64) -> MemorySummary {
+        let cache = meminfo.cached + meminfo.sreclaimable;
+        // MemAvailable was added in linux 3.14; before, free estimated it.
+        let available = if meminfo.memavailable > 0 {
+            meminfo.memavailable
+        } else {
+            meminfo.memfree + meminfo.buffers + cache
+        };
+
+        let tolerance_kb = meminfo.memtotal / 100;
+        let inconsistencies = [
+            ("Dirty", meminfo.dirty, "nr_dirty", vmstat.nr_dirty),
+            ("Writeback", meminfo.writeback, "nr_writeback", vmstat.nr_writeback),
+            ("AnonPages", meminfo.anonpages, "nr_anon_pages", vmstat.nr_anon_pages),
+            ("Mapped", meminfo.mapped, "nr_mapped", vmstat.nr_mapped),
+            ("Shmem", meminfo.shmem, "nr_shmem", vmstat.nr_shmem),
+            ("SReclaimable", meminfo.sreclaimable, "nr_slab_reclaimable", vmstat.nr_slab_reclaimable),
+            ("SUnreclaim", meminfo.sunreclaim, "nr_slab_unreclaimable", vmstat.nr_slab_unreclaimable),
+            ("MemFree", meminfo.memfree, "nr_free_pages", vmstat.nr_free_pages),
+        ]
+            .into_iter()
+            .filter(|(_, meminfo_kb, _, vmstat_pages)| meminfo_kb.abs_diff(vmstat_pages * page_size_kb) > tolerance_kb)
+            .map(|(meminfo_name, meminfo_kb, vmstat_name, vmstat_pages)| Inconsistency {
+                meminfo_name: meminfo_name.to_string(),
+                vmstat_name: vmstat_name.to_string(),
+                meminfo_kb,
+                vmstat_kb: vmstat_pages * page_size_kb,
+            })
+            .collect();
+
+        MemorySummary {
+            total: meminfo.memtotal,
+            used: meminfo.memtotal.saturating_sub(available),
+            free: meminfo.memfree,
+            shared: meminfo.shmem,
+            buffers: meminfo.buffers,
+            cache,
+            available,
+            swap_total: meminfo.swaptotal,
+            swap_used: meminfo.swaptotal.saturating_sub(meminfo.swapfree),
+            swap_free: meminfo.swapfree,
+            slab: meminfo.slab,
+            dirty: meminfo.dirty,
+            writeback: meminfo.writeback,
+            swap_in_pages: vmstat.pswpin,
+            swap_out_pages: vmstat.pswpout,
+            paged_in_kb: vmstat.pgpgin,
+            paged_out_kb: vmstat.pgpgout,
+            inconsistencies,
+        }
+    }
+    /// buffers + cache, which is the buff/cache column of `free`.
+    pub fn buff_cache(&self) -> u64 {
+        self.buffers + self.cache
+    }
+    pub fn read_memory_summary(proc_path: &str) -> Result<MemorySummary, ProcSysParserError> {
+        let meminfo = ProcMemInfo::read_proc_meminfo(&format!("{}/meminfo", proc_path))?;
+        let vmstat = ProcVmStat::read_proc_vmstat(&format!("{}/vmstat", proc_path))?;
+        Ok(MemorySummary::summarize(&meminfo, &vmstat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn summarize_meminfo_and_vmstat() {
+        let meminfo = ProcMemInfo { memtotal: 4000000, memfree: 2000000, memavailable: 3000000, buffers: 40000, cached: 1000000, sreclaimable: 60000, sunreclaim: 30000, slab: 90000, shmem: 5000, swaptotal: 1000000, swapfree: 900000, dirty: 100000, ..Default::default() };
+        let vmstat = ProcVmStat { nr_free_pages: 500000, nr_slab_reclaimable: 15000, nr_slab_unreclaimable: 7500, nr_shmem: 1250, nr_dirty: 5, pswpin: 10, pswpout: 20, ..Default::default() };
+
+        let result = MemorySummary::summarize_with_page_size(&meminfo, &vmstat, 4);
+
+        assert_eq!(result.used, 1000000);
+        assert_eq!(result.cache, 1060000);
+        assert_eq!(result.buff_cache(), 1100000);
+        assert_eq!(result.swap_used, 100000);
+        assert_eq!(result.swap_out_pages, 20);
+        // Dirty is 100000 kB in meminfo, but 20 kB in vmstat, which is more than 1% of memory.
+        assert_eq!(result.inconsistencies, vec![Inconsistency { meminfo_name: "Dirty".to_string(), vmstat_name: "nr_dirty".to_string(), meminfo_kb: 100000, vmstat_kb: 20 }]);
+    }
+
+    #[test]
+    fn summarize_without_memavailable() {
+        let meminfo = ProcMemInfo { memtotal: 4000000, memfree: 2000000, buffers: 40000, cached: 1000000, sreclaimable: 60000, ..Default::default() };
+
+        let result = MemorySummary::summarize_with_page_size(&meminfo, &ProcVmStat::new(), 4);
+
+        assert_eq!(result.available, 3100000);
+        assert_eq!(result.used, 900000);
+    }
+
+    #[test]
+    fn create_proc_meminfo_and_vmstat_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(&test_path).expect("Error creating mock directory.");
+
+        write(format!("{}/meminfo", test_path), "MemTotal:        3997876 kB\nMemFree:         2415136 kB\nMemAvailable:    3654096 kB\nSwapTotal:             0 kB\nSwapFree:              0 kB\n").unwrap_or_else(|_| panic!("Error writing to {}/meminfo", test_path));
+        write(format!("{}/vmstat", test_path), "nr_free_pages 603784\npgpgin 569048\npgpgout 264157\npswpin 0\npswpout 0\n").unwrap_or_else(|_| panic!("Error writing to {}/vmstat", test_path));
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.total, 3997876);
+        assert_eq!(result.used, 343780);
+        assert_eq!(result.paged_in_kb, 569048);
+    }
+}
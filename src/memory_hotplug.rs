@@ -0,0 +1,202 @@
+/*!
+Read the memory blocks from `/sys/devices/system/memory` into the struct [`SysMemoryHotplug`].
+
+With memory hotplug, the memory of a host is divided into blocks of `block_size_bytes` (hexadecimal), which can be set
+online or offline individually, for example when memory is added to or removed from a virtual machine, or when a
+balloon driver unplugs memory. Every block has a directory `memory<nr>`, which shows:
+- `state`: `online`, `offline` or `going-offline`.
+- `removable`: whether the block can be offlined; since linux 5.9 this is always 1 if the kernel supports
+  memory hot-remove.
+- `phys_device`: the physical device the block belongs to, mostly 0.
+- `valid_zones`: the zones the block can be onlined to when offline, or the zone of the block when online.
+- a link `node<nr>` to the NUMA node of the block.
+
+The file `auto_online_blocks` shows how hotplugged blocks are onlined: `offline`, `online`, `online_kernel` or
+`online_movable`.
+
+On a host without memory hotplug support the directory does not exist, which is an error.
+
+Documentation: <https://docs.kernel.org/admin-guide/mm/memory-hotplug.html>
+
+Here is an example obtaining the memory blocks:
+```no_run
+use proc_sys_parser::memory_hotplug;
+
+let sys_memory_hotplug = memory_hotplug::read();
+
+println!("{:#?}", sys_memory_hotplug);
+```
+Example output:
+```text
+SysMemoryHotplug {
+    block_size_bytes: 134217728,
+    auto_online_blocks: Some("online"),
+    blocks: [
+        MemoryBlock {
+            block_nr: 0,
+            state: "online",
+            removable: Some(true),
+            phys_device: Some(0),
+            valid_zones: Some("none"),
+            node_nr: Some(0),
+        },
+        MemoryBlock {
+            block_nr: 1,
+            state: "offline",
+            removable: Some(true),
+            phys_device: Some(0),
+            valid_zones: Some("DMA32 Normal Movable"),
+            node_nr: Some(0),
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysMemoryHotplug`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::memory_hotplug::Builder;
+
+let sys_memory_hotplug = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_hex_u64, read_file_option_string, read_file_option_u64, read_file_string, read_numbered_entries};
+
+/// Struct for holding the memory blocks
+#[derive(Debug, PartialEq, Default)]
+pub struct SysMemoryHotplug {
+    pub block_size_bytes: u64,
+    /// How hotplugged blocks are onlined.
+    pub auto_online_blocks: Option<String>,
+    /// The blocks, sorted by block number.
+    pub blocks: Vec<MemoryBlock>,
+}
+
+/// Struct for holding a single memory block
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct MemoryBlock {
+    pub block_nr: u32,
+    /// `online`, `offline` or `going-offline`
+    pub state: String,
+    pub removable: Option<bool>,
+    pub phys_device: Option<u64>,
+    pub valid_zones: Option<String>,
+    /// None if the kernel is built without NUMA support.
+    pub node_nr: Option<u32>,
+}
+
+/// Builder pattern for [`SysMemoryHotplug`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysMemoryHotplug, ProcSysParserError> {
+        SysMemoryHotplug::read_sys_memory_hotplug(format!("{}/devices/system/memory", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysMemoryHotplug`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysMemoryHotplug, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl MemoryBlock {
+    pub fn is_online(&self) -> bool {
+        self.state == "online"
+    }
+}
+
+impl SysMemoryHotplug {
+    pub fn new() -> SysMemoryHotplug {
+        SysMemoryHotplug::default()
+    }
+    pub fn online_blocks(&self) -> usize {
+        self.blocks.iter().filter(|block| block.is_online()).count()
+    }
+    pub fn offline_blocks(&self) -> usize {
+        self.blocks.len() - self.online_blocks()
+    }
+    /// The size of all blocks in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.blocks.len() as u64 * self.block_size_bytes
+    }
+    /// The size of the online blocks in bytes.
+    pub fn online_bytes(&self) -> u64 {
+        self.online_blocks() as u64 * self.block_size_bytes
+    }
+    /// The size of the blocks that are not online in bytes.
+    pub fn offline_bytes(&self) -> u64 {
+        self.offline_blocks() as u64 * self.block_size_bytes
+    }
+    pub fn read_sys_memory_hotplug(sys_memory_path: &str) -> Result<SysMemoryHotplug, ProcSysParserError> {
+        let sys_memory_path = Path::new(sys_memory_path);
+        let mut sysmemoryhotplug = SysMemoryHotplug {
+            block_size_bytes: read_file_hex_u64(&sys_memory_path.join("block_size_bytes"))?,
+            auto_online_blocks: read_file_option_string(&sys_memory_path.join("auto_online_blocks")),
+            blocks: Vec::new(),
+        };
+
+        for block_nr in read_numbered_entries(sys_memory_path, "memory")? {
+            let block_path = sys_memory_path.join(format!("memory{}", block_nr));
+            sysmemoryhotplug.blocks.push(MemoryBlock {
+                block_nr,
+                state: read_file_string(&block_path.join("state"))?,
+                removable: read_file_option_u64(&block_path.join("removable"))?.map(|removable| removable == 1),
+                phys_device: read_file_option_u64(&block_path.join("phys_device"))?,
+                valid_zones: read_file_option_string(&block_path.join("valid_zones")),
+                node_nr: read_numbered_entries(&block_path, "node")?.first().copied(),
+            });
+        }
+        Ok(sysmemoryhotplug)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_memory_blocks_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let memory_path = format!("{}/devices/system/memory", test_path);
+        for (block, state, valid_zones) in [("memory0", "online", "none"), ("memory1", "offline", "DMA32 Normal Movable"), ("memory8", "online", "Normal")] {
+            create_dir_all(format!("{}/{}/node0", memory_path, block)).expect("Error creating mock directory.");
+            for (file, contents) in [("state", state), ("removable", "1"), ("phys_device", "0"), ("valid_zones", valid_zones)] {
+                write(format!("{}/{}/{}", memory_path, block, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}/{}", memory_path, block, file));
+            }
+        }
+        write(format!("{}/block_size_bytes", memory_path), "8000000\n").expect("Error writing to block_size_bytes");
+        write(format!("{}/auto_online_blocks", memory_path), "online\n").expect("Error writing to auto_online_blocks");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.block_size_bytes, 134217728);
+        assert_eq!(result.auto_online_blocks, Some("online".to_string()));
+        assert_eq!(result.blocks.iter().map(|block| block.block_nr).collect::<Vec<_>>(), vec![0, 1, 8]);
+        assert_eq!(result.blocks[1], MemoryBlock { block_nr: 1, state: "offline".to_string(), removable: Some(true), phys_device: Some(0), valid_zones: Some("DMA32 Normal Movable".to_string()), node_nr: Some(0) });
+        assert_eq!(result.online_blocks(), 2);
+        assert_eq!(result.offline_bytes(), 134217728);
+        assert_eq!(result.total_bytes(), 402653184);
+    }
+}
@@ -0,0 +1,355 @@
+/*!
+Read the connection tracking table from `/proc/net/nf_conntrack` into the struct [`ProcNetConntrack`].
+
+Netfilter connection tracking keeps an entry per connection (or flow, for connectionless protocols), which is used
+by NAT and stateful firewall rules. `/proc/net/nf_conntrack` shows a line per entry:
+```text
+ipv4     2 tcp      6 431999 ESTABLISHED src=10.0.0.2 dst=10.0.0.1 sport=22 dport=54022 packets=10 bytes=1656 src=10.0.0.1 dst=10.0.0.2 sport=54022 dport=22 packets=12 bytes=1044 [ASSURED] mark=0 zone=0 use=2
+```
+- the network layer protocol name and number, and the transport layer protocol name and number.
+- the number of seconds until the entry expires.
+- the state of the connection, only for protocols with a state, such as TCP, SCTP and DCCP.
+- the original direction tuple: source and destination address and, depending on the protocol, the ports
+  (`sport`, `dport`) or the ICMP `type`, `code` and `id`.
+- the packets and bytes of the direction, only when accounting is enabled with `net.netfilter.nf_conntrack_acct`.
+- `[UNREPLIED]` if no packet was seen in the reply direction, then the reply direction tuple.
+- `[ASSURED]` if the entry will not be dropped when the table is full, and `[OFFLOAD]` or `[HW_OFFLOAD]` if the
+  flow is offloaded.
+- the mark, the security context, the zone, the timestamp (`delta-time`, with `net.netfilter.nf_conntrack_timestamp`),
+  the labels and the reference count (`use`). The ones that are not a field of [`ConntrackEntry`] are kept in `other`.
+
+The file requires CONFIG_NF_CONNTRACK_PROCFS, and only shows the entries to root.
+
+Documentation: <https://docs.kernel.org/networking/nf_conntrack-sysctl.html>
+
+Here is an example obtaining the connection tracking table:
+```no_run
+use proc_sys_parser::net_conntrack;
+
+let proc_net_conntrack = net_conntrack::read();
+
+println!("{:#?}", proc_net_conntrack);
+```
+Example output:
+```text
+ProcNetConntrack {
+    entries: [
+        ConntrackEntry {
+            l3_protocol: "ipv4",
+            l3_protocol_nr: 2,
+            protocol: "tcp",
+            protocol_nr: 6,
+            timeout: 431999,
+            state: Some("ESTABLISHED"),
+            original: ConntrackTuple { src: 10.0.0.2, dst: 10.0.0.1, sport: Some(22), dport: Some(54022), icmp_type: None, icmp_code: None, icmp_id: None, packets: Some(10), bytes: Some(1656) },
+            reply: ConntrackTuple { src: 10.0.0.1, dst: 10.0.0.2, sport: Some(54022), dport: Some(22), icmp_type: None, icmp_code: None, icmp_id: None, packets: Some(12), bytes: Some(1044) },
+            flags: ["ASSURED"],
+            mark: Some(0),
+            zone: Some(0),
+            use_count: Some(2),
+            other: {},
+        },
+    ],
+}
+```
+
+The table can contain millions of entries. To process the entries one by one without holding the whole table in
+memory, use the iterator, which reads the file line by line:
+```no_run
+use proc_sys_parser::net_conntrack::Builder;
+
+let mut time_wait = 0;
+for entry in Builder::new().iter().unwrap() {
+    if entry.unwrap().state.as_deref() == Some("TIME_WAIT") { time_wait += 1 };
+}
+println!("TIME_WAIT entries: {}", time_wait);
+```
+
+If you want to change the path that is read for [`ProcNetConntrack`], which is `/proc` by default, or read the table of
+the network namespace of another process (`/proc/<pid>/net/nf_conntrack`), use:
+```no_run
+use proc_sys_parser::net_conntrack::Builder;
+
+let proc_net_conntrack = Builder::new().path("/myproc").read();
+let proc_net_conntrack = Builder::new().pid(1234).read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::net::{IpAddr, Ipv4Addr};
+use crate::ProcSysParserError;
+
+/// Struct for holding the connection tracking table
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcNetConntrack {
+    pub entries: Vec<ConntrackEntry>,
+}
+
+/// Struct for holding a single connection tracking entry
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConntrackEntry {
+    /// The network layer protocol: ipv4 or ipv6.
+    pub l3_protocol: String,
+    pub l3_protocol_nr: u8,
+    /// The transport layer protocol, such as tcp, udp or icmp.
+    pub protocol: String,
+    pub protocol_nr: u8,
+    /// The number of seconds until the entry expires.
+    pub timeout: u64,
+    /// The state of the connection, None for protocols without state, such as udp and icmp.
+    pub state: Option<String>,
+    pub original: ConntrackTuple,
+    pub reply: ConntrackTuple,
+    /// The flags without brackets, such as UNREPLIED and ASSURED.
+    pub flags: Vec<String>,
+    pub mark: Option<u32>,
+    pub zone: Option<u16>,
+    /// The reference count of the entry.
+    pub use_count: Option<u32>,
+    /// The other key=value fields, such as secctx, delta-time and labels.
+    pub other: BTreeMap<String, String>,
+}
+
+/// Struct for holding a direction of a connection tracking entry
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConntrackTuple {
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    pub sport: Option<u16>,
+    pub dport: Option<u16>,
+    pub icmp_type: Option<u8>,
+    pub icmp_code: Option<u8>,
+    pub icmp_id: Option<u16>,
+    /// Only with `net.netfilter.nf_conntrack_acct` enabled.
+    pub packets: Option<u64>,
+    /// Only with `net.netfilter.nf_conntrack_acct` enabled.
+    pub bytes: Option<u64>,
+}
+
+impl Default for ConntrackTuple {
+    fn default() -> ConntrackTuple {
+        ConntrackTuple {
+            src: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            dst: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            sport: None,
+            dport: None,
+            icmp_type: None,
+            icmp_code: None,
+            icmp_id: None,
+            packets: None,
+            bytes: None,
+        }
+    }
+}
+
+/// Iterator over the entries of `/proc/net/nf_conntrack`, which reads the file line by line
+pub struct ConntrackEntries {
+    lines: Lines<BufReader<File>>,
+    file: String,
+}
+
+/// Builder pattern for [`ProcNetConntrack`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    /// Read the table of the network namespace of process `proc_pid` via `/proc/<pid>/net/nf_conntrack`.
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = Some(proc_pid.to_string());
+        self
+    }
+    fn file(&self) -> String {
+        match &self.proc_pid {
+            Some(proc_pid) => format!("{}/{}/net/nf_conntrack", &self.proc_path, proc_pid),
+            None => format!("{}/net/nf_conntrack", &self.proc_path),
+        }
+    }
+    pub fn read(self) -> Result<ProcNetConntrack, ProcSysParserError> {
+        ProcNetConntrack::read_proc_net_conntrack(self.file().as_str())
+    }
+    /// An iterator over the entries, which reads the file line by line.
+    pub fn iter(self) -> Result<ConntrackEntries, ProcSysParserError> {
+        ConntrackEntries::new(self.file().as_str())
+    }
+}
+
+/// The main function for building a [`ProcNetConntrack`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcNetConntrack, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ConntrackEntry {
+    /// No packet was seen in the reply direction.
+    pub fn is_unreplied(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "UNREPLIED")
+    }
+    /// The entry will not be dropped when the table is full.
+    pub fn is_assured(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "ASSURED")
+    }
+}
+
+impl ConntrackEntries {
+    pub fn new(proc_net_conntrack_file: &str) -> Result<ConntrackEntries, ProcSysParserError> {
+        let file = File::open(proc_net_conntrack_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_net_conntrack_file.to_string(), error })?;
+        Ok(ConntrackEntries { lines: BufReader::new(file).lines(), file: proc_net_conntrack_file.to_string() })
+    }
+}
+
+impl Iterator for ConntrackEntries {
+    type Item = Result<ConntrackEntry, ProcSysParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(ProcNetConntrack::parse_proc_net_conntrack_line(&line)),
+                Err(error) => return Some(Err(ProcSysParserError::FileReadError { file: self.file.clone(), error })),
+            }
+        }
+    }
+}
+
+impl ProcNetConntrack {
+    pub fn new() -> ProcNetConntrack {
+        ProcNetConntrack::default()
+    }
+    pub fn parse_proc_net_conntrack_line(line: &str) -> Result<ConntrackEntry, ProcSysParserError> {
+        let mut fields = line.split_whitespace().peekable();
+        let mut next_field = |name: &str| fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("nf_conntrack {} in '{}'", name, line) });
+        let parse_error = |name: &str, error| ProcSysParserError::ParseItemToIntegerError { item: format!("nf_conntrack {} in '{}'", name, line), error };
+
+        let l3_protocol = next_field("l3_protocol")?.to_string();
+        let l3_protocol_nr = next_field("l3_protocol_nr")?.parse::<u8>().map_err(|error| parse_error("l3_protocol_nr", error))?;
+        let protocol = next_field("protocol")?.to_string();
+        let protocol_nr = next_field("protocol_nr")?.parse::<u8>().map_err(|error| parse_error("protocol_nr", error))?;
+        let timeout = next_field("timeout")?.parse::<u64>().map_err(|error| parse_error("timeout", error))?;
+        let state = match fields.peek() {
+            Some(field) if !field.contains('=') && !field.starts_with('[') => fields.next().map(|state| state.to_string()),
+            _ => None,
+        };
+
+        let mut entry = ConntrackEntry {
+            l3_protocol,
+            l3_protocol_nr,
+            protocol,
+            protocol_nr,
+            timeout,
+            state,
+            original: ConntrackTuple::default(),
+            reply: ConntrackTuple::default(),
+            flags: Vec::new(),
+            mark: None,
+            zone: None,
+            use_count: None,
+            other: BTreeMap::new(),
+        };
+        // every src= starts a tuple: the first is the original direction, the second the reply direction.
+        let mut tuples = 0;
+        for field in fields {
+            if let Some(flag) = field.strip_prefix('[').and_then(|flag| flag.strip_suffix(']')) {
+                entry.flags.push(flag.to_string());
+                continue;
+            }
+            let (key, value) = field.split_once('=')
+                .ok_or(ProcSysParserError::FindItemError { item: format!("'=' in nf_conntrack field {} in '{}'", field, line) })?;
+            if key == "src" { tuples += 1 };
+            let tuple = if tuples > 1 { &mut entry.reply } else { &mut entry.original };
+            match key {
+                "src" => tuple.src = value.parse::<IpAddr>()
+                    .map_err(|_| ProcSysParserError::FindItemError { item: format!("address in nf_conntrack src={} in '{}'", value, line) })?,
+                "dst" => tuple.dst = value.parse::<IpAddr>()
+                    .map_err(|_| ProcSysParserError::FindItemError { item: format!("address in nf_conntrack dst={} in '{}'", value, line) })?,
+                "sport" => tuple.sport = Some(value.parse::<u16>().map_err(|error| parse_error("sport", error))?),
+                "dport" => tuple.dport = Some(value.parse::<u16>().map_err(|error| parse_error("dport", error))?),
+                "type" => tuple.icmp_type = Some(value.parse::<u8>().map_err(|error| parse_error("type", error))?),
+                "code" => tuple.icmp_code = Some(value.parse::<u8>().map_err(|error| parse_error("code", error))?),
+                "id" => tuple.icmp_id = Some(value.parse::<u16>().map_err(|error| parse_error("id", error))?),
+                "packets" => tuple.packets = Some(value.parse::<u64>().map_err(|error| parse_error("packets", error))?),
+                "bytes" => tuple.bytes = Some(value.parse::<u64>().map_err(|error| parse_error("bytes", error))?),
+                "mark" => entry.mark = Some(value.parse::<u32>().map_err(|error| parse_error("mark", error))?),
+                "zone" => entry.zone = Some(value.parse::<u16>().map_err(|error| parse_error("zone", error))?),
+                "use" => entry.use_count = Some(value.parse::<u32>().map_err(|error| parse_error("use", error))?),
+                key => { entry.other.insert(key.to_string(), value.to_string()); },
+            }
+        }
+        Ok(entry)
+    }
+    pub fn read_proc_net_conntrack(proc_net_conntrack_file: &str) -> Result<ProcNetConntrack, ProcSysParserError> {
+        Ok(ProcNetConntrack {
+            entries: ConntrackEntries::new(proc_net_conntrack_file)?.collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_conntrack_lines() {
+        let result = ProcNetConntrack::parse_proc_net_conntrack_line("ipv4     2 tcp      6 431999 ESTABLISHED src=10.0.0.2 dst=10.0.0.1 sport=22 dport=54022 packets=10 bytes=1656 src=10.0.0.1 dst=10.0.0.2 sport=54022 dport=22 packets=12 bytes=1044 [ASSURED] mark=0 zone=0 use=2").unwrap();
+        assert_eq!(result.state, Some("ESTABLISHED".to_string()));
+        assert_eq!(result.original, ConntrackTuple { src: "10.0.0.2".parse().unwrap(), dst: "10.0.0.1".parse().unwrap(), sport: Some(22), dport: Some(54022), packets: Some(10), bytes: Some(1656), ..Default::default() });
+        assert_eq!(result.reply.bytes, Some(1044));
+        assert!(result.is_assured());
+        assert!(!result.is_unreplied());
+        assert_eq!(result.use_count, Some(2));
+
+        let result = ProcNetConntrack::parse_proc_net_conntrack_line("ipv6     10 udp      17 27 src=fe80::1 dst=ff02::fb sport=5353 dport=5353 [UNREPLIED] src=ff02::fb dst=fe80::1 sport=5353 dport=5353 mark=0 secctx=system_u:object_r:unlabeled_t:s0 use=2").unwrap();
+        assert_eq!(result.state, None);
+        assert_eq!(result.reply.src, "ff02::fb".parse::<IpAddr>().unwrap());
+        assert_eq!(result.original.packets, None);
+        assert!(result.is_unreplied());
+        assert_eq!(result.other.get("secctx"), Some(&"system_u:object_r:unlabeled_t:s0".to_string()));
+
+        let result = ProcNetConntrack::parse_proc_net_conntrack_line("ipv4     2 icmp     1 29 src=10.0.0.2 dst=10.0.0.1 type=8 code=0 id=4711 src=10.0.0.1 dst=10.0.0.2 type=0 code=0 id=4711 mark=0 use=1").unwrap();
+        assert_eq!((result.original.icmp_type, result.reply.icmp_type, result.reply.icmp_id), (Some(8), Some(0), Some(4711)));
+
+        assert!(ProcNetConntrack::parse_proc_net_conntrack_line("ipv4     2 tcp      6 431999 ESTABLISHED src=10.0.0.2 dst=10.0.0.1 sport=x").is_err());
+    }
+
+    #[test]
+    fn create_proc_net_conntrack_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/net/nf_conntrack", test_path), "ipv4     2 tcp      6 431999 ESTABLISHED src=10.0.0.2 dst=10.0.0.1 sport=22 dport=54022 src=10.0.0.1 dst=10.0.0.2 sport=54022 dport=22 [ASSURED] mark=0 zone=0 use=2
+ipv4     2 tcp      6 98 TIME_WAIT src=10.0.0.2 dst=10.0.0.3 sport=40400 dport=443 src=10.0.0.3 dst=10.0.0.2 sport=443 dport=40400 [ASSURED] mark=0 zone=0 use=2
+ipv4     2 udp      17 27 src=10.0.0.2 dst=10.0.0.53 sport=51000 dport=53 [UNREPLIED] src=10.0.0.53 dst=10.0.0.2 sport=53 dport=51000 mark=0 zone=0 use=2
+").unwrap_or_else(|_| panic!("Error writing to {}/net/nf_conntrack", test_path));
+        let result = Builder::new().path(&test_path).read().unwrap();
+        let time_wait = Builder::new().path(&test_path).iter().unwrap()
+            .filter(|entry| entry.as_ref().unwrap().state.as_deref() == Some("TIME_WAIT"))
+            .count();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.entries.len(), 3);
+        assert_eq!(result.entries[2].protocol, "udp");
+        assert_eq!(result.entries[2].reply.sport, Some(53));
+        assert_eq!(time_wait, 1);
+    }
+}
@@ -15,8 +15,8 @@ Example output:
 ```text
 ProcNetDev {
     interface: [
-        InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 },
-        InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 }
+        InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None },
+        InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
     ]
 }
 ```
@@ -30,10 +30,80 @@ use proc_sys_parser::{net_dev, net_dev::{ProcNetDev, Builder}};
 let proc_net_dev = Builder::new().path("/myproc").read();
 ```
 
+`/proc/net/dev` shows the statistics of the network namespace of the process reading it.
+If you want to read the statistics of the network namespace of another process, for example a process
+inside a container, use the pid of that process, which will read `/proc/<pid>/net/dev`:
+```no_run
+use proc_sys_parser::{net_dev, net_dev::{ProcNetDev, Builder}};
+
+let proc_net_dev = Builder::new().pid(1234).read();
+```
+
+The interfaces are selected with regexes that are matched against the interface name:
+- `filter()` sets the exclude regex, which is `^lo` by default; use `filter("")` to not exclude any interface.
+- `exclude()` adds an exclude regex, in addition to the filter.
+- `include()` adds an include regex: if any include regex is set, only the interfaces matching one of them are selected.
+- `name()` adds an exact interface name: it is selected like an include regex, but is never excluded, so `lo` can be
+  selected without changing the filter.
+```no_run
+use proc_sys_parser::net_dev::Builder;
+
+let proc_net_dev = Builder::new().include("^eth").include("^en").exclude("\\.[0-9]+$").name("lo").read();
+```
+
+The counters in `/proc/net/dev` are totals since the interface was created. [`ProcNetDev::delta`] takes the difference
+of two reads per interface into an [`InterfaceStatsDelta`], which calculates the rates, and [`ProcNetDev::total`] and
+[`ProcNetDev::total_of`] sum the counters of all or of the named interfaces into a single [`InterfaceStats`]:
+```no_run
+use proc_sys_parser::net_dev;
+use std::{thread::sleep, time::Duration};
+
+let previous = net_dev::read().unwrap();
+sleep(Duration::from_secs(1));
+let current = net_dev::read().unwrap();
+
+for delta in current.delta(&previous, 1.0) {
+    println!("{} rx: {} bytes/s {} packets/s, tx: {} bytes/s {} packets/s",
+        delta.name,
+        delta.receive_bytes_per_second(),
+        delta.receive_packets_per_second(),
+        delta.transmit_bytes_per_second(),
+        delta.transmit_packets_per_second(),
+    );
+}
+let total = current.total().delta(&previous.total(), 1.0);
+println!("total rx: {} bytes/s, tx: {} bytes/s", total.receive_bytes_per_second(), total.transmit_bytes_per_second());
+```
+
+The counters in `/proc/net/dev` do not show how busy an interface is relative to its capacity. With `sys_net(true)`,
+each interface is joined with its attributes in `/sys/class/net/<interface>` (speed, operstate, mtu and hardware
+address) in [`InterfaceStats::attributes`], so the bandwidth utilization can be calculated from two reads:
+```no_run
+use proc_sys_parser::net_dev::Builder;
+use std::{thread::sleep, time::Duration};
+
+let previous = Builder::new().sys_net(true).read().unwrap();
+sleep(Duration::from_secs(1));
+let current = Builder::new().sys_net(true).read().unwrap();
+
+for (previous, current) in previous.interface.iter().zip(current.interface.iter()) {
+    println!("{} rx: {:?}% tx: {:?}%",
+        current.name,
+        current.receive_utilization_percent(previous, 1.0),
+        current.transmit_utilization_percent(previous, 1.0),
+    );
+}
+```
+Please mind `/sys/class/net` shows the interfaces of the network namespace of the process reading it, so when the
+statistics of another network namespace are read with `pid()`, only the interfaces with the same name in the own
+namespace are joined. An interface that is not found in `/sys/class/net` has `attributes` None.
+
 */
 use std::fs::read_to_string;
+use std::path::Path;
 use regex::Regex;
 use crate::ProcSysParserError;
+use crate::utils::{read_file_option_i64, read_file_string, read_file_u64};
 
 /// Struct for holding `/proc/net/dev` statistics
 #[derive(Debug, PartialEq, Default)]
@@ -47,6 +117,12 @@ pub struct Builder {
     pub proc_path : String,
     pub proc_file : String,
     pub proc_filter : String,
+    pub proc_pid : Option<String>,
+    pub proc_include : Vec<String>,
+    pub proc_exclude : Vec<String>,
+    pub proc_names : Vec<String>,
+    pub sys_path : String,
+    pub sys_net : bool,
 }
 
 impl Builder {
@@ -55,6 +131,12 @@ impl Builder {
             proc_path: "/proc".to_string(),
             proc_file: "net/dev".to_string(),
             proc_filter: "^lo".to_string(),
+            proc_pid: None,
+            proc_include: Vec::new(),
+            proc_exclude: Vec::new(),
+            proc_names: Vec::new(),
+            sys_path: "/sys".to_string(),
+            sys_net: false,
         }
     }
 
@@ -70,8 +152,50 @@ impl Builder {
         self.proc_filter = proc_filter.to_string();
         self
     }
+    /// Read the statistics of the network namespace of process `proc_pid` via `/proc/<pid>/net/dev`.
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = Some(proc_pid.to_string());
+        self
+    }
+    /// Add a regex to select interfaces by; without include regexes and names all interfaces are selected.
+    pub fn include(mut self, proc_include: &str) -> Builder {
+        self.proc_include.push(proc_include.to_string());
+        self
+    }
+    /// Add a regex to exclude interfaces by, in addition to the filter.
+    pub fn exclude(mut self, proc_exclude: &str) -> Builder {
+        self.proc_exclude.push(proc_exclude.to_string());
+        self
+    }
+    /// Add an interface name to select, which is never excluded.
+    pub fn name(mut self, proc_name: &str) -> Builder {
+        self.proc_names.push(proc_name.to_string());
+        self
+    }
+    /// Join each interface with its attributes in `/sys/class/net/<interface>`.
+    pub fn sys_net(mut self, sys_net: bool) -> Builder {
+        self.sys_net = sys_net;
+        self
+    }
+    pub fn sys_path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
     pub fn read(self) -> Result<ProcNetDev, ProcSysParserError> {
-        ProcNetDev::read_proc_net_dev(format!("{}/{}", &self.proc_path, &self.proc_file).as_str(), self.proc_filter.as_str())
+        let proc_net_dev_file = match &self.proc_pid {
+            Some(proc_pid) => format!("{}/{}/{}", &self.proc_path, proc_pid, &self.proc_file),
+            None => format!("{}/{}", &self.proc_path, &self.proc_file),
+        };
+        let mut procnetdev = ProcNetDev::read_proc_net_dev(proc_net_dev_file.as_str(), "")?;
+        let exclude: Vec<String> = std::iter::once(self.proc_filter.clone())
+            .filter(|filter| !filter.is_empty())
+            .chain(self.proc_exclude.iter().cloned())
+            .collect();
+        procnetdev.select(&self.proc_include, &exclude, &self.proc_names)?;
+        if self.sys_net {
+            procnetdev.read_sys_class_net(format!("{}/class/net", &self.sys_path).as_str())?;
+        }
+        Ok(procnetdev)
     }
 }
 
@@ -82,7 +206,7 @@ pub fn read() -> Result<ProcNetDev, ProcSysParserError> {
 }
 
 /// Struct for holding statistics of individual network interfaces
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default, Clone)]
 pub struct InterfaceStats {
     pub name: String,
     pub receive_bytes: u64,
@@ -101,12 +225,143 @@ pub struct InterfaceStats {
     pub transmit_collisions: u64,
     pub transmit_carrier: u64,
     pub transmit_compressed: u64,
+    /// The attributes from `/sys/class/net/<interface>`, only read with [`Builder::sys_net`].
+    pub attributes: Option<InterfaceAttributes>,
+}
+
+/// Struct for holding the difference of the counters of a network interface between two reads
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InterfaceStatsDelta {
+    pub name: String,
+    pub interval_seconds: f64,
+    pub receive_bytes: u64,
+    pub receive_packets: u64,
+    pub receive_errors: u64,
+    pub receive_drop: u64,
+    pub transmit_bytes: u64,
+    pub transmit_packets: u64,
+    pub transmit_errors: u64,
+    pub transmit_drop: u64,
+}
+
+/// Struct for holding the attributes of a network interface from `/sys/class/net/<interface>`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InterfaceAttributes {
+    /// The link speed in Mbit/s, -1 if unknown, None if the interface is down.
+    pub speed: Option<i64>,
+    /// The RFC 2863 operational state: unknown, notpresent, down, lowerlayerdown, testing, dormant or up.
+    pub operstate: String,
+    pub mtu: u64,
+    /// The hardware address.
+    pub address: String,
+}
+
+impl InterfaceStatsDelta {
+    /// Take the difference of the counters of a previous and a current read of an interface, which are
+    /// `interval_seconds` apart. A counter that is lower in the current read, for example because the interface was
+    /// recreated, counts as zero.
+    pub fn new(previous: &InterfaceStats, current: &InterfaceStats, interval_seconds: f64) -> InterfaceStatsDelta {
+        InterfaceStatsDelta {
+            name: current.name.clone(),
+            interval_seconds,
+            receive_bytes: current.receive_bytes.saturating_sub(previous.receive_bytes),
+            receive_packets: current.receive_packets.saturating_sub(previous.receive_packets),
+            receive_errors: current.receive_errors.saturating_sub(previous.receive_errors),
+            receive_drop: current.receive_drop.saturating_sub(previous.receive_drop),
+            transmit_bytes: current.transmit_bytes.saturating_sub(previous.transmit_bytes),
+            transmit_packets: current.transmit_packets.saturating_sub(previous.transmit_packets),
+            transmit_errors: current.transmit_errors.saturating_sub(previous.transmit_errors),
+            transmit_drop: current.transmit_drop.saturating_sub(previous.transmit_drop),
+        }
+    }
+    fn per_second(&self, difference: u64) -> f64 {
+        if self.interval_seconds > 0. { difference as f64 / self.interval_seconds } else { 0. }
+    }
+    pub fn receive_bytes_per_second(&self) -> f64 {
+        self.per_second(self.receive_bytes)
+    }
+    pub fn transmit_bytes_per_second(&self) -> f64 {
+        self.per_second(self.transmit_bytes)
+    }
+    pub fn receive_packets_per_second(&self) -> f64 {
+        self.per_second(self.receive_packets)
+    }
+    pub fn transmit_packets_per_second(&self) -> f64 {
+        self.per_second(self.transmit_packets)
+    }
+}
+
+impl InterfaceStats {
+    /// The counters between a previous read and this read, which are `interval_seconds` apart.
+    pub fn delta(&self, previous: &InterfaceStats, interval_seconds: f64) -> InterfaceStatsDelta {
+        InterfaceStatsDelta::new(previous, self, interval_seconds)
+    }
+    /// The link speed in bytes per second, None if the attributes are not read or the speed is unknown.
+    pub fn speed_bytes_per_second(&self) -> Option<f64> {
+        match self.attributes.as_ref()?.speed? {
+            speed if speed > 0 => Some(speed as f64 * 1_000_000_f64 / 8_f64),
+            _ => None,
+        }
+    }
+    /// The received bytes since `previous` as percentage of the link speed.
+    /// None if the attributes are not read, the speed is unknown or the interval is not positive.
+    pub fn receive_utilization_percent(&self, previous: &InterfaceStats, interval_seconds: f64) -> Option<f64> {
+        self.utilization_percent(self.receive_bytes.saturating_sub(previous.receive_bytes), interval_seconds)
+    }
+    /// The transmitted bytes since `previous` as percentage of the link speed.
+    /// None if the attributes are not read, the speed is unknown or the interval is not positive.
+    pub fn transmit_utilization_percent(&self, previous: &InterfaceStats, interval_seconds: f64) -> Option<f64> {
+        self.utilization_percent(self.transmit_bytes.saturating_sub(previous.transmit_bytes), interval_seconds)
+    }
+    fn utilization_percent(&self, bytes: u64, interval_seconds: f64) -> Option<f64> {
+        if interval_seconds <= 0_f64 { return None };
+        Some(bytes as f64 / interval_seconds / self.speed_bytes_per_second()? * 100_f64)
+    }
 }
 
 impl ProcNetDev {
     pub fn new() -> ProcNetDev {
         ProcNetDev::default()
     }
+    /// The counters of all interfaces summed into an [`InterfaceStats`] with the name `total`.
+    pub fn total(&self) -> InterfaceStats {
+        ProcNetDev::sum(self.interface.iter())
+    }
+    /// The counters of the interfaces in `names` summed into an [`InterfaceStats`] with the name `total`.
+    /// Names that are not found are ignored.
+    pub fn total_of(&self, names: &[&str]) -> InterfaceStats {
+        ProcNetDev::sum(self.interface.iter().filter(|interface| names.contains(&interface.name.as_str())))
+    }
+    fn sum<'a>(interfaces: impl Iterator<Item = &'a InterfaceStats>) -> InterfaceStats {
+        interfaces.fold(InterfaceStats { name: "total".to_string(), ..Default::default() }, |total, interface| InterfaceStats {
+            receive_bytes: total.receive_bytes + interface.receive_bytes,
+            receive_packets: total.receive_packets + interface.receive_packets,
+            receive_errors: total.receive_errors + interface.receive_errors,
+            receive_drop: total.receive_drop + interface.receive_drop,
+            receive_fifo: total.receive_fifo + interface.receive_fifo,
+            receive_frame: total.receive_frame + interface.receive_frame,
+            receive_compressed: total.receive_compressed + interface.receive_compressed,
+            receive_multicast: total.receive_multicast + interface.receive_multicast,
+            transmit_bytes: total.transmit_bytes + interface.transmit_bytes,
+            transmit_packets: total.transmit_packets + interface.transmit_packets,
+            transmit_errors: total.transmit_errors + interface.transmit_errors,
+            transmit_drop: total.transmit_drop + interface.transmit_drop,
+            transmit_fifo: total.transmit_fifo + interface.transmit_fifo,
+            transmit_collisions: total.transmit_collisions + interface.transmit_collisions,
+            transmit_carrier: total.transmit_carrier + interface.transmit_carrier,
+            transmit_compressed: total.transmit_compressed + interface.transmit_compressed,
+            ..total
+        })
+    }
+    /// The counters per interface between a previous read and this read, which are `interval_seconds` apart.
+    /// Interfaces that are not in both reads are skipped.
+    pub fn delta(&self, previous: &ProcNetDev, interval_seconds: f64) -> Vec<InterfaceStatsDelta> {
+        self.interface.iter()
+            .filter_map(|current| previous.interface.iter()
+                .find(|previous| previous.name == current.name)
+                .map(|previous| current.delta(previous, interval_seconds)))
+            .collect()
+    }
     pub fn parse_proc_net_dev(
         proc_net_dev: &str, 
         filter: &str
@@ -181,8 +436,41 @@ impl ProcNetDev {
             transmit_compressed: fields.next()
                 .ok_or(ProcSysParserError::IteratorItemError {item: "net_dev transmit_compressed".to_string() })?
                 .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            attributes: None,
         })
     }
+    /// Keep the interfaces that are in `names`, or, if `include` is empty or one of its regexes matches the name,
+    /// that do not match any regex in `exclude`. With both `include` and `names` empty, all interfaces not excluded are kept.
+    pub fn select(&mut self, include: &[String], exclude: &[String], names: &[String]) -> Result<(), ProcSysParserError> {
+        let compile = |regexes: &[String]| {
+            regexes.iter()
+                .map(|regex| Regex::new(regex).map_err(|_| ProcSysParserError::RegexCompileError { regex: regex.to_string() }))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let include = compile(include)?;
+        let exclude = compile(exclude)?;
+
+        self.interface.retain(|interface| {
+            names.contains(&interface.name)
+                || ((include.is_empty() && names.is_empty()) || include.iter().any(|regex| regex.is_match(&interface.name)))
+                    && !exclude.iter().any(|regex| regex.is_match(&interface.name))
+        });
+        Ok(())
+    }
+    /// Add the attributes of `/sys/class/net/<interface>` to the interfaces that are found in it.
+    pub fn read_sys_class_net(&mut self, sys_class_net_path: &str) -> Result<(), ProcSysParserError> {
+        for interface in self.interface.iter_mut() {
+            let interface_path = Path::new(sys_class_net_path).join(&interface.name);
+            if !interface_path.is_dir() { continue };
+            interface.attributes = Some(InterfaceAttributes {
+                speed: read_file_option_i64(&interface_path.join("speed"))?,
+                operstate: read_file_string(&interface_path.join("operstate"))?,
+                mtu: read_file_u64(&interface_path.join("mtu"))?,
+                address: read_file_string(&interface_path.join("address"))?,
+            });
+        }
+        Ok(())
+    }
     pub fn read_proc_net_dev(proc_net_dev_file: &str, proc_net_dev_filter: &str) -> Result<ProcNetDev, ProcSysParserError> {
         let proc_net_dev_output = read_to_string(proc_net_dev_file)
             .map_err(|error| ProcSysParserError::FileReadError { file: proc_net_dev_file.to_string(), error })?;
@@ -200,15 +488,15 @@ mod tests {
     #[test]
     fn parse_proc_netdev_valid_line() {
         let netdev_line = "  eth0: 151012532   16720    0    0    0     0          0         0   816228   12257    0    0    0     0       0          0";
-        let result = ProcNetDev::parse_proc_net_dev_line(&netdev_line).unwrap();
+        let result = ProcNetDev::parse_proc_net_dev_line(netdev_line).unwrap();
         assert_eq!(result, InterfaceStats {
-            name: "eth0".to_string(), receive_bytes: 151012532, receive_packets: 16720, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 }
+            name: "eth0".to_string(), receive_bytes: 151012532, receive_packets: 16720, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
         );
     }
     #[test]
     fn parse_proc_netdev_invalid_line() {
         let netdev_line = "Inter-|   Receive                                                |  Transmit";
-        let result = ProcNetDev::parse_proc_net_dev(&netdev_line, "").unwrap();
+        let result = ProcNetDev::parse_proc_net_dev(netdev_line, "").unwrap();
         assert_eq!(result, ProcNetDev { interface: vec![] });
     }
 
@@ -220,8 +508,8 @@ mod tests {
   eth0: 151013652   16736    0    0    0     0          0         0   816228   12257    0    0    0     0       0          0";
         let result = ProcNetDev::parse_proc_net_dev(proc_netdev, "").unwrap();
         assert_eq!(result, ProcNetDev { interface:
-        vec![InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 },
-             InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 }
+        vec![InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None },
+             InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
         ] } );
     }
 
@@ -236,17 +524,121 @@ mod tests {
         let test_path = format!("/tmp/test.{}", directory_suffix);
         create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
 
-        write(format!("{}/net/dev", test_path), proc_netdev).expect(format!("Error writing to {}/net/dev", test_path).as_str());
+        write(format!("{}/net/dev", test_path), proc_netdev).unwrap_or_else(|_| panic!("Error writing to {}/net/dev", test_path));
         // please mind filter("") is used to remove the filter for "^lo", which would remove
         // lo/localhost. This also removes the 'loop' interfaces that are seen with docker.
         let result = Builder::new().filter("").path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
 
         assert_eq!(result, ProcNetDev { interface:
-        vec![InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 },
-             InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0 }
+        vec![InterfaceStats { name: "lo".to_string(), receive_bytes: 0, receive_packets: 0, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 0, transmit_packets: 0, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None },
+             InterfaceStats { name: "eth0".to_string(), receive_bytes: 151013652, receive_packets: 16736, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 816228, transmit_packets: 12257, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
         ] } );
     }
-}
 
+    #[test]
+    fn create_proc_pid_net_dev_file_and_read() {
+        let proc_netdev = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:       0       0    0    0    0     0          0         0        0       0    0    0    0     0       0          0
+  eth0:    1386      17    0    0    0     0          0         0      796      10    0    0    0     0       0          0";
+
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/net/dev", test_path), proc_netdev).unwrap_or_else(|_| panic!("Error writing to {}/1234/net/dev", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcNetDev { interface:
+        vec![InterfaceStats { name: "eth0".to_string(), receive_bytes: 1386, receive_packets: 17, receive_errors: 0, receive_drop: 0, receive_fifo: 0, receive_frame: 0, receive_compressed: 0, receive_multicast: 0, transmit_bytes: 796, transmit_packets: 10, transmit_errors: 0, transmit_drop: 0, transmit_fifo: 0, transmit_collisions: 0, transmit_carrier: 0, transmit_compressed: 0, attributes: None }
+        ] } );
+    }
+
+    #[test]
+    fn proc_net_dev_total_and_delta() {
+        let previous = ProcNetDev { interface: vec![
+            InterfaceStats { name: "eth0".to_string(), receive_bytes: 1000, receive_packets: 10, transmit_bytes: 500, transmit_packets: 5, ..Default::default() },
+            InterfaceStats { name: "eth1".to_string(), receive_bytes: 2000, receive_packets: 20, transmit_bytes: 800, transmit_packets: 8, ..Default::default() },
+        ] };
+        let current = ProcNetDev { interface: vec![
+            InterfaceStats { name: "eth0".to_string(), receive_bytes: 5000, receive_packets: 50, transmit_bytes: 2500, transmit_packets: 25, ..Default::default() },
+            InterfaceStats { name: "eth1".to_string(), receive_bytes: 100, receive_packets: 1, transmit_bytes: 100, transmit_packets: 1, ..Default::default() },
+            InterfaceStats { name: "eth2".to_string(), receive_bytes: 100, receive_packets: 1, ..Default::default() },
+        ] };
 
+        assert_eq!(current.total(), InterfaceStats { name: "total".to_string(), receive_bytes: 5200, receive_packets: 52, transmit_bytes: 2600, transmit_packets: 26, ..Default::default() });
+        assert_eq!(current.total_of(&["eth0", "eth2", "eth9"]).receive_bytes, 5100);
+
+        let delta = current.delta(&previous, 2.0);
+        // eth1 was recreated, so its counters are lower and count as zero, and eth2 is not in the previous read.
+        assert_eq!(delta.iter().map(|delta| delta.name.as_str()).collect::<Vec<_>>(), vec!["eth0", "eth1"]);
+        assert_eq!(delta[0].receive_bytes_per_second(), 2000.);
+        assert_eq!(delta[0].receive_packets_per_second(), 20.);
+        assert_eq!(delta[0].transmit_bytes_per_second(), 1000.);
+        assert_eq!(delta[0].transmit_packets_per_second(), 10.);
+        assert_eq!(delta[1].receive_bytes, 0);
+        assert_eq!(current.total().delta(&previous.total(), 0.).receive_bytes_per_second(), 0.);
+    }
+
+    #[test]
+    fn create_proc_net_dev_file_and_select_interfaces() {
+        let proc_netdev = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:       0       0    0    0    0     0          0         0        0       0    0    0    0     0       0          0
+  eth0: 151013652   16736    0    0    0     0          0         0   816228   12257    0    0    0     0       0          0
+eth0.100:    1386      17    0    0    0     0          0         0      796      10    0    0    0     0       0          0
+enp1s0:    1386      17    0    0    0     0          0         0      796      10    0    0    0     0       0          0
+docker0:    1386      17    0    0    0     0          0         0      796      10    0    0    0     0       0          0";
+
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/net/dev", test_path), proc_netdev).unwrap_or_else(|_| panic!("Error writing to {}/net/dev", test_path));
+        let names = |builder: Builder| builder.path(&test_path).read().unwrap().interface.into_iter().map(|interface| interface.name).collect::<Vec<_>>();
+        let default = names(Builder::new());
+        let included = names(Builder::new().include("^eth").include("^en").exclude("\\.[0-9]+$"));
+        let named = names(Builder::new().name("lo").name("docker0"));
+        let named_and_included = names(Builder::new().name("lo").include("^en"));
+        let invalid = Builder::new().path(&test_path).include("(").read();
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(default, vec!["eth0", "eth0.100", "enp1s0", "docker0"]);
+        assert_eq!(included, vec!["eth0", "enp1s0"]);
+        assert_eq!(named, vec!["lo", "docker0"]);
+        assert_eq!(named_and_included, vec!["lo", "enp1s0"]);
+        assert!(matches!(invalid, Err(ProcSysParserError::RegexCompileError { .. })));
+    }
+
+    #[test]
+    fn create_proc_net_dev_and_sys_class_net_files_and_read() {
+        let proc_netdev = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+  eth0: 151013652   16736    0    0    0     0          0         0   816228   12257    0    0    0     0       0          0
+  eth1:    1386      17    0    0    0     0          0         0      796      10    0    0    0     0       0          0";
+
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/proc/net", test_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/sys/class/net/eth0", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/proc/net/dev", test_path), proc_netdev).unwrap_or_else(|_| panic!("Error writing to {}/proc/net/dev", test_path));
+        for (file, contents) in [("speed", "1000"), ("operstate", "up"), ("mtu", "1500"), ("address", "52:54:00:12:34:56")] {
+            write(format!("{}/sys/class/net/eth0/{}", test_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/sys/class/net/eth0/{}", test_path, file));
+        }
+        let result = Builder::new().path(&format!("{}/proc", test_path)).sys_path(&format!("{}/sys", test_path)).sys_net(true).read().unwrap();
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.interface[0].attributes, Some(InterfaceAttributes { speed: Some(1000), operstate: "up".to_string(), mtu: 1500, address: "52:54:00:12:34:56".to_string() }));
+        // eth1 is not found in /sys/class/net.
+        assert_eq!(result.interface[1].attributes, None);
+
+        let previous = InterfaceStats { receive_bytes: 151013652 - 12_500_000, transmit_bytes: 816228, ..result.interface[0].clone() };
+        assert_eq!(result.interface[0].speed_bytes_per_second(), Some(125_000_000_f64));
+        assert_eq!(result.interface[0].receive_utilization_percent(&previous, 1.0), Some(10_f64));
+        assert_eq!(result.interface[0].transmit_utilization_percent(&previous, 1.0), Some(0_f64));
+        assert_eq!(result.interface[1].receive_utilization_percent(&previous, 1.0), None);
+    }
+}
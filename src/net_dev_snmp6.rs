@@ -0,0 +1,235 @@
+/*!
+Read the per-interface IPv6 statistics from `/proc/net/dev_snmp6/<interface>` into the struct [`ProcNetDevSnmp6`].
+
+Every network interface with IPv6 enabled has a file in `/proc/net/dev_snmp6` containing the Ip6 and Icmp6
+counters of that interface, in the same format as the global `/proc/net/snmp6` file: one counter name and value
+per line. The first line contains the ifIndex of the interface.
+
+Because the set of counters differs between kernel versions (for example, Ip6OutTransmits was added in linux 6.3,
+and the Icmp6InType/Icmp6OutType counters are only shown for ICMPv6 types that were seen), the counters are stored
+by name, with the Ip6 or Icmp6 prefix removed.
+
+Documentation: <https://www.kernel.org/doc/Documentation/networking/ip-sysctl.txt>
+
+Here is an example obtaining the data from `/proc/net/dev_snmp6`:
+```no_run
+use proc_sys_parser::net_dev_snmp6;
+
+let proc_net_dev_snmp6 = net_dev_snmp6::read();
+
+println!("{:#?}", proc_net_dev_snmp6);
+```
+Example output:
+```text
+ProcNetDevSnmp6 {
+    interfaces: [
+        InterfaceSnmp6 {
+            name: "eth0",
+            if_index: 2,
+            ip6: {
+                "InDelivers": 1513,
+                "InOctets": 160024,
+                "InReceives": 1537,
+                "OutOctets": 148312,
+                "OutRequests": 1462,
+                ..
+            },
+            icmp6: {
+                "InMsgs": 12,
+                "OutMLDv2Reports": 4,
+                "OutType135": 1,
+                "OutType143": 4,
+                ..
+            },
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`ProcNetDevSnmp6`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::net_dev_snmp6::Builder;
+
+let proc_net_dev_snmp6 = Builder::new().path("/myproc").read();
+```
+
+Like `/proc/net/dev`, the interfaces starting with "lo" are filtered by default. The filter is a regex that is
+matched against the interface name, and can be changed or removed (using an empty string) with:
+```no_run
+use proc_sys_parser::net_dev_snmp6::Builder;
+
+let proc_net_dev_snmp6 = Builder::new().filter("").read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::read_dir;
+use log::warn;
+use regex::Regex;
+use crate::ProcSysParserError;
+use crate::utils::read_file_string;
+
+/// Struct for holding the IPv6 statistics of all interfaces
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcNetDevSnmp6 {
+    pub interfaces: Vec<InterfaceSnmp6>,
+}
+
+/// Struct for holding the IPv6 statistics of a single interface
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InterfaceSnmp6 {
+    pub name: String,
+    pub if_index: u64,
+    /// The Ip6 counters by name, without the Ip6 prefix, such as "InReceives".
+    pub ip6: BTreeMap<String, u64>,
+    /// The Icmp6 counters by name, without the Icmp6 prefix, such as "InMsgs".
+    pub icmp6: BTreeMap<String, u64>,
+}
+
+/// Builder pattern for [`ProcNetDevSnmp6`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_filter : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_filter: "^lo".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn filter(mut self, proc_filter: &str) -> Builder {
+        self.proc_filter = proc_filter.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcNetDevSnmp6, ProcSysParserError> {
+        ProcNetDevSnmp6::read_proc_net_dev_snmp6(format!("{}/net/dev_snmp6", &self.proc_path).as_str(), self.proc_filter.as_str())
+    }
+}
+
+/// The main function for building a [`ProcNetDevSnmp6`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcNetDevSnmp6, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl InterfaceSnmp6 {
+    /// The value of an Ip6 counter, such as "InReceives", or None if the counter is not shown.
+    pub fn ip6_counter(&self, name: &str) -> Option<u64> {
+        self.ip6.get(name).copied()
+    }
+    /// The value of an Icmp6 counter, such as "InErrors", or None if the counter is not shown.
+    pub fn icmp6_counter(&self, name: &str) -> Option<u64> {
+        self.icmp6.get(name).copied()
+    }
+}
+
+impl ProcNetDevSnmp6 {
+    pub fn new() -> ProcNetDevSnmp6 {
+        ProcNetDevSnmp6::default()
+    }
+    /// The statistics of the interface with the given name.
+    pub fn interface(&self, name: &str) -> Option<&InterfaceSnmp6> {
+        self.interfaces.iter().find(|interface| interface.name == name)
+    }
+    pub fn parse_proc_net_dev_snmp6(proc_net_dev_snmp6: &str, name: &str) -> Result<InterfaceSnmp6, ProcSysParserError> {
+        let mut interface = InterfaceSnmp6 { name: name.to_string(), ..Default::default() };
+
+        for line in proc_net_dev_snmp6.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(counter) = fields.next() else { continue };
+            let value = fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("net_dev_snmp6 {} {}", name, counter) })?
+                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+
+            if counter == "ifIndex" {
+                interface.if_index = value;
+            } else if let Some(counter) = counter.strip_prefix("Icmp6") {
+                interface.icmp6.insert(counter.to_string(), value);
+            } else if let Some(counter) = counter.strip_prefix("Ip6") {
+                interface.ip6.insert(counter.to_string(), value);
+            } else {
+                warn!("net_dev_snmp6: unknown entry found: {}", line);
+            }
+        }
+        Ok(interface)
+    }
+    pub fn read_proc_net_dev_snmp6(proc_net_dev_snmp6_path: &str, proc_net_dev_snmp6_filter: &str) -> Result<ProcNetDevSnmp6, ProcSysParserError> {
+        let mut procnetdevsnmp6 = ProcNetDevSnmp6::new();
+        let filter_regex = Regex::new(proc_net_dev_snmp6_filter)
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: proc_net_dev_snmp6_filter.to_string() })?;
+
+        let interface_files = read_dir(proc_net_dev_snmp6_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: proc_net_dev_snmp6_path.to_string(), error })?;
+
+        for interface_file in interface_files.filter_map(|entry| entry.ok()) {
+            let name = interface_file.file_name().to_string_lossy().to_string();
+            if !filter_regex.as_str().is_empty() && filter_regex.is_match(&name) { continue };
+
+            let contents = match read_file_string(&interface_file.path()) {
+                Ok(contents) => contents,
+                // the interface has been removed in the meantime
+                Err(ProcSysParserError::FileReadError { error, .. }) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(error),
+            };
+            procnetdevsnmp6.interfaces.push(ProcNetDevSnmp6::parse_proc_net_dev_snmp6(&contents, &name)?);
+        }
+        procnetdevsnmp6.interfaces.sort_by_key(|interface| interface.if_index);
+        Ok(procnetdevsnmp6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_dev_snmp6_file() {
+        let dev_snmp6 = "ifIndex                         \t2
+Ip6InReceives                   \t1537
+Ip6InOctets                     \t160024
+Ip6OutTransmits                 \t1462
+Icmp6InMsgs                     \t12
+Icmp6InErrors                   \t0
+Icmp6OutType135                 \t1";
+        let result = ProcNetDevSnmp6::parse_proc_net_dev_snmp6(dev_snmp6, "eth0").unwrap();
+        assert_eq!(result.if_index, 2);
+        assert_eq!(result.ip6.len(), 3);
+        assert_eq!(result.icmp6.len(), 3);
+        assert_eq!(result.ip6_counter("InOctets"), Some(160024));
+        assert_eq!(result.ip6_counter("InHdrErrors"), None);
+        assert_eq!(result.icmp6_counter("OutType135"), Some(1));
+    }
+
+    #[test]
+    fn create_proc_net_dev_snmp6_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net/dev_snmp6", test_path)).expect("Error creating mock directory.");
+        for (interface, if_index, in_receives) in [("lo", 1, 10), ("eth1", 3, 30), ("eth0", 2, 20)] {
+            write(format!("{}/net/dev_snmp6/{}", test_path, interface), format!("ifIndex\t{}\nIp6InReceives\t{}\nIcmp6InMsgs\t1\n", if_index, in_receives))
+                .unwrap_or_else(|_| panic!("Error writing to {}/net/dev_snmp6/{}", test_path, interface));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+        let result_unfiltered = Builder::new().path(&test_path).filter("").read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.interfaces.iter().map(|interface| interface.name.as_str()).collect::<Vec<_>>(), vec!["eth0", "eth1"]);
+        assert_eq!(result.interface("eth1").unwrap().ip6_counter("InReceives"), Some(30));
+        assert_eq!(result.interface("lo"), None);
+        assert_eq!(result_unfiltered.interfaces.len(), 3);
+        assert_eq!(result_unfiltered.interfaces[0].name, "lo");
+    }
+}
@@ -0,0 +1,283 @@
+/*!
+Read the IPv6 addresses from `/proc/net/if_inet6` and `/proc/net/anycast6` into the struct [`ProcNetIfInet6`].
+
+`/proc/net/if_inet6` shows a line per IPv6 address of an interface, with the address as 32 hexadecimal digits, followed
+by the interface index, the prefix length, the scope and the flags, all hexadecimal, and the interface name:
+```text
+00000000000000000000000000000001 01 80 10 80       lo
+fe80000000000000505400fffe123456 02 40 20 80     eth0
+```
+`/proc/net/anycast6` shows a line per IPv6 anycast address, with the interface index, the interface name,
+the address and the number of users of the address, for example the subnet-router anycast address of a router.
+
+The scope is 0x00 for global, 0x10 for host (loopback), 0x20 for link-local and 0x40 for site-local addresses.
+The flags are the IFA_F flags of the address, such as 0x80 for permanent (configured) addresses, 0x40 for
+addresses that are still doing duplicate address detection and 0x01 for temporary (privacy) addresses.
+
+This shows the IPv6 addressing of a host without using netlink.
+
+Documentation: <https://tldp.org/HOWTO/Linux+IPv6-HOWTO/ch11s04.html>
+
+Here is an example obtaining the IPv6 addresses:
+```no_run
+use proc_sys_parser::net_if_inet6;
+
+let proc_net_if_inet6 = net_if_inet6::read();
+
+println!("{:#?}", proc_net_if_inet6);
+```
+Example output:
+```text
+ProcNetIfInet6 {
+    addresses: [
+        Inet6Address { address: ::1, if_index: 1, prefix_length: 128, scope: 16, flags: 128, name: "lo" },
+        Inet6Address { address: fe80::5054:ff:fe12:3456, if_index: 2, prefix_length: 64, scope: 32, flags: 128, name: "eth0" },
+    ],
+    anycast: [
+        Anycast6Address { if_index: 2, name: "eth0", address: fe80::, users: 1 },
+    ],
+}
+```
+
+If you want to change the path that is read for [`ProcNetIfInet6`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::net_if_inet6::Builder;
+
+let proc_net_if_inet6 = Builder::new().path("/myproc").read();
+```
+
+Just like [`crate::net_dev`], the addresses of the network namespace of another process can be read using the pid of
+that process, which will read `/proc/<pid>/net/if_inet6` and `/proc/<pid>/net/anycast6`:
+```no_run
+use proc_sys_parser::net_if_inet6::Builder;
+
+let proc_net_if_inet6 = Builder::new().pid(1234).read();
+```
+*/
+use std::fs::read_to_string;
+use std::net::Ipv6Addr;
+use crate::ProcSysParserError;
+
+/// Struct for holding the IPv6 addresses of all interfaces
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcNetIfInet6 {
+    /// The addresses from `/proc/net/if_inet6`.
+    pub addresses: Vec<Inet6Address>,
+    /// The anycast addresses from `/proc/net/anycast6`.
+    pub anycast: Vec<Anycast6Address>,
+}
+
+/// Struct for holding a single IPv6 address of an interface
+#[derive(Debug, PartialEq, Clone)]
+pub struct Inet6Address {
+    pub address: Ipv6Addr,
+    pub if_index: u32,
+    pub prefix_length: u8,
+    /// The scope of the address: 0x00 global, 0x10 host, 0x20 link, 0x40 site, 0x80 IPv4 compatible.
+    pub scope: u8,
+    /// The IFA_F flags of the address.
+    pub flags: u8,
+    pub name: String,
+}
+
+/// Struct for holding a single IPv6 anycast address of an interface
+#[derive(Debug, PartialEq, Clone)]
+pub struct Anycast6Address {
+    pub if_index: u32,
+    pub name: String,
+    pub address: Ipv6Addr,
+    /// The number of users of the anycast address.
+    pub users: u32,
+}
+
+/// Builder pattern for [`ProcNetIfInet6`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    /// Read the addresses of the network namespace of process `proc_pid` via `/proc/<pid>/net`.
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = Some(proc_pid.to_string());
+        self
+    }
+    pub fn read(self) -> Result<ProcNetIfInet6, ProcSysParserError> {
+        let proc_net_path = match &self.proc_pid {
+            Some(proc_pid) => format!("{}/{}/net", &self.proc_path, proc_pid),
+            None => format!("{}/net", &self.proc_path),
+        };
+        ProcNetIfInet6::read_proc_net_if_inet6(proc_net_path.as_str())
+    }
+}
+
+/// The main function for building a [`ProcNetIfInet6`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcNetIfInet6, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl Inet6Address {
+    /// The name of the scope: global, host, link, site or compat.
+    pub fn scope_name(&self) -> &'static str {
+        match self.scope {
+            0x00 => "global",
+            0x10 => "host",
+            0x20 => "link",
+            0x40 => "site",
+            0x80 => "compat",
+            _ => "unknown",
+        }
+    }
+    /// A temporary (privacy extensions) address (IFA_F_TEMPORARY).
+    pub fn is_temporary(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+    /// Duplicate address detection failed for the address (IFA_F_DADFAILED).
+    pub fn is_dad_failed(&self) -> bool {
+        self.flags & 0x08 != 0
+    }
+    /// The preferred lifetime of the address has expired (IFA_F_DEPRECATED).
+    pub fn is_deprecated(&self) -> bool {
+        self.flags & 0x20 != 0
+    }
+    /// Duplicate address detection is not finished yet, so the address cannot be used yet (IFA_F_TENTATIVE).
+    pub fn is_tentative(&self) -> bool {
+        self.flags & 0x40 != 0
+    }
+    /// A configured address, not obtained via autoconfiguration (IFA_F_PERMANENT).
+    pub fn is_permanent(&self) -> bool {
+        self.flags & 0x80 != 0
+    }
+}
+
+impl ProcNetIfInet6 {
+    pub fn new() -> ProcNetIfInet6 {
+        ProcNetIfInet6::default()
+    }
+    /// The addresses of the interface with the given name.
+    pub fn interface_addresses(&self, name: &str) -> Vec<&Inet6Address> {
+        self.addresses.iter().filter(|address| address.name == name).collect()
+    }
+    fn parse_address(address: &str) -> Result<Ipv6Addr, ProcSysParserError> {
+        if address.len() != 32 {
+            return Err(ProcSysParserError::FindItemError { item: format!("32 hexadecimal digits in IPv6 address {}", address) });
+        }
+        u128::from_str_radix(address, 16)
+            .map(Ipv6Addr::from)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("IPv6 address {}", address), error })
+    }
+    fn parse_hex_field<'a>(fields: &mut impl Iterator<Item = &'a str>, item: &str, line: &str) -> Result<u32, ProcSysParserError> {
+        let field = fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("if_inet6 {} in {}", item, line) })?;
+        u32::from_str_radix(field, 16)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("if_inet6 {} in {}", item, line), error })
+    }
+    pub fn parse_proc_net_if_inet6_line(line: &str) -> Result<Inet6Address, ProcSysParserError> {
+        let mut fields = line.split_whitespace();
+
+        Ok(Inet6Address {
+            address: ProcNetIfInet6::parse_address(fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("if_inet6 address in {}", line) })?)?,
+            if_index: ProcNetIfInet6::parse_hex_field(&mut fields, "if_index", line)?,
+            prefix_length: ProcNetIfInet6::parse_hex_field(&mut fields, "prefix_length", line)? as u8,
+            scope: ProcNetIfInet6::parse_hex_field(&mut fields, "scope", line)? as u8,
+            flags: ProcNetIfInet6::parse_hex_field(&mut fields, "flags", line)? as u8,
+            name: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("if_inet6 name in {}", line) })?
+                .to_string(),
+        })
+    }
+    pub fn parse_proc_net_anycast6_line(line: &str) -> Result<Anycast6Address, ProcSysParserError> {
+        let mut fields = line.split_whitespace();
+        let mut next_field = |item: &str| fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("anycast6 {} in {}", item, line) });
+
+        Ok(Anycast6Address {
+            if_index: next_field("if_index")?.parse::<u32>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("anycast6 if_index in {}", line), error })?,
+            name: next_field("name")?.to_string(),
+            address: ProcNetIfInet6::parse_address(next_field("address")?)?,
+            users: next_field("users")?.parse::<u32>()
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("anycast6 users in {}", line), error })?,
+        })
+    }
+    pub fn parse_proc_net_if_inet6(proc_net_if_inet6: &str, proc_net_anycast6: &str) -> Result<ProcNetIfInet6, ProcSysParserError> {
+        Ok(ProcNetIfInet6 {
+            addresses: proc_net_if_inet6.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ProcNetIfInet6::parse_proc_net_if_inet6_line)
+                .collect::<Result<Vec<_>, _>>()?,
+            anycast: proc_net_anycast6.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ProcNetIfInet6::parse_proc_net_anycast6_line)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+    pub fn read_proc_net_if_inet6(proc_net_path: &str) -> Result<ProcNetIfInet6, ProcSysParserError> {
+        let read_file = |file: String| read_to_string(&file)
+            .map_err(|error| ProcSysParserError::FileReadError { file, error });
+        ProcNetIfInet6::parse_proc_net_if_inet6(
+            &read_file(format!("{}/if_inet6", proc_net_path))?,
+            &read_file(format!("{}/anycast6", proc_net_path))?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_if_inet6_lines() {
+        let result = ProcNetIfInet6::parse_proc_net_if_inet6_line("fe80000000000000505400fffe123456 02 40 20 c0     eth0").unwrap();
+        assert_eq!(result, Inet6Address { address: "fe80::5054:ff:fe12:3456".parse().unwrap(), if_index: 2, prefix_length: 64, scope: 32, flags: 0xc0, name: "eth0".to_string() });
+        assert_eq!(result.scope_name(), "link");
+        assert!(result.is_permanent());
+        assert!(result.is_tentative());
+        assert!(!result.is_temporary());
+
+        let result = ProcNetIfInet6::parse_proc_net_anycast6_line("2    eth0            fe800000000000000000000000000000     1").unwrap();
+        assert_eq!(result, Anycast6Address { if_index: 2, name: "eth0".to_string(), address: "fe80::".parse().unwrap(), users: 1 });
+
+        assert!(ProcNetIfInet6::parse_proc_net_if_inet6_line("fe80 02 40 20 80 eth0").is_err());
+    }
+
+    #[test]
+    fn create_proc_net_if_inet6_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/net/if_inet6", test_path), "00000000000000000000000000000001 01 80 10 80       lo
+20010db8000000000000000000000010 02 40 00 80     eth0
+fe80000000000000505400fffe123456 02 40 20 80     eth0
+").unwrap_or_else(|_| panic!("Error writing to {}/1234/net/if_inet6", test_path));
+        write(format!("{}/1234/net/anycast6", test_path), "2    eth0            fe800000000000000000000000000000     1\n")
+            .unwrap_or_else(|_| panic!("Error writing to {}/1234/net/anycast6", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.addresses.len(), 3);
+        assert_eq!(result.addresses[0].address, Ipv6Addr::LOCALHOST);
+        assert_eq!(result.addresses[0].scope_name(), "host");
+        assert_eq!(result.interface_addresses("eth0").iter().map(|address| address.address.to_string()).collect::<Vec<_>>(), vec!["2001:db8::10", "fe80::5054:ff:fe12:3456"]);
+        assert_eq!(result.anycast.len(), 1);
+    }
+}
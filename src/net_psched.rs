@@ -0,0 +1,184 @@
+/*!
+Read data from `/proc/net/psched` into the struct [`ProcNetPsched`].
+
+The traffic control (tc) statistics and parameters of the packet schedulers, such as the burst and latency of a token
+bucket filter, are shown in scheduler ticks, not in time. `/proc/net/psched` shows four hexadecimal numbers, which are
+the parameters to convert between ticks and time:
+- the number of nanoseconds per microsecond (1000).
+- the number of nanoseconds per scheduler tick (64 since linux 2.6.31).
+- the clock resolution of the packet schedulers, which is 1000000 (microseconds per second).
+- the resolution of the timers in Hz: 1000000000 with high resolution timers, otherwise the timer frequency.
+
+`tc` of iproute2 only uses the first three numbers: the number of ticks per microsecond is the nanoseconds per
+microsecond divided by the nanoseconds per tick, 1000 / 64 = 15.625, times the clock resolution divided by 1000000.
+[`ProcNetPsched::ticks_per_usec`] calculates it in the same way. The fourth number is not used for the conversion.
+
+Documentation: <https://man7.org/linux/man-pages/man8/tc.8.html>
+
+Here is an example obtaining the data from `/proc/net/psched`:
+```no_run
+use proc_sys_parser::net_psched;
+
+let proc_net_psched = net_psched::read();
+
+println!("{:#?}", proc_net_psched);
+```
+Example output:
+```text
+ProcNetPsched {
+    nsec_per_usec: 1000,
+    nsec_per_tick: 64,
+    usec_per_sec: 1000000,
+    clock_resolution_hz: 1000000000,
+}
+```
+
+If you want to change the path and/or file that is read for [`ProcNetPsched`], which is `/proc/net/psched`
+by default, use:
+```no_run
+use proc_sys_parser::net_psched::Builder;
+
+let proc_net_psched = Builder::new().path("/myproc").read();
+```
+
+The ticks of a tc statistic or parameter can be converted to microseconds and back with:
+```no_run
+use proc_sys_parser::net_psched;
+
+let proc_net_psched = net_psched::read().unwrap();
+
+// with 64 nanoseconds per tick, 15625 ticks is 1000 us, and 1 ms is 15625 ticks.
+println!("15625 ticks is {} us", proc_net_psched.ticks_to_usec(15625));
+println!("1 ms is {} ticks", proc_net_psched.usec_to_ticks(1000.));
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/net/psched` parameters
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcNetPsched {
+    pub nsec_per_usec: u32,
+    pub nsec_per_tick: u32,
+    pub usec_per_sec: u32,
+    pub clock_resolution_hz: u32,
+}
+
+/// Builder pattern for [`ProcNetPsched`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path: String,
+    pub proc_file: String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_file: "net/psched".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn file(mut self, proc_file: &str) -> Builder {
+        self.proc_file = proc_file.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcNetPsched, ProcSysParserError> {
+        ProcNetPsched::read_proc_net_psched(format!("{}/{}", &self.proc_path, &self.proc_file).as_str())
+    }
+}
+
+/// The main function for building a [`ProcNetPsched`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the filename to specified.
+pub fn read() -> Result<ProcNetPsched, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcNetPsched {
+    pub fn new() -> ProcNetPsched {
+        ProcNetPsched::default()
+    }
+    /// The number of scheduler ticks per microsecond, calculated like `tc_core_init` of `tc` does, which uses the third
+    /// number as the clock resolution.
+    pub fn ticks_per_usec(&self) -> f64 {
+        if self.nsec_per_tick == 0 { return 0. };
+        // compatibility of tc with kernels that showed the clock resolution of 1000000000 as the third number.
+        let nsec_per_usec = if self.usec_per_sec == 1000000000 { self.nsec_per_tick } else { self.nsec_per_usec };
+        let clock_factor = self.usec_per_sec as f64 / 1000000.;
+        nsec_per_usec as f64 / self.nsec_per_tick as f64 * clock_factor
+    }
+    /// Convert scheduler ticks to microseconds.
+    pub fn ticks_to_usec(&self, ticks: u64) -> f64 {
+        match self.ticks_per_usec() {
+            ticks_per_usec if ticks_per_usec > 0. => ticks as f64 / ticks_per_usec,
+            _ => 0.,
+        }
+    }
+    /// Convert microseconds to scheduler ticks.
+    pub fn usec_to_ticks(&self, usec: f64) -> u64 {
+        (usec * self.ticks_per_usec()) as u64
+    }
+    pub fn parse_proc_net_psched(proc_net_psched: &str) -> Result<ProcNetPsched, ProcSysParserError> {
+        let line = proc_net_psched.trim();
+        let mut fields = line.split_whitespace();
+        let mut next_field = |name: &str| -> Result<u32, ProcSysParserError> {
+            let field = fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("psched {} in '{}'", name, line) })?;
+            u32::from_str_radix(field, 16)
+                .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("psched {} in '{}'", name, line), error })
+        };
+
+        Ok(ProcNetPsched {
+            nsec_per_usec: next_field("nsec_per_usec")?,
+            nsec_per_tick: next_field("nsec_per_tick")?,
+            usec_per_sec: next_field("usec_per_sec")?,
+            clock_resolution_hz: next_field("clock_resolution_hz")?,
+        })
+    }
+    pub fn read_proc_net_psched(proc_net_psched_file: &str) -> Result<ProcNetPsched, ProcSysParserError> {
+        let proc_net_psched_output = read_to_string(proc_net_psched_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_net_psched_file.to_string(), error })?;
+        ProcNetPsched::parse_proc_net_psched(&proc_net_psched_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_psched_line() {
+        let result = ProcNetPsched::parse_proc_net_psched("000003e8 00000040 000f4240 3b9aca00\n").unwrap();
+        assert_eq!(result, ProcNetPsched { nsec_per_usec: 1000, nsec_per_tick: 64, usec_per_sec: 1000000, clock_resolution_hz: 1000000000 });
+        assert_eq!(result.ticks_per_usec(), 15.625);
+        assert_eq!(result.ticks_to_usec(31250), 2000.);
+        assert_eq!(result.usec_to_ticks(1000.), 15625);
+        // an old kernel showing the clock resolution as the third number.
+        assert_eq!(ProcNetPsched::parse_proc_net_psched("000003e8 00000040 3b9aca00 3b9aca00\n").unwrap().ticks_per_usec(), 1000.);
+
+        assert!(ProcNetPsched::parse_proc_net_psched("000003e8 00000040").is_err());
+    }
+
+    #[test]
+    fn create_proc_net_psched_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        // a kernel without high resolution timers and HZ=250.
+        write(format!("{}/net/psched", test_path), "000003e8 00000040 000f4240 000000fa\n").unwrap_or_else(|_| panic!("Error writing to {}/net/psched", test_path));
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.clock_resolution_hz, 250);
+        assert_eq!(result.ticks_per_usec(), 15.625);
+    }
+}
@@ -0,0 +1,518 @@
+/*!
+Read the INET socket tables in `/proc/net` into the struct [`ProcNetSocket`].
+
+The socket tables show a line per socket, with the local and remote address and port, the state, the send and
+receive queue sizes, the timer, the uid of the owner and the inode of the socket. The inode can be matched with
+the `socket:[<inode>]` links in `/proc/<pid>/fd` to find the process owning the socket.
+
+The addresses are shown as hexadecimal numbers in the byte order of the host, and are converted to [`IpAddr`].
+The state uses the numbers of the TCP states for all protocols, for example a connected ICMP socket is 1
+(ESTABLISHED), and an unconnected ICMP socket is 7 (CLOSE).
+
+The following tables are read:
+- `/proc/net/tcp` and `/proc/net/tcp6`: the TCP sockets. For a listening socket, rx_queue is the number of
+  connections waiting to be accepted.
+- `/proc/net/udp` and `/proc/net/udp6`: the UDP sockets.
+- `/proc/net/udplite` and `/proc/net/udplite6`: the UDP-Lite sockets.
+- `/proc/net/raw` and `/proc/net/raw6`: the raw sockets, for which the local port is the IP protocol number,
+  such as 1 for ICMP or 58 for ICMPv6.
+- `/proc/net/icmp` and `/proc/net/icmp6`: the ICMP sockets (ping sockets), which are the unprivileged sockets used
+  by `ping` when `net.ipv4.ping_group_range` allows it.
+
+Together these are all INET sockets, like `ss --all --inet` shows, see [`ProcNetSocket::sockets`].
+
+A table that does not exist, such as the IPv6 tables when IPv6 is disabled, results in an empty table.
+
+Documentation: <https://www.kernel.org/doc/Documentation/networking/proc_net_tcp.txt>
+
+Here is an example obtaining the socket tables:
+```no_run
+use proc_sys_parser::net_socket;
+
+let proc_net_socket = net_socket::read();
+
+println!("{:#?}", proc_net_socket);
+```
+Example output:
+```text
+ProcNetSocket {
+    tcp: [
+        Socket { sl: 0, local_address: 0.0.0.0, local_port: 22, remote_address: 0.0.0.0, remote_port: 0, state: 10, tx_queue: 0, rx_queue: 0, timer_active: 0, timer_expires: 0, retransmits: 0, uid: 0, timeouts: 0, inode: 21436, ref_count: 1, drops: None },
+        Socket { sl: 1, local_address: 192.168.66.2, local_port: 22, remote_address: 192.168.66.1, remote_port: 54022, state: 1, tx_queue: 0, rx_queue: 0, timer_active: 2, timer_expires: 7092, retransmits: 0, uid: 0, timeouts: 0, inode: 38750, ref_count: 4, drops: None },
+    ],
+    tcp6: [],
+    udp: [
+        Socket { sl: 1254, local_address: 127.0.0.53, local_port: 53, remote_address: 0.0.0.0, remote_port: 0, state: 7, tx_queue: 0, rx_queue: 0, timer_active: 0, timer_expires: 0, retransmits: 0, uid: 101, timeouts: 0, inode: 19512, ref_count: 2, drops: Some(0) },
+    ],
+    udp6: [],
+    udplite: [],
+    udplite6: [],
+    raw: [],
+    raw6: [
+        Socket { sl: 58, local_address: ::, local_port: 58, remote_address: ::, remote_port: 0, state: 7, tx_queue: 0, rx_queue: 0, timer_active: 0, timer_expires: 0, retransmits: 0, uid: 0, timeouts: 0, inode: 20471, ref_count: 2, drops: Some(0) },
+    ],
+    icmp: [
+        Socket { sl: 0, local_address: 0.0.0.0, local_port: 3, remote_address: 0.0.0.0, remote_port: 0, state: 7, tx_queue: 0, rx_queue: 0, timer_active: 0, timer_expires: 0, retransmits: 0, uid: 1000, timeouts: 0, inode: 40012, ref_count: 2, drops: Some(0) },
+    ],
+    icmp6: [],
+}
+```
+
+If you want to change the path that is read for [`ProcNetSocket`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::net_socket::Builder;
+
+let proc_net_socket = Builder::new().path("/myproc").read();
+```
+
+Just like [`crate::net_dev`], the sockets of the network namespace of another process can be read using the pid of
+that process, which will read the tables in `/proc/<pid>/net`:
+```no_run
+use proc_sys_parser::net_socket::Builder;
+
+let proc_net_socket = Builder::new().pid(1234).read();
+```
+
+A host with many connections can have hundreds of thousands of TCP sockets. [`TcpSummary`] reads `/proc/net/tcp` and
+`/proc/net/tcp6` line by line, and only keeps the number of sockets per state, like `ss -s`, and per listening socket
+the number of connections waiting to be accepted and the number of connections in SYN_RECV for its port, which show
+if the application accepts connections fast enough:
+```no_run
+use proc_sys_parser::net_socket;
+
+let tcp_summary = net_socket::tcp_summary().unwrap();
+
+println!("established: {}, time_wait: {}", tcp_summary.state_count("ESTABLISHED"), tcp_summary.state_count("TIME_WAIT"));
+for listener in tcp_summary.listeners {
+    println!("{}:{} accept queue: {} syn_recv: {}", listener.local_address, listener.local_port, listener.accept_queue, listener.syn_recv);
+}
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, File};
+use std::io::{BufRead, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::ProcSysParserError;
+
+/// Struct for holding the INET socket tables
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcNetSocket {
+    /// `/proc/net/tcp`
+    pub tcp: Vec<Socket>,
+    /// `/proc/net/tcp6`
+    pub tcp6: Vec<Socket>,
+    /// `/proc/net/udp`
+    pub udp: Vec<Socket>,
+    /// `/proc/net/udp6`
+    pub udp6: Vec<Socket>,
+    /// `/proc/net/udplite`
+    pub udplite: Vec<Socket>,
+    /// `/proc/net/udplite6`
+    pub udplite6: Vec<Socket>,
+    /// `/proc/net/raw`
+    pub raw: Vec<Socket>,
+    /// `/proc/net/raw6`
+    pub raw6: Vec<Socket>,
+    /// `/proc/net/icmp`
+    pub icmp: Vec<Socket>,
+    /// `/proc/net/icmp6`
+    pub icmp6: Vec<Socket>,
+}
+
+/// Struct for holding a single socket of a socket table
+#[derive(Debug, PartialEq, Clone)]
+pub struct Socket {
+    /// The slot of the socket in the hash table of the kernel.
+    pub sl: u64,
+    pub local_address: IpAddr,
+    pub local_port: u16,
+    pub remote_address: IpAddr,
+    pub remote_port: u16,
+    /// The TCP state number, see [`Socket::state_name`].
+    pub state: u8,
+    /// The number of bytes in the send queue.
+    pub tx_queue: u64,
+    /// The number of bytes in the receive queue.
+    pub rx_queue: u64,
+    /// The timer that is active: 0 none, 1 retransmit, 2 keepalive, 3 TIME_WAIT, 4 zero window probe.
+    pub timer_active: u8,
+    /// The jiffies until the timer expires.
+    pub timer_expires: u64,
+    /// The number of unrecovered retransmit timeouts.
+    pub retransmits: u64,
+    /// The effective uid of the creator of the socket.
+    pub uid: u32,
+    /// The number of unanswered zero window probes.
+    pub timeouts: u64,
+    pub inode: u64,
+    /// The reference count of the socket.
+    pub ref_count: u64,
+    /// The number of dropped packets, only shown for datagram sockets.
+    pub drops: Option<u64>,
+}
+
+/// Struct for holding the number of TCP sockets per state and the backlog of the listening sockets
+#[derive(Debug, PartialEq, Default)]
+pub struct TcpSummary {
+    /// The number of TCP sockets of `/proc/net/tcp` and `/proc/net/tcp6`.
+    pub total: u64,
+    /// The number of sockets per state name, such as "ESTABLISHED".
+    pub states: BTreeMap<String, u64>,
+    /// The listening sockets, in the order of the tables.
+    pub listeners: Vec<ListenerBacklog>,
+}
+
+/// Struct for holding the backlog of a listening TCP socket
+#[derive(Debug, PartialEq, Clone)]
+pub struct ListenerBacklog {
+    pub local_address: IpAddr,
+    pub local_port: u16,
+    /// The number of connections that are established, but not accepted yet by the application.
+    pub accept_queue: u64,
+    /// The number of connections to the port of the socket that are still in the handshake (SYN_RECV).
+    pub syn_recv: u64,
+}
+
+/// Builder pattern for [`ProcNetSocket`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    /// Read the sockets of the network namespace of process `proc_pid` via `/proc/<pid>/net`.
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = Some(proc_pid.to_string());
+        self
+    }
+    fn proc_net_path(&self) -> String {
+        match &self.proc_pid {
+            Some(proc_pid) => format!("{}/{}/net", &self.proc_path, proc_pid),
+            None => format!("{}/net", &self.proc_path),
+        }
+    }
+    pub fn read(self) -> Result<ProcNetSocket, ProcSysParserError> {
+        ProcNetSocket::read_proc_net_socket(self.proc_net_path().as_str())
+    }
+    /// Read the TCP tables into a [`TcpSummary`], without keeping the sockets.
+    pub fn tcp_summary(self) -> Result<TcpSummary, ProcSysParserError> {
+        TcpSummary::read_tcp_summary(self.proc_net_path().as_str())
+    }
+}
+
+/// The main function for building a [`ProcNetSocket`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcNetSocket, ProcSysParserError> {
+   Builder::new().read()
+}
+
+/// The function for building a [`TcpSummary`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn tcp_summary() -> Result<TcpSummary, ProcSysParserError> {
+   Builder::new().tcp_summary()
+}
+
+impl Socket {
+    /// The name of the TCP state, such as ESTABLISHED or LISTEN.
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            1 => "ESTABLISHED",
+            2 => "SYN_SENT",
+            3 => "SYN_RECV",
+            4 => "FIN_WAIT1",
+            5 => "FIN_WAIT2",
+            6 => "TIME_WAIT",
+            7 => "CLOSE",
+            8 => "CLOSE_WAIT",
+            9 => "LAST_ACK",
+            10 => "LISTEN",
+            11 => "CLOSING",
+            12 => "NEW_SYN_RECV",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+impl TcpSummary {
+    pub fn new() -> TcpSummary {
+        TcpSummary::default()
+    }
+    /// The number of sockets in a state, such as "TIME_WAIT", 0 if there are none.
+    pub fn state_count(&self, state_name: &str) -> u64 {
+        self.states.get(state_name).copied().unwrap_or_default()
+    }
+    /// Add the sockets of a TCP table. The syn_recv count of a listener is counted per table, so the IPv4 and IPv6
+    /// connections to the same port are counted at their own listener.
+    pub fn add_proc_net_tcp(&mut self, lines: impl Iterator<Item = Result<String, ProcSysParserError>>) -> Result<(), ProcSysParserError> {
+        let mut listeners = Vec::new();
+        let mut syn_recv_per_port: BTreeMap<u16, u64> = BTreeMap::new();
+
+        // the first line is the header.
+        for line in lines.skip(1) {
+            let line = line?;
+            if line.trim().is_empty() { continue };
+            let socket = ProcNetSocket::parse_proc_net_socket_line(&line, false)?;
+            self.total += 1;
+            *self.states.entry(socket.state_name().to_string()).or_default() += 1;
+            match socket.state_name() {
+                // for a listening socket, the receive queue is the accept queue.
+                "LISTEN" => listeners.push(ListenerBacklog { local_address: socket.local_address, local_port: socket.local_port, accept_queue: socket.rx_queue, syn_recv: 0 }),
+                "SYN_RECV" => *syn_recv_per_port.entry(socket.local_port).or_default() += 1,
+                _ => {},
+            }
+        }
+        for mut listener in listeners {
+            listener.syn_recv = syn_recv_per_port.get(&listener.local_port).copied().unwrap_or_default();
+            self.listeners.push(listener);
+        }
+        Ok(())
+    }
+    pub fn read_tcp_summary(proc_net_path: &str) -> Result<TcpSummary, ProcSysParserError> {
+        let mut tcpsummary = TcpSummary::new();
+
+        for table in ["tcp", "tcp6"] {
+            let file = format!("{}/{}", proc_net_path, table);
+            let lines = match File::open(&file) {
+                Ok(opened) => BufReader::new(opened).lines(),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(ProcSysParserError::FileReadError { file, error }),
+            };
+            tcpsummary.add_proc_net_tcp(lines.map(|line| line.map_err(|error| ProcSysParserError::FileReadError { file: file.clone(), error })))?;
+        }
+        Ok(tcpsummary)
+    }
+}
+
+impl ProcNetSocket {
+    pub fn new() -> ProcNetSocket {
+        ProcNetSocket::default()
+    }
+    /// All sockets with the name of the table they are in, such as "tcp6" or "raw".
+    pub fn sockets(&self) -> impl Iterator<Item = (&'static str, &Socket)> {
+        [
+            ("tcp", &self.tcp),
+            ("tcp6", &self.tcp6),
+            ("udp", &self.udp),
+            ("udp6", &self.udp6),
+            ("udplite", &self.udplite),
+            ("udplite6", &self.udplite6),
+            ("raw", &self.raw),
+            ("raw6", &self.raw6),
+            ("icmp", &self.icmp),
+            ("icmp6", &self.icmp6),
+        ]
+            .into_iter()
+            .flat_map(|(table, sockets)| sockets.iter().map(move |socket| (table, socket)))
+    }
+    /// Parse an address and port such as `0100007F:0035` or `00000000000000000000000001000000:0035`.
+    /// The address is shown as 32 bit words in the byte order of the host.
+    fn parse_address_port(address_port: &str) -> Result<(IpAddr, u16), ProcSysParserError> {
+        let (address, port) = address_port.split_once(':')
+            .ok_or(ProcSysParserError::FindItemError { item: format!("':' in socket address {}", address_port) })?;
+        let parse_word = |word: &str| u32::from_str_radix(word, 16)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("socket address {}", address_port), error });
+
+        let address = match address.len() {
+            8 => IpAddr::V4(Ipv4Addr::from(parse_word(address)?.to_ne_bytes())),
+            32 => {
+                let mut octets = [0_u8; 16];
+                for word_nr in 0..4 {
+                    octets[word_nr * 4..word_nr * 4 + 4].copy_from_slice(&parse_word(&address[word_nr * 8..word_nr * 8 + 8])?.to_ne_bytes());
+                }
+                IpAddr::V6(Ipv6Addr::from(octets))
+            },
+            _ => return Err(ProcSysParserError::FindItemError { item: format!("8 or 32 hexadecimal digits in socket address {}", address_port) }),
+        };
+        let port = u16::from_str_radix(port, 16)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("socket port {}", address_port), error })?;
+        Ok((address, port))
+    }
+    /// Parse a line of a socket table. `datagram` means the table shows the drops as last field.
+    pub fn parse_proc_net_socket_line(line: &str, datagram: bool) -> Result<Socket, ProcSysParserError> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < if datagram { 13 } else { 12 } {
+            return Err(ProcSysParserError::IteratorItemError { item: format!("socket fields in {}", line) });
+        }
+        let parse_hex = |field: &str, item: &str| u64::from_str_radix(field, 16)
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("socket {} in {}", item, line), error });
+        let parse_decimal = |field: &str, item: &str| field.parse::<u64>()
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("socket {} in {}", item, line), error });
+        let split_pair = |field_nr: usize| fields[field_nr].split_once(':')
+            .ok_or(ProcSysParserError::FindItemError { item: format!("':' in {} in {}", fields[field_nr], line) });
+
+        let (local_address, local_port) = ProcNetSocket::parse_address_port(fields[1])?;
+        let (remote_address, remote_port) = ProcNetSocket::parse_address_port(fields[2])?;
+        let (tx_queue, rx_queue) = split_pair(4)?;
+        let (timer_active, timer_expires) = split_pair(5)?;
+
+        Ok(Socket {
+            sl: parse_decimal(fields[0].trim_end_matches(':'), "sl")?,
+            local_address,
+            local_port,
+            remote_address,
+            remote_port,
+            state: parse_hex(fields[3], "state")? as u8,
+            tx_queue: parse_hex(tx_queue, "tx_queue")?,
+            rx_queue: parse_hex(rx_queue, "rx_queue")?,
+            timer_active: parse_hex(timer_active, "timer_active")? as u8,
+            timer_expires: parse_hex(timer_expires, "timer_expires")?,
+            retransmits: parse_hex(fields[6], "retransmits")?,
+            uid: parse_decimal(fields[7], "uid")? as u32,
+            timeouts: parse_decimal(fields[8], "timeouts")?,
+            inode: parse_decimal(fields[9], "inode")?,
+            ref_count: parse_decimal(fields[10], "ref")?,
+            drops: if datagram { Some(parse_decimal(fields[12], "drops")?) } else { None },
+        })
+    }
+    /// Parse a socket table, skipping the header line.
+    pub fn parse_proc_net_socket_table(proc_net_socket_table: &str, datagram: bool) -> Result<Vec<Socket>, ProcSysParserError> {
+        proc_net_socket_table.lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| ProcNetSocket::parse_proc_net_socket_line(line, datagram))
+            .collect()
+    }
+    /// Read a socket table. A table that does not exist results in an empty table.
+    pub fn read_proc_net_socket_table(proc_net_socket_file: &str, datagram: bool) -> Result<Vec<Socket>, ProcSysParserError> {
+        match read_to_string(proc_net_socket_file) {
+            Ok(contents) => ProcNetSocket::parse_proc_net_socket_table(&contents, datagram),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(error) => Err(ProcSysParserError::FileReadError { file: proc_net_socket_file.to_string(), error }),
+        }
+    }
+    pub fn read_proc_net_socket(proc_net_path: &str) -> Result<ProcNetSocket, ProcSysParserError> {
+        Ok(ProcNetSocket {
+            tcp: ProcNetSocket::read_proc_net_socket_table(&format!("{}/tcp", proc_net_path), false)?,
+            tcp6: ProcNetSocket::read_proc_net_socket_table(&format!("{}/tcp6", proc_net_path), false)?,
+            udp: ProcNetSocket::read_proc_net_socket_table(&format!("{}/udp", proc_net_path), true)?,
+            udp6: ProcNetSocket::read_proc_net_socket_table(&format!("{}/udp6", proc_net_path), true)?,
+            udplite: ProcNetSocket::read_proc_net_socket_table(&format!("{}/udplite", proc_net_path), true)?,
+            udplite6: ProcNetSocket::read_proc_net_socket_table(&format!("{}/udplite6", proc_net_path), true)?,
+            raw: ProcNetSocket::read_proc_net_socket_table(&format!("{}/raw", proc_net_path), true)?,
+            raw6: ProcNetSocket::read_proc_net_socket_table(&format!("{}/raw6", proc_net_path), true)?,
+            icmp: ProcNetSocket::read_proc_net_socket_table(&format!("{}/icmp", proc_net_path), true)?,
+            icmp6: ProcNetSocket::read_proc_net_socket_table(&format!("{}/icmp6", proc_net_path), true)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_icmp_lines() {
+        let result = ProcNetSocket::parse_proc_net_socket_line("   73: 0100007F:0004 0100007F:0000 01 00000000:00000100 00:00000000 00000000  1000        0 40012 2 0000000000000000 3", true).unwrap();
+        assert_eq!(result, Socket { sl: 73, local_address: "127.0.0.1".parse().unwrap(), local_port: 4, remote_address: "127.0.0.1".parse().unwrap(), remote_port: 0, state: 1, tx_queue: 0, rx_queue: 256, timer_active: 0, timer_expires: 0, retransmits: 0, uid: 1000, timeouts: 0, inode: 40012, ref_count: 2, drops: Some(3) });
+        assert_eq!(result.state_name(), "ESTABLISHED");
+
+        let result = ProcNetSocket::parse_proc_net_socket_line("  203: 00000000000000000000000001000000:0005 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 40013 2 0000000000000000 0", true).unwrap();
+        assert_eq!(result.local_address, "::1".parse::<IpAddr>().unwrap());
+        assert_eq!(result.remote_address, "::".parse::<IpAddr>().unwrap());
+        assert_eq!(result.state_name(), "CLOSE");
+
+        assert!(ProcNetSocket::parse_proc_net_socket_line("   73: 0100007F:0004 0100007F:0000 01", true).is_err());
+    }
+
+    #[test]
+    fn create_proc_net_icmp_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/net/icmp", test_path), "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+   73: 00000000:0003 00000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 40012 2 0000000000000000 0
+").unwrap_or_else(|_| panic!("Error writing to {}/net/icmp", test_path));
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.icmp.len(), 1);
+        assert_eq!(result.icmp[0].local_address, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(result.icmp[0].inode, 40012);
+        // icmp6 does not exist.
+        assert_eq!(result.icmp6, vec![]);
+    }
+
+    #[test]
+    fn create_proc_net_socket_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        for (file, contents) in [
+            ("tcp", "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21436 1 0000000000000000 100 0 0 10 0
+   1: 0242A8C0:0016 0142A8C0:D306 01 00000000:00000000 02:00001BB4 00000000     0        0 38750 4 0000000000000000 20 4 31 10 -1
+   2: 0242A8C0:0016 0142A8C0:D2F4 06 00000000:00000000 03:000016A1 00000000     0        0 0 3 0000000000000000
+"),
+            ("udplite", "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+ 1254: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 19512 2 0000000000000000 5
+"),
+            ("raw6", "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+   58: 00000000000000000000000000000000:003A 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 20471 2 0000000000000000 0
+"),
+        ] {
+            write(format!("{}/net/{}", test_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/net/{}", test_path, file));
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.tcp.iter().map(|socket| socket.state_name()).collect::<Vec<_>>(), vec!["LISTEN", "ESTABLISHED", "TIME_WAIT"]);
+        assert_eq!(result.tcp[1].remote_address, "192.168.66.1".parse::<IpAddr>().unwrap());
+        assert_eq!(result.tcp[1].remote_port, 54022);
+        assert_eq!(result.tcp[1].drops, None);
+        assert_eq!(result.udplite[0].drops, Some(5));
+        assert_eq!(result.raw6[0].local_port, 58);
+        assert_eq!(result.sockets().map(|(table, _)| table).collect::<Vec<_>>(), vec!["tcp", "tcp", "tcp", "udplite", "raw6"]);
+    }
+
+    #[test]
+    fn create_proc_net_tcp_files_and_summarize() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/net/tcp", test_path), "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21436 1 0000000000000000 100 0 0 10 0
+   1: 00000000:1F90 00000000:0000 0A 00000000:00000005 00:00000000 00000000  1000        0 21437 1 0000000000000000 100 0 0 10 0
+   2: 0242A8C0:1F90 0142A8C0:D306 03 00000000:00000000 01:000000C8 00000000  1000        0 0 1 0000000000000000
+   3: 0242A8C0:1F90 0142A8C0:D307 03 00000000:00000000 01:000000C8 00000000  1000        0 0 1 0000000000000000
+   4: 0242A8C0:0016 0142A8C0:D2F4 01 00000000:00000000 02:00001BB4 00000000     0        0 38750 4 0000000000000000 20 4 31 10 -1
+   5: 0242A8C0:0016 0142A8C0:D2F5 06 00000000:00000000 03:000016A1 00000000     0        0 0 3 0000000000000000
+").unwrap_or_else(|_| panic!("Error writing to {}/net/tcp", test_path));
+        write(format!("{}/net/tcp6", test_path), "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 21438 1 0000000000000000 100 0 0 10 0
+").unwrap_or_else(|_| panic!("Error writing to {}/net/tcp6", test_path));
+        let result = Builder::new().path(&test_path).tcp_summary().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.total, 7);
+        assert_eq!(result.state_count("LISTEN"), 3);
+        assert_eq!(result.state_count("SYN_RECV"), 2);
+        assert_eq!(result.state_count("ESTABLISHED"), 1);
+        assert_eq!(result.state_count("CLOSE_WAIT"), 0);
+        assert_eq!(result.listeners, vec![
+            ListenerBacklog { local_address: "0.0.0.0".parse().unwrap(), local_port: 22, accept_queue: 0, syn_recv: 0 },
+            ListenerBacklog { local_address: "0.0.0.0".parse().unwrap(), local_port: 8080, accept_queue: 5, syn_recv: 2 },
+            ListenerBacklog { local_address: "::".parse().unwrap(), local_port: 8080, accept_queue: 0, syn_recv: 0 },
+        ]);
+    }
+}
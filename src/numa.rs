@@ -0,0 +1,470 @@
+/*!
+Read the numa node statistics from `/sys/devices/system/node/node<nr>` into the struct [`SysNuma`].
+
+On a numa system the memory is divided into nodes, each node being close to a group of cpus.
+For every node, the kernel shows the memory usage in `meminfo`, which is the per-node version of `/proc/meminfo`,
+and the allocation statistics in `numastat`, which show if memory was allocated on the intended node.
+
+Systems that are not numa show a single node, node0.
+
+The meminfo values are in kilobytes (kB), just like the values in the original file, except for the HugePages
+statistics, which are counts of huge pages. The numastat values are counts of pages.
+
+Besides the statistics, every node shows its place in the numa topology:
+- `distance`: the relative distance to every online node, in the order of the online nodes in `online`, which are
+  not necessarily numbered without gaps, such as nodes 0 and 2. The distances are stored by node number; the distance
+  to the node itself is 10.
+- `cpulist`: the cpus that are local to the node.
+- `hugepages/hugepages-<size>kB`: the huge pages reserved on the node for every huge page size.
+
+Documentation:
+- `/sys/devices/system/node`: <https://www.kernel.org/doc/Documentation/ABI/stable/sysfs-devices-node>
+- numastat: <https://docs.kernel.org/admin-guide/numastat.html>
+
+Here is an example obtaining the data from `/sys/devices/system/node`:
+```no_run
+use proc_sys_parser::numa;
+
+let sys_numa = numa::read();
+
+println!("{:#?}", sys_numa);
+```
+Example output:
+```text
+SysNuma {
+    nodes: [
+        NumaNode {
+            node_nr: 0,
+            meminfo: NodeMemInfo {
+                memtotal: 5471992,
+                memfree: 3441708,
+                memused: 2030284,
+                ..
+            },
+            numastat: NodeNumaStat {
+                numa_hit: 11834861,
+                numa_miss: 0,
+                numa_foreign: 0,
+                interleave_hit: 1025,
+                local_node: 11834861,
+                other_node: 0,
+            },
+            distance: {0: 10, 1: 21},
+            cpulist: [0, 1, 2, 3],
+            hugepages: [
+                NodeHugePages { size_kb: 2048, nr_hugepages: 0, free_hugepages: 0, surplus_hugepages: 0 },
+                NodeHugePages { size_kb: 1048576, nr_hugepages: 0, free_hugepages: 0, surplus_hugepages: 0 },
+            ],
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysNuma`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::numa::Builder;
+
+let sys_numa = Builder::new().path("/mysys").read();
+```
+*/
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::fs::read_dir;
+use log::warn;
+use crate::ProcSysParserError;
+use crate::utils::{parse_cpu_list, read_file_option_string, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the statistics of all numa nodes
+#[derive(Debug, PartialEq, Default)]
+pub struct SysNuma {
+    pub nodes: Vec<NumaNode>,
+}
+
+/// Struct for holding the statistics of a single numa node
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NumaNode {
+    pub node_nr: u32,
+    pub meminfo: NodeMemInfo,
+    pub numastat: NodeNumaStat,
+    /// The distance to every online node by node number.
+    pub distance: BTreeMap<u32, u64>,
+    /// The cpus local to the node, empty for a memory-only node.
+    pub cpulist: Vec<u32>,
+    /// The huge pages per huge page size, sorted by size.
+    pub hugepages: Vec<NodeHugePages>,
+}
+
+/// Struct for holding `/sys/devices/system/node/node<nr>/hugepages/hugepages-<size>kB`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NodeHugePages {
+    /// The huge page size in kilobytes.
+    pub size_kb: u64,
+    /// The number of huge pages reserved on the node.
+    pub nr_hugepages: u64,
+    /// The number of reserved huge pages that are not allocated.
+    pub free_hugepages: u64,
+    /// The number of huge pages allocated above nr_hugepages (overcommit).
+    pub surplus_hugepages: u64,
+}
+
+/// Struct for holding `/sys/devices/system/node/node<nr>/meminfo`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NodeMemInfo {
+    pub memtotal: u64,
+    pub memfree: u64,
+    pub memused: u64,
+    pub swapcached: u64,
+    pub active: u64,
+    pub inactive: u64,
+    pub active_anon: u64,
+    pub inactive_anon: u64,
+    pub active_file: u64,
+    pub inactive_file: u64,
+    pub unevictable: u64,
+    pub mlocked: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub filepages: u64,
+    pub mapped: u64,
+    pub anonpages: u64,
+    pub shmem: u64,
+    pub kernelstack: u64,
+    pub pagetables: u64,
+    /// Kernel 6.1+
+    pub secpagetables: Option<u64>,
+    pub nfs_unstable: u64,
+    pub bounce: u64,
+    pub writebacktmp: u64,
+    /// Kernel 4.20+
+    pub kreclaimable: Option<u64>,
+    pub slab: u64,
+    pub sreclaimable: u64,
+    pub sunreclaim: u64,
+    pub anonhugepages: u64,
+    pub shmemhugepages: u64,
+    pub shmempmdmapped: u64,
+    /// Kernel 5.4+
+    pub filehugepages: Option<u64>,
+    /// Kernel 5.4+
+    pub filepmdmapped: Option<u64>,
+    /// Number of huge pages of the default size.
+    pub hugepages_total: u64,
+    /// Number of free huge pages of the default size.
+    pub hugepages_free: u64,
+    /// Number of surplus huge pages of the default size.
+    pub hugepages_surp: u64,
+}
+
+/// Struct for holding `/sys/devices/system/node/node<nr>/numastat`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NodeNumaStat {
+    /// Pages allocated on this node as intended.
+    pub numa_hit: u64,
+    /// Pages allocated on this node, while another node was intended because it was low on memory.
+    pub numa_miss: u64,
+    /// Pages intended for this node, but allocated on another node.
+    pub numa_foreign: u64,
+    /// Interleaved pages allocated on this node as intended.
+    pub interleave_hit: u64,
+    /// Pages allocated on this node while the process was running on it.
+    pub local_node: u64,
+    /// Pages allocated on this node while the process was running on another node.
+    pub other_node: u64,
+}
+
+/// Builder pattern for [`SysNuma`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysNuma, ProcSysParserError> {
+        SysNuma::read_sys_numa(format!("{}/devices/system/node", self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysNuma`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysNuma, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysNuma {
+    pub fn new() -> SysNuma {
+        SysNuma::default()
+    }
+    /// The statistics of a node, None if the node is not found.
+    pub fn node(&self, node_nr: u32) -> Option<&NumaNode> {
+        self.nodes.iter().find(|node| node.node_nr == node_nr)
+    }
+    /// The distance between two nodes, None if a node is not found.
+    pub fn distance(&self, from_node_nr: u32, to_node_nr: u32) -> Option<u64> {
+        self.node(from_node_nr)?.distance.get(&to_node_nr).copied()
+    }
+    /// The node a cpu is local to, None if the cpu is not found.
+    pub fn node_of_cpu(&self, cpu_nr: u32) -> Option<u32> {
+        self.nodes.iter()
+            .find(|node| node.cpulist.contains(&cpu_nr))
+            .map(|node| node.node_nr)
+    }
+    fn parse_value(value: &str, item: &str) -> Result<u64, ProcSysParserError> {
+        value.split_whitespace()
+            .next()
+            .ok_or(ProcSysParserError::IteratorItemError {item: format!("numa {}", item) })?
+            .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)
+    }
+    pub fn parse_node_meminfo(node_meminfo: &str) -> Result<NodeMemInfo, ProcSysParserError> {
+        let mut nodememinfo = NodeMemInfo::default();
+
+        for line in node_meminfo.lines() {
+            // the lines have the format: "Node 0 MemTotal:        5471992 kB"
+            let Some((name, value)) = line.split_once(':') else { continue };
+            let Some(key) = name.split_whitespace().nth(2) else { continue };
+            match key {
+                "MemTotal" => nodememinfo.memtotal = SysNuma::parse_value(value, key)?,
+                "MemFree" => nodememinfo.memfree = SysNuma::parse_value(value, key)?,
+                "MemUsed" => nodememinfo.memused = SysNuma::parse_value(value, key)?,
+                "SwapCached" => nodememinfo.swapcached = SysNuma::parse_value(value, key)?,
+                "Active" => nodememinfo.active = SysNuma::parse_value(value, key)?,
+                "Inactive" => nodememinfo.inactive = SysNuma::parse_value(value, key)?,
+                "Active(anon)" => nodememinfo.active_anon = SysNuma::parse_value(value, key)?,
+                "Inactive(anon)" => nodememinfo.inactive_anon = SysNuma::parse_value(value, key)?,
+                "Active(file)" => nodememinfo.active_file = SysNuma::parse_value(value, key)?,
+                "Inactive(file)" => nodememinfo.inactive_file = SysNuma::parse_value(value, key)?,
+                "Unevictable" => nodememinfo.unevictable = SysNuma::parse_value(value, key)?,
+                "Mlocked" => nodememinfo.mlocked = SysNuma::parse_value(value, key)?,
+                "Dirty" => nodememinfo.dirty = SysNuma::parse_value(value, key)?,
+                "Writeback" => nodememinfo.writeback = SysNuma::parse_value(value, key)?,
+                "FilePages" => nodememinfo.filepages = SysNuma::parse_value(value, key)?,
+                "Mapped" => nodememinfo.mapped = SysNuma::parse_value(value, key)?,
+                "AnonPages" => nodememinfo.anonpages = SysNuma::parse_value(value, key)?,
+                "Shmem" => nodememinfo.shmem = SysNuma::parse_value(value, key)?,
+                "KernelStack" => nodememinfo.kernelstack = SysNuma::parse_value(value, key)?,
+                "PageTables" => nodememinfo.pagetables = SysNuma::parse_value(value, key)?,
+                "SecPageTables" => nodememinfo.secpagetables = Some(SysNuma::parse_value(value, key)?),
+                "NFS_Unstable" => nodememinfo.nfs_unstable = SysNuma::parse_value(value, key)?,
+                "Bounce" => nodememinfo.bounce = SysNuma::parse_value(value, key)?,
+                "WritebackTmp" => nodememinfo.writebacktmp = SysNuma::parse_value(value, key)?,
+                "KReclaimable" => nodememinfo.kreclaimable = Some(SysNuma::parse_value(value, key)?),
+                "Slab" => nodememinfo.slab = SysNuma::parse_value(value, key)?,
+                "SReclaimable" => nodememinfo.sreclaimable = SysNuma::parse_value(value, key)?,
+                "SUnreclaim" => nodememinfo.sunreclaim = SysNuma::parse_value(value, key)?,
+                "AnonHugePages" => nodememinfo.anonhugepages = SysNuma::parse_value(value, key)?,
+                "ShmemHugePages" => nodememinfo.shmemhugepages = SysNuma::parse_value(value, key)?,
+                "ShmemPmdMapped" => nodememinfo.shmempmdmapped = SysNuma::parse_value(value, key)?,
+                "FileHugePages" => nodememinfo.filehugepages = Some(SysNuma::parse_value(value, key)?),
+                "FilePmdMapped" => nodememinfo.filepmdmapped = Some(SysNuma::parse_value(value, key)?),
+                "HugePages_Total" => nodememinfo.hugepages_total = SysNuma::parse_value(value, key)?,
+                "HugePages_Free" => nodememinfo.hugepages_free = SysNuma::parse_value(value, key)?,
+                "HugePages_Surp" => nodememinfo.hugepages_surp = SysNuma::parse_value(value, key)?,
+                _ => warn!("numa meminfo: unknown entry found: {}", line),
+            }
+        }
+        Ok(nodememinfo)
+    }
+    pub fn parse_node_numastat(node_numastat: &str) -> Result<NodeNumaStat, ProcSysParserError> {
+        let mut nodenumastat = NodeNumaStat::default();
+
+        for line in node_numastat.lines() {
+            let Some((key, value)) = line.split_once(' ') else { continue };
+            match key {
+                "numa_hit" => nodenumastat.numa_hit = SysNuma::parse_value(value, key)?,
+                "numa_miss" => nodenumastat.numa_miss = SysNuma::parse_value(value, key)?,
+                "numa_foreign" => nodenumastat.numa_foreign = SysNuma::parse_value(value, key)?,
+                "interleave_hit" => nodenumastat.interleave_hit = SysNuma::parse_value(value, key)?,
+                "local_node" => nodenumastat.local_node = SysNuma::parse_value(value, key)?,
+                "other_node" => nodenumastat.other_node = SysNuma::parse_value(value, key)?,
+                _ => warn!("numa numastat: unknown entry found: {}", line),
+            }
+        }
+        Ok(nodenumastat)
+    }
+    /// Read a node; the distances in `distance` are in the order of the node numbers in `online_nodes`.
+    fn read_numa_node(node_path: &Path, node_nr: u32, online_nodes: &[u32]) -> Result<NumaNode, ProcSysParserError> {
+        let distances = read_file_string(&node_path.join("distance"))?
+            .split_whitespace()
+            .map(|distance| distance.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
+            .collect::<Result<Vec<u64>, ProcSysParserError>>()?;
+
+        Ok(NumaNode {
+            node_nr,
+            meminfo: SysNuma::parse_node_meminfo(&read_file_string(&node_path.join("meminfo"))?)?,
+            numastat: SysNuma::parse_node_numastat(&read_file_string(&node_path.join("numastat"))?)?,
+            distance: online_nodes.iter().copied().zip(distances).collect(),
+            cpulist: parse_cpu_list(&read_file_string(&node_path.join("cpulist"))?)?,
+            hugepages: SysNuma::read_node_hugepages(&node_path.join("hugepages"))?,
+        })
+    }
+    pub(crate) fn read_node_hugepages(hugepages_path: &Path) -> Result<Vec<NodeHugePages>, ProcSysParserError> {
+        let mut hugepages = Vec::new();
+        // the hugepages directory does not exist if the kernel is built without hugetlbfs support.
+        let Ok(hugepages_directories) = read_dir(hugepages_path) else { return Ok(hugepages) };
+
+        for hugepages_directory in hugepages_directories.filter_map(|entry| entry.ok()) {
+            let directory_name = hugepages_directory.file_name().to_string_lossy().to_string();
+            let Some(size_kb) = directory_name.strip_prefix("hugepages-").and_then(|size| size.strip_suffix("kB")) else { continue };
+            hugepages.push(NodeHugePages {
+                size_kb: size_kb.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                nr_hugepages: read_file_u64(&hugepages_directory.path().join("nr_hugepages"))?,
+                free_hugepages: read_file_u64(&hugepages_directory.path().join("free_hugepages"))?,
+                surplus_hugepages: read_file_u64(&hugepages_directory.path().join("surplus_hugepages"))?,
+            });
+        }
+        hugepages.sort_by_key(|hugepages| hugepages.size_kb);
+        Ok(hugepages)
+    }
+    pub fn read_sys_numa(sys_node_path: &str) -> Result<SysNuma, ProcSysParserError> {
+        let mut sysnuma = SysNuma::new();
+
+        let node_nrs = read_numbered_entries(Path::new(sys_node_path), "node")?;
+        // the distances are shown for the online nodes; if the online file cannot be read, take the node directories.
+        let online_nodes = match read_file_option_string(&Path::new(sys_node_path).join("online")) {
+            Some(online) => parse_cpu_list(&online)?,
+            None => node_nrs.clone(),
+        };
+
+        for node_nr in node_nrs {
+            sysnuma.nodes.push(SysNuma::read_numa_node(&Path::new(sys_node_path).join(format!("node{}", node_nr)), node_nr, &online_nodes)?);
+        }
+        Ok(sysnuma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_sys_numa_files_and_read() {
+        let node_meminfo = "Node 0 MemTotal:        5471992 kB
+Node 0 MemFree:         3441708 kB
+Node 0 MemUsed:         2030284 kB
+Node 0 SwapCached:            0 kB
+Node 0 Active:           575852 kB
+Node 0 Inactive:        1255636 kB
+Node 0 Active(anon):         24 kB
+Node 0 Inactive(anon):   166428 kB
+Node 0 Active(file):     575828 kB
+Node 0 Inactive(file):  1089208 kB
+Node 0 Unevictable:        9028 kB
+Node 0 Mlocked:            9028 kB
+Node 0 Dirty:            129304 kB
+Node 0 Writeback:             0 kB
+Node 0 FilePages:       1674084 kB
+Node 0 Mapped:           142648 kB
+Node 0 AnonPages:        166464 kB
+Node 0 Shmem:              9048 kB
+Node 0 KernelStack:        1152 kB
+Node 0 PageTables:         2144 kB
+Node 0 SecPageTables:         0 kB
+Node 0 NFS_Unstable:          0 kB
+Node 0 Bounce:                0 kB
+Node 0 WritebackTmp:          0 kB
+Node 0 KReclaimable:      67476 kB
+Node 0 Slab:              88184 kB
+Node 0 SReclaimable:      67476 kB
+Node 0 SUnreclaim:        20708 kB
+Node 0 AnonHugePages:         0 kB
+Node 0 ShmemHugePages:        0 kB
+Node 0 ShmemPmdMapped:        0 kB
+Node 0 FileHugePages:      6144 kB
+Node 0 FilePmdMapped:         0 kB
+Node 0 HugePages_Total:     0
+Node 0 HugePages_Free:      0
+Node 0 HugePages_Surp:      0
+";
+        let node_numastat = "numa_hit 11834861
+numa_miss 0
+numa_foreign 0
+interleave_hit 1025
+local_node 11834861
+other_node 0
+";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let node_path = format!("{}/devices/system/node/node0", test_path);
+        create_dir_all(&node_path).expect("Error creating mock directory.");
+        write(format!("{}/meminfo", node_path), node_meminfo).unwrap_or_else(|_| panic!("Error writing to {}/meminfo", node_path));
+        write(format!("{}/numastat", node_path), node_numastat).unwrap_or_else(|_| panic!("Error writing to {}/numastat", node_path));
+        write(format!("{}/distance", node_path), "10 21\n").unwrap_or_else(|_| panic!("Error writing to {}/distance", node_path));
+        write(format!("{}/devices/system/node/online", test_path), "0-1\n").unwrap_or_else(|_| panic!("Error writing to {}/devices/system/node/online", test_path));
+        write(format!("{}/cpulist", node_path), "0-3\n").unwrap_or_else(|_| panic!("Error writing to {}/cpulist", node_path));
+        for (size, nr_hugepages) in [("2048kB", "128\n"), ("1048576kB", "2\n")] {
+            let hugepages_path = format!("{}/hugepages/hugepages-{}", node_path, size);
+            create_dir_all(&hugepages_path).expect("Error creating mock directory.");
+            write(format!("{}/nr_hugepages", hugepages_path), nr_hugepages).unwrap_or_else(|_| panic!("Error writing to {}/nr_hugepages", hugepages_path));
+            write(format!("{}/free_hugepages", hugepages_path), "1\n").unwrap_or_else(|_| panic!("Error writing to {}/free_hugepages", hugepages_path));
+            write(format!("{}/surplus_hugepages", hugepages_path), "0\n").unwrap_or_else(|_| panic!("Error writing to {}/surplus_hugepages", hugepages_path));
+        }
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysNuma { nodes: vec![
+            NumaNode {
+                node_nr: 0,
+                meminfo: NodeMemInfo { memtotal: 5471992, memfree: 3441708, memused: 2030284, swapcached: 0, active: 575852, inactive: 1255636, active_anon: 24, inactive_anon: 166428, active_file: 575828, inactive_file: 1089208, unevictable: 9028, mlocked: 9028, dirty: 129304, writeback: 0, filepages: 1674084, mapped: 142648, anonpages: 166464, shmem: 9048, kernelstack: 1152, pagetables: 2144, secpagetables: Some(0), nfs_unstable: 0, bounce: 0, writebacktmp: 0, kreclaimable: Some(67476), slab: 88184, sreclaimable: 67476, sunreclaim: 20708, anonhugepages: 0, shmemhugepages: 0, shmempmdmapped: 0, filehugepages: Some(6144), filepmdmapped: Some(0), hugepages_total: 0, hugepages_free: 0, hugepages_surp: 0 },
+                numastat: NodeNumaStat { numa_hit: 11834861, numa_miss: 0, numa_foreign: 0, interleave_hit: 1025, local_node: 11834861, other_node: 0 },
+                distance: BTreeMap::from([(0, 10), (1, 21)]),
+                cpulist: vec![0, 1, 2, 3],
+                hugepages: vec![
+                    NodeHugePages { size_kb: 2048, nr_hugepages: 128, free_hugepages: 1, surplus_hugepages: 0 },
+                    NodeHugePages { size_kb: 1048576, nr_hugepages: 2, free_hugepages: 1, surplus_hugepages: 0 },
+                ],
+            }
+        ]});
+        assert_eq!(result.distance(0, 1), Some(21));
+        assert_eq!(result.distance(1, 0), None);
+        assert_eq!(result.node_of_cpu(2), Some(0));
+        assert_eq!(result.node_of_cpu(4), None);
+    }
+
+    #[test]
+    fn create_sys_numa_nodes_with_gap_and_read_distance() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        // nodes 0 and 2 are online, node 2 is a memory-only node without cpus.
+        write_node_files(&test_path, 0, "10 20\n", "0-3\n");
+        write_node_files(&test_path, 2, "20 10\n", "\n");
+        write(format!("{}/devices/system/node/online", test_path), "0,2\n").unwrap_or_else(|_| panic!("Error writing to {}/devices/system/node/online", test_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.node(2).map(|node| &node.distance), Some(&BTreeMap::from([(0, 20), (2, 10)])));
+        assert_eq!(result.distance(0, 2), Some(20));
+        assert_eq!(result.distance(2, 2), Some(10));
+        assert_eq!(result.distance(0, 1), None);
+    }
+
+    fn write_node_files(test_path: &str, node_nr: u32, distance: &str, cpulist: &str) {
+        let node_path = format!("{}/devices/system/node/node{}", test_path, node_nr);
+        create_dir_all(&node_path).expect("Error creating mock directory.");
+        write(format!("{}/meminfo", node_path), format!("Node {} MemTotal:        5471992 kB\n", node_nr)).unwrap_or_else(|_| panic!("Error writing to {}/meminfo", node_path));
+        write(format!("{}/numastat", node_path), "numa_hit 0\n").unwrap_or_else(|_| panic!("Error writing to {}/numastat", node_path));
+        write(format!("{}/distance", node_path), distance).unwrap_or_else(|_| panic!("Error writing to {}/distance", node_path));
+        write(format!("{}/cpulist", node_path), cpulist).unwrap_or_else(|_| panic!("Error writing to {}/cpulist", node_path));
+    }
+
+    #[test]
+    fn parse_corrupted_node_numastat() {
+        assert!(SysNuma::parse_node_numastat("numa_hit AAA\n").is_err());
+    }
+}
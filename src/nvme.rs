@@ -0,0 +1,281 @@
+/*!
+Read the NVMe controllers and their namespaces from `/sys/class/nvme` into the struct [`SysNvme`].
+
+An NVMe controller (`/sys/class/nvme/nvme<nr>`) shows the model, serial number and firmware revision of the device,
+the transport (`pcie` for a local device, or `tcp`, `rdma`, `fc` or `loop` for NVMe over fabrics), the state of the
+controller (`live`, `resetting`, `connecting`, `deleting`, `dead`), the address (the PCI address or the fabrics
+address) and the NUMA node the controller is attached to.
+
+The namespaces of a controller are the `nvme<nr>n<nsid>` directories in the controller directory, which are the
+block devices. If native NVMe multipath is enabled, the namespace directories of a controller are named
+`nvme<subsystem>c<controller>n<nsid>`, and the block device is `nvme<subsystem>n<nsid>`. For every namespace the
+nsid, wwid and size are read.
+
+Reading these does not require the nvme-cli binary or root privileges.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-nvme>
+
+Here is an example obtaining the NVMe data:
+```no_run
+use proc_sys_parser::nvme;
+
+let sys_nvme = nvme::read();
+
+println!("{:#?}", sys_nvme);
+```
+Example output:
+```text
+SysNvme {
+    controllers: [
+        NvmeController {
+            controller_nr: 0,
+            model: "Samsung SSD 980 PRO 1TB",
+            serial: "S5GXNF0R123456",
+            firmware_rev: "5B2QGXA7",
+            transport: "pcie",
+            state: "live",
+            address: Some("0000:01:00.0"),
+            numa_node: Some(0),
+            cntlid: Some(6),
+            subsysnqn: Some("nqn.1994-11.com.samsung:nvme:980PRO:M.2:S5GXNF0R123456"),
+            queue_count: Some(17),
+            namespaces: [
+                NvmeNamespace {
+                    name: "nvme0n1",
+                    nsid: 1,
+                    wwid: Some("eui.002538b111b12345"),
+                    size: 1953525168,
+                    logical_block_size: Some(512),
+                },
+            ],
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysNvme`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::nvme::Builder;
+
+let sys_nvme = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use regex::Regex;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_i64, read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the NVMe controllers
+#[derive(Debug, PartialEq, Default)]
+pub struct SysNvme {
+    /// The controllers, sorted by controller number.
+    pub controllers: Vec<NvmeController>,
+}
+
+/// Struct for holding a single NVMe controller
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NvmeController {
+    pub controller_nr: u32,
+    pub model: String,
+    pub serial: String,
+    pub firmware_rev: String,
+    /// The transport, such as `pcie`, `tcp`, `rdma`, `fc` or `loop`.
+    pub transport: String,
+    /// The state of the controller, such as `live`, `resetting` or `dead`.
+    pub state: String,
+    /// The PCI address for a pcie controller, or the fabrics address, such as `traddr=10.0.0.1,trsvcid=4420`.
+    pub address: Option<String>,
+    /// The NUMA node of the controller, -1 if the controller is not attached to a specific node.
+    pub numa_node: Option<i64>,
+    /// The controller id within the subsystem.
+    pub cntlid: Option<u64>,
+    /// The NVMe qualified name of the subsystem.
+    pub subsysnqn: Option<String>,
+    /// The number of IO queues plus the admin queue.
+    pub queue_count: Option<u64>,
+    /// The namespaces, sorted by nsid.
+    pub namespaces: Vec<NvmeNamespace>,
+}
+
+/// Struct for holding a single NVMe namespace
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NvmeNamespace {
+    /// The name of the namespace directory, such as `nvme0n1`, or `nvme0c0n1` with native NVMe multipath.
+    pub name: String,
+    pub nsid: u64,
+    /// The world wide identifier, such as `eui.002538b111b12345`.
+    pub wwid: Option<String>,
+    /// The size of the namespace in sectors.
+    /// Sector size is 512 bytes.
+    pub size: u64,
+    /// The logical block size in bytes, which is read from the queue directory of the namespace.
+    pub logical_block_size: Option<u64>,
+}
+
+/// Builder pattern for [`SysNvme`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysNvme, ProcSysParserError> {
+        SysNvme::read_sys_nvme(format!("{}/class/nvme", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysNvme`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysNvme, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl NvmeNamespace {
+    /// The size of the namespace in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.size * 512
+    }
+}
+
+impl SysNvme {
+    pub fn new() -> SysNvme {
+        SysNvme::default()
+    }
+    /// The controller with the given name, such as `nvme0`.
+    pub fn controller(&self, name: &str) -> Option<&NvmeController> {
+        let controller_nr = name.strip_prefix("nvme")?.parse::<u32>().ok()?;
+        self.controllers.iter().find(|controller| controller.controller_nr == controller_nr)
+    }
+    /// The controller and namespace of a namespace name, such as `nvme0n1`.
+    pub fn namespace(&self, name: &str) -> Option<(&NvmeController, &NvmeNamespace)> {
+        self.controllers.iter()
+            .find_map(|controller| controller.namespaces.iter().find(|namespace| namespace.name == name).map(|namespace| (controller, namespace)))
+    }
+    fn read_nvme_namespaces(controller_path: &Path) -> Result<Vec<NvmeNamespace>, ProcSysParserError> {
+        let namespace_regex = Regex::new(r"^nvme\d+(c\d+)?n\d+$")
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: r"^nvme\d+(c\d+)?n\d+$".to_string() })?;
+        let mut namespaces = Vec::new();
+
+        let entries = read_dir(controller_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: controller_path.to_string_lossy().to_string(), error })?;
+        for namespace in entries.flatten().filter(|entry| namespace_regex.is_match(&entry.file_name().to_string_lossy())) {
+            let namespace_path = namespace.path();
+            namespaces.push(NvmeNamespace {
+                name: namespace.file_name().to_string_lossy().to_string(),
+                nsid: read_file_u64(&namespace_path.join("nsid"))?,
+                wwid: read_file_option_string(&namespace_path.join("wwid")).map(|wwid| wwid.trim().to_string()),
+                size: read_file_u64(&namespace_path.join("size"))?,
+                logical_block_size: read_file_option_u64(&namespace_path.join("queue/logical_block_size"))?,
+            });
+        }
+        namespaces.sort_by_key(|namespace| namespace.nsid);
+        Ok(namespaces)
+    }
+    fn read_nvme_controller(controller_path: &Path, controller_nr: u32) -> Result<NvmeController, ProcSysParserError> {
+        Ok(NvmeController {
+            controller_nr,
+            // model, serial and firmware_rev are padded with spaces.
+            model: read_file_string(&controller_path.join("model"))?.trim().to_string(),
+            serial: read_file_string(&controller_path.join("serial"))?.trim().to_string(),
+            firmware_rev: read_file_string(&controller_path.join("firmware_rev"))?.trim().to_string(),
+            transport: read_file_string(&controller_path.join("transport"))?,
+            state: read_file_string(&controller_path.join("state"))?,
+            address: read_file_option_string(&controller_path.join("address")),
+            numa_node: read_file_option_i64(&controller_path.join("numa_node"))?,
+            cntlid: read_file_option_u64(&controller_path.join("cntlid"))?,
+            subsysnqn: read_file_option_string(&controller_path.join("subsysnqn")),
+            queue_count: read_file_option_u64(&controller_path.join("queue_count"))?,
+            namespaces: SysNvme::read_nvme_namespaces(controller_path)?,
+        })
+    }
+    pub fn read_sys_nvme(sys_class_nvme_path: &str) -> Result<SysNvme, ProcSysParserError> {
+        let mut sysnvme = SysNvme::new();
+        let sys_class_nvme_path = Path::new(sys_class_nvme_path);
+
+        for controller_nr in read_numbered_entries(sys_class_nvme_path, "nvme")? {
+            sysnvme.controllers.push(SysNvme::read_nvme_controller(&sys_class_nvme_path.join(format!("nvme{}", controller_nr)), controller_nr)?);
+        }
+        Ok(sysnvme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_nvme_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/nvme/nvme0", test_path), &[
+            ("model", "Samsung SSD 980 PRO 1TB                 "),
+            ("serial", "S5GXNF0R123456      "),
+            ("firmware_rev", "5B2QGXA7"),
+            ("transport", "pcie"),
+            ("state", "live"),
+            ("address", "0000:01:00.0"),
+            ("numa_node", "-1"),
+            ("cntlid", "6"),
+        ]);
+        create_mock_files(&format!("{}/class/nvme/nvme0/nvme0n2", test_path), &[("nsid", "2"), ("size", "2048")]);
+        create_mock_files(&format!("{}/class/nvme/nvme0/nvme0n1", test_path), &[("nsid", "1"), ("wwid", "eui.002538b111b12345"), ("size", "1953525168")]);
+        create_mock_files(&format!("{}/class/nvme/nvme0/nvme0n1/queue", test_path), &[("logical_block_size", "512")]);
+        create_mock_files(&format!("{}/class/nvme/nvme0/power", test_path), &[("control", "auto")]);
+        create_mock_files(&format!("{}/class/nvme/nvme1", test_path), &[
+            ("model", "Linux"),
+            ("serial", "8a2d1e4c5b6f7a8b"),
+            ("firmware_rev", "6.8.0"),
+            ("transport", "tcp"),
+            ("state", "connecting"),
+        ]);
+        create_mock_files(&format!("{}/class/nvme/nvme1/nvme1c1n1", test_path), &[("nsid", "1"), ("size", "4096")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.controllers.len(), 2);
+        let controller = result.controller("nvme0").unwrap();
+        assert_eq!(controller.model, "Samsung SSD 980 PRO 1TB");
+        assert_eq!(controller.serial, "S5GXNF0R123456");
+        assert_eq!(controller.numa_node, Some(-1));
+        assert_eq!(controller.namespaces, vec![
+            NvmeNamespace { name: "nvme0n1".to_string(), nsid: 1, wwid: Some("eui.002538b111b12345".to_string()), size: 1953525168, logical_block_size: Some(512) },
+            NvmeNamespace { name: "nvme0n2".to_string(), nsid: 2, wwid: None, size: 2048, logical_block_size: None },
+        ]);
+        assert_eq!(result.controllers[1], NvmeController {
+            controller_nr: 1,
+            model: "Linux".to_string(),
+            serial: "8a2d1e4c5b6f7a8b".to_string(),
+            firmware_rev: "6.8.0".to_string(),
+            transport: "tcp".to_string(),
+            state: "connecting".to_string(),
+            namespaces: vec![NvmeNamespace { name: "nvme1c1n1".to_string(), nsid: 1, wwid: None, size: 4096, logical_block_size: None }],
+            ..Default::default()
+        });
+        let (controller, namespace) = result.namespace("nvme0n1").unwrap();
+        assert_eq!(controller.controller_nr, 0);
+        assert_eq!(namespace.size_bytes(), 1000204886016);
+    }
+}
@@ -0,0 +1,252 @@
+/*!
+Read the batteries and other power supplies from `/sys/class/power_supply` into the struct [`SysPowerSupply`].
+
+Every power supply in `/sys/class/power_supply/<name>` has a type, which is `Battery` for a battery, and `Mains`
+(AC adapter), `USB` or `Wireless` for external power. Which properties are shown depends on the type and the driver:
+a battery reports its charge either in µAh (`charge_*`) or in µWh (`energy_*`), and an AC adapter mostly only shows
+whether it is online. Therefore all properties are Option.
+
+The units are the units used by the kernel: µV for voltage, µA for current, µW for power, µAh for charge and µWh
+for energy. The battery health, the full capacity as a percentage of the design capacity, can be obtained with
+[`PowerSupply::health_percent`].
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-power>
+
+Here is an example obtaining the data from `/sys/class/power_supply`:
+```no_run
+use proc_sys_parser::power_supply;
+
+let sys_power_supply = power_supply::read();
+
+println!("{:#?}", sys_power_supply);
+```
+Example output:
+```text
+SysPowerSupply {
+    power_supplies: [
+        PowerSupply {
+            name: "AC",
+            supply_type: "Mains",
+            online: Some(1),
+            status: None,
+            capacity: None,
+            charge_now: None,
+            charge_full: None,
+            charge_full_design: None,
+            energy_now: None,
+            energy_full: None,
+            energy_full_design: None,
+            voltage_now: None,
+            current_now: None,
+            power_now: None,
+            cycle_count: None,
+            technology: None,
+            manufacturer: None,
+            model_name: None,
+        },
+        PowerSupply {
+            name: "BAT0",
+            supply_type: "Battery",
+            online: None,
+            status: Some("Charging"),
+            capacity: Some(81),
+            charge_now: None,
+            charge_full: None,
+            charge_full_design: None,
+            energy_now: Some(40120000),
+            energy_full: Some(49530000),
+            energy_full_design: Some(57000000),
+            voltage_now: Some(12644000),
+            current_now: None,
+            power_now: Some(15482000),
+            cycle_count: Some(214),
+            technology: Some("Li-poly"),
+            manufacturer: Some("SMP"),
+            model_name: Some("5B10W13930"),
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysPowerSupply`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::power_supply::Builder;
+
+let sys_power_supply = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_i64, read_file_option_string, read_file_option_u64, read_file_string};
+
+/// Struct for holding all power supplies
+#[derive(Debug, PartialEq, Default)]
+pub struct SysPowerSupply {
+    pub power_supplies: Vec<PowerSupply>,
+}
+
+/// Struct for holding a single power supply
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct PowerSupply {
+    pub name: String,
+    /// `Battery`, `Mains`, `USB`, `UPS` or `Wireless`.
+    pub supply_type: String,
+    /// Whether external power is connected (1) or not (0).
+    pub online: Option<u64>,
+    /// `Charging`, `Discharging`, `Not charging`, `Full` or `Unknown`.
+    pub status: Option<String>,
+    /// The charge of the battery in percent.
+    pub capacity: Option<u64>,
+    pub charge_now: Option<u64>,
+    pub charge_full: Option<u64>,
+    pub charge_full_design: Option<u64>,
+    pub energy_now: Option<u64>,
+    pub energy_full: Option<u64>,
+    pub energy_full_design: Option<u64>,
+    pub voltage_now: Option<u64>,
+    /// The current in µA; some drivers report a negative current when discharging.
+    pub current_now: Option<i64>,
+    /// The power in µW; some drivers report a negative power when discharging.
+    pub power_now: Option<i64>,
+    pub cycle_count: Option<u64>,
+    pub technology: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model_name: Option<String>,
+}
+
+/// Builder pattern for [`SysPowerSupply`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysPowerSupply, ProcSysParserError> {
+        SysPowerSupply::read_sys_power_supply(format!("{}/class/power_supply", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysPowerSupply`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysPowerSupply, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl PowerSupply {
+    pub fn is_battery(&self) -> bool {
+        self.supply_type == "Battery"
+    }
+    /// The full capacity as a percentage of the design capacity, using the energy or the charge properties,
+    /// depending on what the battery reports.
+    pub fn health_percent(&self) -> Option<f64> {
+        let (full, full_design) = match (self.energy_full, self.energy_full_design) {
+            (Some(full), Some(full_design)) => (full, full_design),
+            _ => (self.charge_full?, self.charge_full_design?),
+        };
+        if full_design == 0 { return None };
+        Some(full as f64 / full_design as f64 * 100_f64)
+    }
+}
+
+impl SysPowerSupply {
+    pub fn new() -> SysPowerSupply {
+        SysPowerSupply::default()
+    }
+    fn read_power_supply(power_supply_path: &Path, name: &str) -> Result<PowerSupply, ProcSysParserError> {
+        Ok(PowerSupply {
+            name: name.to_string(),
+            supply_type: read_file_string(&power_supply_path.join("type"))?,
+            online: read_file_option_u64(&power_supply_path.join("online"))?,
+            status: read_file_option_string(&power_supply_path.join("status")),
+            capacity: read_file_option_u64(&power_supply_path.join("capacity"))?,
+            charge_now: read_file_option_u64(&power_supply_path.join("charge_now"))?,
+            charge_full: read_file_option_u64(&power_supply_path.join("charge_full"))?,
+            charge_full_design: read_file_option_u64(&power_supply_path.join("charge_full_design"))?,
+            energy_now: read_file_option_u64(&power_supply_path.join("energy_now"))?,
+            energy_full: read_file_option_u64(&power_supply_path.join("energy_full"))?,
+            energy_full_design: read_file_option_u64(&power_supply_path.join("energy_full_design"))?,
+            voltage_now: read_file_option_u64(&power_supply_path.join("voltage_now"))?,
+            current_now: read_file_option_i64(&power_supply_path.join("current_now"))?,
+            power_now: read_file_option_i64(&power_supply_path.join("power_now"))?,
+            cycle_count: read_file_option_u64(&power_supply_path.join("cycle_count"))?,
+            technology: read_file_option_string(&power_supply_path.join("technology")),
+            manufacturer: read_file_option_string(&power_supply_path.join("manufacturer")),
+            model_name: read_file_option_string(&power_supply_path.join("model_name")),
+        })
+    }
+    pub fn read_sys_power_supply(sys_class_power_supply_path: &str) -> Result<SysPowerSupply, ProcSysParserError> {
+        let mut syspowersupply = SysPowerSupply::new();
+
+        let power_supplies = read_dir(sys_class_power_supply_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_power_supply_path.to_string(), error })?;
+
+        for power_supply in power_supplies.filter_map(|entry| entry.ok()) {
+            syspowersupply.power_supplies.push(SysPowerSupply::read_power_supply(&power_supply.path(), &power_supply.file_name().to_string_lossy())?);
+        }
+        syspowersupply.power_supplies.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(syspowersupply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_power_supply_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/power_supply/AC", test_path), &[("type", "Mains"), ("online", "1")]);
+        create_mock_files(&format!("{}/class/power_supply/BAT0", test_path), &[
+            ("type", "Battery"),
+            ("status", "Discharging"),
+            ("capacity", "62"),
+            ("charge_now", "2480000"),
+            ("charge_full", "3600000"),
+            ("charge_full_design", "4000000"),
+            ("voltage_now", "11820000"),
+            ("current_now", "-1250000"),
+            ("cycle_count", "87"),
+            ("technology", "Li-ion"),
+            ("manufacturer", "LGC"),
+            ("model_name", "L19L3PD1"),
+        ]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.power_supplies.len(), 2);
+        assert_eq!(result.power_supplies[0], PowerSupply { name: "AC".to_string(), supply_type: "Mains".to_string(), online: Some(1), ..Default::default() });
+        let battery = &result.power_supplies[1];
+        assert!(battery.is_battery());
+        assert_eq!(battery.status, Some("Discharging".to_string()));
+        assert_eq!(battery.capacity, Some(62));
+        assert_eq!(battery.energy_now, None);
+        assert_eq!(battery.current_now, Some(-1250000));
+        assert_eq!(battery.cycle_count, Some(87));
+        assert_eq!(battery.model_name, Some("L19L3PD1".to_string()));
+        assert_eq!(battery.health_percent(), Some(90_f64));
+    }
+}
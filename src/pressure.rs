@@ -1,11 +1,13 @@
 /*!
-Read data from `/proc/pressure/cpu`, `/proc/pressure/io`, `/proc/pressure/memory` into the struct [`ProcPressure`].
+Read data from `/proc/pressure/cpu`, `/proc/pressure/io`, `/proc/pressure/memory` and `/proc/pressure/irq` into the
+struct [`ProcPressure`].
 
 The processor of `/proc/pressure` takes the values from the files, and puts them in the struct [`ProcPressure`].
 The files are cpu, io and memory as topics for pressure information.
 Inside the files, these are divided between some and full, meaning some tasks were affected or full, meaning all tasks were.
-For both some and full, the fields are a percentage of ? for 10 seconds, 60 seconds and 300 seconds, and total time spent
-waiting in microseconds. (the linux kernel is not consistent with time units, having jiffies, nanoseconds and milliseconds as units).
+For both some and full, the fields are the percentage of time stalled averaged over 10 seconds, 60 seconds and 300 seconds,
+and the total time spent stalled in microseconds, which is stored as a [`Duration`].
+(the linux kernel is not consistent with time units, having jiffies, nanoseconds and milliseconds as units).
 
 Documentation: <https://docs.kernel.org/accounting/psi.html>
 
@@ -25,27 +27,31 @@ ProcPressure {
                     cpu_some_avg10: 1.0,
                     cpu_some_avg60: 2.0,
                     cpu_some_avg300: 3.0,
-                    cpu_some_total: 373300065,
+                    cpu_some_total: 373.300065s,
                     cpu_full_avg10: Some( 4.0 ),
                     cpu_full_avg60: Some( 5.0 ),
                     cpu_full_avg300: Some( 6.0 ),
-                    cpu_full_total: Some( 0 ),
+                    cpu_full_total: Some( 0ns ),
                     io_some_avg10: 7.0,
                     io_some_avg60: 8.0,
                     io_some_avg300: 9.0,
-                    io_some_total: 55345502,
+                    io_some_total: 55.345502s,
                     io_full_avg10: 10.0,
                     io_full_avg60: 11.0,
                     io_full_avg300: 12.0,
-                    io_full_total: 53895423,
+                    io_full_total: 53.895423s,
                     memory_some_avg10: 13.0,
                     memory_some_avg60: 14.0,
                     memory_some_avg300: 15.0,
-                    memory_some_total: 5425111,
+                    memory_some_total: 5.425111s,
                     memory_full_avg10: 16.0,
                     memory_full_avg60: 17.0,
                     memory_full_avg300: 18.0,
-                    memory_full_total: 5390695,
+                    memory_full_total: 5.390695s,
+                    irq_full_avg10: Some( 0.0 ),
+                    irq_full_avg60: Some( 0.0 ),
+                    irq_full_avg300: Some( 0.0 ),
+                    irq_full_total: Some( 1.033472s ),
                 }
             )
         }
@@ -59,11 +65,43 @@ use proc_sys_parser::{pressure, pressure::Builder};
 let proc_pressure = Builder::new().path("/myproc").read();
 ```
 
+The averages are calculated by the kernel over fixed windows. To get the time stalled in an arbitrary interval, such as the
+interval of a monitoring tool, take two readings and use [`Psi::delta`], which returns the stall time of every resource as a
+[`Duration`] and as a percentage of the interval in [`PsiDelta`]:
+```no_run
+use std::{thread::sleep, time::Duration};
+use proc_sys_parser::pressure;
+
+let previous = pressure::read().unwrap().psi.unwrap();
+sleep(Duration::from_secs(1));
+let current = pressure::read().unwrap().psi.unwrap();
+let delta = current.delta(&previous, 1.0);
+
+if delta.percent(delta.memory_full) > 10.0 {
+    println!("memory full stall: {:?} in the last second", delta.memory_full);
+}
+```
+
 If the `/proc/pressure` entry is not available because it didn't exist in that linux version, or because it's not enabled
 The ProcPressure.psi entry is set to None.
 
+Since linux 6.1, the time spent on interrupts is shown in `/proc/pressure/irq`, if the kernel is built with
+`CONFIG_IRQ_TIME_ACCOUNTING`. This file only contains a full line, which is stored in the irq_full fields, which are
+None if the file is not available.
+
+A cgroup v2 cgroup has the same statistics for its processes in the files `cpu.pressure`, `io.pressure`,
+`memory.pressure` and `irq.pressure`, which are read into the same [`Psi`] struct with [`ProcPressure::read_cgroup_pressure`] or
+[`crate::cgroup::Cgroup::pressure`], so the pressure of a container can be compared with the pressure of the host:
+```no_run
+use proc_sys_parser::pressure::ProcPressure;
+
+let cgroup_pressure = ProcPressure::read_cgroup_pressure("/sys/fs/cgroup/system.slice");
+```
+The root cgroup does not have the pressure files, its pressure is the pressure of the host.
+
 */
 use std::fs::read_to_string;
+use std::time::Duration;
 use crate::ProcSysParserError;
 use log::warn;
 
@@ -74,39 +112,64 @@ pub struct ProcPressure {
     /// psi is None if no /proc/pressure is found.
     pub psi: Option<Psi>,
 }
-///
+/// Struct for holding the cpu, io and memory pressure statistics
 #[derive(Debug, PartialEq, Default)]
 pub struct Psi {
     pub cpu_some_avg10: f64,
     pub cpu_some_avg60: f64,
     pub cpu_some_avg300: f64,
-    pub cpu_some_total: u64,
+    pub cpu_some_total: Duration,
     pub cpu_full_avg10: Option<f64>,
     pub cpu_full_avg60: Option<f64>,
     pub cpu_full_avg300: Option<f64>,
-    pub cpu_full_total: Option<u64>,
+    pub cpu_full_total: Option<Duration>,
     pub io_some_avg10: f64,
     pub io_some_avg60: f64,
     pub io_some_avg300: f64,
-    pub io_some_total: u64,
+    pub io_some_total: Duration,
     pub io_full_avg10: f64,
     pub io_full_avg60: f64,
     pub io_full_avg300: f64,
-    pub io_full_total: u64,
+    pub io_full_total: Duration,
     pub memory_some_avg10: f64,
     pub memory_some_avg60: f64,
     pub memory_some_avg300: f64,
-    pub memory_some_total: u64,
+    pub memory_some_total: Duration,
     pub memory_full_avg10: f64,
     pub memory_full_avg60: f64,
     pub memory_full_avg300: f64,
-    pub memory_full_total: u64,
+    pub memory_full_total: Duration,
+    /// irq pressure only has full. Kernel 6.1+ with CONFIG_IRQ_TIME_ACCOUNTING
+    pub irq_full_avg10: Option<f64>,
+    pub irq_full_avg60: Option<f64>,
+    pub irq_full_avg300: Option<f64>,
+    pub irq_full_total: Option<Duration>,
 }
 
 impl Psi {
     pub fn new() -> Psi {
         Psi::default() 
     }
+    /// The time stalled between a previous reading and this reading, which are `interval_seconds` apart.
+    pub fn delta(&self, previous: &Psi, interval_seconds: f64) -> PsiDelta {
+        PsiDelta::new(previous, self, interval_seconds)
+    }
+}
+
+/// Struct for holding the time stalled between two readings of the pressure files
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct PsiDelta {
+    /// The interval between the two readings.
+    pub interval: Duration,
+    pub cpu_some: Duration,
+    /// Kernel 5.13+
+    pub cpu_full: Option<Duration>,
+    pub io_some: Duration,
+    pub io_full: Duration,
+    pub memory_some: Duration,
+    pub memory_full: Duration,
+    /// Kernel 6.1+ with CONFIG_IRQ_TIME_ACCOUNTING
+    pub irq_full: Option<Duration>,
 }
 
 /// Builder pattern for [`ProcPressure`]
@@ -143,6 +206,28 @@ pub fn read() -> Result<ProcPressure, ProcSysParserError> {
    Builder::new().read()
 }
 
+impl PsiDelta {
+    pub fn new(previous: &Psi, current: &Psi, interval_seconds: f64) -> PsiDelta {
+        let optional = |previous: Option<Duration>, current: Option<Duration>| current.zip(previous).map(|(current, previous)| current.saturating_sub(previous));
+        PsiDelta {
+            interval: Duration::from_secs_f64(interval_seconds.max(0.0)),
+            cpu_some: current.cpu_some_total.saturating_sub(previous.cpu_some_total),
+            cpu_full: optional(previous.cpu_full_total, current.cpu_full_total),
+            io_some: current.io_some_total.saturating_sub(previous.io_some_total),
+            io_full: current.io_full_total.saturating_sub(previous.io_full_total),
+            memory_some: current.memory_some_total.saturating_sub(previous.memory_some_total),
+            memory_full: current.memory_full_total.saturating_sub(previous.memory_full_total),
+            irq_full: optional(previous.irq_full_total, current.irq_full_total),
+        }
+    }
+    /// The stall time as a percentage of the interval, which is directly comparable with the avg fields.
+    /// This is 0 if the interval is zero.
+    pub fn percent(&self, stall: Duration) -> f64 {
+        if self.interval.is_zero() { return 0.0 };
+        stall.as_secs_f64() / self.interval.as_secs_f64() * 100.0
+    }
+}
+
 impl ProcPressure {
     pub fn new() -> ProcPressure {
         ProcPressure {
@@ -155,16 +240,37 @@ impl ProcPressure {
         let mut psi = Psi::new();
 
         for psi_target in ["cpu", "io", "memory"] {
-            if ProcPressure::parse_pressure_entity(psi_target, proc_pressure_path, &mut psi)?.is_none() {
+            if ProcPressure::parse_pressure_entity(psi_target, &format!("{}/{}", proc_pressure_path, psi_target), &mut psi)?.is_none() {
+                return Ok(proc_pressure);
+            }
+        }
+        // irq pressure is optional.
+        ProcPressure::parse_pressure_entity("irq", &format!("{}/irq", proc_pressure_path), &mut psi)?;
+        proc_pressure.psi = Some(psi);
+
+        Ok(proc_pressure)
+    }
+    /// Read the pressure files of a cgroup v2 cgroup directory, or any directory containing the files: `cpu.pressure`,
+    /// `io.pressure`, `memory.pressure` and the optional `irq.pressure`.
+    /// The psi entry is set to None if the files are not available.
+    pub fn read_cgroup_pressure(cgroup_directory: &str) -> Result<ProcPressure, ProcSysParserError> {
+        let mut proc_pressure = ProcPressure::new();
+
+        let mut psi = Psi::new();
+
+        for psi_target in ["cpu", "io", "memory"] {
+            if ProcPressure::parse_pressure_entity(psi_target, &format!("{}/{}.pressure", cgroup_directory, psi_target), &mut psi)?.is_none() {
                 return Ok(proc_pressure);
             }
         }
+        // irq pressure is optional.
+        ProcPressure::parse_pressure_entity("irq", &format!("{}/irq.pressure", cgroup_directory), &mut psi)?;
         proc_pressure.psi = Some(psi);
 
         Ok(proc_pressure)
     }
-    fn parse_pressure_entity(file: &str, proc_pressure_path: &str, psi: &mut Psi) -> Result<Option<usize>, ProcSysParserError> {
-        match read_to_string(format!("{}/{}", &proc_pressure_path, file)) {
+    fn parse_pressure_entity(file: &str, pressure_file: &str, psi: &mut Psi) -> Result<Option<usize>, ProcSysParserError> {
+        match read_to_string(pressure_file) {
             Ok(psi_contents)  => {
                 for line in psi_contents.lines() {
                     match line.split_whitespace().next() {
@@ -190,7 +296,7 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure cpu_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure cpu_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?;
                                 },
                                 "io" => {
                                     psi.io_some_avg10 = line.split_whitespace().nth(1)
@@ -212,7 +318,7 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure io_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure io_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?;
                                 },
                                 "memory" => {
                                     psi.memory_some_avg10 = line.split_whitespace().nth(1)
@@ -234,7 +340,7 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure memory_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure memory_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?;
                                 },
                                 &_ => warn!("Unknown entry in some: {}, {}", file, line),
                             }
@@ -261,7 +367,7 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure cpu_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure cpu_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?);
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?);
                                 },
                                 "io" => {
                                     psi.io_full_avg10 = line.split_whitespace().nth(1)
@@ -283,7 +389,7 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure io_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure io_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?;
                                 },
                                 "memory" => {
                                     psi.memory_full_avg10 = line.split_whitespace().nth(1)
@@ -305,7 +411,29 @@ impl ProcPressure {
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure memory_some_total".to_string() })?
                                         .split('=').nth(1)
                                         .ok_or(ProcSysParserError::IteratorItemError {item: "pressure memory_some_total after split =".to_string() })?
-                                        .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?;
+                                },
+                                "irq" => {
+                                    psi.irq_full_avg10 = Some(line.split_whitespace().nth(1)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg10".to_string() })?
+                                        .split('=').nth(1)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg10 after split =".to_string() })?
+                                        .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?);
+                                    psi.irq_full_avg60 = Some(line.split_whitespace().nth(2)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg60".to_string() })?
+                                        .split('=').nth(1)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg60 after split =".to_string() })?
+                                        .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?);
+                                    psi.irq_full_avg300 = Some(line.split_whitespace().nth(3)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg300".to_string() })?
+                                        .split('=').nth(1)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_avg300 after split =".to_string() })?
+                                        .parse::<f64>().map_err(ProcSysParserError::ParseToFloatError)?);
+                                    psi.irq_full_total = Some(line.split_whitespace().nth(4)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_total".to_string() })?
+                                        .split('=').nth(1)
+                                        .ok_or(ProcSysParserError::IteratorItemError {item: "pressure irq_full_total after split =".to_string() })?
+                                        .parse::<u64>().map(Duration::from_micros).map_err(ProcSysParserError::ParseToIntegerError)?);
                                 },
                                 &_ => warn!("Unknown entry in full: {}, {}", file, line),
                             }
@@ -346,9 +474,10 @@ full avg10=16.00 avg60=17.00 avg300=18.00 total=5390695
         let test_path = format!("/tmp/test.{}", directory_suffix);
         create_dir_all(format!("{}/pressure", test_path)).expect("Error creating mock directory.");
 
-        write(format!("{}/pressure/cpu", test_path), proc_pressure_cpu).expect(format!("Error writing to {}/pressure/cpu", test_path).as_str());
-        write(format!("{}/pressure/io", test_path), proc_pressure_io).expect(format!("Error writing to {}/pressure/io", test_path).as_str());
-        write(format!("{}/pressure/memory", test_path), proc_pressure_memory).expect(format!("Error writing to {}/pressure/memory", test_path).as_str());
+        write(format!("{}/pressure/cpu", test_path), proc_pressure_cpu).unwrap_or_else(|_| panic!("Error writing to {}/pressure/cpu", test_path));
+        write(format!("{}/pressure/io", test_path), proc_pressure_io).unwrap_or_else(|_| panic!("Error writing to {}/pressure/io", test_path));
+        write(format!("{}/pressure/memory", test_path), proc_pressure_memory).unwrap_or_else(|_| panic!("Error writing to {}/pressure/memory", test_path));
+        write(format!("{}/pressure/irq", test_path), "full avg10=0.50 avg60=0.25 avg300=0.10 total=1033472\n").unwrap_or_else(|_| panic!("Error writing to {}/pressure/irq", test_path));
 
         let result = Builder::new().path(&test_path).read().unwrap();
 
@@ -360,7 +489,7 @@ full avg10=16.00 avg60=17.00 avg300=18.00 total=5390695
                     cpu_some_avg10: 1.0,
                     cpu_some_avg60: 2.0,
                     cpu_some_avg300: 3.0,
-                    cpu_some_total: 373300065,
+                    cpu_some_total: Duration::from_micros(373300065),
                     cpu_full_avg10: Some(
                         4.0,
                     ),
@@ -371,39 +500,88 @@ full avg10=16.00 avg60=17.00 avg300=18.00 total=5390695
                         6.0,
                     ),
                     cpu_full_total: Some(
-                        0,
+                        Duration::from_micros(0),
                     ),
                     io_some_avg10: 7.0,
                     io_some_avg60: 8.0,
                     io_some_avg300: 9.0,
-                    io_some_total: 55345502,
+                    io_some_total: Duration::from_micros(55345502),
                     io_full_avg10: 10.0,
                     io_full_avg60: 11.0,
                     io_full_avg300: 12.0,
-                    io_full_total: 53895423,
+                    io_full_total: Duration::from_micros(53895423),
                     memory_some_avg10: 13.0,
                     memory_some_avg60: 14.0,
                     memory_some_avg300: 15.0,
-                    memory_some_total: 5425111,
+                    memory_some_total: Duration::from_micros(5425111),
                     memory_full_avg10: 16.0,
                     memory_full_avg60: 17.0,
                     memory_full_avg300: 18.0,
-                    memory_full_total: 5390695,
+                    memory_full_total: Duration::from_micros(5390695),
+                    irq_full_avg10: Some(
+                        0.5,
+                    ),
+                    irq_full_avg60: Some(
+                        0.25,
+                    ),
+                    irq_full_avg300: Some(
+                        0.1,
+                    ),
+                    irq_full_total: Some(
+                        Duration::from_micros(1033472),
+                    ),
                 },
             ),
         });
     }
     #[test]
+    fn create_cgroup_pressure_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let cgroup_directory = format!("{}/sys/fs/cgroup/system.slice", test_path);
+        create_dir_all(&cgroup_directory).expect("Error creating mock directory.");
+
+        write(format!("{}/cpu.pressure", cgroup_directory), "some avg10=1.50 avg60=0.75 avg300=0.20 total=9120443\nfull avg10=0.50 avg60=0.25 avg300=0.10 total=4000012\n").expect("Error writing to cpu.pressure");
+        write(format!("{}/io.pressure", cgroup_directory), "some avg10=0.00 avg60=0.00 avg300=0.00 total=1204\nfull avg10=0.00 avg60=0.00 avg300=0.00 total=1100\n").expect("Error writing to io.pressure");
+
+        let result_without_memory = ProcPressure::read_cgroup_pressure(&cgroup_directory).unwrap();
+
+        write(format!("{}/memory.pressure", cgroup_directory), "some avg10=0.00 avg60=0.00 avg300=0.00 total=0\nfull avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").expect("Error writing to memory.pressure");
+
+        let result = ProcPressure::read_cgroup_pressure(&cgroup_directory).unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result_without_memory, ProcPressure { psi: None });
+        let psi = result.psi.unwrap();
+        assert_eq!(psi.cpu_some_avg10, 1.5);
+        assert_eq!(psi.cpu_full_total, Some(Duration::from_micros(4000012)));
+        assert_eq!(psi.io_full_total, Duration::from_micros(1100));
+        assert_eq!(psi.memory_some_total, Duration::ZERO);
+        assert_eq!(psi.irq_full_total, None);
+    }
+    #[test]
     fn do_not_create_proc_pressure_directory_for_nonexistent_cases_and_read() {
         let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
         let test_path = format!("/tmp/test.{}", directory_suffix);
-        create_dir_all(format!("{}", test_path)).expect("Error creating mock directory.");
+        create_dir_all(&test_path).expect("Error creating mock directory.");
 
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
 
         assert_eq!(result, ProcPressure { psi: None });
     }
-}
-
 
+    #[test]
+    fn psi_delta() {
+        let previous = Psi { cpu_some_total: Duration::from_micros(1_000_000), memory_full_total: Duration::from_micros(500_000), irq_full_total: Some(Duration::from_micros(100)), ..Default::default() };
+        let current = Psi { cpu_some_total: Duration::from_micros(1_250_000), memory_full_total: Duration::from_micros(400_000), irq_full_total: None, ..Default::default() };
+        let delta = current.delta(&previous, 2.0);
+        assert_eq!(delta.interval, Duration::from_secs(2));
+        assert_eq!(delta.cpu_some, Duration::from_millis(250));
+        assert_eq!(delta.percent(delta.cpu_some), 12.5);
+        assert_eq!(delta.memory_full, Duration::ZERO);
+        assert_eq!(delta.irq_full, None);
+        assert_eq!(PsiDelta::new(&previous, &current, 0.0).percent(Duration::from_secs(1)), 0.0);
+    }
+}
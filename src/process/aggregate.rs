@@ -0,0 +1,240 @@
+/*!
+Aggregate the statistics of all processes grouped by uid, by command name or by cgroup into [`ProcessGroup`] structs.
+
+The aggregation reads all processes in `/proc` once into the struct [`ProcessAggregate`], which holds a
+[`ProcessSample`] per process, obtained from:
+- `/proc/<pid>/stat` using [`crate::process::stat`] for the command name and the cpu time,
+- `/proc/<pid>/status` using [`crate::process::status`] for the effective uid and the resident set size,
+- `/proc/<pid>/cgroup` using [`crate::process::cgroup`] for the cgroup path,
+- optionally `/proc/<pid>/smaps_rollup` using [`crate::process::smaps_rollup`] for the proportional set size.
+
+Reading `smaps_rollup` is considerably more expensive than the other files, and requires ptrace permissions for
+processes of other users, so it is only read when enabled with the `pss()` function of the Builder. If the file can not
+be read for a process, the pss of that process is None, and is not counted in the group total.
+
+Processes that exit during the scan are skipped.
+
+The samples can be grouped multiple times without reading the processes again, using [`ProcessAggregate::group_by`].
+The resulting groups are sorted by total resident set size, largest first, which makes it the building block for
+"top users of memory" reports.
+
+Here is an example obtaining the memory usage per user:
+```no_run
+use proc_sys_parser::process::aggregate::{self, GroupBy};
+
+let process_aggregate = aggregate::read().unwrap();
+
+for group in process_aggregate.group_by(GroupBy::Uid) {
+    println!("{:8} {:6} {:10} kB", group.key, group.count, group.total_rss_kb);
+}
+```
+Example output:
+```text
+1000        112    2834560 kB
+0            98     412320 kB
+998           3      20480 kB
+```
+
+If you want to include the proportional set size, and/or change the path that is read for [`ProcessAggregate`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::aggregate::Builder;
+
+let process_aggregate = Builder::new().path("/myproc").pss(true).read();
+```
+*/
+use std::collections::HashMap;
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::cgroup::ProcPidCgroup;
+use crate::process::smaps_rollup::ProcPidSmapsRollup;
+use crate::process::stat::ProcPidStat;
+use crate::process::status::ProcPidStatus;
+
+/// Struct for holding the samples of all processes
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessAggregate {
+    pub processes: Vec<ProcessSample>,
+}
+
+/// Struct for holding the statistics of a single process used for aggregation
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcessSample {
+    pub pid: u32,
+    /// The command name from stat.
+    pub comm: String,
+    /// The effective uid.
+    pub uid: u32,
+    /// The cgroup v2 path, or the path of the cgroup v1 memory controller if there is no cgroup v2 path.
+    pub cgroup: Option<String>,
+    /// Resident set size in kilobytes, 0 for kernel threads.
+    pub rss_kb: u64,
+    /// Proportional set size in kilobytes, None if smaps_rollup was not read.
+    pub pss_kb: Option<u64>,
+    /// User and system cpu time in milliseconds.
+    pub cpu_time_ms: u64,
+}
+
+/// The key to group the process samples by
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GroupBy {
+    Uid,
+    Comm,
+    Cgroup,
+}
+
+/// Struct for holding the aggregated statistics of a group of processes
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcessGroup {
+    /// The uid, command name or cgroup path, depending on the grouping. Processes without a cgroup are grouped as `-`.
+    pub key: String,
+    pub count: u64,
+    pub total_rss_kb: u64,
+    /// The sum of the pss of the processes for which the pss is known, None if it is not known for any process.
+    pub total_pss_kb: Option<u64>,
+    pub total_cpu_time_ms: u64,
+}
+
+/// Builder pattern for [`ProcessAggregate`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub read_pss : bool,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            read_pss: false,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pss(mut self, read_pss: bool) -> Builder {
+        self.read_pss = read_pss;
+        self
+    }
+    pub fn read(self) -> Result<ProcessAggregate, ProcSysParserError> {
+        ProcessAggregate::read_process_aggregate(&self.proc_path, self.read_pss)
+    }
+}
+
+/// The main function for building a [`ProcessAggregate`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessAggregate, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcessAggregate {
+    pub fn new() -> ProcessAggregate {
+        ProcessAggregate::default()
+    }
+    /// Group the process samples, sorted by total resident set size descending, then by key.
+    pub fn group_by(&self, group_by: GroupBy) -> Vec<ProcessGroup> {
+        let mut groups: HashMap<String, ProcessGroup> = HashMap::new();
+
+        for process in &self.processes {
+            let key = match group_by {
+                GroupBy::Uid => process.uid.to_string(),
+                GroupBy::Comm => process.comm.clone(),
+                GroupBy::Cgroup => process.cgroup.clone().unwrap_or_else(|| "-".to_string()),
+            };
+            let group = groups.entry(key.clone()).or_insert_with(|| ProcessGroup { key, ..Default::default() });
+            group.count += 1;
+            group.total_rss_kb += process.rss_kb;
+            if let Some(pss_kb) = process.pss_kb {
+                group.total_pss_kb = Some(group.total_pss_kb.unwrap_or_default() + pss_kb);
+            }
+            group.total_cpu_time_ms += process.cpu_time_ms;
+        }
+        let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| b.total_rss_kb.cmp(&a.total_rss_kb).then_with(|| a.key.cmp(&b.key)));
+        groups
+    }
+    fn read_process_sample(proc_path: &str, pid: u32, read_pss: bool) -> Result<ProcessSample, ProcSysParserError> {
+        let stat = ProcPidStat::read_proc_pid_stat(format!("{}/{}/stat", proc_path, pid).as_str())?;
+        let status = ProcPidStatus::read_proc_pid_status(format!("{}/{}/status", proc_path, pid).as_str())?;
+        // the cgroup file does not exist if the kernel is built without cgroup support.
+        let cgroup = ProcPidCgroup::read_proc_pid_cgroup(format!("{}/{}/cgroup", proc_path, pid).as_str(), &pid.to_string())
+            .ok()
+            .and_then(|cgroup| cgroup.unified_path().or(cgroup.controller_path("memory")).map(|path| path.to_string()));
+        let pss_kb = if read_pss {
+            ProcPidSmapsRollup::read_proc_pid_smaps_rollup(format!("{}/{}/smaps_rollup", proc_path, pid).as_str())
+                .ok()
+                .map(|smaps_rollup| smaps_rollup.pss)
+        } else {
+            None
+        };
+
+        Ok(ProcessSample {
+            pid,
+            comm: stat.comm,
+            uid: status.uid.get(1).copied().unwrap_or_default(),
+            cgroup,
+            rss_kb: status.vmrss.unwrap_or_default(),
+            pss_kb,
+            cpu_time_ms: stat.utime + stat.stime,
+        })
+    }
+    pub fn read_process_aggregate(proc_path: &str, read_pss: bool) -> Result<ProcessAggregate, ProcSysParserError> {
+        let mut processaggregate = ProcessAggregate::new();
+
+        for pid in read_pids(proc_path)? {
+            match ProcessAggregate::read_process_sample(proc_path, pid, read_pss) {
+                Ok(sample) => processaggregate.processes.push(sample),
+                // the process has exited in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(processaggregate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_process(test_path: &str, pid: u32, comm: &str, uid: u32, rss_kb: u64, cgroup: &str) {
+        create_dir_all(format!("{}/{}", test_path, pid)).expect("Error creating mock directory.");
+        write(format!("{}/{}/stat", test_path, pid), format!("{} ({}) S 1 1 1 0 -1 4194560 0 0 0 0 10 10 0 0 20 0 1 0 5 1000000 100 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0\n", pid, comm))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/stat", test_path, pid));
+        write(format!("{}/{}/status", test_path, pid), format!("Name:\t{}\nState:\tS (sleeping)\nTgid:\t{}\nPid:\t{}\nPPid:\t1\nTracerPid:\t0\nUid:\t{} {} {} {}\nGid:\t0\t0\t0\t0\nFDSize:\t64\nVmRSS:\t{} kB\nThreads:\t1\n", comm, pid, pid, uid, uid, uid, uid, rss_kb))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/status", test_path, pid));
+        write(format!("{}/{}/cgroup", test_path, pid), format!("0::{}\n", cgroup))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/cgroup", test_path, pid));
+        write(format!("{}/{}/smaps_rollup", test_path, pid), format!("Rss:\t{} kB\nPss:\t{} kB\n", rss_kb, rss_kb / 2))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/smaps_rollup", test_path, pid));
+    }
+
+    #[test]
+    fn create_process_aggregate_and_group() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_process(&test_path, 100, "bash", 1000, 4000, "/user.slice");
+        create_mock_process(&test_path, 101, "bash", 0, 2000, "/system.slice/sshd.service");
+        create_mock_process(&test_path, 102, "postgres", 1000, 10000, "/system.slice/postgresql.service");
+        create_mock_process(&test_path, 103, "postgres", 1000, 6000, "/system.slice/postgresql.service");
+
+        let result = Builder::new().path(&test_path).pss(true).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.processes.len(), 4);
+        assert_eq!(result.group_by(GroupBy::Uid), vec![
+            ProcessGroup { key: "1000".to_string(), count: 3, total_rss_kb: 20000, total_pss_kb: Some(10000), total_cpu_time_ms: 600 },
+            ProcessGroup { key: "0".to_string(), count: 1, total_rss_kb: 2000, total_pss_kb: Some(1000), total_cpu_time_ms: 200 },
+        ]);
+        assert_eq!(result.group_by(GroupBy::Comm)[0].key, "postgres");
+        assert_eq!(result.group_by(GroupBy::Comm)[1], ProcessGroup { key: "bash".to_string(), count: 2, total_rss_kb: 6000, total_pss_kb: Some(3000), total_cpu_time_ms: 400 });
+        assert_eq!(result.group_by(GroupBy::Cgroup).iter().map(|group| group.key.as_str()).collect::<Vec<_>>(),
+            vec!["/system.slice/postgresql.service", "/user.slice", "/system.slice/sshd.service"]);
+    }
+}
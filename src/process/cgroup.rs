@@ -0,0 +1,190 @@
+/*!
+Read data from `/proc/<pid>/cgroup` into the struct [`ProcPidCgroup`].
+
+The `/proc/<pid>/cgroup` file contains the control groups the process belongs to, one line per hierarchy, with the
+format `hierarchy-ID:controller-list:cgroup-path`:
+```text
+12:memory:/system.slice/sshd.service
+1:name=systemd:/system.slice/sshd.service
+0::/system.slice/sshd.service
+```
+For the cgroup v2 unified hierarchy the hierarchy-ID is 0 and the controller list is empty.
+On a system that only uses cgroup v2 the file contains a single line starting with `0::`.
+
+Documentation: <https://man7.org/linux/man-pages/man7/cgroups.7.html>
+
+Here is an example obtaining the data from `/proc/self/cgroup`:
+```no_run
+use proc_sys_parser::process::cgroup;
+
+let proc_pid_cgroup = cgroup::read();
+
+println!("{:#?}", proc_pid_cgroup);
+```
+Example output:
+```text
+ProcPidCgroup {
+    pid: "self",
+    cgroups: [
+        CgroupEntry {
+            hierarchy_id: 0,
+            controllers: [],
+            path: "/user.slice/user-1000.slice/session-3.scope",
+        },
+    ],
+}
+```
+
+If you want to read the data of another process, and/or change the path that is read for [`ProcPidCgroup`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::cgroup::Builder;
+
+let proc_pid_cgroup = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/cgroup`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcPidCgroup {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    pub cgroups: Vec<CgroupEntry>,
+}
+
+/// Struct for holding a single line of `/proc/<pid>/cgroup`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CgroupEntry {
+    /// The hierarchy id, which is 0 for the cgroup v2 unified hierarchy.
+    pub hierarchy_id: u64,
+    /// The controllers bound to the hierarchy, empty for the cgroup v2 unified hierarchy.
+    pub controllers: Vec<String>,
+    /// The path of the cgroup relative to the mount point of the hierarchy.
+    pub path: String,
+}
+
+/// Builder pattern for [`ProcPidCgroup`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidCgroup, ProcSysParserError> {
+        ProcPidCgroup::read_proc_pid_cgroup(format!("{}/{}/cgroup", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidCgroup`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidCgroup, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidCgroup {
+    pub fn new() -> ProcPidCgroup {
+        ProcPidCgroup::default()
+    }
+    /// The cgroup v2 unified hierarchy path, None if the process is not in a cgroup v2 hierarchy.
+    pub fn unified_path(&self) -> Option<&str> {
+        self.cgroups.iter()
+            .find(|entry| entry.hierarchy_id == 0)
+            .map(|entry| entry.path.as_str())
+    }
+    /// The cgroup v1 path of the hierarchy the controller is bound to, None if the controller is not found.
+    pub fn controller_path(&self, controller: &str) -> Option<&str> {
+        self.cgroups.iter()
+            .find(|entry| entry.controllers.iter().any(|entry_controller| entry_controller == controller))
+            .map(|entry| entry.path.as_str())
+    }
+    pub fn parse_proc_pid_cgroup(proc_pid_cgroup: &str, pid: &str) -> Result<ProcPidCgroup, ProcSysParserError> {
+        let mut procpidcgroup = ProcPidCgroup::new();
+        procpidcgroup.pid = pid.to_string();
+
+        for line in proc_pid_cgroup.lines() {
+            if line.trim().is_empty() { continue };
+            // the path can contain ':', so split in three parts only.
+            let mut fields = line.splitn(3, ':');
+            procpidcgroup.cgroups.push(CgroupEntry {
+                hierarchy_id: fields.next()
+                    .ok_or(ProcSysParserError::IteratorItemError {item: "process cgroup hierarchy_id".to_string() })?
+                    .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                controllers: fields.next()
+                    .ok_or(ProcSysParserError::IteratorItemError {item: "process cgroup controllers".to_string() })?
+                    .split(',')
+                    .filter(|controller| !controller.is_empty())
+                    .map(|controller| controller.to_string())
+                    .collect(),
+                path: fields.next()
+                    .ok_or(ProcSysParserError::IteratorItemError {item: "process cgroup path".to_string() })?
+                    .to_string(),
+            });
+        }
+        Ok(procpidcgroup)
+    }
+    pub fn read_proc_pid_cgroup(proc_pid_cgroup_file: &str, pid: &str) -> Result<ProcPidCgroup, ProcSysParserError> {
+        let proc_pid_cgroup_output = read_to_string(proc_pid_cgroup_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_cgroup_file.to_string(), error })?;
+        ProcPidCgroup::parse_proc_pid_cgroup(&proc_pid_cgroup_output, pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_cgroup_hybrid() {
+        let proc_pid_cgroup = "12:memory:/system.slice/sshd.service
+3:cpu,cpuacct:/system.slice/sshd.service
+1:name=systemd:/system.slice/sshd.service
+0::/system.slice/sshd.service
+";
+        let result = ProcPidCgroup::parse_proc_pid_cgroup(proc_pid_cgroup, "1").unwrap();
+        assert_eq!(result.cgroups[1], CgroupEntry { hierarchy_id: 3, controllers: vec!["cpu".to_string(), "cpuacct".to_string()], path: "/system.slice/sshd.service".to_string() });
+        assert_eq!(result.unified_path(), Some("/system.slice/sshd.service"));
+        assert_eq!(result.controller_path("cpuacct"), Some("/system.slice/sshd.service"));
+        assert_eq!(result.controller_path("blkio"), None);
+    }
+
+    #[test]
+    fn parse_corrupted_proc_pid_cgroup() {
+        assert!(ProcPidCgroup::parse_proc_pid_cgroup("A::/\n", "1").is_err());
+        assert!(ProcPidCgroup::parse_proc_pid_cgroup("0:\n", "1").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_cgroup_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/cgroup", test_path), "0::/user.slice/user-1000.slice/session-3.scope\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/cgroup", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidCgroup { pid: "1234".to_string(), cgroups: vec![ CgroupEntry { hierarchy_id: 0, controllers: vec![], path: "/user.slice/user-1000.slice/session-3.scope".to_string() } ] });
+    }
+}
@@ -0,0 +1,253 @@
+/*!
+Calculate the cpu usage per process between two samples, like `top`, into the struct [`ProcessCpuUsage`].
+
+A sample, [`ProcessCpuSample`], holds `/proc/stat` read with [`crate::stat`] and `/proc/<pid>/stat` of all processes
+read with [`crate::process::stat`]. The cpu usage between two samples is calculated with
+[`ProcessCpuSample::cpu_usage`]:
+- the elapsed time is the increase of the total cpu time of `/proc/stat` divided by the number of cpus, which is the
+  same clock as the cpu times of the processes, so it is not influenced by the time it takes to read the processes.
+- the cpu usage of a process is the increase of its user and system time as a percentage of the elapsed time, which
+  can be above 100% for a multithreaded process, like `top` shows it. `cpu_percent_of_total` is the percentage of the
+  total cpu capacity of all cpus, like `top` shows it with Irix mode off.
+
+A process is matched between the samples by pid and start time, so a pid that is reused is a new process.
+A process that was spawned between the samples has used all its cpu time in the interval, so its full cpu time is
+counted. A process that exited between the samples is not in the current sample, and its pid is listed in `exited`.
+
+Here is an example showing the processes using the most cpu during a second:
+```no_run
+use proc_sys_parser::process::cpu_usage;
+
+let previous = cpu_usage::read().unwrap();
+std::thread::sleep(std::time::Duration::from_secs(1));
+let current = cpu_usage::read().unwrap();
+
+let process_cpu_usage = current.cpu_usage(&previous);
+for process in process_cpu_usage.processes.iter().take(10) {
+    println!("{:8} {:16} {:6.1}% user {:6.1}% system {:6.1}%", process.pid, process.comm, process.cpu_percent, process.user_percent, process.system_percent);
+}
+```
+Example output:
+```text
+    4321 postgres          98.0% user   90.0% system    8.0%
+    1234 java              45.0% user   40.0% system    5.0%
+```
+
+If you want to change the path that is read for [`ProcessCpuSample`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::cpu_usage::Builder;
+
+let process_cpu_sample = Builder::new().path("/myproc").read();
+```
+*/
+use std::collections::HashMap;
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::stat::ProcPidStat;
+use crate::stat::{CpuStat, ProcStat};
+
+/// Struct for holding a sample of the system and process cpu times
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessCpuSample {
+    pub stat: ProcStat,
+    pub processes: Vec<ProcPidStat>,
+}
+
+/// Struct for holding the cpu usage of the processes between two samples
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessCpuUsage {
+    /// The elapsed time between the samples in milliseconds, obtained from the system cpu times.
+    pub elapsed_ms: u64,
+    /// The number of cpus of the current sample.
+    pub cpus: u64,
+    /// The processes of the current sample, sorted by cpu usage, highest first.
+    pub processes: Vec<ProcessCpu>,
+    /// The pids of the processes that exited between the samples.
+    pub exited: Vec<u32>,
+}
+
+/// Struct for holding the cpu usage of a process between two samples
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcessCpu {
+    pub pid: u32,
+    pub comm: String,
+    /// The user and system time used in the interval in milliseconds.
+    pub cpu_time_ms: u64,
+    /// The user and system time as a percentage of the elapsed time, which can be above 100%.
+    pub cpu_percent: f64,
+    pub user_percent: f64,
+    pub system_percent: f64,
+    /// The cpu time as a percentage of the total cpu capacity of all cpus.
+    pub cpu_percent_of_total: f64,
+    /// The process was spawned between the samples.
+    pub spawned: bool,
+}
+
+/// Builder pattern for [`ProcessCpuSample`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcessCpuSample, ProcSysParserError> {
+        ProcessCpuSample::read_process_cpu_sample(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`ProcessCpuSample`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessCpuSample, ProcSysParserError> {
+   Builder::new().read()
+}
+
+/// The total cpu time in milliseconds. The guest times are included in user and nice by the kernel.
+fn total_ms(cpu: &CpuStat) -> u64 {
+    cpu.user + cpu.nice + cpu.system + cpu.idle
+        + cpu.iowait.unwrap_or_default()
+        + cpu.irq.unwrap_or_default()
+        + cpu.softirq.unwrap_or_default()
+        + cpu.steal.unwrap_or_default()
+}
+
+impl ProcessCpuSample {
+    pub fn new() -> ProcessCpuSample {
+        ProcessCpuSample::default()
+    }
+    /// The cpu usage of the processes between an earlier sample and this sample.
+    /// If no time elapsed, the percentages are 0.
+    pub fn cpu_usage(&self, previous: &ProcessCpuSample) -> ProcessCpuUsage {
+        let cpus = self.stat.cpu_individual.len().max(1) as u64;
+        let elapsed_ms = total_ms(&self.stat.cpu_total).saturating_sub(total_ms(&previous.stat.cpu_total)) / cpus;
+        let percent = |time_ms: u64, interval_ms: u64| if interval_ms == 0 { 0. } else { time_ms as f64 / interval_ms as f64 * 100. };
+        let previous_processes: HashMap<(u32, u64), &ProcPidStat> = previous.processes.iter()
+            .map(|process| ((process.pid, process.starttime), process))
+            .collect();
+
+        let mut processes: Vec<ProcessCpu> = self.processes.iter().map(|process| {
+            let previous_process = previous_processes.get(&(process.pid, process.starttime));
+            let user_ms = process.utime.saturating_sub(previous_process.map(|previous| previous.utime).unwrap_or_default());
+            let system_ms = process.stime.saturating_sub(previous_process.map(|previous| previous.stime).unwrap_or_default());
+            ProcessCpu {
+                pid: process.pid,
+                comm: process.comm.clone(),
+                cpu_time_ms: user_ms + system_ms,
+                cpu_percent: percent(user_ms + system_ms, elapsed_ms),
+                user_percent: percent(user_ms, elapsed_ms),
+                system_percent: percent(system_ms, elapsed_ms),
+                cpu_percent_of_total: percent(user_ms + system_ms, elapsed_ms * cpus),
+                spawned: previous_process.is_none(),
+            }
+        }).collect();
+        processes.sort_by(|a, b| b.cpu_time_ms.cmp(&a.cpu_time_ms).then(a.pid.cmp(&b.pid)));
+
+        let current_processes: HashMap<(u32, u64), &ProcPidStat> = self.processes.iter()
+            .map(|process| ((process.pid, process.starttime), process))
+            .collect();
+        let mut exited: Vec<u32> = previous.processes.iter()
+            .filter(|process| !current_processes.contains_key(&(process.pid, process.starttime)))
+            .map(|process| process.pid)
+            .collect();
+        exited.sort();
+
+        ProcessCpuUsage {
+            elapsed_ms,
+            cpus,
+            processes,
+            exited,
+        }
+    }
+    pub fn read_process_cpu_sample(proc_path: &str) -> Result<ProcessCpuSample, ProcSysParserError> {
+        let mut processes = Vec::new();
+
+        for pid in read_pids(proc_path)? {
+            match ProcPidStat::read_proc_pid_stat(format!("{}/{}/stat", proc_path, pid).as_str()) {
+                Ok(process) => processes.push(process),
+                // the process has exited in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(ProcessCpuSample {
+            stat: ProcStat::read_proc_stat(format!("{}/stat", proc_path).as_str())?,
+            processes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn sample(total: (u64, u64), processes: &[(u32, &str, u64, u64, u64)]) -> ProcessCpuSample {
+        ProcessCpuSample {
+            stat: ProcStat {
+                cpu_total: CpuStat { user: total.0, idle: total.1, ..Default::default() },
+                cpu_individual: vec![CpuStat::default(), CpuStat::default()],
+                ..Default::default()
+            },
+            processes: processes.iter()
+                .map(|(pid, comm, starttime, utime, stime)| ProcPidStat { pid: *pid, comm: comm.to_string(), starttime: *starttime, utime: *utime, stime: *stime, ..Default::default() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn calculate_process_cpu_usage() {
+        // 2 cpus and 2000 ms of cpu time is 1000 ms elapsed.
+        // 10 is busy, 20 exits, 30 is spawned, 40 exits and the pid is reused by a new process.
+        let previous = sample((1000, 9000), &[(10, "postgres", 100, 5000, 1000), (20, "sleep", 200, 10, 10), (40, "bash", 300, 500, 500)]);
+        let current = sample((2500, 9500), &[(10, "postgres", 100, 5900, 1100), (30, "make", 5000, 150, 50), (40, "cc1", 5100, 300, 0)]);
+
+        let result = current.cpu_usage(&previous);
+
+        assert_eq!(result.elapsed_ms, 1000);
+        assert_eq!(result.cpus, 2);
+        assert_eq!(result.processes[0], ProcessCpu { pid: 10, comm: "postgres".to_string(), cpu_time_ms: 1000, cpu_percent: 100., user_percent: 90., system_percent: 10., cpu_percent_of_total: 50., spawned: false });
+        assert_eq!(result.processes.iter().map(|process| (process.pid, process.cpu_time_ms, process.spawned)).collect::<Vec<_>>(), vec![(10, 1000, false), (40, 300, true), (30, 200, true)]);
+        assert_eq!(result.exited, vec![20, 40]);
+        // no time elapsed.
+        assert_eq!(current.cpu_usage(&current).processes[0].cpu_percent, 0.);
+    }
+
+    #[test]
+    fn create_process_stat_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1", test_path)).expect("Error creating mock directory.");
+        write(format!("{}/stat", test_path), "cpu  1000 0 500 80000 100 0 10 0 0 0
+cpu0 500 0 250 40000 50 0 5 0 0 0
+cpu1 500 0 250 40000 50 0 5 0 0 0
+intr 0
+ctxt 1000
+btime 1702127060
+processes 100
+procs_running 1
+procs_blocked 0
+softirq 0 0 0 0 0 0 0 0 0 0 0
+").unwrap_or_else(|_| panic!("Error writing to {}/stat", test_path));
+        write(format!("{}/1/stat", test_path), "1 (systemd) S 0 1 1 0 -1 4194560 0 0 0 0 10 10 0 0 20 0 1 0 5 1000000 100 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0\n")
+            .unwrap_or_else(|_| panic!("Error writing to {}/1/stat", test_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.stat.cpu_individual.len(), 2);
+        assert_eq!(result.processes.len(), 1);
+        assert_eq!(result.processes[0].comm, "systemd");
+    }
+}
@@ -0,0 +1,257 @@
+/*!
+Count the inotify and epoll usage of all processes and the limits into the struct [`ProcessInotify`].
+
+The inotify instances and epoll instances of a process are file descriptors, which are links in `/proc/<pid>/fd`
+with target `anon_inode:inotify` and `anon_inode:[eventpoll]`. The watches of an instance are shown in
+`/proc/<pid>/fdinfo/<fd>`, with an `inotify wd:` line per inotify watch and a `tfd:` line per file descriptor
+watched by epoll.
+
+The number of instances and watches is limited per user by:
+- `/proc/sys/fs/inotify/max_user_instances`: the inotify instances.
+- `/proc/sys/fs/inotify/max_user_watches`: the inotify watches.
+- `/proc/sys/fs/epoll/max_user_watches`: the epoll watches.
+
+When a limit is reached, `inotify_init` or `inotify_add_watch` fails, which shows as "too many open files" or
+"no space left on device" for tools such as `tail -f` or file watchers, so [`ProcessInotify::users`] shows the use
+per user as a percentage of the limits. The file descriptors of processes of other users can only be read by root,
+so as a normal user only the own processes are found. Processes without inotify or epoll instances are not included.
+
+Here is an example showing the processes using the most inotify watches:
+```no_run
+use proc_sys_parser::process::inotify;
+
+let process_inotify = inotify::read().unwrap();
+
+for usage in process_inotify.top_inotify_watches().iter().take(10) {
+    println!("{:8} {:16} uid {:6} instances {:4} watches {:8}", usage.pid, usage.comm, usage.uid, usage.inotify_instances, usage.inotify_watches);
+}
+for (uid, user) in process_inotify.users() {
+    println!("uid {}: {:.1}% of the inotify watches", uid, user.inotify_watches_percent.unwrap_or_default());
+}
+```
+Example output:
+```text
+    1234 code             uid   1000 instances    3 watches    52188
+     812 systemd          uid   1000 instances    6 watches      104
+uid 1000: 99.6% of the inotify watches
+```
+
+If you want to change the path that is read for [`ProcessInotify`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::inotify::Builder;
+
+let process_inotify = Builder::new().path("/myproc").read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::status::ProcPidStatus;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_link_name};
+
+/// Struct for holding the inotify and epoll usage of the processes and the limits
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessInotify {
+    /// The processes with inotify or epoll instances, sorted by pid.
+    pub processes: Vec<InotifyUsage>,
+    pub limits: InotifyLimits,
+}
+
+/// Struct for holding the inotify and epoll usage of a process
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InotifyUsage {
+    pub pid: u32,
+    pub comm: String,
+    /// The effective uid, which the limits apply to.
+    pub uid: u32,
+    pub inotify_instances: u64,
+    pub inotify_watches: u64,
+    pub epoll_instances: u64,
+    pub epoll_watches: u64,
+}
+
+/// Struct for holding the per user limits of `/proc/sys/fs`, None if a limit cannot be read
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InotifyLimits {
+    pub inotify_max_user_instances: Option<u64>,
+    pub inotify_max_user_watches: Option<u64>,
+    pub epoll_max_user_watches: Option<u64>,
+}
+
+/// Struct for holding the inotify and epoll usage of a user as a percentage of the limits
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct UserInotifyUsage {
+    pub processes: u64,
+    pub inotify_instances: u64,
+    pub inotify_watches: u64,
+    pub epoll_instances: u64,
+    pub epoll_watches: u64,
+    pub inotify_instances_percent: Option<f64>,
+    pub inotify_watches_percent: Option<f64>,
+    pub epoll_watches_percent: Option<f64>,
+}
+
+/// Builder pattern for [`ProcessInotify`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcessInotify, ProcSysParserError> {
+        ProcessInotify::read_process_inotify(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`ProcessInotify`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessInotify, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcessInotify {
+    pub fn new() -> ProcessInotify {
+        ProcessInotify::default()
+    }
+    /// The processes sorted by the number of inotify watches, highest first.
+    pub fn top_inotify_watches(&self) -> Vec<&InotifyUsage> {
+        let mut processes: Vec<&InotifyUsage> = self.processes.iter().collect();
+        processes.sort_by(|a, b| b.inotify_watches.cmp(&a.inotify_watches).then(a.pid.cmp(&b.pid)));
+        processes
+    }
+    /// The processes sorted by the number of epoll watches, highest first.
+    pub fn top_epoll_watches(&self) -> Vec<&InotifyUsage> {
+        let mut processes: Vec<&InotifyUsage> = self.processes.iter().collect();
+        processes.sort_by(|a, b| b.epoll_watches.cmp(&a.epoll_watches).then(a.pid.cmp(&b.pid)));
+        processes
+    }
+    /// The usage per uid, with the percentage of the limits.
+    pub fn users(&self) -> BTreeMap<u32, UserInotifyUsage> {
+        let mut users: BTreeMap<u32, UserInotifyUsage> = BTreeMap::new();
+        for process in &self.processes {
+            let user = users.entry(process.uid).or_default();
+            user.processes += 1;
+            user.inotify_instances += process.inotify_instances;
+            user.inotify_watches += process.inotify_watches;
+            user.epoll_instances += process.epoll_instances;
+            user.epoll_watches += process.epoll_watches;
+        }
+        let percent = |used: u64, limit: Option<u64>| limit.filter(|limit| *limit > 0).map(|limit| used as f64 / limit as f64 * 100.);
+        for user in users.values_mut() {
+            user.inotify_instances_percent = percent(user.inotify_instances, self.limits.inotify_max_user_instances);
+            user.inotify_watches_percent = percent(user.inotify_watches, self.limits.inotify_max_user_watches);
+            user.epoll_watches_percent = percent(user.epoll_watches, self.limits.epoll_max_user_watches);
+        }
+        users
+    }
+    pub fn read_inotify_limits(proc_path: &str) -> Result<InotifyLimits, ProcSysParserError> {
+        Ok(InotifyLimits {
+            inotify_max_user_instances: read_file_option_u64(Path::new(&format!("{}/sys/fs/inotify/max_user_instances", proc_path)))?,
+            inotify_max_user_watches: read_file_option_u64(Path::new(&format!("{}/sys/fs/inotify/max_user_watches", proc_path)))?,
+            epoll_max_user_watches: read_file_option_u64(Path::new(&format!("{}/sys/fs/epoll/max_user_watches", proc_path)))?,
+        })
+    }
+    /// Count the inotify and epoll instances and watches of the process `pid`, None if the process has none or its
+    /// file descriptors cannot be read.
+    pub fn read_inotify_usage(proc_path: &str, pid: u32) -> Option<InotifyUsage> {
+        let fds = read_dir(format!("{}/{}/fd", proc_path, pid)).ok()?;
+        let mut usage = InotifyUsage { pid, ..Default::default() };
+
+        for fd in fds.filter_map(|entry| entry.ok()) {
+            let fdinfo = || read_to_string(format!("{}/{}/fdinfo/{}", proc_path, pid, fd.file_name().to_string_lossy())).unwrap_or_default();
+            match read_link_name(&fd.path()).as_deref() {
+                Some("anon_inode:inotify") => {
+                    usage.inotify_instances += 1;
+                    usage.inotify_watches += fdinfo().lines().filter(|line| line.starts_with("inotify wd:")).count() as u64;
+                },
+                Some("anon_inode:[eventpoll]") => {
+                    usage.epoll_instances += 1;
+                    usage.epoll_watches += fdinfo().lines().filter(|line| line.starts_with("tfd:")).count() as u64;
+                },
+                _ => {},
+            }
+        }
+        if usage.inotify_instances == 0 && usage.epoll_instances == 0 { return None };
+
+        usage.comm = read_file_option_string(Path::new(&format!("{}/{}/comm", proc_path, pid))).unwrap_or_default();
+        usage.uid = ProcPidStatus::read_proc_pid_status(format!("{}/{}/status", proc_path, pid).as_str()).ok()
+            .and_then(|status| status.uid.get(1).copied())
+            .unwrap_or_default();
+        Some(usage)
+    }
+    pub fn read_process_inotify(proc_path: &str) -> Result<ProcessInotify, ProcSysParserError> {
+        Ok(ProcessInotify {
+            processes: read_pids(proc_path)?
+                .into_iter()
+                .filter_map(|pid| ProcessInotify::read_inotify_usage(proc_path, pid))
+                .collect(),
+            limits: ProcessInotify::read_inotify_limits(proc_path)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::os::unix::fs::symlink;
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_process_fds_and_limits_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/sys/fs/inotify", test_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/sys/fs/epoll", test_path)).expect("Error creating mock directory.");
+        write(format!("{}/sys/fs/inotify/max_user_instances", test_path), "128\n").unwrap_or_else(|_| panic!("Error writing to {}/sys/fs/inotify/max_user_instances", test_path));
+        write(format!("{}/sys/fs/inotify/max_user_watches", test_path), "8\n").unwrap_or_else(|_| panic!("Error writing to {}/sys/fs/inotify/max_user_watches", test_path));
+
+        let inotify_fdinfo = "pos:\t0\nflags:\t00\nmnt_id:\t15\nino:\t1057\ninotify wd:2 ino:a2 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:a200000000000000\ninotify wd:1 ino:1 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:0100000000000000\n";
+        let epoll_fdinfo = "pos:\t0\nflags:\t02\nmnt_id:\t15\nino:\t1057\ntfd:        5 events:       19 data:                5  pos:0 ino:3a1 sdev:9\ntfd:        7 events:       19 data:                7  pos:0 ino:3a2 sdev:9\ntfd:        8 events:       19 data:                8  pos:0 ino:3a3 sdev:9\n";
+        // 100 uses inotify and epoll, 200 uses inotify twice and runs setuid from root, 300 has no inotify or epoll.
+        for (pid, comm, real_uid, uid, fds) in [
+            (100, "systemd", 1000, 1000, vec![(3, "anon_inode:inotify"), (4, "anon_inode:[eventpoll]"), (5, "socket:[1234]")]),
+            (200, "code", 0, 1000, vec![(10, "anon_inode:inotify"), (11, "anon_inode:inotify")]),
+            (300, "bash", 1001, 1001, vec![(0, "/dev/pts/0")]),
+        ] {
+            create_dir_all(format!("{}/{}/fd", test_path, pid)).expect("Error creating mock directory.");
+            create_dir_all(format!("{}/{}/fdinfo", test_path, pid)).expect("Error creating mock directory.");
+            write(format!("{}/{}/comm", test_path, pid), format!("{}\n", comm)).unwrap_or_else(|_| panic!("Error writing to {}/{}/comm", test_path, pid));
+            write(format!("{}/{}/status", test_path, pid), format!("Name:\t{}\nUid:\t{}\t{}\t{}\t{}\n", comm, real_uid, uid, uid, uid)).unwrap_or_else(|_| panic!("Error writing to {}/{}/status", test_path, pid));
+            for (fd, target) in fds {
+                symlink(target, format!("{}/{}/fd/{}", test_path, pid, fd)).expect("Error creating mock symlink.");
+                let fdinfo = if target.ends_with("inotify") { inotify_fdinfo } else if target.ends_with("[eventpoll]") { epoll_fdinfo } else { "pos:\t0\n" };
+                write(format!("{}/{}/fdinfo/{}", test_path, pid, fd), fdinfo).unwrap_or_else(|_| panic!("Error writing to {}/{}/fdinfo/{}", test_path, pid, fd));
+            }
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.limits, InotifyLimits { inotify_max_user_instances: Some(128), inotify_max_user_watches: Some(8), epoll_max_user_watches: None });
+        assert_eq!(result.processes, vec![
+            InotifyUsage { pid: 100, comm: "systemd".to_string(), uid: 1000, inotify_instances: 1, inotify_watches: 2, epoll_instances: 1, epoll_watches: 3 },
+            InotifyUsage { pid: 200, comm: "code".to_string(), uid: 1000, inotify_instances: 2, inotify_watches: 4, epoll_instances: 0, epoll_watches: 0 },
+        ]);
+        assert_eq!(result.top_inotify_watches()[0].pid, 200);
+        assert_eq!(result.top_epoll_watches()[0].pid, 100);
+        let users = result.users();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[&1000].processes, 2);
+        assert_eq!(users[&1000].inotify_watches_percent, Some(75.));
+        assert_eq!(users[&1000].epoll_watches_percent, None);
+    }
+}
@@ -0,0 +1,203 @@
+/*!
+Report the memory usage per program, like `ps_mem`, into the struct [`ProcessMemoryReport`].
+
+The report reads `/proc/<pid>/smaps_rollup` of all processes using [`crate::process::smaps_rollup`], and adds the
+memory of the processes per program, which is the command name in `/proc/<pid>/comm`. Adding the resident set size
+of processes counts the memory shared between processes, such as the pages of shared libraries or the shared memory
+of a database, once for every process, which makes programs with many processes look much larger than they are.
+This report counts the memory once, in the same way as `ps_mem`:
+- private: the clean and dirty private pages and the private hugetlbfs pages, which are only used by the process.
+- shared: the proportional set size (pss) minus the private pages, plus the shared hugetlbfs pages. The pss divides
+  a shared page between the processes using it, so the shared memory of all processes adds up to the shared pages.
+- total: private plus shared.
+- swap: the proportional swap size, which divides a shared swapped page in the same way.
+
+Kernel threads, which have no memory, are not included. Reading `smaps_rollup` of processes of other users requires
+ptrace permissions, so as a normal user the processes of other users are skipped, and their pids are listed in
+`skipped`, as are processes that exit during the scan.
+
+Here is an example showing the memory usage per program:
+```no_run
+use proc_sys_parser::process::memory_report;
+
+let process_memory_report = memory_report::read().unwrap();
+
+println!(" Private  +   Shared  =  RAM used       Program");
+for program in &process_memory_report.programs {
+    println!("{:>7} kB + {:>7} kB = {:>7} kB       {} ({})", program.private_kb, program.shared_kb, program.total_kb, program.comm, program.processes);
+}
+println!("{:>33} kB", process_memory_report.total_kb());
+```
+Example output:
+```text
+ Private  +   Shared  =  RAM used       Program
+ 812340 kB +   40212 kB =  852552 kB       postgres (12)
+ 120304 kB +    8820 kB =  129124 kB       java (1)
+   2040 kB +     530 kB =    2570 kB       bash (3)
+                           984246 kB
+```
+
+If you want to change the path that is read for [`ProcessMemoryReport`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::memory_report::Builder;
+
+let process_memory_report = Builder::new().path("/myproc").read();
+```
+*/
+use std::collections::HashMap;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::smaps_rollup::ProcPidSmapsRollup;
+use crate::utils::read_file_option_string;
+
+/// Struct for holding the memory usage per program
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessMemoryReport {
+    /// The programs, sorted by total memory, largest first.
+    pub programs: Vec<ProgramMemory>,
+    /// The pids of the processes of which `smaps_rollup` could not be read.
+    pub skipped: Vec<u32>,
+}
+
+/// Struct for holding the memory usage of a program in kilobytes
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProgramMemory {
+    pub comm: String,
+    /// The number of processes of the program.
+    pub processes: u64,
+    pub private_kb: u64,
+    pub shared_kb: u64,
+    pub total_kb: u64,
+    pub swap_kb: u64,
+}
+
+/// Builder pattern for [`ProcessMemoryReport`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcessMemoryReport, ProcSysParserError> {
+        ProcessMemoryReport::read_process_memory_report(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`ProcessMemoryReport`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessMemoryReport, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProgramMemory {
+    /// Add the memory of a process to the program.
+    pub fn add(&mut self, smaps_rollup: &ProcPidSmapsRollup) {
+        let private_kb = smaps_rollup.private_clean + smaps_rollup.private_dirty + smaps_rollup.private_hugetlb;
+        let shared_kb = smaps_rollup.pss.saturating_sub(smaps_rollup.private_clean + smaps_rollup.private_dirty) + smaps_rollup.shared_hugetlb;
+        self.processes += 1;
+        self.private_kb += private_kb;
+        self.shared_kb += shared_kb;
+        self.total_kb += private_kb + shared_kb;
+        self.swap_kb += smaps_rollup.swappss;
+    }
+}
+
+impl ProcessMemoryReport {
+    pub fn new() -> ProcessMemoryReport {
+        ProcessMemoryReport::default()
+    }
+    /// The total memory of all programs in kilobytes.
+    pub fn total_kb(&self) -> u64 {
+        self.programs.iter().map(|program| program.total_kb).sum()
+    }
+    /// The total swap of all programs in kilobytes.
+    pub fn total_swap_kb(&self) -> u64 {
+        self.programs.iter().map(|program| program.swap_kb).sum()
+    }
+    pub fn program(&self, comm: &str) -> Option<&ProgramMemory> {
+        self.programs.iter().find(|program| program.comm == comm)
+    }
+    pub fn read_process_memory_report(proc_path: &str) -> Result<ProcessMemoryReport, ProcSysParserError> {
+        let mut programs: HashMap<String, ProgramMemory> = HashMap::new();
+        let mut skipped = Vec::new();
+
+        for pid in read_pids(proc_path)? {
+            let smaps_rollup = match ProcPidSmapsRollup::read_proc_pid_smaps_rollup(format!("{}/{}/smaps_rollup", proc_path, pid).as_str()) {
+                Ok(smaps_rollup) => smaps_rollup,
+                // no permission, or the process has exited in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) => {
+                    skipped.push(pid);
+                    continue;
+                },
+                Err(error) => return Err(error),
+            };
+            // kernel threads have no memory.
+            if smaps_rollup.rss == 0 && smaps_rollup.swap == 0 { continue };
+            let comm = read_file_option_string(Path::new(&format!("{}/{}/comm", proc_path, pid))).unwrap_or_default();
+            programs.entry(comm.clone())
+                .or_insert_with(|| ProgramMemory { comm, ..Default::default() })
+                .add(&smaps_rollup);
+        }
+        let mut programs: Vec<ProgramMemory> = programs.into_values().collect();
+        programs.sort_by(|a, b| b.total_kb.cmp(&a.total_kb).then_with(|| a.comm.cmp(&b.comm)));
+
+        Ok(ProcessMemoryReport {
+            programs,
+            skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_process(test_path: &str, pid: u32, comm: &str, smaps_rollup: &str) {
+        create_dir_all(format!("{}/{}", test_path, pid)).expect("Error creating mock directory.");
+        write(format!("{}/{}/comm", test_path, pid), format!("{}\n", comm))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/comm", test_path, pid));
+        write(format!("{}/{}/smaps_rollup", test_path, pid), smaps_rollup)
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/smaps_rollup", test_path, pid));
+    }
+
+    #[test]
+    fn create_process_smaps_rollup_and_report() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        // two postgres processes sharing 8000 kB, which the pss divides between them.
+        create_mock_process(&test_path, 100, "postgres", "Rss: 10000 kB\nPss: 6000 kB\nShared_Clean: 8000 kB\nPrivate_Clean: 500 kB\nPrivate_Dirty: 1500 kB\nSwap: 100 kB\nSwapPss: 50 kB\n");
+        create_mock_process(&test_path, 101, "postgres", "Rss: 9000 kB\nPss: 5000 kB\nShared_Clean: 8000 kB\nPrivate_Dirty: 1000 kB\nSwap: 100 kB\nSwapPss: 50 kB\n");
+        create_mock_process(&test_path, 200, "bash", "Rss: 3000 kB\nPss: 1500 kB\nShared_Clean: 2000 kB\nPrivate_Dirty: 1000 kB\nShared_Hugetlb: 2048 kB\n");
+        // a kernel thread.
+        create_mock_process(&test_path, 2, "kthreadd", "");
+        // a process of another user, which can not be read.
+        create_dir_all(format!("{}/300", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.programs, vec![
+            ProgramMemory { comm: "postgres".to_string(), processes: 2, private_kb: 3000, shared_kb: 8000, total_kb: 11000, swap_kb: 100 },
+            ProgramMemory { comm: "bash".to_string(), processes: 1, private_kb: 1000, shared_kb: 2548, total_kb: 3548, swap_kb: 0 },
+        ]);
+        assert_eq!(result.skipped, vec![300]);
+        assert_eq!(result.total_kb(), 14548);
+        assert_eq!(result.total_swap_kb(), 100);
+        assert_eq!(result.program("bash").map(|program| program.processes), Some(1));
+    }
+}
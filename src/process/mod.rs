@@ -0,0 +1,42 @@
+/*!
+Read data from the per-process directories `/proc/<pid>`.
+
+Every submodule in `process` parses a single file (or a small group of files) found in `/proc/<pid>`.
+By default the submodules read the data of the process itself via `/proc/self`; use the `pid()` function of
+the Builder of the submodule to read the data of another process.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#process-specific-subdirectories>
+*/
+use std::fs::read_dir;
+use crate::ProcSysParserError;
+
+pub mod aggregate;
+pub mod cgroup;
+pub mod cpu_usage;
+pub mod inotify;
+pub mod memory_report;
+pub mod mountinfo;
+pub mod numa_maps;
+pub mod oom;
+pub mod pagemap;
+pub mod schedstat;
+pub mod smaps_rollup;
+pub mod socket;
+pub mod stack;
+pub mod stat;
+pub mod status;
+pub mod task;
+pub mod tree;
+pub mod wchan;
+
+/// List the pids of all processes in `proc_path` (normally `/proc`), sorted.
+/// This takes all directories with a numeric name.
+pub fn read_pids(proc_path: &str) -> Result<Vec<u32>, ProcSysParserError> {
+    let mut pids: Vec<u32> = read_dir(proc_path)
+        .map_err(|error| ProcSysParserError::DirectoryReadError { directory: proc_path.to_string(), error })?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())
+        .collect();
+    pids.sort();
+    Ok(pids)
+}
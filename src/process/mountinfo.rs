@@ -0,0 +1,331 @@
+/*!
+Read data from `/proc/<pid>/mountinfo` into the struct [`ProcPidMountInfo`].
+
+The `/proc/<pid>/mountinfo` file contains the mounts of the mount namespace of a process, which differ from the
+mounts of the host for a process in a container or a service with private mounts. A line looks like:
+```text
+36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+```
+- the mount id and the mount id of the parent mount.
+- the major and minor number of the device.
+- the root: the directory of the filesystem that is mounted, which is not `/` for a bind mount of a directory.
+- the mount point and the per mount options.
+- optional fields such as `shared:<id>` and `master:<id>` for mount propagation, ended by `-`.
+- the filesystem type, the source and the per superblock options.
+
+Spaces, tabs, newlines and backslashes in the paths are shown as octal escapes (`\040`), which are unescaped.
+
+The mount namespace of the process is the inode of the link `/proc/<pid>/ns/mnt`; processes with the same inode
+share the mounts. [`ProcPidMountInfo::diff`] shows the differences between the mounts of two processes, by mount point.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#proc-pid-mountinfo-information-about-mounts>
+
+Here is an example obtaining the data from `/proc/self/mountinfo`:
+```no_run
+use proc_sys_parser::process::mountinfo;
+
+let proc_pid_mountinfo = mountinfo::read();
+
+println!("{:#?}", proc_pid_mountinfo);
+```
+Example output:
+```text
+ProcPidMountInfo {
+    pid: "self",
+    mount_namespace: Some(4026531841),
+    mounts: [
+        MountInfo {
+            mount_id: 24,
+            parent_id: 1,
+            major: 253,
+            minor: 0,
+            root: "/",
+            mount_point: "/",
+            mount_options: "rw,relatime",
+            optional_fields: ["shared:1"],
+            filesystem_type: "xfs",
+            source: "/dev/mapper/rl-root",
+            super_options: "rw,attr2,inode64,logbufs=8,logbsize=32k,noquota",
+        },
+        ..
+    ],
+}
+```
+(edited for readability)
+
+Here is an example showing the mounts of a process in a container (pid 1234) that differ from the host (pid 1):
+```no_run
+use proc_sys_parser::process::mountinfo::Builder;
+
+let host = Builder::new().pid(1).read().unwrap();
+let container = Builder::new().pid(1234).read().unwrap();
+
+if host.mount_namespace != container.mount_namespace {
+    let diff = host.diff(&container);
+    for mount in diff.only_in_other {
+        println!("{} {} {}", mount.mount_point, mount.filesystem_type, mount.source);
+    }
+}
+```
+
+If you want to read the data of another process, and/or change the path that is read for [`ProcPidMountInfo`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::mountinfo::Builder;
+
+let proc_pid_mountinfo = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::read_link_name;
+
+/// Struct for holding `/proc/<pid>/mountinfo`
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidMountInfo {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    /// The inode of `/proc/<pid>/ns/mnt`, None if it cannot be read.
+    pub mount_namespace: Option<u64>,
+    pub mounts: Vec<MountInfo>,
+}
+
+/// Struct for holding a single mount
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct MountInfo {
+    pub mount_id: u32,
+    pub parent_id: u32,
+    pub major: u32,
+    pub minor: u32,
+    /// The directory of the filesystem that is mounted.
+    pub root: String,
+    pub mount_point: String,
+    /// The per mount options.
+    pub mount_options: String,
+    /// The optional fields, such as `shared:1` or `master:2`.
+    pub optional_fields: Vec<String>,
+    pub filesystem_type: String,
+    pub source: String,
+    /// The per superblock options.
+    pub super_options: String,
+}
+
+/// Struct for holding the differences between the mounts of two processes
+#[derive(Debug, PartialEq, Default)]
+pub struct MountInfoDiff {
+    /// The mounts with a mount point that is only found in the first process.
+    pub only_in_self: Vec<MountInfo>,
+    /// The mounts with a mount point that is only found in the other process.
+    pub only_in_other: Vec<MountInfo>,
+    /// The mounts of the first and the other process with the same mount point, but a different filesystem type,
+    /// source, root or mount options.
+    pub changed: Vec<(MountInfo, MountInfo)>,
+}
+
+/// Builder pattern for [`ProcPidMountInfo`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidMountInfo, ProcSysParserError> {
+        ProcPidMountInfo::read_proc_pid_mountinfo(format!("{}/{}", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidMountInfo`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidMountInfo, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidMountInfo {
+    pub fn new() -> ProcPidMountInfo {
+        ProcPidMountInfo::default()
+    }
+    /// The mount of a mount point. If multiple mounts are stacked on the mount point, this is the last (visible) one.
+    pub fn mount(&self, mount_point: &str) -> Option<&MountInfo> {
+        self.mounts.iter().rev().find(|mount| mount.mount_point == mount_point)
+    }
+    /// The differences between the mounts of this process and another process, by mount point.
+    /// Mount ids are not compared, because the mounts of a new mount namespace are copies with new ids.
+    pub fn diff(&self, other: &ProcPidMountInfo) -> MountInfoDiff {
+        // later mounts on the same mount point hide the earlier ones.
+        let by_mount_point = |mounts: &[MountInfo]| mounts.iter()
+            .map(|mount| (mount.mount_point.clone(), mount.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let self_mounts = by_mount_point(&self.mounts);
+        let mut other_mounts = by_mount_point(&other.mounts);
+        let mut mountinfodiff = MountInfoDiff::default();
+
+        for (mount_point, self_mount) in self_mounts {
+            match other_mounts.remove(&mount_point) {
+                None => mountinfodiff.only_in_self.push(self_mount),
+                Some(other_mount) => {
+                    if (&self_mount.filesystem_type, &self_mount.source, &self_mount.root, &self_mount.mount_options)
+                        != (&other_mount.filesystem_type, &other_mount.source, &other_mount.root, &other_mount.mount_options) {
+                        mountinfodiff.changed.push((self_mount, other_mount));
+                    }
+                },
+            }
+        }
+        mountinfodiff.only_in_other = other_mounts.into_values().collect();
+        mountinfodiff
+    }
+    /// Replace the octal escapes of the kernel, such as `\040` for a space.
+    fn unescape(field: &str) -> String {
+        let mut unescaped = String::with_capacity(field.len());
+        let mut rest = field;
+
+        while let Some(position) = rest.find('\\') {
+            unescaped.push_str(&rest[..position]);
+            match rest.get(position + 1..position + 4).and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+                Some(character) => {
+                    unescaped.push(character as char);
+                    rest = &rest[position + 4..];
+                },
+                None => {
+                    unescaped.push('\\');
+                    rest = &rest[position + 1..];
+                },
+            }
+        }
+        unescaped.push_str(rest);
+        unescaped
+    }
+    pub fn parse_proc_pid_mountinfo_line(line: &str) -> Result<MountInfo, ProcSysParserError> {
+        let mut fields = line.split_whitespace();
+        let mut next_field = |name: &str| fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("mountinfo {} in '{}'", name, line) });
+        let parse_u32 = |value: &str, name: &str| value.parse::<u32>()
+            .map_err(|error| ProcSysParserError::ParseItemToIntegerError { item: format!("mountinfo {} in '{}'", name, line), error });
+
+        let mount_id = parse_u32(next_field("mount_id")?, "mount_id")?;
+        let parent_id = parse_u32(next_field("parent_id")?, "parent_id")?;
+        let (major, minor) = next_field("major:minor")?.split_once(':')
+            .ok_or(ProcSysParserError::FindItemError { item: format!("':' in mountinfo major:minor in '{}'", line) })?;
+        let major = parse_u32(major, "major")?;
+        let minor = parse_u32(minor, "minor")?;
+        let root = ProcPidMountInfo::unescape(next_field("root")?);
+        let mount_point = ProcPidMountInfo::unescape(next_field("mount_point")?);
+        let mount_options = next_field("mount_options")?.to_string();
+        let mut optional_fields = Vec::new();
+        loop {
+            match next_field("separator")? {
+                "-" => break,
+                optional_field => optional_fields.push(optional_field.to_string()),
+            }
+        }
+
+        Ok(MountInfo {
+            mount_id,
+            parent_id,
+            major,
+            minor,
+            root,
+            mount_point,
+            mount_options,
+            optional_fields,
+            filesystem_type: next_field("filesystem_type")?.to_string(),
+            source: ProcPidMountInfo::unescape(next_field("source")?),
+            super_options: next_field("super_options")?.to_string(),
+        })
+    }
+    pub fn parse_proc_pid_mountinfo(proc_pid_mountinfo: &str, pid: &str) -> Result<ProcPidMountInfo, ProcSysParserError> {
+        Ok(ProcPidMountInfo {
+            pid: pid.to_string(),
+            mount_namespace: None,
+            mounts: proc_pid_mountinfo.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ProcPidMountInfo::parse_proc_pid_mountinfo_line)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+    /// Read `mountinfo` and the mount namespace link `ns/mnt` in the directory of a process, such as `/proc/1`.
+    pub fn read_proc_pid_mountinfo(proc_pid_path: &str, pid: &str) -> Result<ProcPidMountInfo, ProcSysParserError> {
+        let proc_pid_mountinfo_file = format!("{}/mountinfo", proc_pid_path);
+        let proc_pid_mountinfo_output = read_to_string(&proc_pid_mountinfo_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_mountinfo_file, error })?;
+        let mut procpidmountinfo = ProcPidMountInfo::parse_proc_pid_mountinfo(&proc_pid_mountinfo_output, pid)?;
+        // the link target is mnt:[<inode>].
+        procpidmountinfo.mount_namespace = read_link_name(Path::new(&format!("{}/ns/mnt", proc_pid_path)))
+            .and_then(|target| target.strip_prefix("mnt:[")?.strip_suffix(']')?.parse::<u64>().ok());
+        Ok(procpidmountinfo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::os::unix::fs::symlink;
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_mountinfo_lines() {
+        let result = ProcPidMountInfo::parse_proc_pid_mountinfo_line("36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw,errors=continue").unwrap();
+        assert_eq!(result, MountInfo { mount_id: 36, parent_id: 35, major: 98, minor: 0, root: "/mnt1".to_string(), mount_point: "/mnt/parent".to_string(), mount_options: "rw,noatime".to_string(), optional_fields: vec!["master:1".to_string()], filesystem_type: "ext3".to_string(), source: "/dev/root".to_string(), super_options: "rw,errors=continue".to_string() });
+
+        let result = ProcPidMountInfo::parse_proc_pid_mountinfo_line("80 24 0:45 / /mnt/my\\040disk rw,relatime - tmpfs tmpfs rw,size=1024k").unwrap();
+        assert_eq!(result.mount_point, "/mnt/my disk");
+        assert_eq!(result.optional_fields, Vec::<String>::new());
+
+        assert!(ProcPidMountInfo::parse_proc_pid_mountinfo_line("36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_mountinfo_files_read_and_diff() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        for (pid, namespace, mountinfo) in [
+            (1, "mnt:[4026531841]", "24 1 253:0 / / rw,relatime shared:1 - xfs /dev/mapper/rl-root rw,attr2
+25 24 0:22 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+26 24 0:5 / /dev rw,nosuid shared:2 - devtmpfs devtmpfs rw,size=4096k
+"),
+            (1234, "mnt:[4026532290]", "500 450 0:60 / / rw,relatime - overlay overlay rw,lowerdir=/l,upperdir=/u,workdir=/w
+501 500 0:63 / /proc rw,nosuid,nodev,noexec,relatime - proc proc rw
+502 500 0:64 / /dev rw,nosuid - tmpfs tmpfs rw,size=65536k
+503 500 253:0 /var/lib/data /data rw,relatime - xfs /dev/mapper/rl-root rw,attr2
+"),
+        ] {
+            create_dir_all(format!("{}/{}/ns", test_path, pid)).expect("Error creating mock directory.");
+            write(format!("{}/{}/mountinfo", test_path, pid), mountinfo).unwrap_or_else(|_| panic!("Error writing to {}/{}/mountinfo", test_path, pid));
+            symlink(namespace, format!("{}/{}/ns/mnt", test_path, pid)).expect("Error creating mock symlink.");
+        }
+        let host = Builder::new().path(&test_path).pid(1).read().unwrap();
+        let container = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(host.pid, "1");
+        assert_eq!(host.mount_namespace, Some(4026531841));
+        assert_eq!(container.mount_namespace, Some(4026532290));
+        assert_eq!(container.mount("/data").unwrap().root, "/var/lib/data");
+
+        let diff = host.diff(&container);
+        assert!(diff.only_in_self.is_empty());
+        assert_eq!(diff.only_in_other.iter().map(|mount| mount.mount_point.as_str()).collect::<Vec<_>>(), vec!["/data"]);
+        // / is overlay in the container, /dev is tmpfs; /proc only differs in mount id.
+        assert_eq!(diff.changed.iter().map(|(mount, _)| mount.mount_point.as_str()).collect::<Vec<_>>(), vec!["/", "/dev"]);
+    }
+}
@@ -0,0 +1,291 @@
+/*!
+Read data from `/proc/<pid>/numa_maps` into the struct [`ProcPidNumaMaps`].
+
+The `/proc/<pid>/numa_maps` file contains a line per mapping (VMA) of a process, with the start address, the NUMA
+memory policy of the mapping, and the number of pages of the mapping per NUMA node, which shows whether the memory of
+a process is local to the node it runs on. A line looks like:
+```text
+7f2b8c021000 bind:1 anon=1024 dirty=1024 active=512 N0=24 N1=1000 kernelpagesize_kB=4
+```
+- the policy: `default`, `prefer:<node>`, `bind:<nodes>`, `interleave:<nodes>`, `local`, `preferred-many:<nodes>` or
+  `weighted interleave:<nodes>`.
+- `file=<path>` for file backed mappings, or `heap` or `stack`, and `huge` for hugetlbfs mappings.
+- the number of pages: `anon`, `dirty`, `mapped` (if different from the anonymous pages), `mapmax` (the maximum number of
+  processes mapping a single page), `swapcache`, `active` (only shown if not all pages are active) and `writeback`.
+  The kernel does not show a count that is zero.
+- `N<node>=<pages>`: the number of pages on a node.
+- `kernelpagesize_kB`: the size of the pages of the mapping.
+
+Reading the file of another process requires the same permissions as ptrace (`PTRACE_MODE_READ`), and the kernel
+must be built with CONFIG_NUMA.
+
+Documentation: <https://man7.org/linux/man-pages/man7/numa.7.html>
+
+Here is an example obtaining the data from `/proc/self/numa_maps`:
+```no_run
+use proc_sys_parser::process::numa_maps;
+
+let proc_pid_numa_maps = numa_maps::read();
+
+println!("{:#?}", proc_pid_numa_maps);
+```
+Example output:
+```text
+ProcPidNumaMaps {
+    pid: "self",
+    mappings: [
+        NumaMapping {
+            address: 94502278012928,
+            policy: "default",
+            file: Some("/usr/bin/cat"),
+            heap: false,
+            stack: false,
+            huge: false,
+            anon: 0,
+            dirty: 0,
+            mapped: 2,
+            mapmax: 3,
+            swapcache: 0,
+            active: None,
+            writeback: 0,
+            nodes: {0: 2},
+            kernel_page_size_kb: 4,
+        },
+        ..
+    ],
+}
+```
+(edited for readability)
+
+The locality of a process is the share of its pages on a node, which should be high for the node the process runs on:
+```no_run
+use proc_sys_parser::process::numa_maps;
+
+let proc_pid_numa_maps = numa_maps::read().unwrap();
+
+for (node, kb) in proc_pid_numa_maps.node_kb() {
+    println!("node {}: {} kB, {:.1}%", node, kb, proc_pid_numa_maps.node_percent(node));
+}
+```
+
+If you want to read the data of another process, and/or change the path that is read for [`ProcPidNumaMaps`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::numa_maps::Builder;
+
+let proc_pid_numa_maps = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/numa_maps`
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidNumaMaps {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    pub mappings: Vec<NumaMapping>,
+}
+
+/// Struct for holding the NUMA placement of a single mapping. The counts are in pages of `kernel_page_size_kb`.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NumaMapping {
+    /// The start address of the mapping.
+    pub address: u64,
+    /// The memory policy, such as `default` or `bind:0-1`.
+    pub policy: String,
+    /// The file of a file backed mapping.
+    pub file: Option<String>,
+    pub heap: bool,
+    pub stack: bool,
+    /// A hugetlbfs mapping.
+    pub huge: bool,
+    pub anon: u64,
+    pub dirty: u64,
+    pub mapped: u64,
+    /// The maximum number of processes mapping a single page of the mapping.
+    pub mapmax: u64,
+    pub swapcache: u64,
+    /// None if all pages are active.
+    pub active: Option<u64>,
+    pub writeback: u64,
+    /// The number of pages per node.
+    pub nodes: BTreeMap<u32, u64>,
+    pub kernel_page_size_kb: u64,
+}
+
+/// Builder pattern for [`ProcPidNumaMaps`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidNumaMaps, ProcSysParserError> {
+        ProcPidNumaMaps::read_proc_pid_numa_maps(format!("{}/{}/numa_maps", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidNumaMaps`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidNumaMaps, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl NumaMapping {
+    /// The number of kilobytes per node.
+    pub fn node_kb(&self) -> BTreeMap<u32, u64> {
+        self.nodes.iter().map(|(node, pages)| (*node, pages * self.kernel_page_size_kb)).collect()
+    }
+}
+
+impl ProcPidNumaMaps {
+    pub fn new() -> ProcPidNumaMaps {
+        ProcPidNumaMaps::default()
+    }
+    /// The number of kilobytes per node of all mappings.
+    pub fn node_kb(&self) -> BTreeMap<u32, u64> {
+        let mut node_kb = BTreeMap::new();
+        for mapping in &self.mappings {
+            for (node, kb) in mapping.node_kb() {
+                *node_kb.entry(node).or_default() += kb;
+            }
+        }
+        node_kb
+    }
+    /// The percentage of the memory of all mappings that is on a node, 0 if the process has no memory on any node.
+    pub fn node_percent(&self, node: u32) -> f64 {
+        let node_kb = self.node_kb();
+        let total_kb: u64 = node_kb.values().sum();
+        if total_kb == 0 { return 0. };
+        node_kb.get(&node).copied().unwrap_or_default() as f64 / total_kb as f64 * 100.
+    }
+    pub fn parse_proc_pid_numa_maps_line(line: &str) -> Result<NumaMapping, ProcSysParserError> {
+        let mut fields = line.split_whitespace();
+        let parse_error = |item: &str, error| ProcSysParserError::ParseItemToIntegerError { item: format!("numa_maps {} in '{}'", item, line), error };
+
+        let address = fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("numa_maps address in '{}'", line) })?;
+        let mut numamapping = NumaMapping {
+            address: u64::from_str_radix(address, 16).map_err(|error| parse_error("address", error))?,
+            policy: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("numa_maps policy in '{}'", line) })?
+                .to_string(),
+            ..Default::default()
+        };
+        // the weighted interleave policy contains a space.
+        let mut fields = fields.peekable();
+        if numamapping.policy == "weighted" {
+            if let Some(interleave) = fields.next_if(|field| field.starts_with("interleave")) {
+                numamapping.policy = format!("weighted {}", interleave);
+            }
+        }
+
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                match field {
+                    "heap" => numamapping.heap = true,
+                    "stack" => numamapping.stack = true,
+                    "huge" => numamapping.huge = true,
+                    _ => {},
+                }
+                continue;
+            };
+            let parse_pages = || value.parse::<u64>().map_err(|error| parse_error(key, error));
+            match key {
+                "file" => numamapping.file = Some(value.to_string()),
+                "anon" => numamapping.anon = parse_pages()?,
+                "dirty" => numamapping.dirty = parse_pages()?,
+                "mapped" => numamapping.mapped = parse_pages()?,
+                "mapmax" => numamapping.mapmax = parse_pages()?,
+                "swapcache" => numamapping.swapcache = parse_pages()?,
+                "active" => numamapping.active = Some(parse_pages()?),
+                "writeback" => numamapping.writeback = parse_pages()?,
+                "kernelpagesize_kB" => numamapping.kernel_page_size_kb = parse_pages()?,
+                key => if let Some(node) = key.strip_prefix('N').and_then(|node| node.parse::<u32>().ok()) {
+                    numamapping.nodes.insert(node, parse_pages()?);
+                },
+            }
+        }
+        Ok(numamapping)
+    }
+    pub fn parse_proc_pid_numa_maps(proc_pid_numa_maps: &str, pid: &str) -> Result<ProcPidNumaMaps, ProcSysParserError> {
+        Ok(ProcPidNumaMaps {
+            pid: pid.to_string(),
+            mappings: proc_pid_numa_maps.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ProcPidNumaMaps::parse_proc_pid_numa_maps_line)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+    pub fn read_proc_pid_numa_maps(proc_pid_numa_maps_file: &str, pid: &str) -> Result<ProcPidNumaMaps, ProcSysParserError> {
+        let proc_pid_numa_maps_output = read_to_string(proc_pid_numa_maps_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_numa_maps_file.to_string(), error })?;
+        ProcPidNumaMaps::parse_proc_pid_numa_maps(&proc_pid_numa_maps_output, pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_numa_maps_lines() {
+        let result = ProcPidNumaMaps::parse_proc_pid_numa_maps_line("7f2b8c021000 bind:1 anon=1024 dirty=1024 active=512 N0=24 N1=1000 kernelpagesize_kB=4").unwrap();
+        assert_eq!(result, NumaMapping { address: 0x7f2b8c021000, policy: "bind:1".to_string(), anon: 1024, dirty: 1024, active: Some(512), nodes: BTreeMap::from([(0, 24), (1, 1000)]), kernel_page_size_kb: 4, ..Default::default() });
+
+        let result = ProcPidNumaMaps::parse_proc_pid_numa_maps_line("7ffd4b5d7000 weighted interleave:0-1 stack anon=3 dirty=3 N0=3 kernelpagesize_kB=4").unwrap();
+        assert_eq!(result.policy, "weighted interleave:0-1");
+        assert!(result.stack);
+
+        let result = ProcPidNumaMaps::parse_proc_pid_numa_maps_line("7f0000000000 default file=/anon_hugepage\\040(deleted) huge dirty=2 N1=2 kernelpagesize_kB=2048").unwrap();
+        assert!(result.huge);
+        assert_eq!(result.node_kb(), BTreeMap::from([(1, 4096)]));
+
+        assert!(ProcPidNumaMaps::parse_proc_pid_numa_maps_line("7f2b8c021000 default anon=x").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_numa_maps_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/numa_maps", test_path), "55f3a6e00000 default file=/usr/bin/cat mapped=2 mapmax=3 N0=2 kernelpagesize_kB=4
+55f3a8a1b000 default heap anon=33 dirty=33 N0=30 N1=3 kernelpagesize_kB=4
+7ffd4b5d7000 default stack anon=5 dirty=5 N0=5 kernelpagesize_kB=4
+7ffd4b5fb000 default
+").unwrap_or_else(|_| panic!("Error writing to {}/1234/numa_maps", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.pid, "1234");
+        assert_eq!(result.mappings.len(), 4);
+        assert_eq!(result.mappings[0].file, Some("/usr/bin/cat".to_string()));
+        assert!(result.mappings[1].heap);
+        assert_eq!(result.node_kb(), BTreeMap::from([(0, 148), (1, 12)]));
+        assert_eq!(result.node_percent(1), 7.5);
+        assert_eq!(result.node_percent(2), 0.);
+    }
+}
@@ -0,0 +1,150 @@
+/*!
+Read data from `/proc/<pid>/oom_score`, `/proc/<pid>/oom_score_adj` and `/proc/<pid>/oom_adj` into the struct [`ProcPidOom`].
+
+- `oom_score`: the current badness score of the process as calculated by the OOM killer. The higher the score, the more
+  likely the process is selected to be killed when the system runs out of memory. The range is 0 to 2000.
+- `oom_score_adj`: the adjustment added to the badness score, ranging from -1000 (never kill) to 1000 (always kill first).
+- `oom_adj`: the legacy adjustment, ranging from -17 (never kill) to 15. This file is deprecated in favour of
+  `oom_score_adj`, and might not exist, in which case it is set to None.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#proc-pid-oom-adj-proc-pid-oom-score-adj-adjust-the-oom-killer-score>
+
+Here is an example obtaining the data from `/proc/self`:
+```no_run
+use proc_sys_parser::process::oom;
+
+let proc_pid_oom = oom::read();
+
+println!("{:#?}", proc_pid_oom);
+```
+Example output:
+```text
+ProcPidOom {
+    pid: "self",
+    oom_score: 666,
+    oom_score_adj: 0,
+    oom_adj: Some(
+        0,
+    ),
+}
+```
+
+If you want to read the data of another process, and/or change the path that is read for [`ProcPidOom`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::oom::Builder;
+
+let proc_pid_oom = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_i64, read_file_option_i64, read_file_u64};
+
+/// Struct for holding the `/proc/<pid>` OOM killer settings and score
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidOom {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    /// `/proc/<pid>/oom_score`
+    /// The current OOM killer badness score (0-2000).
+    pub oom_score: u64,
+    /// `/proc/<pid>/oom_score_adj`
+    /// The adjustment of the badness score (-1000 to 1000).
+    pub oom_score_adj: i64,
+    /// `/proc/<pid>/oom_adj`
+    /// The legacy adjustment (-17 to 15). Deprecated, None if the file is not found.
+    pub oom_adj: Option<i64>,
+}
+
+/// Builder pattern for [`ProcPidOom`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidOom, ProcSysParserError> {
+        ProcPidOom::read_proc_pid_oom(format!("{}/{}", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidOom`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidOom, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidOom {
+    pub fn new() -> ProcPidOom {
+        ProcPidOom::default()
+    }
+    pub fn read_proc_pid_oom(proc_pid_directory: &str, pid: &str) -> Result<ProcPidOom, ProcSysParserError> {
+        Ok(ProcPidOom {
+            pid: pid.to_string(),
+            oom_score: read_file_u64(Path::new(&format!("{}/oom_score", proc_pid_directory)))?,
+            oom_score_adj: read_file_i64(Path::new(&format!("{}/oom_score_adj", proc_pid_directory)))?,
+            oom_adj: read_file_option_i64(Path::new(&format!("{}/oom_adj", proc_pid_directory)))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_proc_pid_oom_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/oom_score", test_path), "666\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/oom_score", test_path));
+        write(format!("{}/1234/oom_score_adj", test_path), "-500\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/oom_score_adj", test_path));
+        write(format!("{}/1234/oom_adj", test_path), "-8\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/oom_adj", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidOom { pid: "1234".to_string(), oom_score: 666, oom_score_adj: -500, oom_adj: Some(-8) });
+    }
+
+    #[test]
+    fn create_proc_pid_oom_files_without_oom_adj_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/oom_score", test_path), "0\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/oom_score", test_path));
+        write(format!("{}/1234/oom_score_adj", test_path), "0\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/oom_score_adj", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidOom { pid: "1234".to_string(), oom_score: 0, oom_score_adj: 0, oom_adj: None });
+    }
+
+    #[test]
+    fn read_nonexistent_oom_files() {
+        assert!(Builder::new().path("/xxxxxxxxxxxx").pid(1).read().is_err());
+    }
+}
@@ -0,0 +1,268 @@
+/*!
+Read data from `/proc/<pid>/pagemap` for a range of virtual addresses into the struct [`ProcPidPageMap`].
+
+The `/proc/<pid>/pagemap` file is a binary file with a 64 bit number for every virtual page of a process, at offset
+(virtual address / page size) * 8, which shows where the page is:
+- bits 0-54: the page frame number (PFN) if the page is present, which can be used to look up the page in
+  [`crate::kpage`]. The PFN is zero without CAP_SYS_ADMIN.
+- bits 0-4: the swap type and bits 5-54: the swap offset if the page is swapped.
+- bit 55: the page is soft-dirty, which means it was written since the soft-dirty bits were cleared by writing `4` to
+  `/proc/<pid>/clear_refs`.
+- bit 56: the page is exclusively mapped.
+- bit 57: the page is write-protected by userfaultfd.
+- bit 58: the page is a guard region.
+- bit 61: the page is a file page or shared anonymous.
+- bit 62: the page is swapped.
+- bit 63: the page is present.
+
+The range of addresses must be specified, which can be a mapping from `/proc/<pid>/maps`. The default range is empty.
+Pages that are not mapped have all bits zero. Reading the file of another process requires the same permissions as
+ptrace (`PTRACE_MODE_READ`), and the numbers are in the byte order of the host.
+
+Documentation: <https://docs.kernel.org/admin-guide/mm/pagemap.html>
+
+Here is an example obtaining the data of a range of `/proc/self/pagemap`:
+```no_run
+use proc_sys_parser::process::pagemap::Builder;
+
+let proc_pid_pagemap = Builder::new().range(0x7f2b8c021000, 0x7f2b8c025000).read();
+
+println!("{:#?}", proc_pid_pagemap);
+```
+Example output:
+```text
+ProcPidPageMap {
+    pid: "self",
+    page_size: 4096,
+    pages: [
+        PageMapEntry {
+            address: 139825287237632,
+            present: true,
+            swapped: false,
+            file_shared: false,
+            exclusive: true,
+            soft_dirty: true,
+            uffd_wp: false,
+            guard: false,
+            pfn: Some(1342177),
+            swap_type: None,
+            swap_offset: None,
+        },
+        ..
+    ],
+}
+```
+(edited for readability)
+
+The working set of a range is the number of present pages:
+```no_run
+use proc_sys_parser::process::pagemap::Builder;
+
+let proc_pid_pagemap = Builder::new().pid(1).range(0x7f2b8c021000, 0x7f2b8c821000).read().unwrap();
+
+println!("resident: {} bytes, swapped: {} pages, soft-dirty: {} pages",
+    proc_pid_pagemap.resident_bytes(),
+    proc_pid_pagemap.swapped_pages(),
+    proc_pid_pagemap.soft_dirty_pages(),
+);
+```
+
+If you want to change the path that is read for [`ProcPidPageMap`], which is `/proc` by default, or the page size,
+which is the `PAGE_SIZE` sysconf variable by default (4096 if it cannot be obtained), use:
+```no_run
+use proc_sys_parser::process::pagemap::Builder;
+
+let proc_pid_pagemap = Builder::new().path("/myproc").page_size(16384).range(0x7f2b8c020000, 0x7f2b8c030000).read();
+```
+*/
+use nix::unistd::{sysconf, SysconfVar};
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::read_file_u64_entries;
+
+/// Struct for holding `/proc/<pid>/pagemap` for a range of virtual addresses
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidPageMap {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    pub page_size: u64,
+    pub pages: Vec<PageMapEntry>,
+}
+
+/// Struct for holding the pagemap entry of a single virtual page
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct PageMapEntry {
+    /// The virtual address of the page.
+    pub address: u64,
+    pub present: bool,
+    pub swapped: bool,
+    /// The page is a file page or shared anonymous.
+    pub file_shared: bool,
+    pub exclusive: bool,
+    pub soft_dirty: bool,
+    /// The page is write-protected by userfaultfd.
+    pub uffd_wp: bool,
+    pub guard: bool,
+    /// The page frame number if the page is present, zero without CAP_SYS_ADMIN.
+    pub pfn: Option<u64>,
+    pub swap_type: Option<u8>,
+    pub swap_offset: Option<u64>,
+}
+
+/// Builder pattern for [`ProcPidPageMap`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+    pub start_address : u64,
+    pub end_address : u64,
+    pub page_size : u64,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+            start_address: 0,
+            end_address: 0,
+            page_size: sysconf(SysconfVar::PAGE_SIZE).unwrap_or(Some(4096)).unwrap_or(4096) as u64,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    /// Read the pages from `start_address` up to `end_address`, such as the start and end of a mapping.
+    pub fn range(mut self, start_address: u64, end_address: u64) -> Builder {
+        self.start_address = start_address;
+        self.end_address = end_address;
+        self
+    }
+    pub fn page_size(mut self, page_size: u64) -> Builder {
+        self.page_size = page_size;
+        self
+    }
+    pub fn read(self) -> Result<ProcPidPageMap, ProcSysParserError> {
+        ProcPidPageMap::read_proc_pid_pagemap(format!("{}/{}/pagemap", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid, self.start_address, self.end_address, self.page_size)
+    }
+}
+
+/// The main function for building a [`ProcPidPageMap`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the range to specified. The default range is empty.
+pub fn read() -> Result<ProcPidPageMap, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl PageMapEntry {
+    /// Decode the 64 bit pagemap entry of the page at `address`.
+    pub fn from_entry(address: u64, entry: u64) -> PageMapEntry {
+        let bit = |bit: u32| entry & (1 << bit) != 0;
+        let present = bit(63);
+        let swapped = bit(62);
+        PageMapEntry {
+            address,
+            present,
+            swapped,
+            file_shared: bit(61),
+            exclusive: bit(56),
+            soft_dirty: bit(55),
+            uffd_wp: bit(57),
+            guard: bit(58),
+            pfn: if present { Some(entry & ((1 << 55) - 1)) } else { None },
+            swap_type: if swapped { Some((entry & 0x1f) as u8) } else { None },
+            swap_offset: if swapped { Some((entry & ((1 << 55) - 1)) >> 5) } else { None },
+        }
+    }
+}
+
+impl ProcPidPageMap {
+    pub fn new() -> ProcPidPageMap {
+        ProcPidPageMap::default()
+    }
+    pub fn present_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.present).count()
+    }
+    pub fn swapped_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.swapped).count()
+    }
+    pub fn soft_dirty_pages(&self) -> usize {
+        self.pages.iter().filter(|page| page.soft_dirty).count()
+    }
+    /// The size of the present pages in bytes.
+    pub fn resident_bytes(&self) -> u64 {
+        self.present_pages() as u64 * self.page_size
+    }
+    /// Read the pages from `start_address` up to `end_address`, which are rounded down and up to the page size.
+    pub fn read_proc_pid_pagemap(proc_pid_pagemap_file: &str, pid: &str, start_address: u64, end_address: u64, page_size: u64) -> Result<ProcPidPageMap, ProcSysParserError> {
+        let page_size = page_size.max(1);
+        let start_page = start_address / page_size;
+        let end_page = end_address.div_ceil(page_size);
+        let entries = read_file_u64_entries(Path::new(proc_pid_pagemap_file), start_page, end_page.saturating_sub(start_page), 1)?;
+
+        Ok(ProcPidPageMap {
+            pid: pid.to_string(),
+            page_size,
+            pages: entries.into_iter()
+                .enumerate()
+                .map(|(index, entry)| PageMapEntry::from_entry((start_page + index as u64) * page_size, entry))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn decode_pagemap_entries() {
+        // present, exclusive and soft-dirty with PFN 0x147a21.
+        assert_eq!(PageMapEntry::from_entry(0x1000, (1 << 63) | (1 << 56) | (1 << 55) | 0x147a21), PageMapEntry {
+            address: 0x1000, present: true, swapped: false, file_shared: false, exclusive: true, soft_dirty: true,
+            uffd_wp: false, guard: false, pfn: Some(0x147a21), swap_type: None, swap_offset: None,
+        });
+        // swapped with swap type 1 and swap offset 0x2d.
+        let swapped = PageMapEntry::from_entry(0x2000, (1 << 62) | (0x2d << 5) | 1);
+        assert_eq!((swapped.present, swapped.swapped, swapped.pfn, swapped.swap_type, swapped.swap_offset), (false, true, None, Some(1), Some(0x2d)));
+        assert_eq!(PageMapEntry::from_entry(0x3000, 0), PageMapEntry { address: 0x3000, ..Default::default() });
+    }
+
+    #[test]
+    fn create_proc_pid_pagemap_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1", test_path)).expect("Error creating mock directory.");
+
+        // 16 virtual pages: the even pages are present with the page number as PFN, page 5 is swapped, page 4 is soft-dirty.
+        let pagemap: Vec<u8> = (0..16_u64).flat_map(|page| match page {
+            4 => (1_u64 << 63) | (1 << 55) | 4,
+            5 => (1 << 62) | (7 << 5),
+            page if page % 2 == 0 => (1 << 63) | page,
+            _ => 0,
+        }.to_ne_bytes()).collect();
+        write(format!("{}/1/pagemap", test_path), pagemap).unwrap_or_else(|_| panic!("Error writing to {}/1/pagemap", test_path));
+
+        let result = Builder::new().path(&test_path).pid(1).page_size(4096).range(0x2800, 0x6001).read().unwrap();
+        let empty = Builder::new().path(&test_path).pid(1).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.pages.iter().map(|page| page.address).collect::<Vec<_>>(), vec![0x2000, 0x3000, 0x4000, 0x5000, 0x6000]);
+        assert_eq!(result.pages.iter().map(|page| page.pfn).collect::<Vec<_>>(), vec![Some(2), None, Some(4), None, Some(6)]);
+        assert_eq!(result.pages[3].swap_offset, Some(7));
+        assert_eq!(result.present_pages(), 3);
+        assert_eq!(result.swapped_pages(), 1);
+        assert_eq!(result.soft_dirty_pages(), 1);
+        assert_eq!(result.resident_bytes(), 12288);
+        assert_eq!(empty.pages, vec![]);
+    }
+}
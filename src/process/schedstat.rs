@@ -0,0 +1,152 @@
+/*!
+Read data from `/proc/<pid>/schedstat` into the struct [`ProcPidSchedStat`].
+
+The `/proc/<pid>/schedstat` file contains three fields: the time spent on the cpu, the time spent waiting on a
+runqueue and the number of timeslices run on the cpu. The times are in nanoseconds.
+The same file is available for each thread of a process in `/proc/<pid>/task/<tid>/schedstat`.
+
+Documentation: <https://docs.kernel.org/scheduler/sched-stats.html>
+
+Here is an example obtaining the data from `/proc/self/schedstat`:
+```no_run
+use proc_sys_parser::process::schedstat;
+
+let proc_pid_schedstat = schedstat::read();
+
+println!("{:#?}", proc_pid_schedstat);
+```
+Example output:
+```text
+ProcPidSchedStat {
+    run_time_ns: 11254693,
+    wait_time_ns: 196708,
+    timeslices: 48,
+}
+```
+
+If you want to read the data of another process or thread, and/or change the path that is read for
+[`ProcPidSchedStat`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::schedstat::Builder;
+
+let proc_pid_schedstat = Builder::new().path("/myproc").pid(1).read();
+let proc_tid_schedstat = Builder::new().pid(1234).tid(1240).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/schedstat` statistics
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcPidSchedStat {
+    /// Time spent running on the cpu in nanoseconds.
+    pub run_time_ns: u64,
+    /// Time spent waiting on a runqueue in nanoseconds.
+    pub wait_time_ns: u64,
+    /// The number of timeslices run on the cpu.
+    pub timeslices: u64,
+}
+
+/// Builder pattern for [`ProcPidSchedStat`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+    pub proc_tid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+            proc_tid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    /// Read the schedstat file of thread `proc_tid` of the process via `/proc/<pid>/task/<tid>/schedstat`.
+    pub fn tid(mut self, proc_tid: u32) -> Builder {
+        self.proc_tid = Some(proc_tid.to_string());
+        self
+    }
+    pub fn read(self) -> Result<ProcPidSchedStat, ProcSysParserError> {
+        let proc_pid_schedstat_file = match &self.proc_tid {
+            Some(proc_tid) => format!("{}/{}/task/{}/schedstat", &self.proc_path, &self.proc_pid, proc_tid),
+            None => format!("{}/{}/schedstat", &self.proc_path, &self.proc_pid),
+        };
+        ProcPidSchedStat::read_proc_pid_schedstat(proc_pid_schedstat_file.as_str())
+    }
+}
+
+/// The main function for building a [`ProcPidSchedStat`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidSchedStat, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidSchedStat {
+    pub fn new() -> ProcPidSchedStat {
+        ProcPidSchedStat::default()
+    }
+    pub fn parse_proc_pid_schedstat(proc_pid_schedstat: &str) -> Result<ProcPidSchedStat, ProcSysParserError> {
+        let mut fields = proc_pid_schedstat.split_whitespace();
+
+        Ok(ProcPidSchedStat {
+            run_time_ns: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError {item: "process schedstat run_time_ns".to_string() })?
+                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            wait_time_ns: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError {item: "process schedstat wait_time_ns".to_string() })?
+                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            timeslices: fields.next()
+                .ok_or(ProcSysParserError::IteratorItemError {item: "process schedstat timeslices".to_string() })?
+                .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+        })
+    }
+    pub fn read_proc_pid_schedstat(proc_pid_schedstat_file: &str) -> Result<ProcPidSchedStat, ProcSysParserError> {
+        let proc_pid_schedstat_output = read_to_string(proc_pid_schedstat_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_schedstat_file.to_string(), error })?;
+        ProcPidSchedStat::parse_proc_pid_schedstat(&proc_pid_schedstat_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_schedstat_line() {
+        let result = ProcPidSchedStat::parse_proc_pid_schedstat("11254693 196708 48\n").unwrap();
+        assert_eq!(result, ProcPidSchedStat { run_time_ns: 11254693, wait_time_ns: 196708, timeslices: 48 });
+    }
+
+    #[test]
+    fn parse_corrupted_proc_pid_schedstat_line() {
+        assert!(ProcPidSchedStat::parse_proc_pid_schedstat("11254693 196708\n").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_schedstat_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/schedstat", test_path), "11254693 196708 48\n").unwrap_or_else(|_| panic!("Error writing to {}/1234/schedstat", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidSchedStat { run_time_ns: 11254693, wait_time_ns: 196708, timeslices: 48 });
+    }
+}
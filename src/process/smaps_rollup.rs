@@ -0,0 +1,241 @@
+/*!
+Read data from `/proc/<pid>/smaps_rollup` into the struct [`ProcPidSmapsRollup`].
+
+The `/proc/<pid>/smaps_rollup` file contains the memory statistics of `/proc/<pid>/smaps` summed over all mappings
+of a process, which is much cheaper to read than the full smaps file. Kernel 4.14+
+
+The most important statistic is the proportional set size (Pss): the resident memory of the process, where every
+page shared with other processes is divided by the number of processes sharing it. This makes the Pss of all
+processes add up to the actually used memory, unlike the Rss.
+
+All values are in kilobytes, just like in the original file. Reading the file of another process requires
+the same permissions as ptrace (`PTRACE_MODE_READ`).
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#id11>
+
+Here is an example obtaining the data from `/proc/self/smaps_rollup`:
+```no_run
+use proc_sys_parser::process::smaps_rollup;
+
+let proc_pid_smaps_rollup = smaps_rollup::read();
+
+println!("{:#?}", proc_pid_smaps_rollup);
+```
+Example output:
+```text
+ProcPidSmapsRollup {
+    rss: 5632,
+    pss: 2049,
+    pss_dirty: Some(1960),
+    pss_anon: Some(1940),
+    pss_file: Some(109),
+    pss_shmem: Some(0),
+    shared_clean: 3596,
+    shared_dirty: 0,
+    private_clean: 76,
+    private_dirty: 1960,
+    referenced: 5632,
+    anonymous: 1960,
+    lazyfree: 0,
+    anonhugepages: 0,
+    shmempmdmapped: 0,
+    filepmdmapped: Some(0),
+    shared_hugetlb: 0,
+    private_hugetlb: 0,
+    swap: 0,
+    swappss: 0,
+    locked: 0,
+}
+```
+
+If you want to read the data of another process, and/or change the path that is read for [`ProcPidSmapsRollup`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::smaps_rollup::Builder;
+
+let proc_pid_smaps_rollup = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/smaps_rollup` statistics in kilobytes
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcPidSmapsRollup {
+    /// Resident set size.
+    pub rss: u64,
+    /// Proportional set size.
+    pub pss: u64,
+    /// Proportional set size of dirty pages. Kernel 6.0+
+    pub pss_dirty: Option<u64>,
+    /// Proportional set size of anonymous memory. Kernel 5.4+
+    pub pss_anon: Option<u64>,
+    /// Proportional set size of file backed memory. Kernel 5.4+
+    pub pss_file: Option<u64>,
+    /// Proportional set size of shared memory. Kernel 5.4+
+    pub pss_shmem: Option<u64>,
+    /// Clean pages shared with other processes.
+    pub shared_clean: u64,
+    /// Dirty pages shared with other processes.
+    pub shared_dirty: u64,
+    /// Clean pages only used by this process.
+    pub private_clean: u64,
+    /// Dirty pages only used by this process.
+    pub private_dirty: u64,
+    /// Memory currently marked as referenced or accessed.
+    pub referenced: u64,
+    /// Memory that does not belong to any file.
+    pub anonymous: u64,
+    /// Memory marked by madvise(MADV_FREE).
+    pub lazyfree: u64,
+    /// Anonymous memory backed by transparent hugepages.
+    pub anonhugepages: u64,
+    /// Shared memory backed by transparent hugepages.
+    pub shmempmdmapped: u64,
+    /// File backed memory backed by huge pages. Kernel 5.4+
+    pub filepmdmapped: Option<u64>,
+    /// Shared hugetlbfs memory.
+    pub shared_hugetlb: u64,
+    /// Private hugetlbfs memory.
+    pub private_hugetlb: u64,
+    /// Anonymous memory swapped out.
+    pub swap: u64,
+    /// Proportional swap size.
+    pub swappss: u64,
+    /// Memory locked in memory.
+    pub locked: u64,
+}
+
+/// Builder pattern for [`ProcPidSmapsRollup`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidSmapsRollup, ProcSysParserError> {
+        ProcPidSmapsRollup::read_proc_pid_smaps_rollup(format!("{}/{}/smaps_rollup", &self.proc_path, &self.proc_pid).as_str())
+    }
+}
+
+/// The main function for building a [`ProcPidSmapsRollup`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidSmapsRollup, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidSmapsRollup {
+    pub fn new() -> ProcPidSmapsRollup {
+        ProcPidSmapsRollup::default()
+    }
+    pub fn parse_proc_pid_smaps_rollup(proc_pid_smaps_rollup: &str) -> Result<ProcPidSmapsRollup, ProcSysParserError> {
+        let mut procpidsmapsrollup = ProcPidSmapsRollup::new();
+
+        for line in proc_pid_smaps_rollup.lines() {
+            // the first line is the address range header of the virtual rollup mapping.
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let parse_kilobytes = || -> Result<u64, ProcSysParserError> {
+                value.split_whitespace()
+                    .next()
+                    .ok_or(ProcSysParserError::IteratorItemError { item: format!("process smaps_rollup {}", key) })?
+                    .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)
+            };
+            match key {
+                "Rss" => procpidsmapsrollup.rss = parse_kilobytes()?,
+                "Pss" => procpidsmapsrollup.pss = parse_kilobytes()?,
+                "Pss_Dirty" => procpidsmapsrollup.pss_dirty = Some(parse_kilobytes()?),
+                "Pss_Anon" => procpidsmapsrollup.pss_anon = Some(parse_kilobytes()?),
+                "Pss_File" => procpidsmapsrollup.pss_file = Some(parse_kilobytes()?),
+                "Pss_Shmem" => procpidsmapsrollup.pss_shmem = Some(parse_kilobytes()?),
+                "Shared_Clean" => procpidsmapsrollup.shared_clean = parse_kilobytes()?,
+                "Shared_Dirty" => procpidsmapsrollup.shared_dirty = parse_kilobytes()?,
+                "Private_Clean" => procpidsmapsrollup.private_clean = parse_kilobytes()?,
+                "Private_Dirty" => procpidsmapsrollup.private_dirty = parse_kilobytes()?,
+                "Referenced" => procpidsmapsrollup.referenced = parse_kilobytes()?,
+                "Anonymous" => procpidsmapsrollup.anonymous = parse_kilobytes()?,
+                "LazyFree" => procpidsmapsrollup.lazyfree = parse_kilobytes()?,
+                "AnonHugePages" => procpidsmapsrollup.anonhugepages = parse_kilobytes()?,
+                "ShmemPmdMapped" => procpidsmapsrollup.shmempmdmapped = parse_kilobytes()?,
+                "FilePmdMapped" => procpidsmapsrollup.filepmdmapped = Some(parse_kilobytes()?),
+                "Shared_Hugetlb" => procpidsmapsrollup.shared_hugetlb = parse_kilobytes()?,
+                "Private_Hugetlb" => procpidsmapsrollup.private_hugetlb = parse_kilobytes()?,
+                "Swap" => procpidsmapsrollup.swap = parse_kilobytes()?,
+                "SwapPss" => procpidsmapsrollup.swappss = parse_kilobytes()?,
+                "Locked" => procpidsmapsrollup.locked = parse_kilobytes()?,
+                _ => {},
+            }
+        }
+        Ok(procpidsmapsrollup)
+    }
+    pub fn read_proc_pid_smaps_rollup(proc_pid_smaps_rollup_file: &str) -> Result<ProcPidSmapsRollup, ProcSysParserError> {
+        let proc_pid_smaps_rollup_output = read_to_string(proc_pid_smaps_rollup_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_smaps_rollup_file.to_string(), error })?;
+        ProcPidSmapsRollup::parse_proc_pid_smaps_rollup(&proc_pid_smaps_rollup_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_proc_pid_smaps_rollup_file_and_read() {
+        let proc_pid_smaps_rollup = "55d0b4a9d000-7ffd6b9f2000 ---p 00000000 00:00 0                          [rollup]
+Rss:                5632 kB
+Pss:                2049 kB
+Pss_Dirty:          1960 kB
+Pss_Anon:           1940 kB
+Pss_File:            109 kB
+Pss_Shmem:             0 kB
+Shared_Clean:       3596 kB
+Shared_Dirty:          0 kB
+Private_Clean:        76 kB
+Private_Dirty:      1960 kB
+Referenced:         5632 kB
+Anonymous:          1960 kB
+LazyFree:              0 kB
+AnonHugePages:         0 kB
+ShmemPmdMapped:        0 kB
+FilePmdMapped:         0 kB
+Shared_Hugetlb:        0 kB
+Private_Hugetlb:       0 kB
+Swap:                  0 kB
+SwapPss:               0 kB
+Locked:                0 kB
+";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/smaps_rollup", test_path), proc_pid_smaps_rollup).unwrap_or_else(|_| panic!("Error writing to {}/1234/smaps_rollup", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidSmapsRollup { rss: 5632, pss: 2049, pss_dirty: Some(1960), pss_anon: Some(1940), pss_file: Some(109), pss_shmem: Some(0), shared_clean: 3596, shared_dirty: 0, private_clean: 76, private_dirty: 1960, referenced: 5632, anonymous: 1960, lazyfree: 0, anonhugepages: 0, shmempmdmapped: 0, filepmdmapped: Some(0), shared_hugetlb: 0, private_hugetlb: 0, swap: 0, swappss: 0, locked: 0 });
+    }
+
+    #[test]
+    fn parse_corrupted_proc_pid_smaps_rollup() {
+        assert!(ProcPidSmapsRollup::parse_proc_pid_smaps_rollup("Rss:   AAA kB\n").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+/*!
+Map the sockets of the socket tables to the processes owning them into the struct [`ProcessSockets`].
+
+The socket tables in `/proc/net` ([`crate::net_socket`]) show the inode of every socket, but not the process using it.
+Every file descriptor of a process is a link in `/proc/<pid>/fd`, and for a socket the link target is
+`socket:[<inode>]`. Scanning the file descriptors of all processes gives the processes owning each socket inode,
+which is what `ss --processes` and `netstat -p` do.
+
+A socket can be owned by multiple processes, for example after fork, or by none, such as a socket in TIME_WAIT,
+which has inode 0. The file descriptors of processes of other users can only be read by root, so as a normal user only
+the owners of the own sockets are found. The socket tables show the sockets of the network namespace of the reading
+process, while the file descriptors of all processes are scanned, so processes in another network namespace are
+owners of sockets that are not in the tables.
+
+Here is an example showing the processes of all TCP sockets:
+```no_run
+use proc_sys_parser::process::socket;
+
+let process_sockets = socket::read().unwrap();
+
+for owned_socket in process_sockets.owned_sockets().filter(|owned_socket| owned_socket.table.starts_with("tcp")) {
+    println!("{}:{} -> {}:{} {} {:?}",
+        owned_socket.socket.local_address,
+        owned_socket.socket.local_port,
+        owned_socket.socket.remote_address,
+        owned_socket.socket.remote_port,
+        owned_socket.socket.state_name(),
+        owned_socket.owners.iter().map(|owner| format!("{}/{}", owner.pid, owner.comm)).collect::<Vec<_>>(),
+    );
+}
+```
+
+If you want to change the path that is read for [`ProcessSockets`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::socket::Builder;
+
+let process_sockets = Builder::new().path("/myproc").read();
+```
+*/
+use std::collections::HashMap;
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::net_socket::{ProcNetSocket, Socket};
+use crate::process::read_pids;
+use crate::utils::{read_file_option_string, read_link_name};
+
+/// Struct for holding the socket tables and the owners of the sockets
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessSockets {
+    pub sockets: ProcNetSocket,
+    /// The processes using a socket by socket inode.
+    pub owners: HashMap<u64, Vec<SocketOwner>>,
+}
+
+/// Struct for holding a process using a socket
+#[derive(Debug, PartialEq, Clone)]
+pub struct SocketOwner {
+    pub pid: u32,
+    pub comm: String,
+    /// The file descriptor of the socket in the process.
+    pub fd: u32,
+}
+
+/// Struct for holding a socket together with the processes using it
+#[derive(Debug, PartialEq)]
+pub struct OwnedSocket<'a> {
+    /// The name of the table of the socket, such as "tcp6".
+    pub table: &'static str,
+    pub socket: &'a Socket,
+    /// The processes using the socket, empty if none are found.
+    pub owners: &'a [SocketOwner],
+}
+
+/// Builder pattern for [`ProcessSockets`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcessSockets, ProcSysParserError> {
+        ProcessSockets::read_process_sockets(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`ProcessSockets`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessSockets, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcessSockets {
+    pub fn new() -> ProcessSockets {
+        ProcessSockets::default()
+    }
+    /// The processes using the socket with the given inode, empty if none are found.
+    pub fn owners(&self, inode: u64) -> &[SocketOwner] {
+        self.owners.get(&inode).map(|owners| owners.as_slice()).unwrap_or_default()
+    }
+    /// All sockets of the socket tables with the processes using them.
+    pub fn owned_sockets(&self) -> impl Iterator<Item = OwnedSocket<'_>> {
+        self.sockets.sockets().map(|(table, socket)| OwnedSocket {
+            table,
+            socket,
+            // inode 0 means the socket has no file, such as a socket in TIME_WAIT.
+            owners: if socket.inode == 0 { &[] } else { self.owners(socket.inode) },
+        })
+    }
+    /// Scan the file descriptors of all processes in `proc_path` for sockets, and return the owners by socket inode.
+    /// Processes that exited during the scan and file descriptor directories that cannot be read are skipped.
+    pub fn read_socket_owners(proc_path: &str) -> Result<HashMap<u64, Vec<SocketOwner>>, ProcSysParserError> {
+        let mut owners: HashMap<u64, Vec<SocketOwner>> = HashMap::new();
+
+        for pid in read_pids(proc_path)? {
+            let Ok(fds) = read_dir(format!("{}/{}/fd", proc_path, pid)) else { continue };
+            let mut comm = None;
+
+            for fd in fds.filter_map(|entry| entry.ok()) {
+                let Ok(fd_nr) = fd.file_name().to_string_lossy().parse::<u32>() else { continue };
+                let Some(inode) = read_link_name(&fd.path())
+                    .and_then(|target| target.strip_prefix("socket:[")?.strip_suffix(']')?.parse::<u64>().ok()) else { continue };
+                let comm = comm.get_or_insert_with(|| read_file_option_string(Path::new(&format!("{}/{}/comm", proc_path, pid))).unwrap_or_default());
+                owners.entry(inode).or_default().push(SocketOwner { pid, comm: comm.clone(), fd: fd_nr });
+            }
+        }
+        for socket_owners in owners.values_mut() {
+            socket_owners.sort_by_key(|owner| (owner.pid, owner.fd));
+        }
+        Ok(owners)
+    }
+    pub fn read_process_sockets(proc_path: &str) -> Result<ProcessSockets, ProcSysParserError> {
+        Ok(ProcessSockets {
+            sockets: ProcNetSocket::read_proc_net_socket(format!("{}/net", proc_path).as_str())?,
+            owners: ProcessSockets::read_socket_owners(proc_path)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::os::unix::fs::symlink;
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn create_process_fds_and_socket_tables_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/net", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/net/tcp", test_path), "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21436 1 0000000000000000 100 0 0 10 0
+   1: 0242A8C0:0016 0142A8C0:D306 01 00000000:00000000 02:00001BB4 00000000     0        0 38750 4 0000000000000000 20 4 31 10 -1
+   2: 0242A8C0:0016 0142A8C0:D2F5 06 00000000:00000000 03:000016A1 00000000     0        0 0 3 0000000000000000
+").unwrap_or_else(|_| panic!("Error writing to {}/net/tcp", test_path));
+        // sshd (100) listens, and forked 200, which shares the listening socket and owns the connection.
+        for (pid, comm, fds) in [(100, "sshd", vec![(0, "/dev/null"), (3, "socket:[21436]")]), (200, "sshd", vec![(3, "socket:[21436]"), (4, "socket:[38750]"), (5, "pipe:[1234]")])] {
+            create_dir_all(format!("{}/{}/fd", test_path, pid)).expect("Error creating mock directory.");
+            write(format!("{}/{}/comm", test_path, pid), format!("{}\n", comm)).unwrap_or_else(|_| panic!("Error writing to {}/{}/comm", test_path, pid));
+            for (fd, target) in fds {
+                symlink(target, format!("{}/{}/fd/{}", test_path, pid, fd)).expect("Error creating mock symlink.");
+            }
+        }
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.owners.len(), 2);
+        assert_eq!(result.owners(21436), &[SocketOwner { pid: 100, comm: "sshd".to_string(), fd: 3 }, SocketOwner { pid: 200, comm: "sshd".to_string(), fd: 3 }]);
+        let owned_sockets: Vec<_> = result.owned_sockets().collect();
+        assert_eq!(owned_sockets.len(), 3);
+        assert_eq!(owned_sockets[1].owners, &[SocketOwner { pid: 200, comm: "sshd".to_string(), fd: 4 }]);
+        assert_eq!(owned_sockets[2].socket.state_name(), "TIME_WAIT");
+        assert!(owned_sockets[2].owners.is_empty());
+    }
+}
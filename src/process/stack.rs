@@ -0,0 +1,214 @@
+/*!
+Read data from `/proc/<pid>/stack` into the struct [`ProcPidStack`].
+
+The `/proc/<pid>/stack` file contains the kernel stack trace of the process, one frame per line, with the most recent
+function first. The file is only readable by root (`CAP_SYS_ADMIN`); for other users reading it returns an error.
+
+A line in the file looks like:
+```text
+[<0>] do_select+0x5d5/0x730
+[<ffffffffc05a1234>] nfs_wait_bit_killable+0x1e/0x90 [nfs]
+```
+The address between the brackets is shown as `0` by recent kernels, unless `kptr_restrict` allows showing it.
+The frame is split into the (optional) address, the symbol, the offset into the symbol, the size of the symbol
+and the (optional) kernel module the symbol belongs to.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#process-specific-subdirectories>
+
+Here is an example obtaining the data from `/proc/self/stack`:
+```no_run
+use proc_sys_parser::process::stack;
+
+let proc_pid_stack = stack::read();
+
+println!("{:#?}", proc_pid_stack);
+```
+Example output:
+```text
+ProcPidStack {
+    pid: "self",
+    frames: [
+        StackFrame { address: None, symbol: "do_select", offset: Some(1493), size: Some(1840), module: None },
+        StackFrame { address: None, symbol: "core_sys_select", offset: Some(474), size: Some(880), module: None },
+        StackFrame { address: None, symbol: "do_pselect.constprop.0", offset: Some(233), size: Some(384), module: None },
+        StackFrame { address: None, symbol: "__x64_sys_pselect6", offset: Some(101), size: Some(224), module: None },
+        StackFrame { address: None, symbol: "do_syscall_64", offset: Some(89), size: Some(208), module: None },
+        StackFrame { address: None, symbol: "entry_SYSCALL_64_after_hwframe", offset: Some(110), size: Some(216), module: None },
+    ],
+}
+```
+(edited for readability)
+
+If you want to read the stack of another process, and/or change the path that is read for [`ProcPidStack`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::stack::Builder;
+
+let proc_pid_stack = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/stack`
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidStack {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    /// The kernel stack frames, most recent function first.
+    pub frames: Vec<StackFrame>,
+}
+
+/// Struct for holding a single kernel stack frame
+#[derive(Debug, PartialEq, Default)]
+pub struct StackFrame {
+    /// The address of the frame. None if the kernel hides the address (shown as `0`).
+    pub address: Option<u64>,
+    /// The kernel symbol (function name).
+    pub symbol: String,
+    /// The offset in bytes into the symbol.
+    pub offset: Option<u64>,
+    /// The size in bytes of the symbol.
+    pub size: Option<u64>,
+    /// The kernel module the symbol belongs to, None if it is part of the kernel itself.
+    pub module: Option<String>,
+}
+
+/// Builder pattern for [`ProcPidStack`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidStack, ProcSysParserError> {
+        ProcPidStack::read_proc_pid_stack(format!("{}/{}/stack", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidStack`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidStack, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidStack {
+    pub fn new() -> ProcPidStack {
+        ProcPidStack::default()
+    }
+    pub fn parse_proc_pid_stack(proc_pid_stack: &str, pid: &str) -> Result<ProcPidStack, ProcSysParserError> {
+        let mut procpidstack = ProcPidStack::new();
+        procpidstack.pid = pid.to_string();
+
+        for line in proc_pid_stack.lines() {
+            if line.trim().is_empty() { continue };
+            procpidstack.frames.push(ProcPidStack::parse_proc_pid_stack_line(line)?);
+        }
+        Ok(procpidstack)
+    }
+    fn parse_proc_pid_stack_line(proc_pid_stack_line: &str) -> Result<StackFrame, ProcSysParserError> {
+        let mut fields = proc_pid_stack_line.split_whitespace();
+
+        let address = fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError {item: "stack address".to_string() })?
+            .trim_start_matches("[<")
+            .trim_end_matches(">]");
+        let address = match u64::from_str_radix(address, 16).map_err(ProcSysParserError::ParseToIntegerError)? {
+            0 => None,
+            address => Some(address),
+        };
+
+        // symbol+offset/size
+        let function = fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError {item: "stack symbol".to_string() })?;
+        let (symbol, offset, size) = match function.rsplit_once('+') {
+            Some((symbol, offset_size)) => {
+                let (offset, size) = offset_size.split_once('/')
+                    .ok_or(ProcSysParserError::FindItemError {item: "stack symbol offset '/'".to_string() })?;
+                (
+                    symbol.to_string(),
+                    Some(u64::from_str_radix(offset.trim_start_matches("0x"), 16).map_err(ProcSysParserError::ParseToIntegerError)?),
+                    Some(u64::from_str_radix(size.trim_start_matches("0x"), 16).map_err(ProcSysParserError::ParseToIntegerError)?),
+                )
+            },
+            None => (function.to_string(), None, None),
+        };
+
+        let module = fields.next()
+            .map(|module| module.trim_start_matches('[').trim_end_matches(']').to_string());
+
+        Ok(StackFrame { address, symbol, offset, size, module })
+    }
+    pub fn read_proc_pid_stack(proc_pid_stack_file: &str, pid: &str) -> Result<ProcPidStack, ProcSysParserError> {
+        let proc_pid_stack_output = read_to_string(proc_pid_stack_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_stack_file.to_string(), error })?;
+        ProcPidStack::parse_proc_pid_stack(&proc_pid_stack_output, pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_stack_line_hidden_address() {
+        let result = ProcPidStack::parse_proc_pid_stack_line("[<0>] do_select+0x5d5/0x730").unwrap();
+        assert_eq!(result, StackFrame { address: None, symbol: "do_select".to_string(), offset: Some(0x5d5), size: Some(0x730), module: None });
+    }
+
+    #[test]
+    fn parse_stack_line_with_address_and_module() {
+        let result = ProcPidStack::parse_proc_pid_stack_line("[<ffffffffc05a1234>] nfs_wait_bit_killable+0x1e/0x90 [nfs]").unwrap();
+        assert_eq!(result, StackFrame { address: Some(0xffffffffc05a1234), symbol: "nfs_wait_bit_killable".to_string(), offset: Some(0x1e), size: Some(0x90), module: Some("nfs".to_string()) });
+    }
+
+    #[test]
+    fn parse_corrupted_stack_line() {
+        assert!(ProcPidStack::parse_proc_pid_stack_line("[<zz>] do_select+0x5d5/0x730").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_stack_file_and_read() {
+        let proc_pid_stack = "[<0>] do_select+0x5d5/0x730
+[<0>] core_sys_select+0x1da/0x370
+[<0>] do_syscall_64+0x59/0xd0
+[<0>] entry_SYSCALL_64_after_hwframe+0x6e/0xd8
+";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/stack", test_path), proc_pid_stack).unwrap_or_else(|_| panic!("Error writing to {}/1234/stack", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidStack { pid: "1234".to_string(),
+            frames: vec![
+                StackFrame { address: None, symbol: "do_select".to_string(), offset: Some(0x5d5), size: Some(0x730), module: None },
+                StackFrame { address: None, symbol: "core_sys_select".to_string(), offset: Some(0x1da), size: Some(0x370), module: None },
+                StackFrame { address: None, symbol: "do_syscall_64".to_string(), offset: Some(0x59), size: Some(0xd0), module: None },
+                StackFrame { address: None, symbol: "entry_SYSCALL_64_after_hwframe".to_string(), offset: Some(0x6e), size: Some(0xd8), module: None },
+            ]
+        });
+    }
+}
@@ -0,0 +1,311 @@
+/*!
+Read data from `/proc/<pid>/stat` into the struct [`ProcPidStat`].
+
+The `/proc/<pid>/stat` file contains the status information of a process in a single line, which is used by `ps`.
+The same file is available for each thread of a process in `/proc/<pid>/task/<tid>/stat`.
+
+The second field, the command name (`comm`), is enclosed in parentheses, and can contain spaces and parentheses itself.
+Therefore the command name is taken between the first '(' and the last ')'.
+
+Just like the stat module, the process stat module converts the jiffies of the cpu time fields (utime, stime, cutime,
+cstime, guest_time, cguest_time, delayacct_blkio_ticks) and starttime into milliseconds, using the `CLK_TCK` sysconf
+variable.
+
+Documentation: <https://man7.org/linux/man-pages/man5/proc_pid_stat.5.html>
+
+Here is an example obtaining the data from `/proc/self/stat`:
+```no_run
+use proc_sys_parser::process::stat;
+
+let proc_pid_stat = stat::read();
+
+println!("{:#?}", proc_pid_stat);
+```
+Example output:
+```text
+ProcPidStat {
+    pid: 1,
+    comm: "systemd",
+    state: 'S',
+    ppid: 0,
+    pgrp: 1,
+    session: 1,
+    tty_nr: 0,
+    tpgid: -1,
+    flags: 4194560,
+    minflt: 31745,
+    cminflt: 591843,
+    majflt: 110,
+    cmajflt: 1049,
+    utime: 2170,
+    stime: 3480,
+    cutime: 10090,
+    cstime: 6340,
+    priority: 20,
+    nice: 0,
+    num_threads: 1,
+    starttime: 50,
+    vsize: 23015424,
+    rss: 3294,
+    rsslim: 18446744073709551615,
+    exit_signal: 17,
+    processor: 3,
+    rt_priority: 0,
+    policy: 0,
+    delayacct_blkio_ticks: 1040,
+    guest_time: 0,
+    cguest_time: 0,
+}
+```
+(edited for readability)
+
+If you want to read the data of another process or thread, and/or change the path that is read for [`ProcPidStat`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::stat::Builder;
+
+let proc_pid_stat = Builder::new().path("/myproc").pid(1).read();
+let proc_tid_stat = Builder::new().pid(1234).tid(1240).read();
+```
+*/
+use nix::unistd::{sysconf, SysconfVar};
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/stat` statistics
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcPidStat {
+    /// The process id (or thread id for `/proc/<pid>/task/<tid>/stat`).
+    pub pid: u32,
+    /// The filename of the executable, without the enclosing parentheses.
+    pub comm: String,
+    /// The process state: R (running), S (sleeping), D (disk sleep), Z (zombie), T (stopped), t (tracing stop),
+    /// X (dead), I (idle).
+    pub state: char,
+    /// The pid of the parent process.
+    pub ppid: u32,
+    /// The process group id.
+    pub pgrp: i64,
+    /// The session id.
+    pub session: i64,
+    /// The controlling terminal of the process.
+    pub tty_nr: i64,
+    /// The id of the foreground process group of the controlling terminal.
+    pub tpgid: i64,
+    /// The kernel flags word of the process.
+    pub flags: u64,
+    /// The number of minor faults.
+    pub minflt: u64,
+    /// The number of minor faults of waited-for children.
+    pub cminflt: u64,
+    /// The number of major faults (faults requiring a disk read).
+    pub majflt: u64,
+    /// The number of major faults of waited-for children.
+    pub cmajflt: u64,
+    /// Time scheduled in user mode in milliseconds.
+    pub utime: u64,
+    /// Time scheduled in kernel mode in milliseconds.
+    pub stime: u64,
+    /// Time waited-for children were scheduled in user mode in milliseconds.
+    pub cutime: u64,
+    /// Time waited-for children were scheduled in kernel mode in milliseconds.
+    pub cstime: u64,
+    /// The scheduling priority.
+    pub priority: i64,
+    /// The nice value.
+    pub nice: i64,
+    /// The number of threads in the process.
+    pub num_threads: u64,
+    /// The time the process started after system boot in milliseconds.
+    pub starttime: u64,
+    /// Virtual memory size in bytes.
+    pub vsize: u64,
+    /// Resident set size in pages.
+    pub rss: u64,
+    /// The current soft limit in bytes on the rss of the process.
+    pub rsslim: u64,
+    /// The signal sent to the parent when the process dies.
+    pub exit_signal: Option<i64>,
+    /// The cpu number the process last executed on.
+    pub processor: Option<u64>,
+    /// The realtime scheduling priority.
+    pub rt_priority: Option<u64>,
+    /// The scheduling policy.
+    pub policy: Option<u64>,
+    /// Aggregated block IO delays in milliseconds.
+    pub delayacct_blkio_ticks: Option<u64>,
+    /// Time spent running a virtual cpu for a guest operating system in milliseconds.
+    pub guest_time: Option<u64>,
+    /// Guest time of waited-for children in milliseconds.
+    pub cguest_time: Option<u64>,
+}
+
+/// Builder pattern for [`ProcPidStat`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+    pub proc_tid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+            proc_tid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    /// Read the stat file of thread `proc_tid` of the process via `/proc/<pid>/task/<tid>/stat`.
+    pub fn tid(mut self, proc_tid: u32) -> Builder {
+        self.proc_tid = Some(proc_tid.to_string());
+        self
+    }
+    pub fn read(self) -> Result<ProcPidStat, ProcSysParserError> {
+        let proc_pid_stat_file = match &self.proc_tid {
+            Some(proc_tid) => format!("{}/{}/task/{}/stat", &self.proc_path, &self.proc_pid, proc_tid),
+            None => format!("{}/{}/stat", &self.proc_path, &self.proc_pid),
+        };
+        ProcPidStat::read_proc_pid_stat(proc_pid_stat_file.as_str())
+    }
+}
+
+/// The main function for building a [`ProcPidStat`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidStat, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidStat {
+    pub fn new() -> ProcPidStat {
+        ProcPidStat::default()
+    }
+    pub fn parse_proc_pid_stat(proc_pid_stat: &str) -> Result<ProcPidStat, ProcSysParserError> {
+        // Note: time in jiffies, must be divided by CLK_TCK to show time in seconds.
+        // CLK_TCK is set by CONFIG_HZ and is 100 on most enterprise linuxes.
+        let clock_time = sysconf(SysconfVar::CLK_TCK).unwrap_or(Some(100)).unwrap_or(100) as u64;
+
+        let comm_start = proc_pid_stat.find('(')
+            .ok_or(ProcSysParserError::FindItemError { item: "process stat comm '('".to_string() })?;
+        let comm_end = proc_pid_stat.rfind(')')
+            .ok_or(ProcSysParserError::FindItemError { item: "process stat comm ')'".to_string() })?;
+
+        let pid = proc_pid_stat[..comm_start].trim()
+            .parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+        let comm = proc_pid_stat[comm_start+1..comm_end].to_string();
+
+        // the fields after comm start with field 3 (state).
+        let fields: Vec<&str> = proc_pid_stat[comm_end+1..].split_whitespace().collect();
+
+        let field_str = |number: usize| -> Result<&str, ProcSysParserError> {
+            fields.get(number-3).copied()
+                .ok_or(ProcSysParserError::IteratorItemError { item: format!("process stat field {}", number) })
+        };
+        let field_u64 = |number: usize| -> Result<u64, ProcSysParserError> {
+            field_str(number)?.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)
+        };
+        let field_i64 = |number: usize| -> Result<i64, ProcSysParserError> {
+            field_str(number)?.parse::<i64>().map_err(ProcSysParserError::ParseToIntegerError)
+        };
+        let field_option_u64 = |number: usize| -> Option<u64> {
+            fields.get(number-3).and_then(|field| field.parse::<u64>().ok())
+        };
+        let to_milliseconds = |jiffies: u64| -> u64 { (jiffies*1000_u64)/clock_time };
+
+        Ok(ProcPidStat {
+            pid,
+            comm,
+            state: field_str(3)?.chars().next()
+                .ok_or(ProcSysParserError::IteratorItemError { item: "process stat state".to_string() })?,
+            ppid: field_str(4)?.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?,
+            pgrp: field_i64(5)?,
+            session: field_i64(6)?,
+            tty_nr: field_i64(7)?,
+            tpgid: field_i64(8)?,
+            flags: field_u64(9)?,
+            minflt: field_u64(10)?,
+            cminflt: field_u64(11)?,
+            majflt: field_u64(12)?,
+            cmajflt: field_u64(13)?,
+            utime: to_milliseconds(field_u64(14)?),
+            stime: to_milliseconds(field_u64(15)?),
+            cutime: to_milliseconds(field_i64(16)?.max(0) as u64),
+            cstime: to_milliseconds(field_i64(17)?.max(0) as u64),
+            priority: field_i64(18)?,
+            nice: field_i64(19)?,
+            num_threads: field_u64(20)?,
+            starttime: to_milliseconds(field_u64(22)?),
+            vsize: field_u64(23)?,
+            rss: field_i64(24)?.max(0) as u64,
+            rsslim: field_u64(25)?,
+            exit_signal: fields.get(38-3).and_then(|field| field.parse::<i64>().ok()),
+            processor: field_option_u64(39),
+            rt_priority: field_option_u64(40),
+            policy: field_option_u64(41),
+            delayacct_blkio_ticks: field_option_u64(42).map(to_milliseconds),
+            guest_time: field_option_u64(43).map(to_milliseconds),
+            cguest_time: fields.get(44-3).and_then(|field| field.parse::<i64>().ok()).map(|jiffies| to_milliseconds(jiffies.max(0) as u64)),
+        })
+    }
+    pub fn read_proc_pid_stat(proc_pid_stat_file: &str) -> Result<ProcPidStat, ProcSysParserError> {
+        let proc_pid_stat_output = read_to_string(proc_pid_stat_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_stat_file.to_string(), error })?;
+        ProcPidStat::parse_proc_pid_stat(&proc_pid_stat_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    // cpu times are in jiffies, which are clock ticks.
+    // clock ticks are defined in the getconf value CLK_TCK.
+    // this crate dynamically obtains the CLK_TCK value.
+    // the common value of CLK_TCK is 100, which is a hard assumption here.
+    #[test]
+    fn parse_proc_pid_stat_line() {
+        let proc_pid_stat = "1 (systemd) S 0 1 1 0 -1 4194560 31745 591843 110 1049 217 348 1009 634 20 0 1 0 5 23015424 3294 18446744073709551615 1 1 0 0 0 0 671173123 4096 1260 0 0 0 17 3 0 0 104 0 0 0 0 0 0 0 0 0 0\n";
+        let result = ProcPidStat::parse_proc_pid_stat(proc_pid_stat).unwrap();
+        assert_eq!(result, ProcPidStat { pid: 1, comm: "systemd".to_string(), state: 'S', ppid: 0, pgrp: 1, session: 1, tty_nr: 0, tpgid: -1, flags: 4194560, minflt: 31745, cminflt: 591843, majflt: 110, cmajflt: 1049, utime: 2170, stime: 3480, cutime: 10090, cstime: 6340, priority: 20, nice: 0, num_threads: 1, starttime: 50, vsize: 23015424, rss: 3294, rsslim: 18446744073709551615, exit_signal: Some(17), processor: Some(3), rt_priority: Some(0), policy: Some(0), delayacct_blkio_ticks: Some(1040), guest_time: Some(0), cguest_time: Some(0) });
+    }
+
+    #[test]
+    fn parse_proc_pid_stat_line_comm_with_spaces_and_parentheses() {
+        let proc_pid_stat = "4321 (my (weird) proc) R 1 4321 4321 0 -1 4194304 100 0 0 0 10 20 0 0 20 0 4 0 1000 1000000 100 18446744073709551615";
+        let result = ProcPidStat::parse_proc_pid_stat(proc_pid_stat).unwrap();
+        assert_eq!(result, ProcPidStat { pid: 4321, comm: "my (weird) proc".to_string(), state: 'R', ppid: 1, pgrp: 4321, session: 4321, tty_nr: 0, tpgid: -1, flags: 4194304, minflt: 100, cminflt: 0, majflt: 0, cmajflt: 0, utime: 100, stime: 200, cutime: 0, cstime: 0, priority: 20, nice: 0, num_threads: 4, starttime: 10000, vsize: 1000000, rss: 100, rsslim: 18446744073709551615, exit_signal: None, processor: None, rt_priority: None, policy: None, delayacct_blkio_ticks: None, guest_time: None, cguest_time: None });
+    }
+
+    #[test]
+    fn parse_corrupted_proc_pid_stat_line() {
+        assert!(ProcPidStat::parse_proc_pid_stat("1 (systemd) S 0 1").is_err());
+        assert!(ProcPidStat::parse_proc_pid_stat("1 systemd S 0 1").is_err());
+    }
+
+    #[test]
+    fn create_proc_pid_task_tid_stat_file_and_read() {
+        let proc_pid_stat = "1240 (worker) S 1 1234 1234 0 -1 4194368 5 0 0 0 1 2 0 0 20 0 2 0 1000 1000000 100 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 -1 5 0 0 0 0 0\n";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234/task/1240", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/task/1240/stat", test_path), proc_pid_stat).unwrap_or_else(|_| panic!("Error writing to {}/1234/task/1240/stat", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).tid(1240).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidStat { pid: 1240, comm: "worker".to_string(), state: 'S', ppid: 1, pgrp: 1234, session: 1234, tty_nr: 0, tpgid: -1, flags: 4194368, minflt: 5, cminflt: 0, majflt: 0, cmajflt: 0, utime: 10, stime: 20, cutime: 0, cstime: 0, priority: 20, nice: 0, num_threads: 2, starttime: 10000, vsize: 1000000, rss: 100, rsslim: 18446744073709551615, exit_signal: Some(-1), processor: Some(5), rt_priority: Some(0), policy: Some(0), delayacct_blkio_ticks: Some(0), guest_time: Some(0), cguest_time: Some(0) });
+    }
+}
@@ -0,0 +1,345 @@
+/*!
+Read data from `/proc/<pid>/status` into the struct [`ProcPidStatus`].
+
+The `/proc/<pid>/status` file contains much of the information in `/proc/<pid>/stat` and `/proc/<pid>/statm` in a
+format that is easier to read for humans, as well as the user and group ids of the process.
+The same file is available for each thread of a process in `/proc/<pid>/task/<tid>/status`.
+
+The memory fields (Vm*, Rss*, HugetlbPages) are in kilobytes, just like in the original file.
+The memory fields are not shown for kernel threads, in which case they are set to None.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#id10>
+
+Here is an example obtaining the data from `/proc/self/status`:
+```no_run
+use proc_sys_parser::process::status;
+
+let proc_pid_status = status::read();
+
+println!("{:#?}", proc_pid_status);
+```
+Example output:
+```text
+ProcPidStatus {
+    name: "bash",
+    umask: Some("0022"),
+    state: "S (sleeping)",
+    tgid: 1234,
+    pid: 1234,
+    ppid: 1230,
+    tracerpid: 0,
+    uid: [1000, 1000, 1000, 1000],
+    gid: [1000, 1000, 1000, 1000],
+    fdsize: 256,
+    vmpeak: Some(9100),
+    vmsize: Some(9068),
+    vmlck: Some(0),
+    vmpin: Some(0),
+    vmhwm: Some(5632),
+    vmrss: Some(5632),
+    rssanon: Some(1964),
+    rssfile: Some(3668),
+    rssshmem: Some(0),
+    vmdata: Some(1880),
+    vmstk: Some(132),
+    vmexe: Some(892),
+    vmlib: Some(1812),
+    vmpte: Some(56),
+    vmswap: Some(0),
+    hugetlbpages: Some(0),
+    threads: 1,
+    cpus_allowed_list: "0-5",
+    mems_allowed_list: "0",
+    voluntary_ctxt_switches: 133,
+    nonvoluntary_ctxt_switches: 2,
+}
+```
+(edited for readability)
+
+If you want to read the data of another process or thread, and/or change the path that is read for [`ProcPidStatus`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::status::Builder;
+
+let proc_pid_status = Builder::new().path("/myproc").pid(1).read();
+let proc_tid_status = Builder::new().pid(1234).tid(1240).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/status` information
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ProcPidStatus {
+    /// The command name of the process.
+    pub name: String,
+    /// The process umask in octal. Kernel 4.7+
+    pub umask: Option<String>,
+    /// The state of the process, such as "S (sleeping)".
+    pub state: String,
+    /// The thread group id (the process id).
+    pub tgid: u32,
+    /// The thread id.
+    pub pid: u32,
+    /// The pid of the parent process.
+    pub ppid: u32,
+    /// The pid of the tracing process, 0 if not traced.
+    pub tracerpid: u32,
+    /// Real, effective, saved set and filesystem uid.
+    pub uid: Vec<u32>,
+    /// Real, effective, saved set and filesystem gid.
+    pub gid: Vec<u32>,
+    /// The number of file descriptor slots currently allocated.
+    pub fdsize: u64,
+    /// Peak virtual memory size in kB.
+    pub vmpeak: Option<u64>,
+    /// Virtual memory size in kB.
+    pub vmsize: Option<u64>,
+    /// Locked memory size in kB.
+    pub vmlck: Option<u64>,
+    /// Pinned memory size in kB.
+    pub vmpin: Option<u64>,
+    /// Peak resident set size ("high water mark") in kB.
+    pub vmhwm: Option<u64>,
+    /// Resident set size in kB, which is the sum of rssanon, rssfile and rssshmem.
+    pub vmrss: Option<u64>,
+    /// Resident anonymous memory in kB.
+    pub rssanon: Option<u64>,
+    /// Resident file mappings in kB.
+    pub rssfile: Option<u64>,
+    /// Resident shared memory in kB.
+    pub rssshmem: Option<u64>,
+    /// Size of private data segments in kB.
+    pub vmdata: Option<u64>,
+    /// Size of stack segments in kB.
+    pub vmstk: Option<u64>,
+    /// Size of text segments in kB.
+    pub vmexe: Option<u64>,
+    /// Size of shared library code in kB.
+    pub vmlib: Option<u64>,
+    /// Size of page table entries in kB.
+    pub vmpte: Option<u64>,
+    /// Amount of swap used by anonymous private data in kB.
+    pub vmswap: Option<u64>,
+    /// Size of hugetlb memory portions in kB.
+    pub hugetlbpages: Option<u64>,
+    /// The number of threads.
+    pub threads: u64,
+    /// The list of cpus this process is allowed to run on.
+    pub cpus_allowed_list: String,
+    /// The list of memory nodes this process is allowed to use.
+    pub mems_allowed_list: String,
+    /// The number of voluntary context switches.
+    pub voluntary_ctxt_switches: u64,
+    /// The number of involuntary context switches.
+    pub nonvoluntary_ctxt_switches: u64,
+}
+
+/// Builder pattern for [`ProcPidStatus`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+    pub proc_tid : Option<String>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+            proc_tid: None,
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    /// Read the status file of thread `proc_tid` of the process via `/proc/<pid>/task/<tid>/status`.
+    pub fn tid(mut self, proc_tid: u32) -> Builder {
+        self.proc_tid = Some(proc_tid.to_string());
+        self
+    }
+    pub fn read(self) -> Result<ProcPidStatus, ProcSysParserError> {
+        let proc_pid_status_file = match &self.proc_tid {
+            Some(proc_tid) => format!("{}/{}/task/{}/status", &self.proc_path, &self.proc_pid, proc_tid),
+            None => format!("{}/{}/status", &self.proc_path, &self.proc_pid),
+        };
+        ProcPidStatus::read_proc_pid_status(proc_pid_status_file.as_str())
+    }
+}
+
+/// The main function for building a [`ProcPidStatus`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidStatus, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidStatus {
+    pub fn new() -> ProcPidStatus {
+        ProcPidStatus::default()
+    }
+    pub fn parse_proc_pid_status(proc_pid_status: &str) -> Result<ProcPidStatus, ProcSysParserError> {
+        let mut procpidstatus = ProcPidStatus::new();
+
+        for line in proc_pid_status.lines() {
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let value = value.trim();
+            match key {
+                "Name" => procpidstatus.name = value.to_string(),
+                "Umask" => procpidstatus.umask = Some(value.to_string()),
+                "State" => procpidstatus.state = value.to_string(),
+                "Tgid" => procpidstatus.tgid = value.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "Pid" => procpidstatus.pid = value.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "PPid" => procpidstatus.ppid = value.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "TracerPid" => procpidstatus.tracerpid = value.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "Uid" => procpidstatus.uid = ProcPidStatus::parse_id_list(value)?,
+                "Gid" => procpidstatus.gid = ProcPidStatus::parse_id_list(value)?,
+                "FDSize" => procpidstatus.fdsize = value.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "VmPeak" => procpidstatus.vmpeak = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmSize" => procpidstatus.vmsize = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmLck" => procpidstatus.vmlck = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmPin" => procpidstatus.vmpin = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmHWM" => procpidstatus.vmhwm = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmRSS" => procpidstatus.vmrss = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "RssAnon" => procpidstatus.rssanon = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "RssFile" => procpidstatus.rssfile = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "RssShmem" => procpidstatus.rssshmem = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmData" => procpidstatus.vmdata = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmStk" => procpidstatus.vmstk = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmExe" => procpidstatus.vmexe = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmLib" => procpidstatus.vmlib = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmPTE" => procpidstatus.vmpte = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "VmSwap" => procpidstatus.vmswap = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "HugetlbPages" => procpidstatus.hugetlbpages = Some(ProcPidStatus::parse_kilobytes(value)?),
+                "Threads" => procpidstatus.threads = value.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "Cpus_allowed_list" => procpidstatus.cpus_allowed_list = value.to_string(),
+                "Mems_allowed_list" => procpidstatus.mems_allowed_list = value.to_string(),
+                "voluntary_ctxt_switches" => procpidstatus.voluntary_ctxt_switches = value.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                "nonvoluntary_ctxt_switches" => procpidstatus.nonvoluntary_ctxt_switches = value.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                // the status file contains many more entries (signal masks, capabilities, seccomp, etc.)
+                // which are currently not implemented.
+                _ => {},
+            }
+        }
+        Ok(procpidstatus)
+    }
+    fn parse_kilobytes(value: &str) -> Result<u64, ProcSysParserError> {
+        value.split_whitespace()
+            .next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: "process status kilobytes".to_string() })?
+            .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)
+    }
+    fn parse_id_list(value: &str) -> Result<Vec<u32>, ProcSysParserError> {
+        value.split_whitespace()
+            .map(|id| id.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError))
+            .collect::<Result<Vec<_>, _>>()
+    }
+    pub fn read_proc_pid_status(proc_pid_status_file: &str) -> Result<ProcPidStatus, ProcSysParserError> {
+        let proc_pid_status_output = read_to_string(proc_pid_status_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_status_file.to_string(), error })?;
+        ProcPidStatus::parse_proc_pid_status(&proc_pid_status_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_status_kernel_thread() {
+        let proc_pid_status = "Name:\tkthreadd
+Umask:\t0000
+State:\tS (sleeping)
+Tgid:\t2
+Ngid:\t0
+Pid:\t2
+PPid:\t0
+TracerPid:\t0
+Uid:\t0\t0\t0\t0
+Gid:\t0\t0\t0\t0
+FDSize:\t64
+Groups:\t
+NStgid:\t2
+NSpid:\t2
+NSpgid:\t0
+NSsid:\t0
+Threads:\t1
+SigQ:\t0/15187
+Cpus_allowed:\t3f
+Cpus_allowed_list:\t0-5
+Mems_allowed:\t00000000,00000001
+Mems_allowed_list:\t0
+voluntary_ctxt_switches:\t389
+nonvoluntary_ctxt_switches:\t1
+";
+        let result = ProcPidStatus::parse_proc_pid_status(proc_pid_status).unwrap();
+        assert_eq!(result, ProcPidStatus { name: "kthreadd".to_string(), umask: Some("0000".to_string()), state: "S (sleeping)".to_string(), tgid: 2, pid: 2, ppid: 0, tracerpid: 0, uid: vec![0, 0, 0, 0], gid: vec![0, 0, 0, 0], fdsize: 64, threads: 1, cpus_allowed_list: "0-5".to_string(), mems_allowed_list: "0".to_string(), voluntary_ctxt_switches: 389, nonvoluntary_ctxt_switches: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn create_proc_pid_status_file_and_read() {
+        let proc_pid_status = "Name:\tbash
+Umask:\t0022
+State:\tS (sleeping)
+Tgid:\t1234
+Ngid:\t0
+Pid:\t1234
+PPid:\t1230
+TracerPid:\t0
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t1001\t1001\t1001\t1001
+FDSize:\t256
+Groups:\t4 24 27 1001
+VmPeak:\t    9100 kB
+VmSize:\t    9068 kB
+VmLck:\t       0 kB
+VmPin:\t       0 kB
+VmHWM:\t    5632 kB
+VmRSS:\t    5632 kB
+RssAnon:\t    1964 kB
+RssFile:\t    3668 kB
+RssShmem:\t       0 kB
+VmData:\t    1880 kB
+VmStk:\t     132 kB
+VmExe:\t     892 kB
+VmLib:\t    1812 kB
+VmPTE:\t      56 kB
+VmSwap:\t       0 kB
+HugetlbPages:\t       0 kB
+CoreDumping:\t0
+THP_enabled:\t1
+Threads:\t1
+SigQ:\t0/15187
+Cpus_allowed:\t3f
+Cpus_allowed_list:\t0-5
+Mems_allowed:\t00000000,00000001
+Mems_allowed_list:\t0
+voluntary_ctxt_switches:\t133
+nonvoluntary_ctxt_switches:\t2
+";
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/status", test_path), proc_pid_status).unwrap_or_else(|_| panic!("Error writing to {}/1234/status", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidStatus { name: "bash".to_string(), umask: Some("0022".to_string()), state: "S (sleeping)".to_string(), tgid: 1234, pid: 1234, ppid: 1230, tracerpid: 0, uid: vec![1000, 1000, 1000, 1000], gid: vec![1001, 1001, 1001, 1001], fdsize: 256, vmpeak: Some(9100), vmsize: Some(9068), vmlck: Some(0), vmpin: Some(0), vmhwm: Some(5632), vmrss: Some(5632), rssanon: Some(1964), rssfile: Some(3668), rssshmem: Some(0), vmdata: Some(1880), vmstk: Some(132), vmexe: Some(892), vmlib: Some(1812), vmpte: Some(56), vmswap: Some(0), hugetlbpages: Some(0), threads: 1, cpus_allowed_list: "0-5".to_string(), mems_allowed_list: "0".to_string(), voluntary_ctxt_switches: 133, nonvoluntary_ctxt_switches: 2 });
+    }
+
+    #[test]
+    fn parse_corrupted_proc_pid_status() {
+        assert!(ProcPidStatus::parse_proc_pid_status("Pid:\tAAA\n").is_err());
+    }
+}
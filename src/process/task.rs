@@ -0,0 +1,224 @@
+/*!
+Read the threads of a process from `/proc/<pid>/task` into the struct [`ProcPidTask`].
+
+Every thread of a process has a directory `/proc/<pid>/task/<tid>`, which contains the same files as the process
+directory, but with the data of the individual thread. For every thread the `stat`, `status` and `schedstat` files
+are parsed using the [`crate::process::stat`], [`crate::process::status`] and [`crate::process::schedstat`] modules.
+
+Threads can exit between listing the task directory and reading the files of the thread. A thread for which the
+files cannot be read anymore is skipped.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#process-specific-subdirectories>
+
+Here is an example obtaining the threads of the current process:
+```no_run
+use proc_sys_parser::process::task;
+
+let proc_pid_task = task::read().unwrap();
+
+println!("{:#?}", proc_pid_task);
+println!("total cpu time: {} ms", proc_pid_task.total_cpu_time_ms());
+```
+Example output:
+```text
+ProcPidTask {
+    pid: "self",
+    threads: [
+        Thread {
+            tid: 1234,
+            stat: ProcPidStat { pid: 1234, comm: "java", state: 'S', .. },
+            status: ProcPidStatus { name: "java", .. },
+            schedstat: Some(ProcPidSchedStat { run_time_ns: 11254693, wait_time_ns: 196708, timeslices: 48 }),
+        },
+        Thread {
+            tid: 1240,
+            stat: ProcPidStat { pid: 1240, comm: "GC Thread#0", state: 'S', .. },
+            status: ProcPidStatus { name: "GC Thread#0", .. },
+            schedstat: Some(ProcPidSchedStat { run_time_ns: 5254693, wait_time_ns: 96708, timeslices: 12 }),
+        },
+    ],
+}
+```
+(edited for readability)
+
+If you only want the thread ids of a process, and/or change the path that is read, which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::task::Builder;
+
+let tids = Builder::new().path("/myproc").pid(1234).tids();
+```
+*/
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::schedstat::ProcPidSchedStat;
+use crate::process::stat::ProcPidStat;
+use crate::process::status::ProcPidStatus;
+
+/// Struct for holding the threads of a process from `/proc/<pid>/task`
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidTask {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    pub threads: Vec<Thread>,
+}
+
+/// Struct for holding the statistics of a single thread from `/proc/<pid>/task/<tid>`
+#[derive(Debug, PartialEq, Default)]
+pub struct Thread {
+    /// The thread id.
+    pub tid: u32,
+    /// `/proc/<pid>/task/<tid>/stat`
+    pub stat: ProcPidStat,
+    /// `/proc/<pid>/task/<tid>/status`
+    pub status: ProcPidStatus,
+    /// `/proc/<pid>/task/<tid>/schedstat`
+    /// None if the kernel is compiled without scheduler statistics (`CONFIG_SCHED_INFO`).
+    pub schedstat: Option<ProcPidSchedStat>,
+}
+
+/// Builder pattern for [`ProcPidTask`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    /// Only list the thread ids of the process, without reading the thread statistics.
+    pub fn tids(self) -> Result<Vec<u32>, ProcSysParserError> {
+        ProcPidTask::read_tids(format!("{}/{}/task", &self.proc_path, &self.proc_pid).as_str())
+    }
+    pub fn read(self) -> Result<ProcPidTask, ProcSysParserError> {
+        ProcPidTask::read_proc_pid_task(format!("{}/{}/task", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidTask`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidTask, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidTask {
+    pub fn new() -> ProcPidTask {
+        ProcPidTask::default()
+    }
+    /// The sum of the user and system time of all threads in milliseconds.
+    pub fn total_cpu_time_ms(&self) -> u64 {
+        self.threads.iter()
+            .map(|thread| thread.stat.utime + thread.stat.stime)
+            .sum()
+    }
+    /// The sum of the time spent running on the cpu of all threads in nanoseconds, from schedstat.
+    pub fn total_run_time_ns(&self) -> u64 {
+        self.threads.iter()
+            .filter_map(|thread| thread.schedstat.as_ref())
+            .map(|schedstat| schedstat.run_time_ns)
+            .sum()
+    }
+    /// The sum of the time spent waiting on a runqueue of all threads in nanoseconds, from schedstat.
+    pub fn total_wait_time_ns(&self) -> u64 {
+        self.threads.iter()
+            .filter_map(|thread| thread.schedstat.as_ref())
+            .map(|schedstat| schedstat.wait_time_ns)
+            .sum()
+    }
+    /// The thread with the highest runqueue wait time, None if no thread has schedstat data.
+    pub fn max_wait_time_thread(&self) -> Option<&Thread> {
+        self.threads.iter()
+            .filter(|thread| thread.schedstat.is_some())
+            .max_by_key(|thread| thread.schedstat.as_ref().map(|schedstat| schedstat.wait_time_ns))
+    }
+    pub fn read_tids(proc_pid_task_directory: &str) -> Result<Vec<u32>, ProcSysParserError> {
+        // the task directory contains a directory per thread, just like /proc contains a directory per process.
+        read_pids(proc_pid_task_directory)
+    }
+    pub fn read_proc_pid_task(proc_pid_task_directory: &str, pid: &str) -> Result<ProcPidTask, ProcSysParserError> {
+        let mut procpidtask = ProcPidTask::new();
+        procpidtask.pid = pid.to_string();
+
+        for tid in ProcPidTask::read_tids(proc_pid_task_directory)? {
+            let stat = match ProcPidStat::read_proc_pid_stat(format!("{}/{}/stat", proc_pid_task_directory, tid).as_str()) {
+                Ok(stat) => stat,
+                // the thread has exited in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+            let status = match ProcPidStatus::read_proc_pid_status(format!("{}/{}/status", proc_pid_task_directory, tid).as_str()) {
+                Ok(status) => status,
+                Err(ProcSysParserError::FileReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+            let schedstat = match ProcPidSchedStat::read_proc_pid_schedstat(format!("{}/{}/schedstat", proc_pid_task_directory, tid).as_str()) {
+                Ok(schedstat) => Some(schedstat),
+                Err(ProcSysParserError::FileReadError { .. }) => None,
+                Err(error) => return Err(error),
+            };
+            procpidtask.threads.push(Thread { tid, stat, status, schedstat });
+        }
+        Ok(procpidtask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_thread(test_path: &str, tid: u32, utime: u64, wait_time_ns: u64) {
+        create_dir_all(format!("{}/1234/task/{}", test_path, tid)).expect("Error creating mock directory.");
+        write(format!("{}/1234/task/{}/stat", test_path, tid), format!("{} (worker) S 1 1234 1234 0 -1 4194368 5 0 0 0 {} 2 0 0 20 0 2 0 1000 1000000 100 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 -1 5 0 0 0 0 0\n", tid, utime))
+            .unwrap_or_else(|_| panic!("Error writing to {}/1234/task/{}/stat", test_path, tid));
+        write(format!("{}/1234/task/{}/status", test_path, tid), format!("Name:\tworker\nPid:\t{}\nTgid:\t1234\nThreads:\t2\n", tid))
+            .unwrap_or_else(|_| panic!("Error writing to {}/1234/task/{}/status", test_path, tid));
+        write(format!("{}/1234/task/{}/schedstat", test_path, tid), format!("1000000 {} 10\n", wait_time_ns))
+            .unwrap_or_else(|_| panic!("Error writing to {}/1234/task/{}/schedstat", test_path, tid));
+    }
+
+    #[test]
+    fn create_proc_pid_task_directories_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_thread(&test_path, 1234, 10, 500);
+        create_mock_thread(&test_path, 1240, 30, 2000);
+        // a thread directory without files mimics a thread that exited during the scan.
+        create_dir_all(format!("{}/1234/task/1241", test_path)).expect("Error creating mock directory.");
+
+        let tids = Builder::new().path(&test_path).pid(1234).tids().unwrap();
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(tids, vec![1234, 1240, 1241]);
+        assert_eq!(result.threads.len(), 2);
+        assert_eq!(result.threads.iter().map(|thread| thread.tid).collect::<Vec<_>>(), vec![1234, 1240]);
+        assert_eq!(result.threads[1].status.pid, 1240);
+        // utime: 10 and 30 jiffies, stime: 2 jiffies each; with CLK_TCK 100 that is 440 ms.
+        assert_eq!(result.total_cpu_time_ms(), 440);
+        assert_eq!(result.total_run_time_ns(), 2000000);
+        assert_eq!(result.total_wait_time_ns(), 2500);
+        assert_eq!(result.max_wait_time_thread().unwrap().tid, 1240);
+    }
+
+    #[test]
+    fn read_nonexistent_task_directory() {
+        assert!(Builder::new().path("/xxxxxxxxxxxx").pid(1).read().is_err());
+    }
+}
@@ -0,0 +1,240 @@
+/*!
+Build a process tree from the `/proc/<pid>/stat` files of all processes into the struct [`ProcessTree`].
+
+The process tree reads the stat file of every process in `/proc` using [`crate::process::stat`], and links every
+process to its parent using the ppid field. Processes for which the parent is not found (pid 1 and kthreadd, which
+have ppid 0, or processes whose parent exited during the scan) are the roots of the tree. Because there can be
+multiple roots, the tree actually is a forest.
+
+The tree allows traversal of the children and descendants of a process, and aggregation of statistics over a
+process and all of its descendants, such as the total resident set size or cpu time of a service.
+
+Here is an example obtaining the process tree, and showing the total cpu time of pid 1 and all its descendants:
+```no_run
+use proc_sys_parser::process::tree;
+
+let process_tree = tree::read().unwrap();
+
+for root in process_tree.roots() {
+    println!("{} {}", root, process_tree.subtree_cpu_time_ms(root));
+}
+```
+
+If you want to change the path that is read for [`ProcessTree`], which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::tree::Builder;
+
+let process_tree = Builder::new().path("/myproc").read();
+```
+*/
+use std::collections::{HashMap, HashSet};
+use crate::ProcSysParserError;
+use crate::process::read_pids;
+use crate::process::stat::ProcPidStat;
+
+/// Struct for holding the process tree
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcessTree {
+    /// The stat data of every process by pid.
+    pub processes: HashMap<u32, ProcPidStat>,
+    /// The pids of the children of every process by pid.
+    pub children: HashMap<u32, Vec<u32>>,
+}
+
+/// Builder pattern for [`ProcessTree`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcessTree, ProcSysParserError> {
+        ProcessTree::read_process_tree(&self.proc_path)
+    }
+}
+
+/// The main function for building a [`ProcessTree`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<ProcessTree, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcessTree {
+    pub fn new() -> ProcessTree {
+        ProcessTree::default()
+    }
+    /// Build the tree from already obtained process stat data.
+    pub fn from_stats(stats: Vec<ProcPidStat>) -> ProcessTree {
+        let mut processtree = ProcessTree::new();
+
+        for stat in stats {
+            processtree.processes.insert(stat.pid, stat);
+        }
+        for (pid, stat) in &processtree.processes {
+            if processtree.processes.contains_key(&stat.ppid) && stat.ppid != *pid {
+                processtree.children.entry(stat.ppid).or_default().push(*pid);
+            }
+        }
+        for children in processtree.children.values_mut() {
+            children.sort();
+        }
+        processtree
+    }
+    /// The pids of the processes without a (known) parent, sorted.
+    pub fn roots(&self) -> Vec<u32> {
+        let mut roots: Vec<u32> = self.processes.values()
+            .filter(|stat| !self.processes.contains_key(&stat.ppid) || stat.ppid == stat.pid)
+            .map(|stat| stat.pid)
+            .collect();
+        roots.sort();
+        roots
+    }
+    /// The pid of the parent of a process, None if the process or the parent is not in the tree.
+    pub fn parent(&self, pid: u32) -> Option<u32> {
+        self.processes.get(&pid)
+            .map(|stat| stat.ppid)
+            .filter(|ppid| self.processes.contains_key(ppid))
+    }
+    /// The pids of the direct children of a process.
+    pub fn children(&self, pid: u32) -> &[u32] {
+        self.children.get(&pid).map(|children| children.as_slice()).unwrap_or_default()
+    }
+    /// The pids of all descendants of a process (depth first), excluding the process itself.
+    pub fn descendants(&self, pid: u32) -> Vec<u32> {
+        let mut descendants = Vec::new();
+        let mut visited = HashSet::from([pid]);
+        let mut stack: Vec<u32> = self.children(pid).iter().rev().copied().collect();
+
+        while let Some(current) = stack.pop() {
+            // guard against loops, which could be created by pid reuse during the scan.
+            if !visited.insert(current) { continue };
+            descendants.push(current);
+            stack.extend(self.children(current).iter().rev());
+        }
+        descendants
+    }
+    /// The pids of the parent, grandparent, etc. of a process, up to the root.
+    pub fn ancestors(&self, pid: u32) -> Vec<u32> {
+        let mut ancestors = Vec::new();
+        let mut current = pid;
+
+        while let Some(parent) = self.parent(current) {
+            // guard against loops, which could be created by pid reuse during the scan.
+            if ancestors.contains(&parent) || parent == pid { break };
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+    /// The pids of a process and all its descendants.
+    pub fn subtree(&self, pid: u32) -> Vec<u32> {
+        if !self.processes.contains_key(&pid) { return Vec::new() };
+        let mut subtree = vec![pid];
+        subtree.extend(self.descendants(pid));
+        subtree
+    }
+    /// The total resident set size in pages of a process and all its descendants.
+    pub fn subtree_rss_pages(&self, pid: u32) -> u64 {
+        self.subtree(pid).iter()
+            .filter_map(|pid| self.processes.get(pid))
+            .map(|stat| stat.rss)
+            .sum()
+    }
+    /// The total user and system cpu time in milliseconds of a process and all its descendants.
+    /// This does not include the time of children that already exited (cutime and cstime).
+    pub fn subtree_cpu_time_ms(&self, pid: u32) -> u64 {
+        self.subtree(pid).iter()
+            .filter_map(|pid| self.processes.get(pid))
+            .map(|stat| stat.utime + stat.stime)
+            .sum()
+    }
+    pub fn read_process_tree(proc_path: &str) -> Result<ProcessTree, ProcSysParserError> {
+        let mut stats = Vec::new();
+
+        for pid in read_pids(proc_path)? {
+            match ProcPidStat::read_proc_pid_stat(format!("{}/{}/stat", proc_path, pid).as_str()) {
+                Ok(stat) => stats.push(stat),
+                // the process has exited in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(ProcessTree::from_stats(stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_process(test_path: &str, pid: u32, ppid: u32, rss: u64) {
+        create_dir_all(format!("{}/{}", test_path, pid)).expect("Error creating mock directory.");
+        write(format!("{}/{}/stat", test_path, pid), format!("{} (proc{}) S {} 1 1 0 -1 4194560 0 0 0 0 10 10 0 0 20 0 1 0 5 1000000 {} 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0\n", pid, pid, ppid, rss))
+            .unwrap_or_else(|_| panic!("Error writing to {}/{}/stat", test_path, pid));
+    }
+
+    #[test]
+    fn create_process_tree_and_traverse() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_process(&test_path, 1, 0, 100);
+        create_mock_process(&test_path, 2, 0, 0);
+        create_mock_process(&test_path, 100, 1, 10);
+        create_mock_process(&test_path, 101, 100, 20);
+        create_mock_process(&test_path, 102, 100, 30);
+        create_mock_process(&test_path, 103, 101, 40);
+        create_mock_process(&test_path, 200, 2, 0);
+        // a non-pid directory should be ignored
+        create_dir_all(format!("{}/sys", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.processes.len(), 7);
+        assert_eq!(result.roots(), vec![1, 2]);
+        assert_eq!(result.children(100), &[101, 102]);
+        assert_eq!(result.children(103), &[] as &[u32]);
+        assert_eq!(result.parent(103), Some(101));
+        assert_eq!(result.parent(1), None);
+        assert_eq!(result.descendants(1), vec![100, 101, 103, 102]);
+        assert_eq!(result.ancestors(103), vec![101, 100, 1]);
+        assert_eq!(result.subtree_rss_pages(100), 100);
+        assert_eq!(result.subtree_rss_pages(1), 200);
+        // utime and stime are 10 jiffies each; with CLK_TCK 100 that is 200 ms per process.
+        assert_eq!(result.subtree_cpu_time_ms(100), 800);
+        assert_eq!(result.subtree_cpu_time_ms(999), 0);
+    }
+
+    #[test]
+    fn create_process_tree_with_ppid_loop_and_traverse() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        // 300 and 301 are each other's parent, as can happen when a pid is reused during the scan.
+        create_mock_process(&test_path, 300, 301, 10);
+        create_mock_process(&test_path, 301, 300, 20);
+        create_mock_process(&test_path, 302, 301, 30);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.descendants(300), vec![301, 302]);
+        assert_eq!(result.subtree(301), vec![301, 300, 302]);
+        assert_eq!(result.subtree_rss_pages(300), 60);
+        assert_eq!(result.ancestors(302), vec![301, 300]);
+    }
+}
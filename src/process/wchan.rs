@@ -0,0 +1,143 @@
+/*!
+Read data from `/proc/<pid>/wchan` into the struct [`ProcPidWchan`].
+
+The `/proc/<pid>/wchan` file contains the symbol name of the kernel function ("wait channel") in which the process
+is sleeping. If the process is not sleeping in the kernel (it is running), the file contains "0".
+If the kernel is configured without `CONFIG_KALLSYMS`, or the caller is not allowed to see the symbol, the file
+contains "0" too. In both cases `wchan` is set to None.
+
+Documentation: <https://docs.kernel.org/filesystems/proc.html#process-specific-subdirectories>
+
+Here is an example obtaining the data from `/proc/self/wchan`:
+```no_run
+use proc_sys_parser::process::wchan;
+
+let proc_pid_wchan = wchan::read();
+
+println!("{:#?}", proc_pid_wchan);
+```
+Example output:
+```text
+ProcPidWchan {
+    pid: "self",
+    wchan: Some(
+        "do_select",
+    ),
+}
+```
+
+If you want to read the wchan of another process, and/or change the path that is read for [`ProcPidWchan`],
+which is `/proc` by default, use:
+```no_run
+use proc_sys_parser::process::wchan::Builder;
+
+let proc_pid_wchan = Builder::new().path("/myproc").pid(1).read();
+```
+*/
+use std::fs::read_to_string;
+use crate::ProcSysParserError;
+
+/// Struct for holding `/proc/<pid>/wchan`
+#[derive(Debug, PartialEq, Default)]
+pub struct ProcPidWchan {
+    /// The pid as used in the path, which is `self` if no pid was specified.
+    pub pid: String,
+    /// The kernel function the process is waiting in.
+    /// None if the process is not waiting in the kernel ("0").
+    pub wchan: Option<String>,
+}
+
+/// Builder pattern for [`ProcPidWchan`]
+#[derive(Default)]
+pub struct Builder {
+    pub proc_path : String,
+    pub proc_pid : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            proc_path: "/proc".to_string(),
+            proc_pid: "self".to_string(),
+        }
+    }
+
+    pub fn path(mut self, proc_path: &str) -> Builder {
+        self.proc_path = proc_path.to_string();
+        self
+    }
+    pub fn pid(mut self, proc_pid: u32) -> Builder {
+        self.proc_pid = proc_pid.to_string();
+        self
+    }
+    pub fn read(self) -> Result<ProcPidWchan, ProcSysParserError> {
+        ProcPidWchan::read_proc_pid_wchan(format!("{}/{}/wchan", &self.proc_path, &self.proc_pid).as_str(), &self.proc_pid)
+    }
+}
+
+/// The main function for building a [`ProcPidWchan`] struct with current data of the process itself.
+/// This uses the Builder pattern, which allows settings such as the pid to specified.
+pub fn read() -> Result<ProcPidWchan, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ProcPidWchan {
+    pub fn new() -> ProcPidWchan {
+        ProcPidWchan::default()
+    }
+    pub fn parse_proc_pid_wchan(proc_pid_wchan: &str, pid: &str) -> ProcPidWchan {
+        let wchan = proc_pid_wchan.trim();
+
+        ProcPidWchan {
+            pid: pid.to_string(),
+            wchan: match wchan {
+                "" | "0" => None,
+                symbol => Some(symbol.to_string()),
+            },
+        }
+    }
+    pub fn read_proc_pid_wchan(proc_pid_wchan_file: &str, pid: &str) -> Result<ProcPidWchan, ProcSysParserError> {
+        let proc_pid_wchan_output = read_to_string(proc_pid_wchan_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_pid_wchan_file.to_string(), error })?;
+        Ok(ProcPidWchan::parse_proc_pid_wchan(&proc_pid_wchan_output, pid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    #[test]
+    fn parse_proc_pid_wchan_sleeping() {
+        let result = ProcPidWchan::parse_proc_pid_wchan("do_select", "1");
+        assert_eq!(result, ProcPidWchan { pid: "1".to_string(), wchan: Some("do_select".to_string()) });
+    }
+
+    #[test]
+    fn parse_proc_pid_wchan_running() {
+        let result = ProcPidWchan::parse_proc_pid_wchan("0", "1");
+        assert_eq!(result, ProcPidWchan { pid: "1".to_string(), wchan: None });
+    }
+
+    #[test]
+    fn create_proc_pid_wchan_file_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_dir_all(format!("{}/1234", test_path)).expect("Error creating mock directory.");
+
+        write(format!("{}/1234/wchan", test_path), "io_schedule").unwrap_or_else(|_| panic!("Error writing to {}/1234/wchan", test_path));
+        let result = Builder::new().path(&test_path).pid(1234).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, ProcPidWchan { pid: "1234".to_string(), wchan: Some("io_schedule".to_string()) });
+    }
+
+    #[test]
+    fn read_nonexistent_wchan_file() {
+        assert!(Builder::new().path("/xxxxxxxxxxxx").pid(1).read().is_err());
+    }
+}
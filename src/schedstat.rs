@@ -20,18 +20,18 @@ Example output:
 ProcSchedStat {
     version: 15,
     timestamp: 4294964691,
-    cpu: [[0, 0, 0, 0, 0, 0, 0, 40178371330, 4778820750, 26299],
-          [1, 0, 0, 0, 0, 0, 0, 35526916030, 3606934630, 20919],
-          [2, 0, 0, 0, 0, 0, 0, 29224692150, 5614007710, 28163],
-          [3, 0, 0, 0, 0, 0, 0, 23848255950, 2265375620, 26240],
-          [4, 0, 0, 0, 0, 0, 0, 33846671420, 2990792870, 25605],
-          [5, 0, 0, 0, 0, 0, 0, 34565043670, 2885580430, 22629]],
-    domain: [[0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-             [0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]]
+    cpu: [CpuSchedStat { cpu_nr: 0, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 40178371330, wait_time_ns: 4778820750, timeslices: 26299 },
+          CpuSchedStat { cpu_nr: 1, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 35526916030, wait_time_ns: 3606934630, timeslices: 20919 },
+          CpuSchedStat { cpu_nr: 2, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 29224692150, wait_time_ns: 5614007710, timeslices: 28163 },
+          CpuSchedStat { cpu_nr: 3, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 23848255950, wait_time_ns: 2265375620, timeslices: 26240 },
+          CpuSchedStat { cpu_nr: 4, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 33846671420, wait_time_ns: 2990792870, timeslices: 25605 },
+          CpuSchedStat { cpu_nr: 5, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 34565043670, wait_time_ns: 2885580430, timeslices: 22629 }],
+    domain: [Domain { cpu_nr: 0, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 1, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 2, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 3, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 4, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. },
+             Domain { cpu_nr: 5, domain_nr: 0, name: None, cpu_masks: [63], idle: LoadBalanceStat { lb_count: 0, .. }, busy: LoadBalanceStat { lb_count: 0, .. }, .. }],
 }
 ```
 (edited for readability)
@@ -44,42 +44,59 @@ use proc_sys_parser::{schedstat, schedstat::{ProcSchedStat, Builder}};
 let proc_schedstat = Builder::new().path("/myproc").read();
 ```
 
-# cpu vector numbers
+# cpu statistics
 A `/proc/schedstat` cpu line might look like this:
 ```text
 cpu0 0 0 0 0 0 0 4017837133 477882075 26299
 ```
-Such a line will be transformed to the following (`Vec<u64>`) vector in ProcSchedStat.cpu:
-```text
-[0, 0, 0, 0, 0, 0, 0, 40178371330, 4778820750, 26299]
+Such a line will be transformed to a [`CpuSchedStat`] struct in ProcSchedStat.cpu, with the cpu number (`cpu0`) in
+`cpu_nr`, and the `CPU statistics` from the description at kernel.org in named fields. The second statistic is a
+legacy field of the O(1) scheduler, which is always 0, and is not stored.
+
+Also mind that the time running on cpu (`run_time_ns`) and the time waiting to run (`wait_time_ns`), statistics
+number 7 and 8 in the description at kernel.org, are in nanoseconds, not in jiffies.
+
+The times are obtained as [`Duration`] with `run_time()` and `wait_time()`. The time waiting to run is the time
+tasks were runnable but waited for the cpu, which is the key signal of scheduling latency. The metrics between two
+readings, such as the wait time during the interval, are calculated with [`ProcSchedStat::delta`], which takes the
+number of seconds between the readings:
+```no_run
+use proc_sys_parser::schedstat;
+
+let previous = schedstat::read().unwrap();
+std::thread::sleep(std::time::Duration::from_secs(1));
+let current = schedstat::read().unwrap();
+for delta in current.delta(&previous, 1.) {
+    println!("cpu{} wait: {:?} waiting tasks: {:.2}", delta.cpu_nr, delta.wait_time, delta.average_waiting_tasks);
+}
 ```
-The first number is the cpu number (`cpu0`), the other numbers for cpu statistics follow.
-This has the consequence that the `CPU statistics` field numbers in the description from kernel.org
-now have to be increased by one to find the statistic in the vector.
 
-Also mind that:
-- The time running on cpu in jiffies, statistic number 7 in the description at kernel.org is changed
-  to time in milliseconds in the 8th position in the vector.
-- The time waiting to run in jiffies, statistic number 8 in the description at kernel.org is changed
-  to time in milliseconds in the 9th position in the vector.
+The numbers of the line as a vector, with the cpu number first, so the statistic number in the description at
+kernel.org is the index in the vector, are obtained with [`CpuSchedStat::raw`]:
+```text
+[0, 0, 0, 0, 0, 0, 0, 4017837133, 477882075, 26299]
+```
 
-# domain vector numbers
+# domain statistics
 A `/proc/schedstat` domain line might look like this:
 ```text
 domain0 3f 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
 ```
-Such a line will be transformed to the following (`Vec<u64>`) vector in ProcSchedStat.domain:
-```text
-[0, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
-```
-The first number is the domain number (`domain0`).
-The second number is the decimal representation of the hexadecimal `3f` number representing the cpu mask.
-Starting from the third number, the statistics that are explained with the `Domain statistics` are available.
-This has the consequence that the field numbers in the kernel.org explanation have to be increased by two
-to match the statistic in the vector.
+Such a line will be transformed to a [`Domain`] struct in ProcSchedStat.domain, with the cpu number of the cpu line
+it follows, the domain number (`domain0`), and the hexadecimal `3f` cpu mask as the decimal number 63 in `cpu_masks`.
+The cpu masks can be expanded into a list of cpu numbers with [`Domain::cpulist`].
+
+The `Domain statistics` from the description at kernel.org are in named fields: the load balancing statistics per
+cpu idle type are in the `idle`, `busy` and `newidle` [`LoadBalanceStat`] structs. The version of the file determines
+the layout of the statistics:
+- Version 15: the idle types are in the order idle, busy, newidle, with 8 statistics each.
+- Version 16: the idle types are in the order busy, idle, newidle.
+- Version 17: the imbalance is split into load, utilization, tasks and misfit, making 11 statistics per idle type,
+  and the domain name, such as `MC`, is shown after the domain number.
 
 */
 use std::fs::read_to_string;
+use std::time::Duration;
 use crate::ProcSysParserError;
 use log::warn;
 
@@ -122,27 +139,132 @@ pub fn read() -> Result<ProcSchedStat, ProcSysParserError> {
 pub struct ProcSchedStat {
     pub version: u64,
     pub timestamp: u64,
-    pub cpu: Vec<Vec<u64>>,
+    pub cpu: Vec<CpuSchedStat>,
     pub domain: Vec<Domain>,
 }
 
+/// Struct for holding the statistics of a `cpuN` line
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuSchedStat {
+    pub cpu_nr: u64,
+    /// The number of times sched_yield() was called.
+    pub yld_count: u64,
+    /// The number of times schedule() was called.
+    pub sched_count: u64,
+    /// The number of times schedule() left the cpu idle.
+    pub sched_goidle: u64,
+    /// The number of times try_to_wake_up() was called.
+    pub ttwu_count: u64,
+    /// The number of times try_to_wake_up() was called to wake up the local cpu.
+    pub ttwu_local: u64,
+    /// The time spent running by tasks on this cpu in nanoseconds.
+    pub run_time_ns: u64,
+    /// The time spent waiting to run by tasks on this cpu in nanoseconds.
+    pub wait_time_ns: u64,
+    /// The number of timeslices run on this cpu.
+    pub timeslices: u64,
+}
+
+/// Struct for holding the scheduler metrics of a cpu between two readings of `/proc/schedstat`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuSchedStatDelta {
+    pub cpu_nr: u64,
+    /// The time tasks ran on the cpu during the interval.
+    pub run_time: Duration,
+    /// The time tasks waited on the runqueue of the cpu to run during the interval.
+    pub wait_time: Duration,
+    /// The number of timeslices run during the interval.
+    pub timeslices: u64,
+    /// The run time as a percentage of the interval.
+    pub utilization_percent: f64,
+    /// The wait time per second of the interval, which is the average number of tasks waiting to run.
+    pub average_waiting_tasks: f64,
+    /// The average wait time per timeslice, which is the scheduling latency; zero if no timeslices were run.
+    pub average_wait: Duration,
+}
+
+/// Struct for holding the statistics of a `domainN` line
 #[derive(Debug, PartialEq, Default)]
 pub struct Domain {
     pub cpu_nr: u64,
     pub domain_nr: u64,
+    /// The name of the domain, such as `SMT`, `MC` or `PKG`. Version 17+
+    pub name: Option<String>,
+    /// The cpus in the domain as 32 bit words, the highest cpus first; see [`Domain::cpulist`].
     pub cpu_masks: Vec<u64>,
-    pub statistics: Vec<u64>,
+    /// The load balancing when the cpu was idle.
+    pub idle: LoadBalanceStat,
+    /// The load balancing when the cpu was busy.
+    pub busy: LoadBalanceStat,
+    /// The load balancing when the cpu was just becoming idle.
+    pub newidle: LoadBalanceStat,
+    /// The number of times active load balancing was called.
+    pub alb_count: u64,
+    /// The number of times active load balancing tried to move a task and failed.
+    pub alb_failed: u64,
+    /// The number of times active load balancing moved a task.
+    pub alb_pushed: u64,
+    /// Balancing at exec(): unused, always 0.
+    pub sbe_count: u64,
+    pub sbe_balanced: u64,
+    pub sbe_pushed: u64,
+    /// Balancing at fork(): unused, always 0.
+    pub sbf_count: u64,
+    pub sbf_balanced: u64,
+    pub sbf_pushed: u64,
+    /// The number of times a task was woken up on a cpu in this domain by another cpu.
+    pub ttwu_wake_remote: u64,
+    /// The number of times a woken up task was moved to the waking cpu because it was cache-cold.
+    pub ttwu_move_affine: u64,
+    /// The number of times a woken up task was moved to the waking cpu to balance the load.
+    pub ttwu_move_balance: u64,
+}
+
+/// Struct for holding the load balancing statistics of a domain for a cpu idle type
+#[derive(Debug, PartialEq, Default)]
+pub struct LoadBalanceStat {
+    /// The number of times load balancing was called.
+    pub lb_count: u64,
+    /// The number of times the load did not require balancing.
+    pub lb_balanced: u64,
+    /// The number of times moving tasks failed.
+    pub lb_failed: u64,
+    /// The sum of the imbalances found. Since version 17 this is the imbalance in load.
+    pub lb_imbalance: u64,
+    /// The sum of the imbalances in utilization. Version 17+
+    pub lb_imbalance_util: Option<u64>,
+    /// The sum of the imbalances in number of tasks. Version 17+
+    pub lb_imbalance_task: Option<u64>,
+    /// The sum of the imbalances due to misfit tasks. Version 17+
+    pub lb_imbalance_misfit: Option<u64>,
+    /// The number of tasks pulled to the cpu.
+    pub lb_gained: u64,
+    /// The number of cache-hot tasks pulled to the cpu.
+    pub lb_hot_gained: u64,
+    /// The number of times no busier queue was found.
+    pub lb_nobusyq: u64,
+    /// The number of times no busier group was found.
+    pub lb_nobusyg: u64,
 }
 
 impl ProcSchedStat {
     pub fn new() -> ProcSchedStat {
         ProcSchedStat::default() 
     }
+    /// The scheduler metrics of every cpu between a previous reading and this reading, which are `interval_seconds`
+    /// apart. Cpus that are not in both readings are skipped.
+    pub fn delta(&self, previous: &ProcSchedStat, interval_seconds: f64) -> Vec<CpuSchedStatDelta> {
+        self.cpu.iter()
+            .filter_map(|current| previous.cpu.iter()
+                .find(|previous| previous.cpu_nr == current.cpu_nr)
+                .map(|previous| CpuSchedStatDelta::new(previous, current, interval_seconds)))
+            .collect()
+    }
     pub fn parse_proc_schedstat_output(proc_schedstat: &str) -> Result<ProcSchedStat, ProcSysParserError> {
         let mut schedstat = ProcSchedStat::new();
         // current_cpu keeps the current cpu number.
         // this is used for populating the domain struct belonging to each cpu
-        let mut current_cpu = &0;
+        let mut current_cpu = 0;
         for line in proc_schedstat.lines() {
             match line {
                 line if line.starts_with("version ") => {
@@ -152,15 +274,12 @@ impl ProcSchedStat {
                     schedstat.timestamp = ProcSchedStat::generate_number_unsigned(line)?;
                 },
                 line if line.starts_with("cpu") => {
-                    schedstat.cpu.push(ProcSchedStat::generate_number_vector(line)?);
-                    current_cpu = schedstat.cpu.last()
-                        .unwrap()
-                        .iter()
-                        .next()
-                        .unwrap();
+                    let cpu = CpuSchedStat::from_raw(&ProcSchedStat::generate_number_vector(line)?)?;
+                    current_cpu = cpu.cpu_nr;
+                    schedstat.cpu.push(cpu);
                 },
                 line if line.starts_with("domain") => {
-                    schedstat.domain.push(ProcSchedStat::generate_domain_struct(line, current_cpu)?);
+                    schedstat.domain.push(ProcSchedStat::generate_domain_struct(line, &current_cpu, schedstat.version)?);
                 },
                 _  => warn!("schedstat: unknown entry found: {}", line),
             }
@@ -184,7 +303,7 @@ impl ProcSchedStat {
         };
         Ok(proc_schedstat_line)
     }
-    fn generate_domain_struct(proc_schedstat_line: &str, current_cpu: &u64) -> Result<Domain, ProcSysParserError> {
+    fn generate_domain_struct(proc_schedstat_line: &str, current_cpu: &u64, version: u64) -> Result<Domain, ProcSysParserError> {
         
         /*
         let domain_nr = proc_schedstat_line
@@ -211,9 +330,12 @@ impl ProcSchedStat {
             .map(|cpu_mask| u64::from_str_radix(cpu_mask, 16).unwrap())
             .collect();
         */
+        // since version 17, the domain name is shown before the cpu masks.
+        let name = if version >= 17 { proc_schedstat_line.split_whitespace().nth(1).map(|name| name.to_string()) } else { None };
+        let masks_field = if version >= 17 { 2 } else { 1 };
         let cpu_masks: Vec<u64> = proc_schedstat_line
             .split_whitespace()
-            .nth(1)
+            .nth(masks_field)
             .ok_or(ProcSysParserError::IteratorItemError {item: "schedstat generate_domain_struct cpu_masks".to_string() })?
             .split(',')
             .map(|cpu_mask| u64::from_str_radix(cpu_mask, 16).map_err(ProcSysParserError::ParseToIntegerError))
@@ -221,27 +343,76 @@ impl ProcSchedStat {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()?;
 
-        /*
-        let statistics: Vec<u64> = proc_schedstat_line
-            .split_whitespace()
-            .skip(2)
-            .map(|statistics| statistics.parse::<u64>().unwrap())
-            .collect();
-        */
         let statistics: Vec<u64> = proc_schedstat_line
             .split_whitespace()
-            .skip(2)
+            .skip(masks_field + 1)
             .map(|statistics| statistics.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
             .collect::<Vec<_>>()
             .into_iter()
             .collect::<Result<Vec<_>, _>>()?;
 
+        // version 17 added 3 imbalance statistics per idle type, and version 16 changed the order of the idle types.
+        let per_idle_type = if version >= 17 { 11 } else { 8 };
+        if statistics.len() < per_idle_type * 3 + 12 {
+            return Err(ProcSysParserError::IteratorItemError { item: format!("schedstat domain{} statistics of cpu{}", domain_nr, current_cpu) });
+        }
+        let load_balance_stat = |offset: usize| {
+            let statistics = &statistics[offset..offset + per_idle_type];
+            if per_idle_type == 11 {
+                LoadBalanceStat {
+                    lb_count: statistics[0],
+                    lb_balanced: statistics[1],
+                    lb_failed: statistics[2],
+                    lb_imbalance: statistics[3],
+                    lb_imbalance_util: Some(statistics[4]),
+                    lb_imbalance_task: Some(statistics[5]),
+                    lb_imbalance_misfit: Some(statistics[6]),
+                    lb_gained: statistics[7],
+                    lb_hot_gained: statistics[8],
+                    lb_nobusyq: statistics[9],
+                    lb_nobusyg: statistics[10],
+                }
+            } else {
+                LoadBalanceStat {
+                    lb_count: statistics[0],
+                    lb_balanced: statistics[1],
+                    lb_failed: statistics[2],
+                    lb_imbalance: statistics[3],
+                    lb_gained: statistics[4],
+                    lb_hot_gained: statistics[5],
+                    lb_nobusyq: statistics[6],
+                    lb_nobusyg: statistics[7],
+                    ..Default::default()
+                }
+            }
+        };
+        let (idle, busy) = if version >= 16 {
+            (load_balance_stat(per_idle_type), load_balance_stat(0))
+        } else {
+            (load_balance_stat(0), load_balance_stat(per_idle_type))
+        };
+        let other = &statistics[per_idle_type * 3..];
 
         Ok(Domain {
             cpu_nr: *current_cpu,
             domain_nr,
+            name,
             cpu_masks,
-            statistics,
+            idle,
+            busy,
+            newidle: load_balance_stat(per_idle_type * 2),
+            alb_count: other[0],
+            alb_failed: other[1],
+            alb_pushed: other[2],
+            sbe_count: other[3],
+            sbe_balanced: other[4],
+            sbe_pushed: other[5],
+            sbf_count: other[6],
+            sbf_balanced: other[7],
+            sbf_pushed: other[8],
+            ttwu_wake_remote: other[9],
+            ttwu_move_affine: other[10],
+            ttwu_move_balance: other[11],
         })
     }
     fn generate_number_unsigned(proc_stat_line: &str) -> Result<u64, ProcSysParserError> {
@@ -257,6 +428,71 @@ impl ProcSchedStat {
     }
 }
 
+impl CpuSchedStatDelta {
+    /// Calculate the metrics of a cpu from a previous and a current reading, which are `interval_seconds` apart.
+    /// A counter that decreased, such as after a cpu hotplug, is taken as 0.
+    pub fn new(previous: &CpuSchedStat, current: &CpuSchedStat, interval_seconds: f64) -> CpuSchedStatDelta {
+        let run_time = current.run_time().saturating_sub(previous.run_time());
+        let wait_time = current.wait_time().saturating_sub(previous.wait_time());
+        let timeslices = current.timeslices.saturating_sub(previous.timeslices);
+
+        CpuSchedStatDelta {
+            cpu_nr: current.cpu_nr,
+            run_time,
+            wait_time,
+            timeslices,
+            utilization_percent: if interval_seconds > 0. { run_time.as_secs_f64() * 100. / interval_seconds } else { 0. },
+            average_waiting_tasks: if interval_seconds > 0. { wait_time.as_secs_f64() / interval_seconds } else { 0. },
+            average_wait: if timeslices > 0 { Duration::from_nanos((wait_time.as_nanos() / timeslices as u128) as u64) } else { Duration::ZERO },
+        }
+    }
+}
+
+impl Domain {
+    /// The cpu numbers in the cpu masks of the domain, such as `[0, 1, 2, 3, 4, 5]` for the mask `3f`.
+    pub fn cpulist(&self) -> Vec<u32> {
+        self.cpu_masks.iter()
+            .rev()
+            .enumerate()
+            .flat_map(|(word_nr, cpu_mask)| (0..32).filter(move |bit| cpu_mask & (1 << bit) != 0).map(move |bit| word_nr as u32 * 32 + bit))
+            .collect()
+    }
+}
+
+impl CpuSchedStat {
+    /// Create the struct from the numbers of a `cpuN` line, of which the first is the cpu number.
+    pub fn from_raw(raw: &[u64]) -> Result<CpuSchedStat, ProcSysParserError> {
+        if raw.len() < 10 {
+            return Err(ProcSysParserError::IteratorItemError { item: format!("schedstat cpu{} statistics", raw.first().copied().unwrap_or_default()) });
+        }
+        // raw[2] is the legacy array expiration count of the O(1) scheduler, which is always 0.
+        Ok(CpuSchedStat {
+            cpu_nr: raw[0],
+            yld_count: raw[1],
+            sched_count: raw[3],
+            sched_goidle: raw[4],
+            ttwu_count: raw[5],
+            ttwu_local: raw[6],
+            run_time_ns: raw[7],
+            wait_time_ns: raw[8],
+            timeslices: raw[9],
+        })
+    }
+    /// The time running on the cpu. The time is in nanoseconds since kernel version 2.6.23.
+    pub fn run_time(&self) -> Duration {
+        Duration::from_nanos(self.run_time_ns)
+    }
+    /// The time waiting to run on the cpu. The time is in nanoseconds since kernel version 2.6.23.
+    pub fn wait_time(&self) -> Duration {
+        Duration::from_nanos(self.wait_time_ns)
+    }
+    /// The numbers of the `cpuN` line as a vector, with the cpu number first, so the kernel.org statistic number is
+    /// the index in the vector.
+    pub fn raw(&self) -> Vec<u64> {
+        vec![self.cpu_nr, self.yld_count, 0, self.sched_count, self.sched_goidle, self.ttwu_count, self.ttwu_local, self.run_time_ns, self.wait_time_ns, self.timeslices]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::{write, remove_dir_all, create_dir_all};
@@ -271,30 +507,79 @@ mod tests {
     #[test]
     fn parse_version_line() {
         let version_line = "version 15";
-        let result = ProcSchedStat::generate_number_unsigned(&version_line).unwrap();
+        let result = ProcSchedStat::generate_number_unsigned(version_line).unwrap();
         assert_eq!(result, 15);
     }
     #[test]
     fn parse_timestamp_line() {
         let timestamp_line = "timestamp 4318766637";
-        let result = ProcSchedStat::generate_number_unsigned(&timestamp_line).unwrap();
+        let result = ProcSchedStat::generate_number_unsigned(timestamp_line).unwrap();
         assert_eq!(result, 4318766637);
     }
 
     #[test]
     fn parse_cpu_line() {
         let cpu_line = "cpu0 0 0 0 0 0 0 455307306435 48519572891 4320349";
-        let result = ProcSchedStat::generate_number_vector(&cpu_line).unwrap();
+        let result = ProcSchedStat::generate_number_vector(cpu_line).unwrap();
         assert_eq!(result, vec![0, 0, 0, 0, 0, 0, 0, 455307306435, 48519572891, 4320349]);
     }
 
+    #[test]
+    fn parse_cpu_line_into_struct_and_back() {
+        let raw = ProcSchedStat::generate_number_vector("cpu3 12 0 9043 2211 5418 1077 455307306435 48519572891 4320349").unwrap();
+        let result = CpuSchedStat::from_raw(&raw).unwrap();
+        assert_eq!(result, CpuSchedStat { cpu_nr: 3, yld_count: 12, sched_count: 9043, sched_goidle: 2211, ttwu_count: 5418, ttwu_local: 1077, run_time_ns: 455307306435, wait_time_ns: 48519572891, timeslices: 4320349 });
+        assert_eq!(result.raw(), raw);
+        assert!(CpuSchedStat::from_raw(&[3, 12, 0]).is_err());
+    }
+
     #[test]
     fn parse_domain_line() {
         let domain_line = "domain0 3f 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0";
-        let result = ProcSchedStat::generate_number_vector(&domain_line).unwrap();
+        let result = ProcSchedStat::generate_number_vector(domain_line).unwrap();
         assert_eq!(result, vec![]);
     }
     #[test]
+    fn calculate_proc_schedstat_delta() {
+        let previous = ProcSchedStat { cpu: vec![
+            CpuSchedStat { cpu_nr: 0, run_time_ns: 1_000_000_000, wait_time_ns: 100_000_000, timeslices: 1000, ..Default::default() },
+            CpuSchedStat { cpu_nr: 1, run_time_ns: 2_000_000_000, wait_time_ns: 200_000_000, timeslices: 2000, ..Default::default() },
+        ], ..Default::default() };
+        let current = ProcSchedStat { cpu: vec![
+            CpuSchedStat { cpu_nr: 0, run_time_ns: 1_500_000_000, wait_time_ns: 300_000_000, timeslices: 1100, ..Default::default() },
+            CpuSchedStat { cpu_nr: 2, run_time_ns: 1_000_000_000, wait_time_ns: 100_000_000, timeslices: 100, ..Default::default() },
+        ], ..Default::default() };
+        assert_eq!(current.cpu[0].wait_time(), Duration::from_millis(300));
+        assert_eq!(current.delta(&previous, 2.), vec![CpuSchedStatDelta {
+            cpu_nr: 0,
+            run_time: Duration::from_millis(500),
+            wait_time: Duration::from_millis(200),
+            timeslices: 100,
+            utilization_percent: 25.,
+            average_waiting_tasks: 0.1,
+            average_wait: Duration::from_millis(2),
+        }]);
+    }
+    #[test]
+    fn parse_domain_line_into_named_fields() {
+        let domain_line = "domain1 00000000,ffffffff,00000000,00000003 1 2 3 4 5 6 7 8 11 12 13 14 15 16 17 18 21 22 23 24 25 26 27 28 31 32 33 34 35 36 37 38 39 40 41 42";
+        let result = ProcSchedStat::generate_domain_struct(domain_line, &1, 15).unwrap();
+        assert_eq!((result.cpu_nr, result.domain_nr, result.name.clone()), (1, 1, None));
+        assert_eq!(result.idle, LoadBalanceStat { lb_count: 1, lb_balanced: 2, lb_failed: 3, lb_imbalance: 4, lb_imbalance_util: None, lb_imbalance_task: None, lb_imbalance_misfit: None, lb_gained: 5, lb_hot_gained: 6, lb_nobusyq: 7, lb_nobusyg: 8 });
+        assert_eq!((result.busy.lb_count, result.newidle.lb_count), (11, 21));
+        assert_eq!((result.alb_count, result.sbe_count, result.sbf_count, result.ttwu_wake_remote, result.ttwu_move_balance), (31, 34, 37, 40, 42));
+        assert_eq!(result.cpulist(), [vec![0, 1], (64..96).collect()].concat());
+
+        let domain_line = "domain0 MC 3f 1 2 3 4 5 6 7 8 9 10 11 21 22 23 24 25 26 27 28 29 30 31 41 42 43 44 45 46 47 48 49 50 51 61 62 63 64 65 66 67 68 69 70 71 72";
+        let result = ProcSchedStat::generate_domain_struct(domain_line, &0, 17).unwrap();
+        assert_eq!(result.name, Some("MC".to_string()));
+        assert_eq!(result.cpulist(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(result.busy, LoadBalanceStat { lb_count: 1, lb_balanced: 2, lb_failed: 3, lb_imbalance: 4, lb_imbalance_util: Some(5), lb_imbalance_task: Some(6), lb_imbalance_misfit: Some(7), lb_gained: 8, lb_hot_gained: 9, lb_nobusyq: 10, lb_nobusyg: 11 });
+        assert_eq!((result.idle.lb_count, result.newidle.lb_count, result.alb_count, result.ttwu_move_balance), (21, 41, 61, 72));
+
+        assert!(ProcSchedStat::generate_domain_struct("domain0 3f 0 0 0", &0, 15).is_err());
+    }
+    #[test]
     fn parse_full_proc_schedstat_file() {
         let proc_schedstat = "version 15
 timestamp 4318961659
@@ -314,20 +599,20 @@ domain0 3f 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
         assert_eq!(result, ProcSchedStat { version: 15,
             timestamp: 4318961659,
             cpu: vec![
-                   vec![0, 0, 0, 0, 0, 0, 0, 457571901633, 48594074614, 4348645],
-                   vec![1, 0, 0, 0, 0, 0, 0, 435206433012, 44944145715, 3928368],
-                   vec![2, 0, 0, 0, 0, 0, 0, 429637514081, 43591673257, 3833297],
-                   vec![3, 0, 0, 0, 0, 0, 0, 445308389036, 43102743982, 3851418],
-                   vec![4, 0, 0, 0, 0, 0, 0, 438666554521, 43706845278, 3787400],
-                   vec![5, 0, 0, 0, 0, 0, 0, 444708323872, 42862371788, 3900565],
+                   CpuSchedStat { cpu_nr: 0, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 457571901633, wait_time_ns: 48594074614, timeslices: 4348645 },
+                   CpuSchedStat { cpu_nr: 1, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 435206433012, wait_time_ns: 44944145715, timeslices: 3928368 },
+                   CpuSchedStat { cpu_nr: 2, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 429637514081, wait_time_ns: 43591673257, timeslices: 3833297 },
+                   CpuSchedStat { cpu_nr: 3, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 445308389036, wait_time_ns: 43102743982, timeslices: 3851418 },
+                   CpuSchedStat { cpu_nr: 4, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 438666554521, wait_time_ns: 43706845278, timeslices: 3787400 },
+                   CpuSchedStat { cpu_nr: 5, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 444708323872, wait_time_ns: 42862371788, timeslices: 3900565 },
             ],
             domain: vec![
-                Domain { cpu_nr: 0, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 1, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 2, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 3, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 4, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 5, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] },
+                Domain { cpu_nr: 0, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 1, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 2, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 3, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 4, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 5, domain_nr: 0, cpu_masks: vec![63], ..Default::default() },
             ] 
         });
     }
@@ -351,7 +636,7 @@ domain0 3f 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
         let test_path = format!("/tmp/test.{}", directory_suffix);
         create_dir_all(format!("{}/proc", test_path)).expect("Error creating mock directory.");
 
-        write(format!("{}/schedstat", test_path), proc_schedstat).expect(format!("Error writing to {}/schedstat", test_path).as_str());
+        write(format!("{}/schedstat", test_path), proc_schedstat).unwrap_or_else(|_| panic!("Error writing to {}/schedstat", test_path));
 
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
@@ -359,20 +644,20 @@ domain0 3f 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
         assert_eq!(result, ProcSchedStat { version: 15,
             timestamp: 4318961659,
             cpu: vec![
-                vec![0, 0, 0, 0, 0, 0, 0, 457571901633, 48594074614, 4348645],
-                vec![1, 0, 0, 0, 0, 0, 0, 435206433012, 44944145715, 3928368],
-                vec![2, 0, 0, 0, 0, 0, 0, 429637514081, 43591673257, 3833297],
-                vec![3, 0, 0, 0, 0, 0, 0, 445308389036, 43102743982, 3851418],
-                vec![4, 0, 0, 0, 0, 0, 0, 438666554521, 43706845278, 3787400],
-                vec![5, 0, 0, 0, 0, 0, 0, 444708323872, 42862371788, 3900565],
+                CpuSchedStat { cpu_nr: 0, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 457571901633, wait_time_ns: 48594074614, timeslices: 4348645 },
+                CpuSchedStat { cpu_nr: 1, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 435206433012, wait_time_ns: 44944145715, timeslices: 3928368 },
+                CpuSchedStat { cpu_nr: 2, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 429637514081, wait_time_ns: 43591673257, timeslices: 3833297 },
+                CpuSchedStat { cpu_nr: 3, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 445308389036, wait_time_ns: 43102743982, timeslices: 3851418 },
+                CpuSchedStat { cpu_nr: 4, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 438666554521, wait_time_ns: 43706845278, timeslices: 3787400 },
+                CpuSchedStat { cpu_nr: 5, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 444708323872, wait_time_ns: 42862371788, timeslices: 3900565 },
             ],
             domain: vec![
-                Domain { cpu_nr: 0, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 1, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 2, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 3, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 4, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }, 
-                Domain { cpu_nr: 5, domain_nr: 0, cpu_masks: vec![63], statistics: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] },
+                Domain { cpu_nr: 0, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 1, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 2, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 3, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 4, domain_nr: 0, cpu_masks: vec![63], ..Default::default() }, 
+                Domain { cpu_nr: 5, domain_nr: 0, cpu_masks: vec![63], ..Default::default() },
             ] 
         });
     }
@@ -392,7 +677,7 @@ domain2 ffffffff,ffffffff,ffffffff,ffffffff 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
         let test_path = format!("/tmp/test.{}", directory_suffix);
         create_dir_all(format!("{}/proc", test_path)).expect("Error creating mock directory.");
 
-        write(format!("{}/schedstat", test_path), proc_schedstat).expect(format!("Error writing to {}/schedstat", test_path).as_str());
+        write(format!("{}/schedstat", test_path), proc_schedstat).unwrap_or_else(|_| panic!("Error writing to {}/schedstat", test_path));
 
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
@@ -400,45 +685,45 @@ domain2 ffffffff,ffffffff,ffffffff,ffffffff 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
         assert_eq!(result, ProcSchedStat { version: 15, 
             timestamp: 7452455604, 
             cpu: vec![
-                vec![0, 0, 0, 0, 0, 0, 0, 1400897766846392, 136499688631908, 38913706186], 
-                vec![127, 0, 0, 0, 0, 0, 0, 932015010103181, 156376242256299, 10212355591]
+                CpuSchedStat { cpu_nr: 0, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 1400897766846392, wait_time_ns: 136499688631908, timeslices: 38913706186 }, 
+                CpuSchedStat { cpu_nr: 127, yld_count: 0, sched_count: 0, sched_goidle: 0, ttwu_count: 0, ttwu_local: 0, run_time_ns: 932015010103181, wait_time_ns: 156376242256299, timeslices: 10212355591 }
             ],
             domain: vec![
                 Domain {
                     cpu_nr: 0,
                     domain_nr: 0,
                     cpu_masks: vec![ 0, 1, 0, 1, ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ],
+                    ..Default::default()
                 },
                 Domain {
                     cpu_nr: 0,
                     domain_nr: 1,
                     cpu_masks: vec![ 0, 4294967295, 0, 4294967295, ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ],
+                    ..Default::default()
                 },
                 Domain {
                     cpu_nr: 0,
                     domain_nr: 2,
                     cpu_masks: vec![ 4294967295, 4294967295, 4294967295, 4294967295, ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ], 
+                    ..Default::default()
                 },
                 Domain {
                     cpu_nr: 127,
                     domain_nr: 0,
                     cpu_masks: vec![ 2147483648, 0, 2147483648,0 ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ],
+                    ..Default::default()
                 },
                 Domain {
                     cpu_nr: 127,
                     domain_nr: 1,
                     cpu_masks: vec![ 4294967295, 0, 4294967295, 0, ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ],
+                    ..Default::default()
                 },
                 Domain {
                     cpu_nr: 127,
                     domain_nr: 2,
                     cpu_masks: vec![ 4294967295, 4294967295, 4294967295, 4294967295, ],
-                    statistics: vec![ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ],
+                    ..Default::default()
                 },
             ],
         }
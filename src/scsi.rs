@@ -0,0 +1,287 @@
+/*!
+Read the SCSI hosts and devices from `/sys/class/scsi_host` and `/sys/class/scsi_device` into the struct [`SysScsi`].
+
+A SCSI host (`/sys/class/scsi_host/host<nr>`) is a host bus adapter, such as a SAS/SATA controller (ahci, mpt3sas,
+megaraid_sas), a Fibre Channel HBA or virtio-scsi. The host shows the driver (proc_name), the number of commands
+that can be queued and the number of commands that are currently busy.
+
+A SCSI device (`/sys/class/scsi_device/<host>:<channel>:<target>:<lun>`) is a device attached to a host, such as a
+disk. The device shows the vendor, model and revision, the state (`running`, `offline`, `blocked`, etc.), the queue
+depth, the command timeout in seconds and the counters of the number of requests issued (iorequest_cnt), completed
+(iodone_cnt) and completed with an error (ioerr_cnt). The difference between iorequest_cnt and iodone_cnt is the
+number of commands in flight; a rising ioerr_cnt indicates path or device problems.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-bus-scsi>
+
+Here is an example obtaining the SCSI data:
+```no_run
+use proc_sys_parser::scsi;
+
+let sys_scsi = scsi::read();
+
+println!("{:#?}", sys_scsi);
+```
+Example output:
+```text
+SysScsi {
+    hosts: [
+        ScsiHost { host_nr: 0, proc_name: "ahci", unique_id: Some(1), host_busy: Some(0), can_queue: Some(32), cmd_per_lun: Some(0), sg_tablesize: Some(168), state: Some("running") },
+    ],
+    devices: [
+        ScsiDevice {
+            address: "0:0:0:0",
+            host: 0,
+            channel: 0,
+            target: 0,
+            lun: 0,
+            vendor: "ATA",
+            model: "Samsung SSD 870",
+            rev: Some("2B6Q"),
+            device_type: 0,
+            state: "running",
+            queue_depth: Some(32),
+            timeout: Some(30),
+            device_busy: Some(0),
+            iorequest_cnt: Some(2731994),
+            iodone_cnt: Some(2731994),
+            ioerr_cnt: Some(3),
+            block_device: Some("sda"),
+        },
+    ],
+}
+```
+
+If you want to change the path that is read for [`SysScsi`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::scsi::Builder;
+
+let sys_scsi = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_hex_u64, read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the SCSI hosts and devices
+#[derive(Debug, PartialEq, Default)]
+pub struct SysScsi {
+    pub hosts: Vec<ScsiHost>,
+    pub devices: Vec<ScsiDevice>,
+}
+
+/// Struct for holding a single SCSI host
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ScsiHost {
+    pub host_nr: u32,
+    /// The name of the driver of the host.
+    pub proc_name: String,
+    pub unique_id: Option<u64>,
+    /// The number of commands that are currently issued to the host.
+    pub host_busy: Option<u64>,
+    pub can_queue: Option<u64>,
+    pub cmd_per_lun: Option<u64>,
+    pub sg_tablesize: Option<u64>,
+    pub state: Option<String>,
+}
+
+/// Struct for holding a single SCSI device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ScsiDevice {
+    /// The address of the device in the form host:channel:target:lun.
+    pub address: String,
+    pub host: u32,
+    pub channel: u32,
+    pub target: u32,
+    pub lun: u64,
+    pub vendor: String,
+    pub model: String,
+    pub rev: Option<String>,
+    /// The SCSI peripheral device type, such as 0 (disk), 1 (tape), 5 (cdrom) or 13 (enclosure).
+    pub device_type: u64,
+    pub state: String,
+    pub queue_depth: Option<u64>,
+    /// The command timeout in seconds.
+    pub timeout: Option<u64>,
+    /// The number of commands that are currently issued to the device.
+    pub device_busy: Option<u64>,
+    pub iorequest_cnt: Option<u64>,
+    pub iodone_cnt: Option<u64>,
+    pub ioerr_cnt: Option<u64>,
+    /// The name of the block device of the SCSI device, such as `sda`, if it is a block device.
+    pub block_device: Option<String>,
+}
+
+/// Builder pattern for [`SysScsi`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysScsi, ProcSysParserError> {
+        SysScsi::read_sys_scsi(
+            format!("{}/class/scsi_host", &self.sys_path).as_str(),
+            format!("{}/class/scsi_device", &self.sys_path).as_str(),
+        )
+    }
+}
+
+/// The main function for building a [`SysScsi`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysScsi, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ScsiDevice {
+    /// The number of commands issued to the device that have not completed yet.
+    pub fn in_flight(&self) -> Option<u64> {
+        Some(self.iorequest_cnt?.saturating_sub(self.iodone_cnt?))
+    }
+}
+
+impl SysScsi {
+    pub fn new() -> SysScsi {
+        SysScsi::default()
+    }
+    /// The SCSI device of a block device, such as `sda`.
+    pub fn device_of_block_device(&self, block_device: &str) -> Option<&ScsiDevice> {
+        self.devices.iter().find(|device| device.block_device.as_deref() == Some(block_device))
+    }
+    fn read_scsi_host(host_path: &Path, host_nr: u32) -> Result<ScsiHost, ProcSysParserError> {
+        Ok(ScsiHost {
+            host_nr,
+            proc_name: read_file_string(&host_path.join("proc_name"))?,
+            unique_id: read_file_option_u64(&host_path.join("unique_id"))?,
+            host_busy: read_file_option_u64(&host_path.join("host_busy"))?,
+            can_queue: read_file_option_u64(&host_path.join("can_queue"))?,
+            cmd_per_lun: read_file_option_u64(&host_path.join("cmd_per_lun"))?,
+            sg_tablesize: read_file_option_u64(&host_path.join("sg_tablesize"))?,
+            state: read_file_option_string(&host_path.join("state")),
+        })
+    }
+    fn read_scsi_device(device_path: &Path, address: &str) -> Result<ScsiDevice, ProcSysParserError> {
+        let mut fields = address.split(':');
+        let mut next_field = |item: &str| fields.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: format!("scsi device {} {}", address, item) });
+        let host = next_field("host")?.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+        let channel = next_field("channel")?.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+        let target = next_field("target")?.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+        let lun = next_field("lun")?.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?;
+
+        Ok(ScsiDevice {
+            address: address.to_string(),
+            host,
+            channel,
+            target,
+            lun,
+            // vendor and model are padded with spaces.
+            vendor: read_file_string(&device_path.join("vendor"))?.trim().to_string(),
+            model: read_file_string(&device_path.join("model"))?.trim().to_string(),
+            rev: read_file_option_string(&device_path.join("rev")).map(|rev| rev.trim().to_string()),
+            device_type: read_file_u64(&device_path.join("type"))?,
+            state: read_file_string(&device_path.join("state"))?,
+            queue_depth: read_file_option_u64(&device_path.join("queue_depth"))?,
+            timeout: read_file_option_u64(&device_path.join("timeout"))?,
+            device_busy: read_file_option_u64(&device_path.join("device_busy"))?,
+            // the io counters are shown in hexadecimal, such as 0x29b0da.
+            iorequest_cnt: read_file_option_hex_u64(&device_path.join("iorequest_cnt"))?,
+            iodone_cnt: read_file_option_hex_u64(&device_path.join("iodone_cnt"))?,
+            ioerr_cnt: read_file_option_hex_u64(&device_path.join("ioerr_cnt"))?,
+            block_device: read_dir(device_path.join("block")).ok()
+                .and_then(|mut entries| entries.find_map(|entry| entry.ok()))
+                .map(|entry| entry.file_name().to_string_lossy().to_string()),
+        })
+    }
+    pub fn read_sys_scsi(sys_class_scsi_host_path: &str, sys_class_scsi_device_path: &str) -> Result<SysScsi, ProcSysParserError> {
+        let mut sysscsi = SysScsi::new();
+        let sys_class_scsi_host_path = Path::new(sys_class_scsi_host_path);
+
+        for host_nr in read_numbered_entries(sys_class_scsi_host_path, "host")? {
+            sysscsi.hosts.push(SysScsi::read_scsi_host(&sys_class_scsi_host_path.join(format!("host{}", host_nr)), host_nr)?);
+        }
+
+        let devices = read_dir(sys_class_scsi_device_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_scsi_device_path.to_string(), error })?;
+        for device in devices.flatten() {
+            sysscsi.devices.push(SysScsi::read_scsi_device(&device.path().join("device"), &device.file_name().to_string_lossy())?);
+        }
+        sysscsi.devices.sort_by_key(|device| (device.host, device.channel, device.target, device.lun));
+        Ok(sysscsi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_scsi_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/scsi_host/host0", test_path), &[
+            ("proc_name", "ahci"),
+            ("unique_id", "1"),
+            ("host_busy", "0"),
+            ("can_queue", "32"),
+            ("cmd_per_lun", "0"),
+            ("sg_tablesize", "168"),
+            ("state", "running"),
+        ]);
+        create_mock_files(&format!("{}/class/scsi_host/host1", test_path), &[("proc_name", "virtio_scsi")]);
+        create_mock_files(&format!("{}/class/scsi_device/1:0:2:0/device", test_path), &[
+            ("vendor", "QEMU    "),
+            ("model", "QEMU HARDDISK   "),
+            ("type", "0"),
+            ("state", "running"),
+        ]);
+        create_mock_files(&format!("{}/class/scsi_device/0:0:0:0/device", test_path), &[
+            ("vendor", "ATA     "),
+            ("model", "Samsung SSD 870 "),
+            ("rev", "2B6Q"),
+            ("type", "0"),
+            ("state", "running"),
+            ("queue_depth", "32"),
+            ("timeout", "30"),
+            ("device_busy", "1"),
+            ("iorequest_cnt", "0x29b0dc"),
+            ("iodone_cnt", "0x29b0da"),
+            ("ioerr_cnt", "0x3"),
+        ]);
+        create_dir_all(format!("{}/class/scsi_device/0:0:0:0/device/block/sda", test_path)).expect("Error creating mock directory.");
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.hosts, vec![
+            ScsiHost { host_nr: 0, proc_name: "ahci".to_string(), unique_id: Some(1), host_busy: Some(0), can_queue: Some(32), cmd_per_lun: Some(0), sg_tablesize: Some(168), state: Some("running".to_string()) },
+            ScsiHost { host_nr: 1, proc_name: "virtio_scsi".to_string(), ..Default::default() },
+        ]);
+        assert_eq!(result.devices, vec![
+            ScsiDevice { address: "0:0:0:0".to_string(), host: 0, channel: 0, target: 0, lun: 0, vendor: "ATA".to_string(), model: "Samsung SSD 870".to_string(), rev: Some("2B6Q".to_string()), device_type: 0, state: "running".to_string(), queue_depth: Some(32), timeout: Some(30), device_busy: Some(1), iorequest_cnt: Some(2732252), iodone_cnt: Some(2732250), ioerr_cnt: Some(3), block_device: Some("sda".to_string()) },
+            ScsiDevice { address: "1:0:2:0".to_string(), host: 1, channel: 0, target: 2, lun: 0, vendor: "QEMU".to_string(), model: "QEMU HARDDISK".to_string(), state: "running".to_string(), ..Default::default() },
+        ]);
+        assert_eq!(result.device_of_block_device("sda").unwrap().in_flight(), Some(2));
+    }
+}
@@ -24,13 +24,13 @@ let proc_stat = stat::read();
 Example output:
 ```text
 ProcStat {
-    cpu_total: CpuStat { name: "cpu", user: 8570, nice: 0, system: 7530, idle: 1710040, iowait: 2780, irq: 0, softirq: 150, steal: 0, guest: 0, guest_nice: 0 },
-    cpu_individual: [CpuStat { name: "cpu0", user: 1800, nice: 0, system: 1450, idle: 283400, iowait: 460, irq: 0, softirq: 120, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu1", user: 1720, nice: 0, system: 1320, idle: 284780, iowait: 580, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu2", user: 1060, nice: 0, system: 1220, idle: 285410, iowait: 510, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu3", user: 890, nice: 0, system: 990, idle: 286130, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu4", user: 1400, nice: 0, system: 1280, idle: 285260, iowait: 310, irq: 0, softirq: 30, steal: 0, guest: 0, guest_nice: 0 },
-                     CpuStat { name: "cpu5", user: 1680, nice: 0, system: 1250, idle: 285020, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 }],
+    cpu_total: CpuStat { name: "cpu", cpu_id: None, user: 8570, nice: 0, system: 7530, idle: 1710040, iowait: 2780, irq: 0, softirq: 150, steal: 0, guest: 0, guest_nice: 0 },
+    cpu_individual: [CpuStat { name: "cpu0", cpu_id: Some(0), user: 1800, nice: 0, system: 1450, idle: 283400, iowait: 460, irq: 0, softirq: 120, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu1", cpu_id: Some(1), user: 1720, nice: 0, system: 1320, idle: 284780, iowait: 580, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu2", cpu_id: Some(2), user: 1060, nice: 0, system: 1220, idle: 285410, iowait: 510, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu3", cpu_id: Some(3), user: 890, nice: 0, system: 990, idle: 286130, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu4", cpu_id: Some(4), user: 1400, nice: 0, system: 1280, idle: 285260, iowait: 310, irq: 0, softirq: 30, steal: 0, guest: 0, guest_nice: 0 },
+                     CpuStat { name: "cpu5", cpu_id: Some(5), user: 1680, nice: 0, system: 1250, idle: 285020, iowait: 450, irq: 0, softirq: 0, steal: 0, guest: 0, guest_nice: 0 }],
     interrupts: [184655, 0, 4500, 60546, 0, 0, 0, 2, 0, 0, 0, 70138, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 548, 0, 0, 0, 0, 0, 2, 0, 3410, 2927, 4739, 5542, 1595, 1913, 0, 0, 0, 79, 154, 208, 282, 43, 52, 0, 14842, 11679, 0, 0, 17, 0, 0, 0, 0, 0, 0, 0, 1437, 0, 0, 0, 0, 0, 0],
     context_switches: 275716,
     boot_time: 1702127060,
@@ -50,10 +50,46 @@ use proc_sys_parser::{stat, stat::{ProcStat, CpuStat, Builder}};
 let proc_stat = Builder::new().path("/myproc").read();
 ```
 
+The `interrupts` vector is the `intr` line: the total number of interrupts, followed by the number of interrupts per
+IRQ number. The total is obtained with `interrupts_total()`, and the counts per IRQ with `interrupts_per_irq()`.
+The counts can be joined with `/proc/interrupts` to get the interrupt controller and device of each IRQ:
+```no_run
+use proc_sys_parser::stat;
+
+let proc_stat = stat::read().unwrap();
+let named_interrupts = proc_stat.read_named_interrupts("/proc/interrupts");
+```
+
+The utilization of a cpu between two samples in percent is calculated with `utilization_since()`:
+```no_run
+use proc_sys_parser::stat;
+
+let previous = stat::read().unwrap();
+std::thread::sleep(std::time::Duration::from_secs(1));
+let current = stat::read().unwrap();
+let utilization = current.cpu_total.utilization_since(&previous.cpu_total);
+```
+
+On machines with many cpus, the individual cpus can be added per socket or die with the [`SysCpuTopology`] of
+[`crate::cpu::topology`], or per numa node with the [`SysNuma`] of [`crate::numa`]:
+```no_run
+use proc_sys_parser::{stat, cpu::topology, numa};
+
+let proc_stat = stat::read().unwrap();
+let per_socket = proc_stat.by_socket(&topology::read().unwrap());
+let per_node = proc_stat.by_numa_node(&numa::read().unwrap());
+```
+
+The `softirq` vector is the `softirq` line: the total number of softirqs, followed by the number per softirq type.
+Use `softirq_stat()` to obtain these as a [`SoftIrqStat`] struct with a named field per softirq type.
+
 */
 use nix::unistd::{sysconf, SysconfVar};
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
 use crate::ProcSysParserError;
+use crate::cpu::topology::SysCpuTopology;
+use crate::numa::SysNuma;
 use log::warn;
 
 
@@ -62,6 +98,8 @@ use log::warn;
 pub struct CpuStat {
     /// cpu name. 'cpu' means total of all cpus, cpuN means individual cpu
     pub name: String,
+    /// cpu number N of cpuN, None for the total of all cpus
+    pub cpu_id: Option<u32>,
     /// user time in milliseconds
     pub user: u64,
     /// user time reniced in milliseconds
@@ -87,6 +125,23 @@ pub struct CpuStat {
     pub guest_nice: Option<u64>,
 }
 
+/// Struct for holding the utilization of a cpu over the interval between two [`CpuStat`] samples in percent
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CpuUtilization {
+    /// user time excluding guest time
+    pub user: f64,
+    /// user time reniced excluding guest_nice time
+    pub nice: f64,
+    pub system: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+    pub guest: f64,
+    pub guest_nice: f64,
+    pub idle: f64,
+}
+
 /// Builder pattern for [`ProcStat`]
 #[derive(Default)]
 pub struct Builder {
@@ -135,10 +190,143 @@ pub struct ProcStat {
     pub softirq: Vec<u64>,
 }
 
+/// Struct for holding the count of a numbered interrupt, with its name from `/proc/interrupts`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct IrqStat {
+    /// The IRQ number.
+    pub irq: u32,
+    /// The number of interrupts since boot, from the `intr` line of `/proc/stat`.
+    pub count: u64,
+    /// The interrupt controller, such as `IO-APIC` or `PCI-MSI`.
+    pub chip: String,
+    /// The hardware IRQ number and trigger type, such as `2-edge`.
+    pub hwirq: String,
+    /// The devices (actions) using the interrupt, such as `nvme0q1`; empty if none.
+    pub device: String,
+}
+
+/// Struct for holding the `softirq` line of `/proc/stat` with named fields
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct SoftIrqStat {
+    pub total: u64,
+    pub hi: u64,
+    pub timer: u64,
+    pub net_tx: u64,
+    pub net_rx: u64,
+    pub block: u64,
+    /// Named block_iopoll before kernel version 4.4
+    pub irq_poll: u64,
+    pub tasklet: u64,
+    pub sched: u64,
+    pub hrtimer: u64,
+    pub rcu: u64,
+}
+
 impl ProcStat {
     pub fn new() -> ProcStat {
         ProcStat::default() 
     }
+    /// The individual cpu with the cpu number.
+    pub fn cpu(&self, cpu_id: u32) -> Option<&CpuStat> {
+        self.cpu_individual.iter().find(|cpu| cpu.cpu_id == Some(cpu_id))
+    }
+    /// The cpu numbers below the highest cpu number that are not shown, which are offline cpus.
+    /// Offline cpus above the highest online cpu number can not be detected from `/proc/stat`.
+    pub fn offline_cpus(&self) -> Vec<u32> {
+        let online: Vec<u32> = self.cpu_individual.iter().filter_map(|cpu| cpu.cpu_id).collect();
+        (0..online.iter().max().copied().unwrap_or_default())
+            .filter(|cpu_id| !online.contains(cpu_id))
+            .collect()
+    }
+    /// Add the times of the individual cpus per group into a [`CpuStat`] with the name of the group.
+    fn aggregate<K: Ord>(&self, group_of: impl Fn(u32) -> Option<K>, name_of: impl Fn(&K) -> String) -> BTreeMap<K, CpuStat> {
+        let mut groups: BTreeMap<K, CpuStat> = BTreeMap::new();
+        for cpu in &self.cpu_individual {
+            let Some(group) = cpu.cpu_id.and_then(&group_of) else { continue };
+            let name = name_of(&group);
+            groups.entry(group)
+                .or_insert_with(|| CpuStat { name, ..Default::default() })
+                .add(cpu);
+        }
+        groups
+    }
+    /// The cpu times added per physical package (socket), named `socketN`.
+    pub fn by_socket(&self, topology: &SysCpuTopology) -> BTreeMap<i64, CpuStat> {
+        self.aggregate(|cpu_id| topology.socket_of(cpu_id), |socket| format!("socket{}", socket))
+    }
+    /// The cpu times added per die of a physical package, named `socketNdieN`. Before kernel 5.2, which does not show
+    /// the die, every package is taken as a single die 0.
+    pub fn by_die(&self, topology: &SysCpuTopology) -> BTreeMap<(i64, i64), CpuStat> {
+        self.aggregate(
+            |cpu_id| topology.cpu(cpu_id).map(|cpu| (cpu.physical_package_id, cpu.die_id.unwrap_or_default())),
+            |(socket, die)| format!("socket{}die{}", socket, die),
+        )
+    }
+    /// The cpu times added per numa node, named `nodeN`.
+    pub fn by_numa_node(&self, numa: &SysNuma) -> BTreeMap<u32, CpuStat> {
+        self.aggregate(
+            |cpu_id| numa.nodes.iter().find(|node| node.cpulist.contains(&cpu_id)).map(|node| node.node_nr),
+            |node| format!("node{}", node),
+        )
+    }
+    /// The total number of interrupts, which is the first value of the `intr` line.
+    pub fn interrupts_total(&self) -> Option<u64> {
+        self.interrupts.first().copied()
+    }
+    /// The number of interrupts per IRQ number, which are the values of the `intr` line after the total.
+    /// The index is the IRQ number.
+    pub fn interrupts_per_irq(&self) -> &[u64] {
+        self.interrupts.get(1..).unwrap_or_default()
+    }
+    /// The `softirq` vector as a struct with named fields; values that are not shown are 0.
+    pub fn softirq_stat(&self) -> SoftIrqStat {
+        let value = |index: usize| self.softirq.get(index).copied().unwrap_or_default();
+        SoftIrqStat {
+            total: value(0),
+            hi: value(1),
+            timer: value(2),
+            net_tx: value(3),
+            net_rx: value(4),
+            block: value(5),
+            irq_poll: value(6),
+            tasklet: value(7),
+            sched: value(8),
+            hrtimer: value(9),
+            rcu: value(10),
+        }
+    }
+    /// Join the interrupt counts with the numbered interrupts in the contents of `/proc/interrupts`, to obtain the
+    /// name of each counted IRQ. IRQ numbers that are not shown in `/proc/interrupts` are not in use, and are skipped,
+    /// as are the architecture specific interrupts such as `NMI` and `LOC`, which are not counted per IRQ number.
+    pub fn parse_named_interrupts(&self, proc_interrupts: &str) -> Result<Vec<IrqStat>, ProcSysParserError> {
+        let mut lines = proc_interrupts.lines();
+        let cpus = lines.next()
+            .ok_or(ProcSysParserError::IteratorItemError { item: "interrupts header".to_string() })?
+            .split_whitespace()
+            .count();
+        let mut irq_stats = Vec::new();
+
+        for line in lines {
+            let Some((irq, rest)) = line.split_once(':') else { continue };
+            let Ok(irq) = irq.trim().parse::<u32>() else { continue };
+            let Some(count) = self.interrupts_per_irq().get(irq as usize) else { continue };
+            let mut fields = rest.split_whitespace().skip(cpus);
+            irq_stats.push(IrqStat {
+                irq,
+                count: *count,
+                chip: fields.next().unwrap_or_default().to_string(),
+                hwirq: fields.next().unwrap_or_default().to_string(),
+                device: fields.collect::<Vec<_>>().join(" "),
+            });
+        }
+        Ok(irq_stats)
+    }
+    /// Read `/proc/interrupts`, and join it with the interrupt counts, see [`ProcStat::parse_named_interrupts`].
+    pub fn read_named_interrupts(&self, proc_interrupts_file: &str) -> Result<Vec<IrqStat>, ProcSysParserError> {
+        let proc_interrupts = read_to_string(proc_interrupts_file)
+            .map_err(|error| ProcSysParserError::FileReadError { file: proc_interrupts_file.to_string(), error })?;
+        self.parse_named_interrupts(&proc_interrupts)
+    }
     pub fn parse_proc_stat_output(proc_stat: &str,) -> Result<ProcStat, ProcSysParserError> {
         let mut procstat = ProcStat::new();
         for line in proc_stat.lines() {
@@ -197,6 +385,55 @@ impl ProcStat {
 }
 
 impl CpuStat {
+    fn add(&mut self, other: &CpuStat) {
+        let add_option = |total: Option<u64>, value: Option<u64>| match (total, value) {
+            (None, None) => None,
+            (total, value) => Some(total.unwrap_or_default() + value.unwrap_or_default()),
+        };
+        self.user += other.user;
+        self.nice += other.nice;
+        self.system += other.system;
+        self.idle += other.idle;
+        self.iowait = add_option(self.iowait, other.iowait);
+        self.irq = add_option(self.irq, other.irq);
+        self.softirq = add_option(self.softirq, other.softirq);
+        self.steal = add_option(self.steal, other.steal);
+        self.guest = add_option(self.guest, other.guest);
+        self.guest_nice = add_option(self.guest_nice, other.guest_nice);
+    }
+    /// The utilization of the cpu between an earlier sample and this sample in percent, like `mpstat` calculates it.
+    /// The guest times are included in the user and nice times by the kernel, and are subtracted from these.
+    /// A time that decreased, which can happen with iowait, is taken as 0, and if no time passed, all are 0.
+    pub fn utilization_since(&self, previous: &CpuStat) -> CpuUtilization {
+        let delta = |current: u64, previous: u64| current.saturating_sub(previous);
+        let delta_option = |current: Option<u64>, previous: Option<u64>| delta(current.unwrap_or_default(), previous.unwrap_or_default());
+        let guest = delta_option(self.guest, previous.guest);
+        let guest_nice = delta_option(self.guest_nice, previous.guest_nice);
+        let user = delta(self.user, previous.user).saturating_sub(guest);
+        let nice = delta(self.nice, previous.nice).saturating_sub(guest_nice);
+        let system = delta(self.system, previous.system);
+        let idle = delta(self.idle, previous.idle);
+        let iowait = delta_option(self.iowait, previous.iowait);
+        let irq = delta_option(self.irq, previous.irq);
+        let softirq = delta_option(self.softirq, previous.softirq);
+        let steal = delta_option(self.steal, previous.steal);
+
+        let total = user + nice + system + idle + iowait + irq + softirq + steal + guest + guest_nice;
+        if total == 0 { return CpuUtilization::default() };
+        let percent = |time: u64| time as f64 * 100_f64 / total as f64;
+        CpuUtilization {
+            user: percent(user),
+            nice: percent(nice),
+            system: percent(system),
+            iowait: percent(iowait),
+            irq: percent(irq),
+            softirq: percent(softirq),
+            steal: percent(steal),
+            guest: percent(guest),
+            guest_nice: percent(guest_nice),
+            idle: percent(idle),
+        }
+    }
     pub fn generate_cpu_times(proc_stat_cpu_line: &str) -> Result<CpuStat, ProcSysParserError> {
         // Note: time in jiffies, must be divided by CLK_TCK to show time in seconds.
         // CLK_TCK is set by CONFIG_HZ and is 100 on most enterprise linuxes.
@@ -215,10 +452,11 @@ impl CpuStat {
         };
 
         let mut splitted = proc_stat_cpu_line.split_whitespace();
+        let name = splitted.next()
+            .ok_or(ProcSysParserError::IteratorItemError {item: "stat generate_cpu_times name".to_string() })?;
         Ok(CpuStat {
-            name: splitted.next()
-                .ok_or(ProcSysParserError::IteratorItemError {item: "stat generate_cpu_times name".to_string() })?
-                .to_string(),
+            name: name.to_string(),
+            cpu_id: name.strip_prefix("cpu").and_then(|cpu_id| cpu_id.parse::<u32>().ok()),
             user: ((splitted.next()
                 .ok_or(ProcSysParserError::IteratorItemError {item: "stat generate_cpu_times user".to_string() })?
                 .parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)? *1000_u64)/clock_time),
@@ -255,8 +493,8 @@ mod tests {
     #[test]
     fn parse_cpu_line() {
         let cpu_line = "cpu  101521 47 66467 43586274 7651 0 1367 0 0 0";
-        let result = CpuStat::generate_cpu_times(&cpu_line).unwrap();
-        assert_eq!(result, CpuStat { name:"cpu".to_string(), user:1015210, nice:470, system:664670, idle:435862740, iowait:Some(76510), irq:Some(0), softirq:Some(13670), steal:Some(0), guest:Some(0), guest_nice:Some(0) });
+        let result = CpuStat::generate_cpu_times(cpu_line).unwrap();
+        assert_eq!(result, CpuStat { name:"cpu".to_string(), cpu_id: None, user:1015210, nice:470, system:664670, idle:435862740, iowait:Some(76510), irq:Some(0), softirq:Some(13670), steal:Some(0), guest:Some(0), guest_nice:Some(0) });
     }
 
     // This mimics a (much) lower linux version which provides lesser statistics
@@ -264,18 +502,47 @@ mod tests {
     #[test]
     fn parse_cpu_line_with_less_statistics() {
         let cpu_line = "cpu  101521 47 66467 43586274";
-        let result = CpuStat::generate_cpu_times(&cpu_line).unwrap();
-        assert_eq!(result, CpuStat { name:"cpu".to_string(), user:1015210, nice:470, system:664670, idle:435862740, iowait:None, irq:None, softirq:None, steal:None, guest:None, guest_nice:None });
+        let result = CpuStat::generate_cpu_times(cpu_line).unwrap();
+        assert_eq!(result, CpuStat { name:"cpu".to_string(), cpu_id: None, user:1015210, nice:470, system:664670, idle:435862740, iowait:None, irq:None, softirq:None, steal:None, guest:None, guest_nice:None });
     }
 
 
     #[test]
     fn parse_interrupt_line() {
         let interrupt_line = "intr 21965856 0 520030 7300523 0 0 0 2 0 0 0 12267292 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 644 0 0 0 0 0 2 0 77822 81889 80164 70697 68349 79207 0 0 0 6172 6117 6131 5983 6483 6062 0 588204 437602 0 0 1202 0 0 0 0 0 0 0 0 0 0 0 355279 0 0";
-        let result = ProcStat::generate_number_vector(&interrupt_line).unwrap();
+        let result = ProcStat::generate_number_vector(interrupt_line).unwrap();
         assert_eq!(result, vec![21965856, 0, 520030, 7300523, 0, 0, 0, 2, 0, 0, 0, 12267292, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 644, 0, 0, 0, 0, 0, 2, 0, 77822, 81889, 80164, 70697, 68349, 79207, 0, 0, 0, 6172, 6117, 6131, 5983, 6483, 6062, 0, 588204, 437602, 0, 0, 1202, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 355279, 0, 0]);
     }
 
+    #[test]
+    fn calculate_cpu_utilization_since_previous_sample() {
+        let previous = CpuStat { name: "cpu0".to_string(), cpu_id: Some(0), user: 1000, nice: 0, system: 500, idle: 8000, iowait: Some(300), irq: Some(0), softirq: Some(100), steal: Some(0), guest: Some(100), guest_nice: Some(0) };
+        let current = CpuStat { name: "cpu0".to_string(), cpu_id: Some(0), user: 1600, nice: 0, system: 700, idle: 9000, iowait: Some(250), irq: Some(0), softirq: Some(200), steal: Some(100), guest: Some(200), guest_nice: Some(0) };
+        assert_eq!(current.utilization_since(&previous), CpuUtilization { user: 25.0, nice: 0.0, system: 10.0, iowait: 0.0, irq: 0.0, softirq: 5.0, steal: 5.0, guest: 5.0, guest_nice: 0.0, idle: 50.0 });
+        assert_eq!(current.utilization_since(&current), CpuUtilization::default());
+    }
+
+    #[test]
+    fn parse_named_interrupts() {
+        let proc_stat = ProcStat { interrupts: vec![1206, 44, 9, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1152], ..Default::default() };
+        let proc_interrupts = "           CPU0       CPU1
+  0:         44          0   IO-APIC   2-edge      timer
+  1:          0          9   IO-APIC   1-edge      i8042
+  9:          0          1   IO-APIC   9-fasteoi   acpi
+ 24:        601        551   PCI-MSI 65536-edge      nvme0q0, nvme0q1
+ 25:          0          0   PCI-MSI 65537-edge
+NMI:          0          0   Non-maskable interrupts
+LOC:     297186     276311   Local timer interrupts";
+        assert_eq!(proc_stat.interrupts_total(), Some(1206));
+        assert_eq!(proc_stat.interrupts_per_irq()[24], 1152);
+        assert_eq!(proc_stat.parse_named_interrupts(proc_interrupts).unwrap(), vec![
+            IrqStat { irq: 0, count: 44, chip: "IO-APIC".to_string(), hwirq: "2-edge".to_string(), device: "timer".to_string() },
+            IrqStat { irq: 1, count: 9, chip: "IO-APIC".to_string(), hwirq: "1-edge".to_string(), device: "i8042".to_string() },
+            IrqStat { irq: 9, count: 1, chip: "IO-APIC".to_string(), hwirq: "9-fasteoi".to_string(), device: "acpi".to_string() },
+            IrqStat { irq: 24, count: 1152, chip: "PCI-MSI".to_string(), hwirq: "65536-edge".to_string(), device: "nvme0q0, nvme0q1".to_string() },
+        ]);
+    }
+
     #[test]
     fn parse_context_switches_line() {
         let context_switches_line = "ctxt 36432936";
@@ -301,13 +568,13 @@ procs_running 1
 procs_blocked 0
 softirq 7616206 32 1416021 213 1102885 11 0 1409 2270709 0 2824926";
         let result = ProcStat::parse_proc_stat_output(proc_stat).unwrap();
-        assert_eq!(result, ProcStat { cpu_total: CpuStat { name: "cpu".to_string(), user: 1015210, nice: 470, system: 664670, idle: 435862740, iowait: Some(76510), irq: Some(0), softirq: Some(13670), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-            cpu_individual: vec![CpuStat { name: "cpu0".to_string(), user: 162980, nice: 0, system: 115900, idle: 72592620, iowait: Some(12130), irq: Some(0), softirq: Some(8460), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-                                 CpuStat { name: "cpu1".to_string(), user: 162720, nice: 0, system: 112910, idle: 72656150, iowait: Some(12890), irq: Some(0), softirq: Some(1100), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-                                 CpuStat { name: "cpu2".to_string(), user: 161210, nice: 470, system: 109860, idle: 72663580, iowait: Some(12510), irq: Some(0), softirq: Some(1110), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-                                 CpuStat { name: "cpu3".to_string(), user: 177860, nice: 0, system: 110230, idle: 72647150, iowait: Some(13500), irq: Some(0), softirq: Some(1160), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-                                 CpuStat { name: "cpu4".to_string(), user: 174260, nice: 0, system: 107360, idle: 72654910, iowait: Some(11950), irq: Some(0), softirq: Some(790), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-                                 CpuStat { name: "cpu5".to_string(), user: 176160, nice: 0, system: 108400, idle: 72648320, iowait: Some(13510), irq: Some(0), softirq: Some(1030), steal: Some(0), guest: Some(0), guest_nice: Some(0) }],
+        assert_eq!(result, ProcStat { cpu_total: CpuStat { name: "cpu".to_string(), cpu_id: None, user: 1015210, nice: 470, system: 664670, idle: 435862740, iowait: Some(76510), irq: Some(0), softirq: Some(13670), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+            cpu_individual: vec![CpuStat { name: "cpu0".to_string(), cpu_id: Some(0), user: 162980, nice: 0, system: 115900, idle: 72592620, iowait: Some(12130), irq: Some(0), softirq: Some(8460), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+                                 CpuStat { name: "cpu1".to_string(), cpu_id: Some(1), user: 162720, nice: 0, system: 112910, idle: 72656150, iowait: Some(12890), irq: Some(0), softirq: Some(1100), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+                                 CpuStat { name: "cpu2".to_string(), cpu_id: Some(2), user: 161210, nice: 470, system: 109860, idle: 72663580, iowait: Some(12510), irq: Some(0), softirq: Some(1110), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+                                 CpuStat { name: "cpu3".to_string(), cpu_id: Some(3), user: 177860, nice: 0, system: 110230, idle: 72647150, iowait: Some(13500), irq: Some(0), softirq: Some(1160), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+                                 CpuStat { name: "cpu4".to_string(), cpu_id: Some(4), user: 174260, nice: 0, system: 107360, idle: 72654910, iowait: Some(11950), irq: Some(0), softirq: Some(790), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+                                 CpuStat { name: "cpu5".to_string(), cpu_id: Some(5), user: 176160, nice: 0, system: 108400, idle: 72648320, iowait: Some(13510), irq: Some(0), softirq: Some(1030), steal: Some(0), guest: Some(0), guest_nice: Some(0) }],
             interrupts: vec![21965856, 0, 520030, 7300523, 0, 0, 0, 2, 0, 0, 0, 12267292, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 644, 0, 0, 0, 0, 0, 2, 0, 77822, 81889, 80164, 70697, 68349, 79207, 0, 0, 0, 6172, 6117, 6131, 5983, 6483, 6062, 0, 588204, 437602, 0, 0, 1202, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 355279, 0, 0],
             context_switches: 36432936,
             boot_time: 1701783048,
@@ -316,6 +583,44 @@ softirq 7616206 32 1416021 213 1102885 11 0 1409 2270709 0 2824926";
             processes_blocked: 0,
             softirq: vec![7616206, 32, 1416021, 213, 1102885, 11, 0, 1409, 2270709, 0, 2824926],
         });
+        assert_eq!(result.softirq_stat(), SoftIrqStat { total: 7616206, hi: 32, timer: 1416021, net_tx: 213, net_rx: 1102885, block: 11, irq_poll: 0, tasklet: 1409, sched: 2270709, hrtimer: 0, rcu: 2824926 });
+    }
+
+    #[test]
+    fn parse_proc_stat_with_offline_cpus() {
+        let proc_stat = "cpu  101521 47 66467 43586274 7651 0 1367 0 0 0
+cpu0 16298 0 11590 7259262 1213 0 846 0 0 0
+cpu2 16121 47 10986 7266358 1251 0 111 0 0 0
+cpu3 17786 0 11023 7264715 1350 0 116 0 0 0
+cpu5 17616 0 10840 7264832 1351 0 103 0 0 0";
+        let result = ProcStat::parse_proc_stat_output(proc_stat).unwrap();
+        assert_eq!(result.cpu_total.cpu_id, None);
+        assert_eq!(result.cpu_individual.iter().map(|cpu| cpu.cpu_id).collect::<Vec<_>>(), vec![Some(0), Some(2), Some(3), Some(5)]);
+        assert_eq!(result.cpu(3).unwrap().name, "cpu3");
+        assert_eq!(result.cpu(1), None);
+        assert_eq!(result.offline_cpus(), vec![1, 4]);
+    }
+
+    #[test]
+    fn aggregate_cpus_by_socket_die_and_numa_node() {
+        use crate::cpu::topology::CpuTopology;
+        use crate::numa::NumaNode;
+
+        let proc_stat = "cpu  40 0 40 400 4 0 0 0 0 0
+cpu0 10 0 10 100 1 0 0 0 0 0
+cpu1 10 0 10 100 1 0 0 0 0 0
+cpu2 10 0 10 100 1 0 0 0 0 0
+cpu3 10 0 10 100 1 0 0 0 0 0";
+        let result = ProcStat::parse_proc_stat_output(proc_stat).unwrap();
+        let topology = SysCpuTopology { cpus: (0..4).map(|cpu_nr| CpuTopology { cpu_nr, physical_package_id: (cpu_nr / 2) as i64, die_id: Some(0), core_id: (cpu_nr % 2) as i64, ..Default::default() }).collect() };
+        let numa = SysNuma { nodes: vec![NumaNode { node_nr: 0, cpulist: vec![0, 1, 2], ..Default::default() }, NumaNode { node_nr: 1, cpulist: vec![3], ..Default::default() }] };
+
+        let by_socket = result.by_socket(&topology);
+        assert_eq!(by_socket.keys().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(by_socket[&1], CpuStat { name: "socket1".to_string(), cpu_id: None, user: 200, nice: 0, system: 200, idle: 2000, iowait: Some(20), irq: Some(0), softirq: Some(0), steal: Some(0), guest: Some(0), guest_nice: Some(0) });
+        assert_eq!(result.by_die(&topology)[&(0, 0)].name, "socket0die0");
+        let by_numa_node = result.by_numa_node(&numa);
+        assert_eq!((by_numa_node[&0].user, by_numa_node[&1].user), (300, 100));
     }
 
     #[test]
@@ -334,12 +639,12 @@ softirq 100 0 1 1";
         let test_path = format!("/tmp/test.{}", directory_suffix);
         create_dir_all(test_path.clone()).expect("Error creating mock sysfs directories.");
         
-        write(format!("{}/stat", test_path), proc_stat).expect(format!("Error writing to {}/stat", test_path).as_str());
+        write(format!("{}/stat", test_path), proc_stat).unwrap_or_else(|_| panic!("Error writing to {}/stat", test_path));
         let result = Builder::new().path(&test_path).read().unwrap();
         remove_dir_all(test_path).unwrap();
 
-        assert_eq!(result, ProcStat { cpu_total: CpuStat { name: "cpu".to_string(), user: 10, nice: 10, system: 10, idle: 10, iowait: Some(10), irq: Some(0), softirq: Some(10), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
-            cpu_individual: vec![CpuStat { name: "cpu0".to_string(),user: 10, nice: 10, system: 10, idle: 10, iowait: Some(10), irq: Some(0), softirq: Some(10), steal: Some(0), guest: Some(0), guest_nice: Some(0) }],
+        assert_eq!(result, ProcStat { cpu_total: CpuStat { name: "cpu".to_string(), cpu_id: None, user: 10, nice: 10, system: 10, idle: 10, iowait: Some(10), irq: Some(0), softirq: Some(10), steal: Some(0), guest: Some(0), guest_nice: Some(0) },
+            cpu_individual: vec![CpuStat { name: "cpu0".to_string(), cpu_id: Some(0),user: 10, nice: 10, system: 10, idle: 10, iowait: Some(10), irq: Some(0), softirq: Some(10), steal: Some(0), guest: Some(0), guest_nice: Some(0) }],
             interrupts: vec![100, 0, 1, 1],
             context_switches: 100,
             boot_time: 100,
@@ -0,0 +1,569 @@
+/*!
+Read the network interface attributes from `/sys/class/net/<interface>` into the struct [`SysNet`].
+
+The network statistics in `/proc/net/dev` ([`crate::net_dev`]) only show the counters of the interfaces.
+The sysfs directory of an interface shows the properties of it, such as the link speed, the duplex mode, the
+operational state, the mtu and the hardware address.
+
+Some attributes cannot be read when the interface has no link: reading `carrier`, `speed` and `duplex` then results
+in an error (EINVAL), which is why these are Option. Virtual interfaces often show a speed of -1 (unknown).
+
+The `statistics` directory of an interface contains the same counters as `/proc/net/dev`, but with the errors broken
+down into the specific error types, such as CRC errors and missed packets, which show the cause of the errors.
+
+The `queues` directory of an interface contains a directory per receive and transmit queue, showing the cpus the
+packets of the queue are steered to (RPS and XPS), and the byte queue limits of the transmit queues. With these,
+an unbalanced distribution of the queues over the cpus of a multi-queue NIC can be detected.
+
+For SR-IOV capable network devices, the PCI device directory `device` of the interface of the physical function
+shows the number of virtual functions (sriov_totalvfs and sriov_numvfs) and links to the virtual functions (virtfn<nr>).
+The interface of a virtual function has a link to the physical function owning it (physfn).
+
+The `device` link of an interface points to the device backing the interface, which shows the vendor and device id,
+the driver, the numa node and the PCIe link of the NIC. Virtual interfaces have no device link.
+
+Just like [`crate::net_dev`], the loopback interface is filtered out by default using the regex `^lo`.
+
+Documentation: <https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net>
+
+Here is an example obtaining the data from `/sys/class/net`:
+```no_run
+use proc_sys_parser::sys_net;
+
+let sys_net = sys_net::read();
+
+println!("{:#?}", sys_net);
+```
+Example output:
+```text
+SysNet {
+    interfaces: [
+        NetInterface {
+            name: "eth0",
+            ifindex: 2,
+            iflink: 2,
+            interface_type: 1,
+            address: "52:54:00:12:34:56",
+            mtu: 1500,
+            tx_queue_len: 1000,
+            flags: 4099,
+            operstate: "up",
+            carrier: Some(1),
+            speed: Some(10000),
+            duplex: Some("full"),
+            statistics: NetStatistics {
+                collisions: 0,
+                multicast: 0,
+                rx_bytes: 151013652,
+                rx_compressed: 0,
+                rx_crc_errors: 0,
+                rx_dropped: 0,
+                rx_errors: 0,
+                ..
+                tx_packets: 12257,
+                tx_window_errors: 0,
+            },
+            rx_queues: [
+                RxQueue { queue_nr: 0, rps_cpus: [], rps_flow_cnt: 0 },
+            ],
+            tx_queues: [
+                TxQueue { queue_nr: 0, tx_maxrate: Some(0), tx_timeout: Some(0), traffic_class: None, xps_cpus: Some([0, 1]), byte_queue_limits: Some(ByteQueueLimits { limit: 1981, limit_max: 1879048192, limit_min: 0, inflight: 0, hold_time: 1000 }) },
+            ],
+            sriov: Some(Sriov { totalvfs: 64, numvfs: 2, vfs: [SriovVf { vf_nr: 0, pci_address: "0000:3b:02.0", interface: Some("eth2") }, SriovVf { vf_nr: 1, pci_address: "0000:3b:02.1", interface: None }] }),
+            physfn: None,
+            device: Some(NetDevice { bus_address: "0000:3b:00.0", driver: Some("ixgbe"), vendor: Some(32902), device: Some(5515), subsystem_vendor: Some(32902), subsystem_device: Some(6), numa_node: Some(0), current_link_speed: Some("5.0 GT/s PCIe"), current_link_width: Some(8), max_link_speed: Some("5.0 GT/s PCIe"), max_link_width: Some(8) }),
+        },
+    ],
+}
+```
+
+If you want to change the path and/or the filter that is used for [`SysNet`], which is `/sys` and `^lo` by default, use:
+```no_run
+use proc_sys_parser::sys_net::Builder;
+
+let sys_net = Builder::new().path("/mysys").filter("^(lo|docker)").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use regex::Regex;
+use crate::ProcSysParserError;
+use crate::utils::{parse_cpu_mask, read_file_hex_u64, read_file_option_hex_u64, read_file_option_i64, read_file_option_string, read_file_option_u64, read_file_string, read_file_u64, read_link_name, read_numbered_entries};
+
+/// Struct for holding the attributes of all network interfaces
+#[derive(Debug, PartialEq, Default)]
+pub struct SysNet {
+    pub interfaces: Vec<NetInterface>,
+}
+
+/// Struct for holding the attributes of a single network interface
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NetInterface {
+    pub name: String,
+    /// The unique index of the interface.
+    pub ifindex: u64,
+    /// The index of the interface the packets are transmitted on, which is different from ifindex for virtual interfaces.
+    pub iflink: u64,
+    /// The ARPHRD type of the interface, 1 is ethernet, 772 is loopback.
+    pub interface_type: u64,
+    /// The hardware address.
+    pub address: String,
+    pub mtu: u64,
+    pub tx_queue_len: u64,
+    /// The interface flags (IFF_UP, IFF_BROADCAST, etc.), which is shown as hexadecimal number in the file.
+    pub flags: u64,
+    /// The RFC 2863 operational state: unknown, notpresent, down, lowerlayerdown, testing, dormant or up.
+    pub operstate: String,
+    /// 1 if the interface has a link, None if the interface is down.
+    pub carrier: Option<u64>,
+    /// The link speed in Mbit/s, -1 if unknown, None if the interface is down.
+    pub speed: Option<i64>,
+    /// half, full or unknown, None if the interface is down or the interface has no duplex setting.
+    pub duplex: Option<String>,
+    pub statistics: NetStatistics,
+    /// The receive queues, sorted by queue number.
+    pub rx_queues: Vec<RxQueue>,
+    /// The transmit queues, sorted by queue number.
+    pub tx_queues: Vec<TxQueue>,
+    /// The SR-IOV configuration, None if the device is not an SR-IOV capable physical function.
+    pub sriov: Option<Sriov>,
+    /// The PCI address of the physical function owning this interface, None if the interface is not a virtual function.
+    pub physfn: Option<String>,
+    /// The device backing the interface, None for virtual interfaces such as bridges, bonds and veth.
+    pub device: Option<NetDevice>,
+}
+
+/// Struct for holding the identity of the (PCI) device of a network interface
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NetDevice {
+    /// The bus address of the device, such as `0000:3b:00.0` for a PCI device, or `virtio3` for a virtio device.
+    pub bus_address: String,
+    /// The name of the driver bound to the device, such as `ixgbe`.
+    pub driver: Option<String>,
+    pub vendor: Option<u64>,
+    pub device: Option<u64>,
+    pub subsystem_vendor: Option<u64>,
+    pub subsystem_device: Option<u64>,
+    /// The numa node the device is attached to, -1 if the system is not numa.
+    pub numa_node: Option<i64>,
+    /// The current PCIe link speed, such as `8.0 GT/s PCIe`.
+    pub current_link_speed: Option<String>,
+    /// The current PCIe link width (number of lanes).
+    pub current_link_width: Option<u64>,
+    pub max_link_speed: Option<String>,
+    pub max_link_width: Option<u64>,
+}
+
+/// Struct for holding the SR-IOV configuration of a physical function
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Sriov {
+    /// The maximal number of virtual functions the device supports.
+    pub totalvfs: u64,
+    /// The number of virtual functions currently enabled.
+    pub numvfs: u64,
+    pub vfs: Vec<SriovVf>,
+}
+
+/// Struct for holding a single virtual function of a physical function
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct SriovVf {
+    pub vf_nr: u32,
+    /// The PCI address of the virtual function, such as `0000:3b:02.0`.
+    pub pci_address: String,
+    /// The network interface of the virtual function, None if it is not bound to a network driver in the host,
+    /// which is the case when it is passed through to a virtual machine.
+    pub interface: Option<String>,
+}
+
+/// Struct for holding `/sys/class/net/<interface>/queues/rx-<nr>`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct RxQueue {
+    pub queue_nr: u32,
+    /// The cpus receive packet steering (RPS) distributes the packets of the queue to, empty if RPS is disabled.
+    pub rps_cpus: Vec<u32>,
+    /// The number of flows for receive flow steering (RFS).
+    pub rps_flow_cnt: u64,
+}
+
+/// Struct for holding `/sys/class/net/<interface>/queues/tx-<nr>`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct TxQueue {
+    pub queue_nr: u32,
+    /// The rate limit in Mbit/s, 0 means no limit. Only available if the driver supports it.
+    pub tx_maxrate: Option<u64>,
+    /// The number of transmit timeouts of the queue.
+    pub tx_timeout: Option<u64>,
+    /// The traffic class of the queue, None if no multiqueue traffic classes are configured.
+    pub traffic_class: Option<String>,
+    /// The cpus transmit packet steering (XPS) maps to the queue, None if XPS is not available.
+    pub xps_cpus: Option<Vec<u32>>,
+    /// The byte queue limits, None if BQL is not supported.
+    pub byte_queue_limits: Option<ByteQueueLimits>,
+}
+
+/// Struct for holding `/sys/class/net/<interface>/queues/tx-<nr>/byte_queue_limits`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ByteQueueLimits {
+    /// The current limit of bytes queued to the device.
+    pub limit: u64,
+    pub limit_max: u64,
+    pub limit_min: u64,
+    /// The bytes currently queued to the device.
+    pub inflight: u64,
+    /// The time in milliseconds the limit must be exceeded before it is decreased.
+    pub hold_time: u64,
+}
+
+/// Struct for holding `/sys/class/net/<interface>/statistics`
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct NetStatistics {
+    /// Collisions during packet transmission.
+    pub collisions: u64,
+    /// Multicast packets received.
+    pub multicast: u64,
+    /// Bytes received.
+    pub rx_bytes: u64,
+    pub rx_compressed: u64,
+    /// Packets received with a CRC (FCS) error.
+    pub rx_crc_errors: u64,
+    /// Packets received but dropped, for example because of a lack of buffer space.
+    pub rx_dropped: u64,
+    /// Total receive errors, the sum of the more specific rx error counters.
+    pub rx_errors: u64,
+    /// Receive FIFO overruns.
+    pub rx_fifo_errors: u64,
+    /// Packets received with a frame alignment error.
+    pub rx_frame_errors: u64,
+    /// Packets received with an invalid length.
+    pub rx_length_errors: u64,
+    /// Packets missed by the host because the NIC ran out of buffer space.
+    pub rx_missed_errors: u64,
+    /// Packets received on an inactive device, such as an inactive bonding slave. Kernel 4.6+
+    pub rx_nohandler: Option<u64>,
+    /// Receive ring buffer overflows.
+    pub rx_over_errors: u64,
+    pub rx_packets: u64,
+    /// Transmissions aborted, for example because of excessive collisions.
+    pub tx_aborted_errors: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+    /// Transmissions failed because of carrier loss.
+    pub tx_carrier_errors: u64,
+    pub tx_compressed: u64,
+    pub tx_dropped: u64,
+    pub tx_errors: u64,
+    pub tx_fifo_errors: u64,
+    /// Transmissions with a missing heartbeat (SQE) signal.
+    pub tx_heartbeat_errors: u64,
+    pub tx_packets: u64,
+    /// Transmissions failed because of a late collision.
+    pub tx_window_errors: u64,
+}
+
+/// Builder pattern for [`SysNet`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+    pub sys_filter : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+            sys_filter: "^lo".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn filter(mut self, sys_filter: &str) -> Builder {
+        self.sys_filter = sys_filter.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysNet, ProcSysParserError> {
+        SysNet::read_sys_net(format!("{}/class/net", self.sys_path).as_str(), self.sys_filter.as_str())
+    }
+}
+
+/// The main function for building a [`SysNet`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysNet, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl NetInterface {
+    /// True if the interface is administratively up (IFF_UP).
+    pub fn is_up(&self) -> bool {
+        self.flags & 0x1 != 0
+    }
+}
+
+impl SysNet {
+    pub fn new() -> SysNet {
+        SysNet::default()
+    }
+    /// The attributes of an interface by name, None if the interface is not found.
+    pub fn interface(&self, name: &str) -> Option<&NetInterface> {
+        self.interfaces.iter().find(|interface| interface.name == name)
+    }
+    fn read_net_statistics(statistics_path: &Path) -> Result<NetStatistics, ProcSysParserError> {
+        Ok(NetStatistics {
+            collisions: read_file_u64(&statistics_path.join("collisions"))?,
+            multicast: read_file_u64(&statistics_path.join("multicast"))?,
+            rx_bytes: read_file_u64(&statistics_path.join("rx_bytes"))?,
+            rx_compressed: read_file_u64(&statistics_path.join("rx_compressed"))?,
+            rx_crc_errors: read_file_u64(&statistics_path.join("rx_crc_errors"))?,
+            rx_dropped: read_file_u64(&statistics_path.join("rx_dropped"))?,
+            rx_errors: read_file_u64(&statistics_path.join("rx_errors"))?,
+            rx_fifo_errors: read_file_u64(&statistics_path.join("rx_fifo_errors"))?,
+            rx_frame_errors: read_file_u64(&statistics_path.join("rx_frame_errors"))?,
+            rx_length_errors: read_file_u64(&statistics_path.join("rx_length_errors"))?,
+            rx_missed_errors: read_file_u64(&statistics_path.join("rx_missed_errors"))?,
+            rx_nohandler: read_file_option_u64(&statistics_path.join("rx_nohandler"))?,
+            rx_over_errors: read_file_u64(&statistics_path.join("rx_over_errors"))?,
+            rx_packets: read_file_u64(&statistics_path.join("rx_packets"))?,
+            tx_aborted_errors: read_file_u64(&statistics_path.join("tx_aborted_errors"))?,
+            tx_bytes: read_file_u64(&statistics_path.join("tx_bytes"))?,
+            tx_carrier_errors: read_file_u64(&statistics_path.join("tx_carrier_errors"))?,
+            tx_compressed: read_file_u64(&statistics_path.join("tx_compressed"))?,
+            tx_dropped: read_file_u64(&statistics_path.join("tx_dropped"))?,
+            tx_errors: read_file_u64(&statistics_path.join("tx_errors"))?,
+            tx_fifo_errors: read_file_u64(&statistics_path.join("tx_fifo_errors"))?,
+            tx_heartbeat_errors: read_file_u64(&statistics_path.join("tx_heartbeat_errors"))?,
+            tx_packets: read_file_u64(&statistics_path.join("tx_packets"))?,
+            tx_window_errors: read_file_u64(&statistics_path.join("tx_window_errors"))?,
+        })
+    }
+    fn read_rx_queue(queue_path: &Path, queue_nr: u32) -> Result<RxQueue, ProcSysParserError> {
+        Ok(RxQueue {
+            queue_nr,
+            rps_cpus: read_file_option_string(&queue_path.join("rps_cpus"))
+                .map(|rps_cpus| parse_cpu_mask(&rps_cpus))
+                .transpose()?
+                .unwrap_or_default(),
+            rps_flow_cnt: read_file_option_u64(&queue_path.join("rps_flow_cnt"))?.unwrap_or_default(),
+        })
+    }
+    fn read_tx_queue(queue_path: &Path, queue_nr: u32) -> Result<TxQueue, ProcSysParserError> {
+        let byte_queue_limits_path = queue_path.join("byte_queue_limits");
+        Ok(TxQueue {
+            queue_nr,
+            tx_maxrate: read_file_option_u64(&queue_path.join("tx_maxrate"))?,
+            tx_timeout: read_file_option_u64(&queue_path.join("tx_timeout"))?,
+            traffic_class: read_file_option_string(&queue_path.join("traffic_class")),
+            xps_cpus: read_file_option_string(&queue_path.join("xps_cpus"))
+                .map(|xps_cpus| parse_cpu_mask(&xps_cpus))
+                .transpose()?,
+            byte_queue_limits: if byte_queue_limits_path.is_dir() {
+                Some(ByteQueueLimits {
+                    limit: read_file_u64(&byte_queue_limits_path.join("limit"))?,
+                    limit_max: read_file_u64(&byte_queue_limits_path.join("limit_max"))?,
+                    limit_min: read_file_u64(&byte_queue_limits_path.join("limit_min"))?,
+                    inflight: read_file_u64(&byte_queue_limits_path.join("inflight"))?,
+                    hold_time: read_file_u64(&byte_queue_limits_path.join("hold_time"))?,
+                })
+            } else {
+                None
+            },
+        })
+    }
+    fn read_net_queues(queues_path: &Path) -> Result<(Vec<RxQueue>, Vec<TxQueue>), ProcSysParserError> {
+        let mut rx_queues = Vec::new();
+        let mut tx_queues = Vec::new();
+        if !queues_path.is_dir() { return Ok((rx_queues, tx_queues)) };
+
+        for queue_nr in read_numbered_entries(queues_path, "rx-")? {
+            rx_queues.push(SysNet::read_rx_queue(&queues_path.join(format!("rx-{}", queue_nr)), queue_nr)?);
+        }
+        for queue_nr in read_numbered_entries(queues_path, "tx-")? {
+            tx_queues.push(SysNet::read_tx_queue(&queues_path.join(format!("tx-{}", queue_nr)), queue_nr)?);
+        }
+        Ok((rx_queues, tx_queues))
+    }
+    fn read_sriov(device_path: &Path) -> Result<Option<Sriov>, ProcSysParserError> {
+        let Some(totalvfs) = read_file_option_u64(&device_path.join("sriov_totalvfs"))? else { return Ok(None) };
+        let mut sriov = Sriov {
+            totalvfs,
+            numvfs: read_file_u64(&device_path.join("sriov_numvfs"))?,
+            vfs: Vec::new(),
+        };
+        for vf_nr in read_numbered_entries(device_path, "virtfn")? {
+            let vf_path = device_path.join(format!("virtfn{}", vf_nr));
+            sriov.vfs.push(SriovVf {
+                vf_nr,
+                pci_address: read_link_name(&vf_path).unwrap_or_default(),
+                interface: read_dir(vf_path.join("net")).ok()
+                    .and_then(|mut interfaces| interfaces.next())
+                    .and_then(|interface| interface.ok())
+                    .map(|interface| interface.file_name().to_string_lossy().to_string()),
+            });
+        }
+        Ok(Some(sriov))
+    }
+    fn read_net_device(device_path: &Path) -> Result<Option<NetDevice>, ProcSysParserError> {
+        let Some(bus_address) = read_link_name(device_path) else { return Ok(None) };
+        Ok(Some(NetDevice {
+            bus_address,
+            driver: read_link_name(&device_path.join("driver")),
+            vendor: read_file_option_hex_u64(&device_path.join("vendor"))?,
+            device: read_file_option_hex_u64(&device_path.join("device"))?,
+            subsystem_vendor: read_file_option_hex_u64(&device_path.join("subsystem_vendor"))?,
+            subsystem_device: read_file_option_hex_u64(&device_path.join("subsystem_device"))?,
+            numa_node: read_file_option_i64(&device_path.join("numa_node"))?,
+            current_link_speed: read_file_option_string(&device_path.join("current_link_speed")),
+            current_link_width: read_file_option_u64(&device_path.join("current_link_width"))?,
+            max_link_speed: read_file_option_string(&device_path.join("max_link_speed")),
+            max_link_width: read_file_option_u64(&device_path.join("max_link_width"))?,
+        }))
+    }
+    fn read_net_interface(interface_path: &Path, name: &str) -> Result<NetInterface, ProcSysParserError> {
+        let (rx_queues, tx_queues) = SysNet::read_net_queues(&interface_path.join("queues"))?;
+        Ok(NetInterface {
+            name: name.to_string(),
+            ifindex: read_file_u64(&interface_path.join("ifindex"))?,
+            iflink: read_file_u64(&interface_path.join("iflink"))?,
+            interface_type: read_file_u64(&interface_path.join("type"))?,
+            address: read_file_string(&interface_path.join("address"))?,
+            mtu: read_file_u64(&interface_path.join("mtu"))?,
+            tx_queue_len: read_file_u64(&interface_path.join("tx_queue_len"))?,
+            flags: read_file_hex_u64(&interface_path.join("flags"))?,
+            operstate: read_file_string(&interface_path.join("operstate"))?,
+            carrier: read_file_option_string(&interface_path.join("carrier"))
+                .map(|carrier| carrier.trim().parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
+                .transpose()?,
+            speed: read_file_option_string(&interface_path.join("speed"))
+                .map(|speed| speed.trim().parse::<i64>().map_err(ProcSysParserError::ParseToIntegerError))
+                .transpose()?,
+            duplex: read_file_option_string(&interface_path.join("duplex")),
+            statistics: SysNet::read_net_statistics(&interface_path.join("statistics"))?,
+            rx_queues,
+            tx_queues,
+            sriov: SysNet::read_sriov(&interface_path.join("device"))?,
+            physfn: read_link_name(&interface_path.join("device/physfn")),
+            device: SysNet::read_net_device(&interface_path.join("device"))?,
+        })
+    }
+    /// Read all interfaces in `sys_class_net_path`. An interface that cannot be read after it was listed, such as a
+    /// veth interface of a container that was removed in the meantime, is skipped.
+    pub fn read_sys_net(sys_class_net_path: &str, filter: &str) -> Result<SysNet, ProcSysParserError> {
+        let mut sysnet = SysNet::new();
+        let filter_regex = Regex::new(filter)
+            .map_err(|_| ProcSysParserError::RegexCompileError { regex: filter.to_string() })?;
+
+        let interface_directories = read_dir(sys_class_net_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: sys_class_net_path.to_string(), error })?;
+
+        for interface in interface_directories.filter_map(|entry| entry.ok()) {
+            let name = interface.file_name().to_string_lossy().to_string();
+            // bonding_masters is a file in the same directory.
+            if !interface.path().is_dir() { continue };
+            if !filter_regex.as_str().is_empty() && filter_regex.is_match(&name) { continue };
+            match SysNet::read_net_interface(&interface.path(), &name) {
+                Ok(net_interface) => sysnet.interfaces.push(net_interface),
+                // the interface has been removed in the meantime
+                Err(ProcSysParserError::FileReadError { .. }) | Err(ProcSysParserError::DirectoryReadError { .. }) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        sysnet.interfaces.sort_by_key(|interface| interface.ifindex);
+        Ok(sysnet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use std::os::unix::fs::symlink;
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_interface(test_path: &str, name: &str, ifindex: u64, link: bool) {
+        let interface_path = format!("{}/class/net/{}", test_path, name);
+        create_dir_all(&interface_path).expect("Error creating mock directory.");
+        let mut files = vec![
+            ("ifindex", ifindex.to_string()),
+            ("iflink", ifindex.to_string()),
+            ("type", "1".to_string()),
+            ("address", "52:54:00:12:34:56".to_string()),
+            ("mtu", "1500".to_string()),
+            ("tx_queue_len", "1000".to_string()),
+            ("flags", if link { "0x1003".to_string() } else { "0x1002".to_string() }),
+            ("operstate", if link { "up".to_string() } else { "down".to_string() }),
+        ];
+        if link {
+            files.extend([("carrier", "1".to_string()), ("speed", "10000".to_string()), ("duplex", "full".to_string())]);
+        }
+        for (file, contents) in files {
+            write(format!("{}/{}", interface_path, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", interface_path, file));
+        }
+        create_dir_all(format!("{}/statistics", interface_path)).expect("Error creating mock directory.");
+        for (nr, file) in ["collisions", "multicast", "rx_bytes", "rx_compressed", "rx_crc_errors", "rx_dropped", "rx_errors", "rx_fifo_errors", "rx_frame_errors", "rx_length_errors", "rx_missed_errors", "rx_over_errors", "rx_packets", "tx_aborted_errors", "tx_bytes", "tx_carrier_errors", "tx_compressed", "tx_dropped", "tx_errors", "tx_fifo_errors", "tx_heartbeat_errors", "tx_packets", "tx_window_errors"].iter().enumerate() {
+            write(format!("{}/statistics/{}", interface_path, file), format!("{}\n", nr)).unwrap_or_else(|_| panic!("Error writing to {}/statistics/{}", interface_path, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_net_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_interface(&test_path, "lo", 1, true);
+        create_mock_interface(&test_path, "eth0", 2, true);
+        create_mock_interface(&test_path, "eth1", 3, false);
+        let queues_path = format!("{}/class/net/eth0/queues", test_path);
+        create_dir_all(format!("{}/rx-0", queues_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/tx-0/byte_queue_limits", queues_path)).expect("Error creating mock directory.");
+        for (file, contents) in [
+            ("rx-0/rps_cpus", "00000000,00000006\n"),
+            ("rx-0/rps_flow_cnt", "4096\n"),
+            ("tx-0/tx_maxrate", "0\n"),
+            ("tx-0/tx_timeout", "2\n"),
+            ("tx-0/xps_cpus", "1\n"),
+            ("tx-0/byte_queue_limits/limit", "1981\n"),
+            ("tx-0/byte_queue_limits/limit_max", "1879048192\n"),
+            ("tx-0/byte_queue_limits/limit_min", "0\n"),
+            ("tx-0/byte_queue_limits/inflight", "0\n"),
+            ("tx-0/byte_queue_limits/hold_time", "1000\n"),
+        ] {
+            write(format!("{}/{}", queues_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", queues_path, file));
+        }
+        let device_path = format!("{}/devices/pci0000:3a/0000:3b:00.0", test_path);
+        create_dir_all(format!("{}/devices/pci0000:3a/0000:3b:02.0/net/eth2", test_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/bus/pci/drivers/ixgbe", test_path)).expect("Error creating mock directory.");
+        create_dir_all(format!("{}/devices/pci0000:3a/0000:3b:02.1", test_path)).expect("Error creating mock directory.");
+        create_dir_all(&device_path).expect("Error creating mock directory.");
+        symlink(&device_path, format!("{}/class/net/eth0/device", test_path)).expect("Error creating mock symlink.");
+        symlink(format!("{}/bus/pci/drivers/ixgbe", test_path), format!("{}/driver", device_path)).expect("Error creating mock symlink.");
+        for (file, contents) in [("vendor", "0x8086\n"), ("device", "0x158b\n"), ("numa_node", "-1\n"), ("current_link_speed", "8.0 GT/s PCIe\n"), ("current_link_width", "8\n")] {
+            write(format!("{}/{}", device_path, file), contents).unwrap_or_else(|_| panic!("Error writing to {}/{}", device_path, file));
+        }
+        write(format!("{}/sriov_totalvfs", device_path), "64\n").unwrap_or_else(|_| panic!("Error writing to {}/sriov_totalvfs", device_path));
+        write(format!("{}/sriov_numvfs", device_path), "2\n").unwrap_or_else(|_| panic!("Error writing to {}/sriov_numvfs", device_path));
+        symlink(format!("{}/devices/pci0000:3a/0000:3b:02.0", test_path), format!("{}/virtfn0", device_path)).expect("Error creating mock symlink.");
+        symlink(format!("{}/devices/pci0000:3a/0000:3b:02.1", test_path), format!("{}/virtfn1", device_path)).expect("Error creating mock symlink.");
+        create_dir_all(format!("{}/class/net/eth1/device", test_path)).expect("Error creating mock directory.");
+        symlink(format!("{}/devices/pci0000:3a/0000:3b:00.0", test_path), format!("{}/class/net/eth1/device/physfn", test_path)).expect("Error creating mock symlink.");
+        write(format!("{}/class/net/bonding_masters", test_path), "\n").unwrap_or_else(|_| panic!("Error writing to {}/class/net/bonding_masters", test_path));
+        // a veth interface that was removed while it was read, leaving only the ifindex file.
+        create_dir_all(format!("{}/class/net/veth1a2b3c", test_path)).expect("Error creating mock directory.");
+        write(format!("{}/class/net/veth1a2b3c/ifindex", test_path), "4\n").unwrap_or_else(|_| panic!("Error writing to {}/class/net/veth1a2b3c/ifindex", test_path));
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result.interfaces.len(), 2);
+        assert_eq!(result.interfaces[0], NetInterface { name: "eth0".to_string(), ifindex: 2, iflink: 2, interface_type: 1, address: "52:54:00:12:34:56".to_string(), mtu: 1500, tx_queue_len: 1000, flags: 4099, operstate: "up".to_string(), carrier: Some(1), speed: Some(10000), duplex: Some("full".to_string()), statistics: NetStatistics { collisions: 0, multicast: 1, rx_bytes: 2, rx_compressed: 3, rx_crc_errors: 4, rx_dropped: 5, rx_errors: 6, rx_fifo_errors: 7, rx_frame_errors: 8, rx_length_errors: 9, rx_missed_errors: 10, rx_nohandler: None, rx_over_errors: 11, rx_packets: 12, tx_aborted_errors: 13, tx_bytes: 14, tx_carrier_errors: 15, tx_compressed: 16, tx_dropped: 17, tx_errors: 18, tx_fifo_errors: 19, tx_heartbeat_errors: 20, tx_packets: 21, tx_window_errors: 22 }, sriov: Some(Sriov { totalvfs: 64, numvfs: 2, vfs: vec![SriovVf { vf_nr: 0, pci_address: "0000:3b:02.0".to_string(), interface: Some("eth2".to_string()) }, SriovVf { vf_nr: 1, pci_address: "0000:3b:02.1".to_string(), interface: None }] }), physfn: None, device: Some(NetDevice { bus_address: "0000:3b:00.0".to_string(), driver: Some("ixgbe".to_string()), vendor: Some(0x8086), device: Some(0x158b), subsystem_vendor: None, subsystem_device: None, numa_node: Some(-1), current_link_speed: Some("8.0 GT/s PCIe".to_string()), current_link_width: Some(8), max_link_speed: None, max_link_width: None }), rx_queues: vec![RxQueue { queue_nr: 0, rps_cpus: vec![1, 2], rps_flow_cnt: 4096 }], tx_queues: vec![TxQueue { queue_nr: 0, tx_maxrate: Some(0), tx_timeout: Some(2), traffic_class: None, xps_cpus: Some(vec![0]), byte_queue_limits: Some(ByteQueueLimits { limit: 1981, limit_max: 1879048192, limit_min: 0, inflight: 0, hold_time: 1000 }) }] });
+        assert_eq!(result.interface("eth1").unwrap().rx_queues, vec![]);
+        assert_eq!(result.interface("eth1").unwrap().sriov, None);
+        assert_eq!(result.interface("eth1").unwrap().physfn, Some("0000:3b:00.0".to_string()));
+        assert!(result.interfaces[0].is_up());
+        assert_eq!(result.interface("eth1").unwrap().speed, None);
+        assert!(!result.interface("eth1").unwrap().is_up());
+        assert_eq!(result.interface("lo"), None);
+        assert_eq!(result.interface("veth1a2b3c"), None);
+    }
+}
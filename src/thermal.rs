@@ -0,0 +1,275 @@
+/*!
+Read the thermal zones and cooling devices from `/sys/class/thermal` into the struct [`SysThermal`].
+
+Every thermal zone in `/sys/class/thermal/thermal_zone<nr>` is a sensor with a type (the name of the sensor, such as
+`x86_pkg_temp` or `acpitz`), the current temperature and the trip points, which are the temperatures at which the
+kernel takes action, such as throttling (passive), starting a fan (active) or shutting down the system (critical).
+
+Every cooling device in `/sys/class/thermal/cooling_device<nr>` is a device that can cool the system, such as a fan
+or processor throttling, with its current and maximal cooling state. A current state higher than 0 means the device
+is actively cooling, which for the `Processor` and `intel_powerclamp` devices means the cpus are throttled.
+
+The temperatures are in millidegree Celsius. Reading the temperature of a zone can fail for some sensors, for
+example when the device is suspended; the temperature then is None.
+
+Documentation: <https://www.kernel.org/doc/Documentation/driver-api/thermal/sysfs-api.rst>
+
+Here is an example obtaining the data from `/sys/class/thermal`:
+```no_run
+use proc_sys_parser::thermal;
+
+let sys_thermal = thermal::read();
+
+println!("{:#?}", sys_thermal);
+```
+Example output:
+```text
+SysThermal {
+    zones: [
+        ThermalZone {
+            zone_nr: 0,
+            zone_type: "acpitz",
+            temp: Some(27800),
+            mode: Some("enabled"),
+            policy: Some("step_wise"),
+            trip_points: [
+                TripPoint { trip_nr: 0, trip_type: "critical", temp: 119000, hysteresis: Some(0) },
+            ],
+        },
+        ThermalZone {
+            zone_nr: 1,
+            zone_type: "x86_pkg_temp",
+            temp: Some(46000),
+            mode: Some("enabled"),
+            policy: Some("step_wise"),
+            trip_points: [
+                TripPoint { trip_nr: 0, trip_type: "passive", temp: 0, hysteresis: Some(0) },
+                TripPoint { trip_nr: 1, trip_type: "passive", temp: 0, hysteresis: Some(0) },
+            ],
+        },
+    ],
+    cooling_devices: [
+        CoolingDevice { device_nr: 0, device_type: "Processor", cur_state: 0, max_state: 3 },
+        CoolingDevice { device_nr: 1, device_type: "Fan", cur_state: 1, max_state: 1 },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysThermal`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::thermal::Builder;
+
+let sys_thermal = Builder::new().path("/mysys").read();
+```
+*/
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{read_file_i64, read_file_option_i64, read_file_option_string, read_file_string, read_file_u64, read_numbered_entries};
+
+/// Struct for holding the thermal zones and cooling devices
+#[derive(Debug, PartialEq, Default)]
+pub struct SysThermal {
+    pub zones: Vec<ThermalZone>,
+    pub cooling_devices: Vec<CoolingDevice>,
+}
+
+/// Struct for holding a single thermal zone
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ThermalZone {
+    pub zone_nr: u32,
+    /// The type of the zone, which is the name of the sensor, such as `x86_pkg_temp`.
+    pub zone_type: String,
+    /// The current temperature in millidegree Celsius, None if the temperature cannot be read.
+    pub temp: Option<i64>,
+    /// `enabled` or `disabled`.
+    pub mode: Option<String>,
+    /// The thermal governor of the zone, such as `step_wise`.
+    pub policy: Option<String>,
+    pub trip_points: Vec<TripPoint>,
+}
+
+/// Struct for holding a trip point of a thermal zone
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct TripPoint {
+    pub trip_nr: u32,
+    /// `critical`, `hot`, `passive` or `active`.
+    pub trip_type: String,
+    /// The temperature of the trip point in millidegree Celsius.
+    pub temp: i64,
+    /// The hysteresis of the trip point in millidegree Celsius.
+    pub hysteresis: Option<i64>,
+}
+
+/// Struct for holding a single cooling device
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct CoolingDevice {
+    pub device_nr: u32,
+    /// The type of the cooling device, such as `Processor`, `Fan` or `intel_powerclamp`.
+    pub device_type: String,
+    pub cur_state: u64,
+    pub max_state: u64,
+}
+
+/// Builder pattern for [`SysThermal`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysThermal, ProcSysParserError> {
+        SysThermal::read_sys_thermal(format!("{}/class/thermal", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysThermal`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysThermal, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl ThermalZone {
+    /// The current temperature in degrees Celsius.
+    pub fn temp_celsius(&self) -> Option<f64> {
+        self.temp.map(|temp| temp as f64 / 1000_f64)
+    }
+    /// The temperature of the critical trip point of the zone, if it has one.
+    pub fn critical_temp(&self) -> Option<i64> {
+        self.trip_points.iter().find(|trip_point| trip_point.trip_type == "critical").map(|trip_point| trip_point.temp)
+    }
+}
+
+impl CoolingDevice {
+    /// Whether the cooling device is cooling (current state is not 0), which for processor cooling devices
+    /// means the cpus are throttled.
+    pub fn is_active(&self) -> bool {
+        self.cur_state > 0
+    }
+}
+
+impl SysThermal {
+    pub fn new() -> SysThermal {
+        SysThermal::default()
+    }
+    fn read_thermal_zone(zone_path: &Path, zone_nr: u32) -> Result<ThermalZone, ProcSysParserError> {
+        let mut trip_points = Vec::new();
+
+        // the trip points are numbered from 0 without gaps.
+        for trip_nr in 0.. {
+            let Some(trip_type) = read_file_option_string(&zone_path.join(format!("trip_point_{}_type", trip_nr))) else { break };
+            trip_points.push(TripPoint {
+                trip_nr,
+                trip_type,
+                temp: read_file_i64(&zone_path.join(format!("trip_point_{}_temp", trip_nr)))?,
+                hysteresis: read_file_option_i64(&zone_path.join(format!("trip_point_{}_hyst", trip_nr)))?,
+            });
+        }
+
+        Ok(ThermalZone {
+            zone_nr,
+            zone_type: read_file_string(&zone_path.join("type"))?,
+            // reading temp returns an error such as EAGAIN or ENODATA if the sensor is not available.
+            temp: read_file_option_string(&zone_path.join("temp")).and_then(|temp| temp.trim().parse::<i64>().ok()),
+            mode: read_file_option_string(&zone_path.join("mode")),
+            policy: read_file_option_string(&zone_path.join("policy")),
+            trip_points,
+        })
+    }
+    fn read_cooling_device(device_path: &Path, device_nr: u32) -> Result<CoolingDevice, ProcSysParserError> {
+        Ok(CoolingDevice {
+            device_nr,
+            device_type: read_file_string(&device_path.join("type"))?,
+            cur_state: read_file_u64(&device_path.join("cur_state"))?,
+            max_state: read_file_u64(&device_path.join("max_state"))?,
+        })
+    }
+    pub fn read_sys_thermal(sys_class_thermal_path: &str) -> Result<SysThermal, ProcSysParserError> {
+        let mut systhermal = SysThermal::new();
+        let sys_class_thermal_path = Path::new(sys_class_thermal_path);
+
+        for zone_nr in read_numbered_entries(sys_class_thermal_path, "thermal_zone")? {
+            systhermal.zones.push(SysThermal::read_thermal_zone(&sys_class_thermal_path.join(format!("thermal_zone{}", zone_nr)), zone_nr)?);
+        }
+        for device_nr in read_numbered_entries(sys_class_thermal_path, "cooling_device")? {
+            systhermal.cooling_devices.push(SysThermal::read_cooling_device(&sys_class_thermal_path.join(format!("cooling_device{}", device_nr)), device_nr)?);
+        }
+        Ok(systhermal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_thermal_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/class/thermal/thermal_zone0", test_path), &[
+            ("type", "acpitz"),
+            ("temp", "27800"),
+            ("mode", "enabled"),
+            ("policy", "step_wise"),
+            ("trip_point_0_type", "critical"),
+            ("trip_point_0_temp", "119000"),
+            ("trip_point_0_hyst", "0"),
+            ("trip_point_1_type", "passive"),
+            ("trip_point_1_temp", "95000"),
+        ]);
+        // a sensor that cannot be read has a temp file that cannot be read or does not contain a number.
+        create_mock_files(&format!("{}/class/thermal/thermal_zone1", test_path), &[("type", "iwlwifi_1")]);
+        create_mock_files(&format!("{}/class/thermal/cooling_device0", test_path), &[
+            ("type", "Processor"),
+            ("cur_state", "2"),
+            ("max_state", "3"),
+        ]);
+        create_mock_files(&format!("{}/class/thermal/cooling_device1", test_path), &[
+            ("type", "Fan"),
+            ("cur_state", "0"),
+            ("max_state", "1"),
+        ]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysThermal {
+            zones: vec![
+                ThermalZone { zone_nr: 0, zone_type: "acpitz".to_string(), temp: Some(27800), mode: Some("enabled".to_string()), policy: Some("step_wise".to_string()), trip_points: vec![
+                    TripPoint { trip_nr: 0, trip_type: "critical".to_string(), temp: 119000, hysteresis: Some(0) },
+                    TripPoint { trip_nr: 1, trip_type: "passive".to_string(), temp: 95000, hysteresis: None },
+                ] },
+                ThermalZone { zone_nr: 1, zone_type: "iwlwifi_1".to_string(), temp: None, mode: None, policy: None, trip_points: vec![] },
+            ],
+            cooling_devices: vec![
+                CoolingDevice { device_nr: 0, device_type: "Processor".to_string(), cur_state: 2, max_state: 3 },
+                CoolingDevice { device_nr: 1, device_type: "Fan".to_string(), cur_state: 0, max_state: 1 },
+            ],
+        });
+        assert_eq!(result.zones[0].temp_celsius(), Some(27.8));
+        assert_eq!(result.zones[0].critical_temp(), Some(119000));
+        assert!(result.cooling_devices[0].is_active());
+        assert!(!result.cooling_devices[1].is_active());
+    }
+}
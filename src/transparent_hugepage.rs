@@ -0,0 +1,240 @@
+/*!
+Read the transparent hugepage settings from `/sys/kernel/mm/transparent_hugepage` into the struct [`SysTransparentHugepage`].
+
+The `enabled`, `defrag` and `shmem_enabled` files show all possible modes, with the active mode between brackets,
+such as `always [madvise] never`. Only the active mode is stored. Many databases, such as Oracle, PostgreSQL,
+MongoDB and Redis, recommend to set `enabled` to `never` or `madvise`.
+
+The `khugepaged` directory contains the tunables of the khugepaged kernel thread, which collapses small pages into
+transparent hugepages in the background, and its statistics (pages_collapsed and full_scans).
+
+Since linux 6.8, every transparent hugepage size (multi-size THP, mTHP) has its own `hugepages-<size>kB/enabled`
+setting, in which `inherit` means the top level `enabled` setting is used.
+
+Documentation: <https://www.kernel.org/doc/Documentation/admin-guide/mm/transhuge.rst>
+
+Here is an example obtaining the data from `/sys/kernel/mm/transparent_hugepage`:
+```no_run
+use proc_sys_parser::transparent_hugepage;
+
+let sys_transparent_hugepage = transparent_hugepage::read();
+
+println!("{:#?}", sys_transparent_hugepage);
+```
+Example output:
+```text
+SysTransparentHugepage {
+    enabled: "madvise",
+    defrag: "madvise",
+    shmem_enabled: Some("never"),
+    use_zero_page: Some(1),
+    hpage_pmd_size: Some(2097152),
+    khugepaged: Khugepaged {
+        defrag: 1,
+        pages_to_scan: 4096,
+        scan_sleep_millisecs: 10000,
+        alloc_sleep_millisecs: 60000,
+        max_ptes_none: 511,
+        max_ptes_swap: 64,
+        max_ptes_shared: Some(256),
+        pages_collapsed: 0,
+        full_scans: 0,
+    },
+    sizes: [
+        ThpSize { size_kb: 64, enabled: "never" },
+        ThpSize { size_kb: 2048, enabled: "inherit" },
+    ],
+}
+```
+(edited for readability)
+
+If you want to change the path that is read for [`SysTransparentHugepage`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::transparent_hugepage::Builder;
+
+let sys_transparent_hugepage = Builder::new().path("/mysys").read();
+```
+*/
+use std::fs::read_dir;
+use std::path::Path;
+use crate::ProcSysParserError;
+use crate::utils::{parse_bracketed_value, read_file_option_string, read_file_option_u64, read_file_string, read_file_u64};
+
+/// Struct for holding the transparent hugepage settings
+#[derive(Debug, PartialEq, Default)]
+pub struct SysTransparentHugepage {
+    /// The active mode: `always`, `madvise` or `never`.
+    pub enabled: String,
+    /// The active mode: `always`, `defer`, `defer+madvise`, `madvise` or `never`.
+    pub defrag: String,
+    /// The active mode for shmem/tmpfs: `always`, `within_size`, `advise`, `never`, `deny` or `force`.
+    pub shmem_enabled: Option<String>,
+    pub use_zero_page: Option<u64>,
+    /// The size of a PMD sized transparent hugepage in bytes.
+    pub hpage_pmd_size: Option<u64>,
+    pub khugepaged: Khugepaged,
+    /// The per-size settings (mTHP), sorted by size.
+    pub sizes: Vec<ThpSize>,
+}
+
+/// Struct for holding the khugepaged tunables and statistics
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Khugepaged {
+    pub defrag: u64,
+    pub pages_to_scan: u64,
+    pub scan_sleep_millisecs: u64,
+    pub alloc_sleep_millisecs: u64,
+    pub max_ptes_none: u64,
+    pub max_ptes_swap: u64,
+    pub max_ptes_shared: Option<u64>,
+    /// The number of hugepages khugepaged has collapsed.
+    pub pages_collapsed: u64,
+    /// The number of times khugepaged has scanned all memory.
+    pub full_scans: u64,
+}
+
+/// Struct for holding the setting of a single transparent hugepage size
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ThpSize {
+    pub size_kb: u64,
+    /// The active mode: `always`, `inherit`, `madvise` or `never`.
+    pub enabled: String,
+}
+
+/// Builder pattern for [`SysTransparentHugepage`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysTransparentHugepage, ProcSysParserError> {
+        SysTransparentHugepage::read_sys_transparent_hugepage(format!("{}/kernel/mm/transparent_hugepage", &self.sys_path).as_str())
+    }
+}
+
+/// The main function for building a [`SysTransparentHugepage`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysTransparentHugepage, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysTransparentHugepage {
+    pub fn new() -> SysTransparentHugepage {
+        SysTransparentHugepage::default()
+    }
+    /// Whether transparent hugepages are used for all anonymous memory, instead of only for madvised regions.
+    pub fn is_always_enabled(&self) -> bool {
+        self.enabled == "always"
+    }
+    fn read_khugepaged(khugepaged_path: &Path) -> Result<Khugepaged, ProcSysParserError> {
+        Ok(Khugepaged {
+            defrag: read_file_u64(&khugepaged_path.join("defrag"))?,
+            pages_to_scan: read_file_u64(&khugepaged_path.join("pages_to_scan"))?,
+            scan_sleep_millisecs: read_file_u64(&khugepaged_path.join("scan_sleep_millisecs"))?,
+            alloc_sleep_millisecs: read_file_u64(&khugepaged_path.join("alloc_sleep_millisecs"))?,
+            max_ptes_none: read_file_u64(&khugepaged_path.join("max_ptes_none"))?,
+            max_ptes_swap: read_file_u64(&khugepaged_path.join("max_ptes_swap"))?,
+            max_ptes_shared: read_file_option_u64(&khugepaged_path.join("max_ptes_shared"))?,
+            pages_collapsed: read_file_u64(&khugepaged_path.join("pages_collapsed"))?,
+            full_scans: read_file_u64(&khugepaged_path.join("full_scans"))?,
+        })
+    }
+    fn read_thp_sizes(transparent_hugepage_path: &Path) -> Result<Vec<ThpSize>, ProcSysParserError> {
+        let mut sizes = Vec::new();
+
+        let entries = read_dir(transparent_hugepage_path)
+            .map_err(|error| ProcSysParserError::DirectoryReadError { directory: transparent_hugepage_path.to_string_lossy().to_string(), error })?;
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(size_kb) = file_name.strip_prefix("hugepages-").and_then(|size| size.strip_suffix("kB")) else { continue };
+            sizes.push(ThpSize {
+                size_kb: size_kb.parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError)?,
+                enabled: parse_bracketed_value(&read_file_string(&entry.path().join("enabled"))?)?,
+            });
+        }
+        sizes.sort_by_key(|size| size.size_kb);
+        Ok(sizes)
+    }
+    pub fn read_sys_transparent_hugepage(sys_transparent_hugepage_path: &str) -> Result<SysTransparentHugepage, ProcSysParserError> {
+        let path = Path::new(sys_transparent_hugepage_path);
+
+        Ok(SysTransparentHugepage {
+            enabled: parse_bracketed_value(&read_file_string(&path.join("enabled"))?)?,
+            defrag: parse_bracketed_value(&read_file_string(&path.join("defrag"))?)?,
+            shmem_enabled: read_file_option_string(&path.join("shmem_enabled")).map(|contents| parse_bracketed_value(&contents)).transpose()?,
+            use_zero_page: read_file_option_u64(&path.join("use_zero_page"))?,
+            hpage_pmd_size: read_file_option_u64(&path.join("hpage_pmd_size"))?,
+            khugepaged: SysTransparentHugepage::read_khugepaged(&path.join("khugepaged"))?,
+            sizes: SysTransparentHugepage::read_thp_sizes(path)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_transparent_hugepage_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        let thp_path = format!("{}/kernel/mm/transparent_hugepage", test_path);
+        create_mock_files(&thp_path, &[
+            ("enabled", "[always] madvise never"),
+            ("defrag", "always defer defer+madvise [madvise] never"),
+            ("use_zero_page", "1"),
+            ("hpage_pmd_size", "2097152"),
+        ]);
+        create_mock_files(&format!("{}/khugepaged", thp_path), &[
+            ("defrag", "1"),
+            ("pages_to_scan", "4096"),
+            ("scan_sleep_millisecs", "10000"),
+            ("alloc_sleep_millisecs", "60000"),
+            ("max_ptes_none", "511"),
+            ("max_ptes_swap", "64"),
+            ("pages_collapsed", "12"),
+            ("full_scans", "3"),
+        ]);
+        create_mock_files(&format!("{}/hugepages-2048kB", thp_path), &[("enabled", "always [inherit] madvise never")]);
+        create_mock_files(&format!("{}/hugepages-64kB", thp_path), &[("enabled", "always inherit madvise [never]")]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result, SysTransparentHugepage {
+            enabled: "always".to_string(),
+            defrag: "madvise".to_string(),
+            shmem_enabled: None,
+            use_zero_page: Some(1),
+            hpage_pmd_size: Some(2097152),
+            khugepaged: Khugepaged { defrag: 1, pages_to_scan: 4096, scan_sleep_millisecs: 10000, alloc_sleep_millisecs: 60000, max_ptes_none: 511, max_ptes_swap: 64, max_ptes_shared: None, pages_collapsed: 12, full_scans: 3 },
+            sizes: vec![
+                ThpSize { size_kb: 64, enabled: "never".to_string() },
+                ThpSize { size_kb: 2048, enabled: "inherit".to_string() },
+            ],
+        });
+        assert!(result.is_always_enabled());
+    }
+}
@@ -0,0 +1,165 @@
+//! Helper functions for reading single value files, which are the norm in `/sys`.
+use std::fs::{read_dir, read_link, read_to_string, File};
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use crate::ProcSysParserError;
+
+/// Read a file and return the contents without the trailing newline.
+pub(crate) fn read_file_string(file: &Path) -> Result<String, ProcSysParserError> {
+    Ok(read_to_string(file)
+        .map_err(|error| ProcSysParserError::FileReadError { file: file.to_string_lossy().to_string(), error })?
+        .trim_end_matches('\n')
+        .to_string())
+}
+/// Read a file and return the contents without the trailing newline, None if the file cannot be read.
+pub(crate) fn read_file_option_string(file: &Path) -> Option<String> {
+    read_to_string(file).ok().map(|contents| contents.trim_end_matches('\n').to_string())
+}
+/// Read a file containing a single unsigned integer.
+pub(crate) fn read_file_u64(file: &Path) -> Result<u64, ProcSysParserError> {
+    read_file_string(file)?
+        .trim()
+        .parse::<u64>()
+        .map_err(ProcSysParserError::ParseToIntegerError)
+}
+/// Read a file containing a single unsigned integer, None if the file cannot be read.
+/// A file that can be read but does not contain an integer still is an error.
+pub(crate) fn read_file_option_u64(file: &Path) -> Result<Option<u64>, ProcSysParserError> {
+    read_file_option_string(file)
+        .map(|contents| contents.trim().parse::<u64>().map_err(ProcSysParserError::ParseToIntegerError))
+        .transpose()
+}
+/// Read a file containing a single hexadecimal unsigned integer with or without `0x` prefix, such as `0x1003`.
+pub(crate) fn read_file_hex_u64(file: &Path) -> Result<u64, ProcSysParserError> {
+    let contents = read_file_string(file)?;
+    let contents = contents.trim();
+    u64::from_str_radix(contents.strip_prefix("0x").unwrap_or(contents), 16)
+        .map_err(ProcSysParserError::ParseToIntegerError)
+}
+/// Read a file containing a single hexadecimal unsigned integer with or without `0x` prefix, None if the file
+/// cannot be read.
+pub(crate) fn read_file_option_hex_u64(file: &Path) -> Result<Option<u64>, ProcSysParserError> {
+    read_file_option_string(file)
+        .map(|contents| {
+            let contents = contents.trim();
+            u64::from_str_radix(contents.strip_prefix("0x").unwrap_or(contents), 16).map_err(ProcSysParserError::ParseToIntegerError)
+        })
+        .transpose()
+}
+/// Read the 64 bit numbers in host byte order of a binary file that has a number per index at offset index * 8,
+/// such as `/proc/kpageflags` or `/proc/<pid>/pagemap`, for `count` indexes starting at `start_index`, only reading
+/// every `step`th index. Stops at the end of the file, or at an index whose offset does not fit in a file offset.
+pub(crate) fn read_file_u64_entries(file: &Path, start_index: u64, count: u64, step: u64) -> Result<Vec<u64>, ProcSysParserError> {
+    let file_error = |error| ProcSysParserError::FileReadError { file: file.to_string_lossy().to_string(), error };
+    let file_handle = File::open(file).map_err(file_error)?;
+    let mut entries = Vec::new();
+    let step = step.max(1);
+    let end_index = start_index.saturating_add(count);
+    // a contiguous range is read in chunks, a sampled range per index.
+    let chunk_entries = if step == 1 { 8192 } else { 1 };
+    let mut buffer = vec![0_u8; chunk_entries * 8];
+    let mut index = start_index;
+
+    while index < end_index {
+        let read_entries = (end_index - index).min(chunk_entries as u64) as usize;
+        let Some(offset) = index.checked_mul(8).filter(|offset| *offset <= i64::MAX as u64) else { break };
+        let bytes = file_handle.read_at(&mut buffer[..read_entries * 8], offset).map_err(file_error)?;
+        entries.extend(buffer[..bytes - bytes % 8].chunks_exact(8).map(|entry| u64::from_ne_bytes(entry.try_into().unwrap_or_default())));
+        if bytes < read_entries * 8 { break };
+        index = index.saturating_add(if step == 1 { read_entries as u64 } else { step });
+    }
+    Ok(entries)
+}
+/// The name of the target of a symlink, such as the PCI address for a device link or the driver name for a driver
+/// link, None if the link does not exist.
+pub(crate) fn read_link_name(link: &Path) -> Option<String> {
+    read_link(link).ok()
+        .and_then(|target| target.file_name().map(|name| name.to_string_lossy().to_string()))
+}
+/// List the names of the entries in a directory that start with `prefix` followed by a number, and return the
+/// numbers sorted. For example `cpu` returns the numbers of `cpu0`, `cpu1`, etc., but not `cpufreq`.
+pub(crate) fn read_numbered_entries(directory: &Path, prefix: &str) -> Result<Vec<u32>, ProcSysParserError> {
+    let mut numbers: Vec<u32> = read_dir(directory)
+        .map_err(|error| ProcSysParserError::DirectoryReadError { directory: directory.to_string_lossy().to_string(), error })?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().strip_prefix(prefix).and_then(|number| number.parse::<u32>().ok()))
+        .collect();
+    numbers.sort();
+    Ok(numbers)
+}
+/// Parse a cpu or node list such as `0-3,8,10-11` into the individual numbers.
+/// An empty list (or a list containing only whitespace) results in an empty vector.
+pub fn parse_cpu_list(list: &str) -> Result<Vec<u32>, ProcSysParserError> {
+    let mut numbers = Vec::new();
+
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let start = start.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                let end = end.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?;
+                numbers.extend(start..=end);
+            },
+            None => numbers.push(range.parse::<u32>().map_err(ProcSysParserError::ParseToIntegerError)?),
+        }
+    }
+    Ok(numbers)
+}
+/// Parse a hexadecimal cpu mask such as `ff,00000001`, which consists of comma separated 32 bit words with the most
+/// significant word first, into the numbers of the cpus that are set.
+pub fn parse_cpu_mask(mask: &str) -> Result<Vec<u32>, ProcSysParserError> {
+    let mut numbers = Vec::new();
+
+    for (word_nr, word) in mask.trim().split(',').rev().enumerate() {
+        let word = u32::from_str_radix(word, 16).map_err(ProcSysParserError::ParseToIntegerError)?;
+        numbers.extend((0..32).filter(|bit| word & (1 << bit) != 0).map(|bit| word_nr as u32 * 32 + bit));
+    }
+    Ok(numbers)
+}
+/// Read a file containing a single signed integer, None if the file cannot be read.
+/// A file that can be read but does not contain an integer still is an error.
+pub(crate) fn read_file_option_i64(file: &Path) -> Result<Option<i64>, ProcSysParserError> {
+    read_file_option_string(file)
+        .map(|contents| contents.trim().parse::<i64>().map_err(ProcSysParserError::ParseToIntegerError))
+        .transpose()
+}
+/// Read a file containing a single signed integer.
+pub(crate) fn read_file_i64(file: &Path) -> Result<i64, ProcSysParserError> {
+    read_file_string(file)?
+        .trim()
+        .parse::<i64>()
+        .map_err(ProcSysParserError::ParseToIntegerError)
+}
+/// Parse the active value out of a sysfs setting that shows all values with the active one between brackets,
+/// such as `always [madvise] never`.
+pub(crate) fn parse_bracketed_value(contents: &str) -> Result<String, ProcSysParserError> {
+    let start = contents.find('[').ok_or(ProcSysParserError::FindItemError { item: format!("'[' in {}", contents) })?;
+    let end = contents[start..].find(']').ok_or(ProcSysParserError::FindItemError { item: format!("']' in {}", contents) })?;
+    Ok(contents[start + 1..start + end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cpu_lists() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n").unwrap(), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5").unwrap(), vec![5]);
+        assert_eq!(parse_cpu_list("\n").unwrap(), Vec::<u32>::new());
+        assert!(parse_cpu_list("0-a").is_err());
+    }
+
+    #[test]
+    fn parse_cpu_masks() {
+        assert_eq!(parse_cpu_mask("00000003,00000101\n").unwrap(), vec![0, 8, 32, 33]);
+        assert_eq!(parse_cpu_mask("0").unwrap(), Vec::<u32>::new());
+        assert!(parse_cpu_mask("0,xyz").is_err());
+    }
+
+    #[test]
+    fn parse_bracketed_values() {
+        assert_eq!(parse_bracketed_value("always defer defer+madvise [madvise] never").unwrap(), "madvise");
+        assert_eq!(parse_bracketed_value("[none] mq-deadline").unwrap(), "none");
+        assert!(parse_bracketed_value("none").is_err());
+    }
+}
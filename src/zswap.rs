@@ -0,0 +1,208 @@
+/*!
+Read the zswap configuration and statistics into the struct [`SysZswap`].
+
+zswap is a compressed cache for swap pages: pages that are swapped out are compressed and stored in memory, and only
+written to the swap device when the pool is full. The configuration is read from the module parameters in
+`/sys/module/zswap/parameters`:
+- `enabled`: whether zswap is enabled (`Y`/`N`).
+- `compressor`: the compression algorithm, such as `lzo`, `lz4` or `zstd`.
+- `zpool`: the allocator of the compressed pool, such as `zsmalloc` or `z3fold` (removed in linux 6.15).
+- `max_pool_percent`: the maximal size of the pool as a percentage of memory.
+- `accept_threshold_percent` and `shrinker_enabled`, which exist in later linux versions only.
+
+The statistics, such as `pool_total_size`, `stored_pages`, `pool_limit_hit` and `written_back_pages`, are shown in
+`/sys/kernel/debug/zswap`, which requires debugfs to be mounted and is only readable by root. If the statistics
+cannot be read, `statistics` is None. Because the statistics differ per linux version, these are stored by name.
+
+The number of pages that are loaded from and stored into zswap are shown as zswpin and zswpout in `/proc/vmstat`,
+see [`crate::vmstat::ProcVmStat`].
+
+Documentation: <https://www.kernel.org/doc/Documentation/admin-guide/mm/zswap.rst>
+
+Here is an example obtaining the zswap data:
+```no_run
+use proc_sys_parser::zswap;
+
+let sys_zswap = zswap::read();
+
+println!("{:#?}", sys_zswap);
+```
+Example output:
+```text
+SysZswap {
+    enabled: true,
+    compressor: "zstd",
+    zpool: Some("zsmalloc"),
+    max_pool_percent: 20,
+    accept_threshold_percent: Some(90),
+    shrinker_enabled: Some(true),
+    statistics: Some({
+        "decompress_fail": 0,
+        "duplicate_entry": 0,
+        "pool_limit_hit": 0,
+        "pool_total_size": 93241344,
+        "reject_alloc_fail": 0,
+        "reject_compress_fail": 0,
+        "reject_compress_poor": 0,
+        "reject_kmemcache_fail": 0,
+        "reject_reclaim_fail": 0,
+        "same_filled_pages": 1822,
+        "stored_pages": 68471,
+        "written_back_pages": 0,
+    }),
+}
+```
+
+If you want to change the path that is read for [`SysZswap`], which is `/sys` by default, use:
+```no_run
+use proc_sys_parser::zswap::Builder;
+
+let sys_zswap = Builder::new().path("/mysys").read();
+```
+*/
+use std::collections::BTreeMap;
+use std::fs::read_dir;
+use std::path::Path;
+use nix::unistd::{sysconf, SysconfVar};
+use crate::ProcSysParserError;
+use crate::utils::{read_file_option_string, read_file_option_u64, read_file_string, read_file_u64};
+
+/// Struct for holding the zswap configuration and statistics
+#[derive(Debug, PartialEq, Default)]
+pub struct SysZswap {
+    pub enabled: bool,
+    pub compressor: String,
+    pub zpool: Option<String>,
+    pub max_pool_percent: u64,
+    pub accept_threshold_percent: Option<u64>,
+    pub shrinker_enabled: Option<bool>,
+    /// The statistics from `/sys/kernel/debug/zswap` by name, None if these cannot be read.
+    pub statistics: Option<BTreeMap<String, u64>>,
+}
+
+/// Builder pattern for [`SysZswap`]
+#[derive(Default)]
+pub struct Builder {
+    pub sys_path : String,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder {
+            sys_path: "/sys".to_string(),
+        }
+    }
+
+    pub fn path(mut self, sys_path: &str) -> Builder {
+        self.sys_path = sys_path.to_string();
+        self
+    }
+    pub fn read(self) -> Result<SysZswap, ProcSysParserError> {
+        SysZswap::read_sys_zswap(
+            format!("{}/module/zswap/parameters", &self.sys_path).as_str(),
+            format!("{}/kernel/debug/zswap", &self.sys_path).as_str(),
+        )
+    }
+}
+
+/// The main function for building a [`SysZswap`] struct with current data.
+/// This uses the Builder pattern, which allows settings such as the path to specified.
+pub fn read() -> Result<SysZswap, ProcSysParserError> {
+   Builder::new().read()
+}
+
+impl SysZswap {
+    pub fn new() -> SysZswap {
+        SysZswap::default()
+    }
+    /// The value of a statistic, such as "stored_pages", or None if the statistics or the statistic are not available.
+    pub fn statistic(&self, name: &str) -> Option<u64> {
+        self.statistics.as_ref().and_then(|statistics| statistics.get(name).copied())
+    }
+    /// The size of the stored pages divided by the size of the compressed pool.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        let page_size = sysconf(SysconfVar::PAGE_SIZE).unwrap_or(Some(4096)).unwrap_or(4096) as u64;
+        let pool_total_size = self.statistic("pool_total_size")?;
+        if pool_total_size == 0 { return None };
+        Some((self.statistic("stored_pages")? * page_size) as f64 / pool_total_size as f64)
+    }
+    fn parse_bool(value: &str) -> bool {
+        matches!(value.trim(), "Y" | "y" | "1")
+    }
+    fn read_statistics(debug_zswap_path: &Path) -> Option<BTreeMap<String, u64>> {
+        let statistic_files = read_dir(debug_zswap_path).ok()?;
+
+        Some(statistic_files
+            .flatten()
+            .filter_map(|statistic| {
+                read_file_u64(&statistic.path()).ok().map(|value| (statistic.file_name().to_string_lossy().to_string(), value))
+            })
+            .collect())
+    }
+    pub fn read_sys_zswap(sys_zswap_parameters_path: &str, sys_debug_zswap_path: &str) -> Result<SysZswap, ProcSysParserError> {
+        let path = Path::new(sys_zswap_parameters_path);
+
+        Ok(SysZswap {
+            enabled: SysZswap::parse_bool(&read_file_string(&path.join("enabled"))?),
+            compressor: read_file_string(&path.join("compressor"))?,
+            zpool: read_file_option_string(&path.join("zpool")),
+            max_pool_percent: read_file_u64(&path.join("max_pool_percent"))?,
+            accept_threshold_percent: read_file_option_u64(&path.join("accept_threshold_percent"))?,
+            shrinker_enabled: read_file_option_string(&path.join("shrinker_enabled")).map(|value| SysZswap::parse_bool(&value)),
+            statistics: SysZswap::read_statistics(Path::new(sys_debug_zswap_path)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{write, remove_dir_all, create_dir_all};
+    use rand::{thread_rng, Rng};
+    use rand::distributions::Alphanumeric;
+    use super::*;
+
+    fn create_mock_files(directory: &str, files: &[(&str, &str)]) {
+        create_dir_all(directory).expect("Error creating mock directory.");
+        for (file, contents) in files {
+            write(format!("{}/{}", directory, file), format!("{}\n", contents)).unwrap_or_else(|_| panic!("Error writing to {}/{}", directory, file));
+        }
+    }
+
+    #[test]
+    fn create_sys_zswap_files_and_read() {
+        let directory_suffix: String = thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+        let test_path = format!("/tmp/test.{}", directory_suffix);
+        create_mock_files(&format!("{}/module/zswap/parameters", test_path), &[
+            ("enabled", "Y"),
+            ("compressor", "zstd"),
+            ("zpool", "zsmalloc"),
+            ("max_pool_percent", "20"),
+            ("shrinker_enabled", "N"),
+        ]);
+
+        let result_without_debugfs = Builder::new().path(&test_path).read().unwrap();
+
+        create_mock_files(&format!("{}/kernel/debug/zswap", test_path), &[
+            ("pool_total_size", "1048576"),
+            ("stored_pages", "1024"),
+            ("pool_limit_hit", "3"),
+        ]);
+
+        let result = Builder::new().path(&test_path).read().unwrap();
+
+        remove_dir_all(test_path).unwrap();
+
+        assert_eq!(result_without_debugfs, SysZswap {
+            enabled: true,
+            compressor: "zstd".to_string(),
+            zpool: Some("zsmalloc".to_string()),
+            max_pool_percent: 20,
+            accept_threshold_percent: None,
+            shrinker_enabled: Some(false),
+            statistics: None,
+        });
+        assert_eq!(result.statistics.as_ref().unwrap().len(), 3);
+        assert_eq!(result.statistic("pool_limit_hit"), Some(3));
+        assert_eq!(result.statistic("written_back_pages"), None);
+    }
+}